@@ -0,0 +1,27 @@
+-- ================================================
+-- 고객 전화번호 정규화
+-- ================================================
+-- 입력 형식(010-1234-5678, +82 10 1234 5678 등)과 무관하게
+-- 숫자만으로 된 국내 표기(01012345678)를 저장하여 중복 확인과 검색에 사용
+
+ALTER TABLE customers ADD COLUMN phone_normalized VARCHAR(20);
+
+-- 기존 데이터 채우기: 숫자만 남기고 국제 전화 접두어(+82, 0082)를 국내 표기로 변환
+UPDATE customers
+SET phone_normalized = CASE
+    WHEN digits LIKE '0082%' THEN
+        CASE WHEN substr(digits, 5) LIKE '0%' THEN substr(digits, 5) ELSE '0' || substr(digits, 5) END
+    WHEN btrim(phone) LIKE '+82%' THEN
+        CASE WHEN substr(digits, 3) LIKE '0%' THEN substr(digits, 3) ELSE '0' || substr(digits, 3) END
+    ELSE digits
+END
+FROM (
+    SELECT id AS customer_id, regexp_replace(phone, '[^0-9]', '', 'g') AS digits
+    FROM customers
+) AS normalized
+WHERE customers.id = normalized.customer_id;
+
+CREATE INDEX idx_customers_user_phone_normalized
+    ON customers(user_id, phone_normalized) WHERE deleted_at IS NULL;
+
+COMMENT ON COLUMN customers.phone_normalized IS '숫자만으로 정규화한 전화번호 (예: 01012345678), 중복 확인/검색용';
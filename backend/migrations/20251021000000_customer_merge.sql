@@ -0,0 +1,12 @@
+-- ================================================
+-- 고객 병합
+-- ================================================
+-- 중복 고객을 병합하면 원본 고객은 soft delete 되고
+-- 병합 대상 고객을 가리키도록 기록
+
+ALTER TABLE customers
+ADD COLUMN merged_into_id BIGINT NULL REFERENCES customers(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_customers_merged_into ON customers(merged_into_id) WHERE merged_into_id IS NOT NULL;
+
+COMMENT ON COLUMN customers.merged_into_id IS '병합되어 삭제된 경우 병합 대상 고객 ID';
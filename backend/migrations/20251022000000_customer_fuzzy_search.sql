@@ -0,0 +1,43 @@
+-- ================================================
+-- 고객 검색 개선 (트라이그램 + 초성 검색)
+-- ================================================
+-- "ㄱㅁㅅ" 처럼 초성만 입력해도 "김민수"를 찾을 수 있도록 초성 파생 컬럼을 두고,
+-- 이름/초성/전화번호 부분 일치 검색이 인덱스를 사용할 수 있도록 트라이그램 인덱스 생성
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- 한글 음절을 초성(호환용 자모)으로 변환, 공백은 제거하고 그 외 문자는 그대로 유지
+CREATE OR REPLACE FUNCTION hangul_choseong(input TEXT)
+RETURNS TEXT AS $$
+DECLARE
+    initials TEXT[] := ARRAY[
+        'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ',
+        'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ'
+    ];
+    result TEXT := '';
+    ch TEXT;
+    code INTEGER;
+BEGIN
+    FOREACH ch IN ARRAY regexp_split_to_array(regexp_replace(input, '\s', '', 'g'), '') LOOP
+        code := ascii(ch);
+        IF code BETWEEN 44032 AND 55203 THEN
+            result := result || initials[(code - 44032) / 588 + 1];
+        ELSE
+            result := result || ch;
+        END IF;
+    END LOOP;
+    RETURN result;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE STRICT;
+
+ALTER TABLE customers
+ADD COLUMN name_choseong TEXT GENERATED ALWAYS AS (hangul_choseong(name)) STORED;
+
+CREATE INDEX idx_customers_name_trgm
+    ON customers USING gin (name gin_trgm_ops) WHERE deleted_at IS NULL;
+CREATE INDEX idx_customers_name_choseong_trgm
+    ON customers USING gin (name_choseong gin_trgm_ops) WHERE deleted_at IS NULL;
+CREATE INDEX idx_customers_phone_normalized_trgm
+    ON customers USING gin (phone_normalized gin_trgm_ops) WHERE deleted_at IS NULL;
+
+COMMENT ON COLUMN customers.name_choseong IS '이름의 초성 (예: 김민수 → ㄱㅁㅅ), 초성 검색용';
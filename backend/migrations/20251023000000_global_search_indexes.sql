@@ -0,0 +1,29 @@
+-- ================================================
+-- 통합 검색용 전문 검색(Full-text) 인덱스
+-- ================================================
+-- 한국어 형태소 분석기가 없으므로 'simple' 설정으로 공백 단위 토큰화 후
+-- 접두어 검색(예: '분당:*')으로 조사가 붙은 단어("분당에서")도 찾음
+
+CREATE INDEX idx_job_postings_description_fts
+    ON job_postings USING gin (to_tsvector('simple', description))
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX idx_job_seeking_postings_fts
+    ON job_seeking_postings USING gin (to_tsvector('simple', description || ' ' || preferred_location))
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX idx_customer_memos_content_fts
+    ON customer_memos USING gin (to_tsvector('simple', memo_content))
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX idx_matching_memos_content_fts
+    ON matching_memos USING gin (to_tsvector('simple', memo_content))
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX idx_user_memos_content_fts
+    ON user_memos USING gin (to_tsvector('simple', memo_content))
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX idx_tags_name_trgm
+    ON tags USING gin (tag_name gin_trgm_ops)
+    WHERE deleted_at IS NULL;
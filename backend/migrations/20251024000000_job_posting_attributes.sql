@@ -0,0 +1,65 @@
+-- ================================================
+-- 구인/구직 공고 구조화 속성
+-- ================================================
+-- 설명(description) 자유 텍스트 대신 업무 분야, 근무 지역, 입주/출퇴근, 근무 요일/시간,
+-- 시작일, 급여 기준, 가족 구성원 수, 반려동물 여부를 별도 컬럼으로 저장하여 목록 필터에 사용
+-- 구직 공고는 같은 컬럼을 "희망 조건"의 의미로 사용
+
+ALTER TABLE job_postings
+    ADD COLUMN job_category VARCHAR(30) NULL
+        CHECK (job_category IN ('housekeeping', 'childcare', 'postpartum', 'eldercare', 'patient_care', 'other')),
+    ADD COLUMN region_codes TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN work_arrangement VARCHAR(20) NULL
+        CHECK (work_arrangement IN ('live_in', 'commute')),
+    ADD COLUMN work_days TEXT[] NOT NULL DEFAULT '{}'
+        CHECK (work_days <@ ARRAY['mon', 'tue', 'wed', 'thu', 'fri', 'sat', 'sun']::TEXT[]),
+    ADD COLUMN work_start_time TIME NULL,
+    ADD COLUMN work_end_time TIME NULL,
+    ADD COLUMN start_date DATE NULL,
+    ADD COLUMN salary_basis VARCHAR(20) NOT NULL DEFAULT 'monthly'
+        CHECK (salary_basis IN ('monthly', 'daily', 'hourly')),
+    ADD COLUMN household_members INTEGER NULL CHECK (household_members > 0),
+    ADD COLUMN has_pets BOOLEAN NULL;
+
+ALTER TABLE job_seeking_postings
+    ADD COLUMN job_category VARCHAR(30) NULL
+        CHECK (job_category IN ('housekeeping', 'childcare', 'postpartum', 'eldercare', 'patient_care', 'other')),
+    ADD COLUMN region_codes TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN work_arrangement VARCHAR(20) NULL
+        CHECK (work_arrangement IN ('live_in', 'commute')),
+    ADD COLUMN work_days TEXT[] NOT NULL DEFAULT '{}'
+        CHECK (work_days <@ ARRAY['mon', 'tue', 'wed', 'thu', 'fri', 'sat', 'sun']::TEXT[]),
+    ADD COLUMN work_start_time TIME NULL,
+    ADD COLUMN work_end_time TIME NULL,
+    ADD COLUMN start_date DATE NULL,
+    ADD COLUMN salary_basis VARCHAR(20) NOT NULL DEFAULT 'monthly'
+        CHECK (salary_basis IN ('monthly', 'daily', 'hourly')),
+    ADD COLUMN max_household_members INTEGER NULL CHECK (max_household_members > 0),
+    ADD COLUMN accepts_pets BOOLEAN NULL;
+
+CREATE INDEX idx_job_postings_job_category ON job_postings(job_category) WHERE deleted_at IS NULL;
+CREATE INDEX idx_job_postings_region_codes ON job_postings USING GIN (region_codes) WHERE deleted_at IS NULL;
+CREATE INDEX idx_job_seeking_postings_job_category ON job_seeking_postings(job_category) WHERE deleted_at IS NULL;
+CREATE INDEX idx_job_seeking_postings_region_codes ON job_seeking_postings USING GIN (region_codes) WHERE deleted_at IS NULL;
+
+COMMENT ON COLUMN job_postings.job_category IS '업무 분야 (housekeeping: 가사, childcare: 육아, postpartum: 산후조리, eldercare: 노인돌봄, patient_care: 간병, other: 기타)';
+COMMENT ON COLUMN job_postings.region_codes IS '근무 지역 행정구역 코드 목록 (시도 2자리, 시군구 5자리, 읍면동 8/10자리)';
+COMMENT ON COLUMN job_postings.work_arrangement IS '근무 형태 (live_in: 입주, commute: 출퇴근)';
+COMMENT ON COLUMN job_postings.work_days IS '근무 요일 (mon ~ sun)';
+COMMENT ON COLUMN job_postings.work_start_time IS '근무 시작 시각';
+COMMENT ON COLUMN job_postings.work_end_time IS '근무 종료 시각 (시작 시각보다 이르면 익일 종료)';
+COMMENT ON COLUMN job_postings.start_date IS '근무 시작 예정일';
+COMMENT ON COLUMN job_postings.salary_basis IS '급여 기준 (monthly: 월급, daily: 일급, hourly: 시급)';
+COMMENT ON COLUMN job_postings.household_members IS '가족 구성원 수';
+COMMENT ON COLUMN job_postings.has_pets IS '반려동물 유무';
+
+COMMENT ON COLUMN job_seeking_postings.job_category IS '희망 업무 분야';
+COMMENT ON COLUMN job_seeking_postings.region_codes IS '희망 근무 지역 행정구역 코드 목록';
+COMMENT ON COLUMN job_seeking_postings.work_arrangement IS '희망 근무 형태 (live_in: 입주, commute: 출퇴근)';
+COMMENT ON COLUMN job_seeking_postings.work_days IS '근무 가능 요일 (mon ~ sun)';
+COMMENT ON COLUMN job_seeking_postings.work_start_time IS '근무 가능 시작 시각';
+COMMENT ON COLUMN job_seeking_postings.work_end_time IS '근무 가능 종료 시각';
+COMMENT ON COLUMN job_seeking_postings.start_date IS '근무 가능 시작일';
+COMMENT ON COLUMN job_seeking_postings.salary_basis IS '희망 급여 기준 (monthly: 월급, daily: 일급, hourly: 시급)';
+COMMENT ON COLUMN job_seeking_postings.max_household_members IS '근무 가능한 최대 가족 구성원 수';
+COMMENT ON COLUMN job_seeking_postings.accepts_pets IS '반려동물이 있는 가정 근무 가능 여부';
@@ -0,0 +1,69 @@
+-- ================================================
+-- 급여 기준 정규화 (월 환산 급여)
+-- ================================================
+-- 시급/일급/월급으로 입력된 급여를 비교할 수 있도록 주당 근무시간을 저장하고
+-- 월 환산 급여(monthly_salary)를 생성 컬럼으로 유지하여 필터, 매칭 추천, 수수료 계산에 사용
+-- 환산식은 백엔드 services::salary::monthly_equivalent 와 동일하게 유지해야 함
+
+CREATE OR REPLACE FUNCTION monthly_salary_equivalent(
+    amount NUMERIC,
+    basis TEXT,
+    weekly_hours NUMERIC,
+    days_per_week INTEGER
+) RETURNS NUMERIC
+LANGUAGE SQL
+IMMUTABLE
+AS $$
+    SELECT ROUND(
+        CASE basis
+            WHEN 'hourly' THEN amount * COALESCE(weekly_hours, 40) * 4.345
+            WHEN 'daily' THEN amount * COALESCE(
+                NULLIF(days_per_week, 0)::NUMERIC,
+                weekly_hours / 8,
+                5
+            ) * 4.345
+            ELSE amount
+        END,
+        0
+    )
+$$;
+
+COMMENT ON FUNCTION monthly_salary_equivalent(NUMERIC, TEXT, NUMERIC, INTEGER)
+    IS '급여를 월 환산액(원)으로 변환: 시급 x 주당 근무시간(기본 40) x 4.345주, 일급 x 주당 근무일(근무 요일 수, 주당 근무시간/8, 기본 5일) x 4.345주';
+
+ALTER TABLE job_postings
+    ADD COLUMN expected_weekly_hours NUMERIC(4, 1) NULL
+        CHECK (expected_weekly_hours > 0 AND expected_weekly_hours <= 168);
+ALTER TABLE job_postings
+    ADD COLUMN monthly_salary NUMERIC(14, 0) GENERATED ALWAYS AS (
+        monthly_salary_equivalent(salary, salary_basis, expected_weekly_hours, cardinality(work_days))
+    ) STORED;
+
+ALTER TABLE job_seeking_postings
+    ADD COLUMN expected_weekly_hours NUMERIC(4, 1) NULL
+        CHECK (expected_weekly_hours > 0 AND expected_weekly_hours <= 168);
+ALTER TABLE job_seeking_postings
+    ADD COLUMN monthly_salary NUMERIC(14, 0) GENERATED ALWAYS AS (
+        monthly_salary_equivalent(desired_salary, salary_basis, expected_weekly_hours, cardinality(work_days))
+    ) STORED;
+
+ALTER TABLE matchings
+    ADD COLUMN salary_basis VARCHAR(20) NOT NULL DEFAULT 'monthly'
+        CHECK (salary_basis IN ('monthly', 'daily', 'hourly')),
+    ADD COLUMN expected_weekly_hours NUMERIC(4, 1) NULL
+        CHECK (expected_weekly_hours > 0 AND expected_weekly_hours <= 168);
+ALTER TABLE matchings
+    ADD COLUMN monthly_salary NUMERIC(14, 0) GENERATED ALWAYS AS (
+        monthly_salary_equivalent(agreed_salary, salary_basis, expected_weekly_hours, NULL)
+    ) STORED;
+
+CREATE INDEX idx_job_postings_monthly_salary ON job_postings(monthly_salary) WHERE deleted_at IS NULL;
+CREATE INDEX idx_job_seeking_postings_monthly_salary ON job_seeking_postings(monthly_salary) WHERE deleted_at IS NULL;
+
+COMMENT ON COLUMN job_postings.expected_weekly_hours IS '주당 예상 근무시간 (시급/일급 월 환산에 사용)';
+COMMENT ON COLUMN job_postings.monthly_salary IS '월 환산 급여 (자동 계산)';
+COMMENT ON COLUMN job_seeking_postings.expected_weekly_hours IS '주당 희망 근무시간 (시급/일급 월 환산에 사용)';
+COMMENT ON COLUMN job_seeking_postings.monthly_salary IS '월 환산 희망 급여 (자동 계산)';
+COMMENT ON COLUMN matchings.salary_basis IS '합의 급여 기준 (monthly: 월급, daily: 일급, hourly: 시급)';
+COMMENT ON COLUMN matchings.expected_weekly_hours IS '주당 예상 근무시간';
+COMMENT ON COLUMN matchings.monthly_salary IS '월 환산 합의 급여 (자동 계산, 수수료 산정 기준)';
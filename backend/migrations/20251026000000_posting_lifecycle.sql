@@ -0,0 +1,63 @@
+-- ================================================
+-- 공고 상태 이력 및 자동 만료
+-- ================================================
+-- 구인/구직 공고의 상태 변경을 누가, 언제, 무엇을 바꿨는지 기록하고
+-- expires_at 이 지난 게시중 공고는 백그라운드 작업이 마감 처리
+
+ALTER TABLE job_postings ADD COLUMN expires_at TIMESTAMP NULL;
+ALTER TABLE job_seeking_postings ADD COLUMN expires_at TIMESTAMP NULL;
+
+CREATE INDEX idx_job_postings_expires_at
+    ON job_postings(expires_at) WHERE posting_status = 'published' AND deleted_at IS NULL;
+CREATE INDEX idx_job_seeking_postings_expires_at
+    ON job_seeking_postings(expires_at) WHERE posting_status = 'published' AND deleted_at IS NULL;
+
+COMMENT ON COLUMN job_postings.expires_at IS '공고 만료 시각. 지나면 게시중 공고가 자동으로 마감됨';
+COMMENT ON COLUMN job_seeking_postings.expires_at IS '공고 만료 시각. 지나면 게시중 공고가 자동으로 마감됨';
+
+CREATE TABLE job_posting_status_history (
+    id BIGSERIAL PRIMARY KEY,
+    job_posting_id BIGINT NOT NULL,
+    from_status VARCHAR(20) NOT NULL,
+    to_status VARCHAR(20) NOT NULL,
+    change_source VARCHAR(20) NOT NULL DEFAULT 'manual' CHECK (change_source IN ('manual', 'expiry', 'matching')),
+    changed_by BIGINT NULL,
+    changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (job_posting_id) REFERENCES job_postings(id) ON DELETE CASCADE,
+    FOREIGN KEY (changed_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE TABLE job_seeking_status_history (
+    id BIGSERIAL PRIMARY KEY,
+    job_seeking_posting_id BIGINT NOT NULL,
+    from_status VARCHAR(20) NOT NULL,
+    to_status VARCHAR(20) NOT NULL,
+    change_source VARCHAR(20) NOT NULL DEFAULT 'manual' CHECK (change_source IN ('manual', 'expiry', 'matching')),
+    changed_by BIGINT NULL,
+    changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (job_seeking_posting_id) REFERENCES job_seeking_postings(id) ON DELETE CASCADE,
+    FOREIGN KEY (changed_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_job_posting_status_history_posting
+    ON job_posting_status_history(job_posting_id, changed_at);
+CREATE INDEX idx_job_seeking_status_history_posting
+    ON job_seeking_status_history(job_seeking_posting_id, changed_at);
+
+COMMENT ON TABLE job_posting_status_history IS '구인 공고 상태 변경 이력';
+COMMENT ON TABLE job_seeking_status_history IS '구직 공고 상태 변경 이력';
+COMMENT ON COLUMN job_posting_status_history.change_source IS '변경 주체 (manual: 사용자, expiry: 자동 만료, matching: 매칭 연동)';
+COMMENT ON COLUMN job_posting_status_history.changed_by IS '변경한 사용자. 자동 변경이면 NULL';
+COMMENT ON COLUMN job_seeking_status_history.change_source IS '변경 주체 (manual: 사용자, expiry: 자동 만료, matching: 매칭 연동)';
+COMMENT ON COLUMN job_seeking_status_history.changed_by IS '변경한 사용자. 자동 변경이면 NULL';
+
+-- 기존 데이터: 게시중이 아닌 공고는 마지막 수정 시각에 상태가 바뀐 것으로 기록
+INSERT INTO job_posting_status_history (job_posting_id, from_status, to_status, changed_at)
+SELECT id, 'published', posting_status, COALESCE(updated_at, created_at, CURRENT_TIMESTAMP)
+FROM job_postings
+WHERE posting_status <> 'published';
+
+INSERT INTO job_seeking_status_history (job_seeking_posting_id, from_status, to_status, changed_at)
+SELECT id, 'published', posting_status, COALESCE(updated_at, created_at, CURRENT_TIMESTAMP)
+FROM job_seeking_postings
+WHERE posting_status <> 'published';
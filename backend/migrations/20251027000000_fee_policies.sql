@@ -0,0 +1,67 @@
+-- ================================================
+-- 수수료 정책
+-- ================================================
+-- 최저 수수료, 급여 구간별 수수료율, 단기 근무 고정 수수료, 부가세를 묶은 수수료 정책
+-- 사용자(구인자/구직자 측 각각) 또는 공고에 연결하며, 공고에 연결된 정책이 우선함
+-- 계산식은 백엔드 services::fee::calculate 에 있음
+
+CREATE TABLE fee_policies (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    minimum_fee NUMERIC(14, 0) NULL CHECK (minimum_fee >= 0),
+    short_term_max_days INTEGER NULL CHECK (short_term_max_days > 0),
+    short_term_fee NUMERIC(14, 0) NULL CHECK (short_term_fee >= 0),
+    vat_rate NUMERIC(5, 2) NOT NULL DEFAULT 10.00 CHECK (vat_rate >= 0 AND vat_rate <= 100),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE (user_id, name),
+    CHECK ((short_term_max_days IS NULL) = (short_term_fee IS NULL))
+);
+
+CREATE TABLE fee_policy_tiers (
+    id BIGSERIAL PRIMARY KEY,
+    fee_policy_id BIGINT NOT NULL,
+    min_monthly_salary NUMERIC(14, 0) NOT NULL CHECK (min_monthly_salary >= 0),
+    rate NUMERIC(5, 2) NOT NULL CHECK (rate >= 0 AND rate <= 100),
+    FOREIGN KEY (fee_policy_id) REFERENCES fee_policies(id) ON DELETE CASCADE,
+    UNIQUE (fee_policy_id, min_monthly_salary)
+);
+
+CREATE INDEX idx_fee_policies_user_id ON fee_policies(user_id);
+
+CREATE TRIGGER update_fee_policies_updated_at
+BEFORE UPDATE ON fee_policies FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE fee_policies IS '사용자별 수수료 정책';
+COMMENT ON COLUMN fee_policies.minimum_fee IS '최저 수수료 (원, 부가세 별도)';
+COMMENT ON COLUMN fee_policies.short_term_max_days IS '이 일수 이하의 단기 근무에는 고정 수수료 적용';
+COMMENT ON COLUMN fee_policies.short_term_fee IS '단기 근무 고정 수수료 (원, 부가세 별도)';
+COMMENT ON COLUMN fee_policies.vat_rate IS '수수료에 더하는 부가세율 (%)';
+COMMENT ON TABLE fee_policy_tiers IS '수수료 정책의 월 환산 급여 구간별 수수료율. 구간이 없으면 매칭의 수수료율 사용';
+COMMENT ON COLUMN fee_policy_tiers.min_monthly_salary IS '구간 시작 월 환산 급여 (원, 이상)';
+
+-- 정책 연결: 사용자 기본 정책(구인자/구직자 측), 공고별 정책
+ALTER TABLE users
+    ADD COLUMN employer_fee_policy_id BIGINT NULL REFERENCES fee_policies(id) ON DELETE SET NULL,
+    ADD COLUMN employee_fee_policy_id BIGINT NULL REFERENCES fee_policies(id) ON DELETE SET NULL;
+ALTER TABLE job_postings
+    ADD COLUMN fee_policy_id BIGINT NULL REFERENCES fee_policies(id) ON DELETE SET NULL;
+ALTER TABLE job_seeking_postings
+    ADD COLUMN fee_policy_id BIGINT NULL REFERENCES fee_policies(id) ON DELETE SET NULL;
+
+COMMENT ON COLUMN users.employer_fee_policy_id IS '구인자 수수료 기본 정책. 설정되면 default_employer_fee_rate 대신 사용';
+COMMENT ON COLUMN users.employee_fee_policy_id IS '구직자 수수료 기본 정책. 설정되면 default_employee_fee_rate 대신 사용';
+COMMENT ON COLUMN job_postings.fee_policy_id IS '이 공고의 구인자 수수료 정책 (사용자 기본 정책보다 우선)';
+COMMENT ON COLUMN job_seeking_postings.fee_policy_id IS '이 공고의 구직자 수수료 정책 (사용자 기본 정책보다 우선)';
+
+-- 매칭: 근무 기간과 수수료 계산 내역
+ALTER TABLE matchings
+    ADD COLUMN contract_days INTEGER NULL CHECK (contract_days > 0),
+    ADD COLUMN employer_fee_breakdown JSONB NULL,
+    ADD COLUMN employee_fee_breakdown JSONB NULL;
+
+COMMENT ON COLUMN matchings.contract_days IS '계약 근무 일수. 단기 근무 고정 수수료 판단에 사용';
+COMMENT ON COLUMN matchings.employer_fee_breakdown IS '구인자 수수료 계산 내역 (적용 정책, 구간, 최저 수수료, 부가세)';
+COMMENT ON COLUMN matchings.employee_fee_breakdown IS '구직자 수수료 계산 내역 (적용 정책, 구간, 최저 수수료, 부가세)';
@@ -0,0 +1,15 @@
+-- ================================================
+-- 매칭 수수료율 출처
+-- ================================================
+-- 매칭 생성 시 수수료율은 요청 → 공고 → 사용자 기본값 순으로 결정되며
+-- 수수료 정책의 급여 구간이 적용되면 정책 수수료율을 사용함
+-- 어떤 값이 적용됐는지 구인자/구직자 측 각각 기록
+
+ALTER TABLE matchings
+    ADD COLUMN employer_fee_rate_source VARCHAR(20) NOT NULL DEFAULT 'request'
+        CHECK (employer_fee_rate_source IN ('request', 'posting', 'user_default', 'policy')),
+    ADD COLUMN employee_fee_rate_source VARCHAR(20) NOT NULL DEFAULT 'request'
+        CHECK (employee_fee_rate_source IN ('request', 'posting', 'user_default', 'policy'));
+
+COMMENT ON COLUMN matchings.employer_fee_rate_source IS '구인자 수수료율 출처 (request: 요청값, posting: 구인 공고, user_default: 사용자 기본값, policy: 수수료 정책 구간)';
+COMMENT ON COLUMN matchings.employee_fee_rate_source IS '구직자 수수료율 출처 (request: 요청값, posting: 구직 공고, user_default: 사용자 기본값, policy: 수수료 정책 구간)';
@@ -0,0 +1,36 @@
+-- ================================================
+-- 매칭 보증 기간 (무상 교체 / 환불)
+-- ================================================
+-- 완료된 매칭의 근무자가 보증 기간 안에 그만두면 구인자에게
+-- 수수료 없이 다른 구직자를 연결(교체)하거나 남은 기간만큼 수수료를 환불함
+-- 환불 계산식은 백엔드 services::guarantee 에 있음
+
+ALTER TABLE matchings
+    ADD COLUMN guarantee_days INTEGER NULL CHECK (guarantee_days > 0),
+    ADD COLUMN guarantee_expires_at TIMESTAMP
+        GENERATED ALWAYS AS (completed_at + guarantee_days * INTERVAL '1 day') STORED,
+    ADD COLUMN replaces_matching_id BIGINT NULL REFERENCES matchings(id) ON DELETE SET NULL,
+    ADD COLUMN guarantee_resolution VARCHAR(20) NULL
+        CHECK (guarantee_resolution IN ('replacement', 'refund')),
+    ADD COLUMN guarantee_broken_on DATE NULL,
+    ADD COLUMN guarantee_refund_amount DECIMAL(12, 2) NULL CHECK (guarantee_refund_amount >= 0);
+
+-- 교체 매칭의 구인자 수수료율 출처
+ALTER TABLE matchings
+    DROP CONSTRAINT matchings_employer_fee_rate_source_check,
+    ADD CONSTRAINT matchings_employer_fee_rate_source_check
+        CHECK (employer_fee_rate_source IN ('request', 'posting', 'user_default', 'policy', 'replacement'));
+
+-- 원 매칭 하나에 취소되지 않은 교체 매칭은 하나만
+CREATE UNIQUE INDEX idx_matchings_replaces_matching_id
+    ON matchings(replaces_matching_id)
+    WHERE replaces_matching_id IS NOT NULL AND matching_status <> 'cancelled' AND deleted_at IS NULL;
+CREATE INDEX idx_matchings_guarantee_expires_at
+    ON matchings(guarantee_expires_at) WHERE guarantee_resolution IS NULL AND deleted_at IS NULL;
+
+COMMENT ON COLUMN matchings.guarantee_days IS '보증 기간 (일). 매칭 완료일부터 계산, NULL이면 보증 없음';
+COMMENT ON COLUMN matchings.guarantee_expires_at IS '보증 만료 시각 (completed_at + guarantee_days)';
+COMMENT ON COLUMN matchings.replaces_matching_id IS '이 매칭이 무상 교체한 원 매칭';
+COMMENT ON COLUMN matchings.guarantee_resolution IS '보증 처리 방식 (replacement: 무상 교체, refund: 환불)';
+COMMENT ON COLUMN matchings.guarantee_broken_on IS '근무자가 그만둔 날';
+COMMENT ON COLUMN matchings.guarantee_refund_amount IS '구인자에게 환불한 수수료 (남은 보증 일수 비율)';
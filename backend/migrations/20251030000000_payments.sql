@@ -0,0 +1,62 @@
+-- ================================================
+-- 수수료 입금 내역 (분할 입금)
+-- ================================================
+-- 매칭의 구인자/구직자 측 수수료 입금을 건별로 기록
+-- 미수금은 매칭 수수료 - 보증 환불 - 입금 합계로 계산하며
+-- 공고의 settlement_status 는 입금이 바뀔 때마다 연결된 매칭들로부터 다시 계산됨
+
+CREATE TABLE payments (
+    id BIGSERIAL PRIMARY KEY,
+    matching_id BIGINT NOT NULL,
+    payer_side VARCHAR(20) NOT NULL CHECK (payer_side IN ('employer', 'employee')),
+    amount DECIMAL(12, 2) NOT NULL CHECK (amount > 0),
+    paid_on DATE NOT NULL,
+    payment_method VARCHAR(20) NOT NULL DEFAULT 'bank_transfer'
+        CHECK (payment_method IN ('cash', 'bank_transfer', 'card', 'other')),
+    memo TEXT NULL,
+    created_by BIGINT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (matching_id) REFERENCES matchings(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_payments_matching_id ON payments(matching_id, payer_side);
+CREATE INDEX idx_payments_paid_on ON payments(paid_on);
+
+COMMENT ON TABLE payments IS '매칭 수수료 입금 내역';
+COMMENT ON COLUMN payments.payer_side IS '입금한 쪽 (employer: 구인자, employee: 구직자)';
+COMMENT ON COLUMN payments.amount IS '입금액 (원)';
+COMMENT ON COLUMN payments.paid_on IS '입금일';
+COMMENT ON COLUMN payments.payment_method IS '입금 방법 (cash: 현금, bank_transfer: 계좌이체, card: 카드, other: 기타)';
+
+-- 부분 정산 상태
+ALTER TABLE job_postings
+    DROP CONSTRAINT job_postings_settlement_status_check,
+    ADD CONSTRAINT job_postings_settlement_status_check
+        CHECK (settlement_status IN ('unsettled', 'partially_settled', 'settled'));
+ALTER TABLE job_seeking_postings
+    DROP CONSTRAINT job_seeking_postings_settlement_status_check,
+    ADD CONSTRAINT job_seeking_postings_settlement_status_check
+        CHECK (settlement_status IN ('unsettled', 'partially_settled', 'settled'));
+
+-- 기존에 정산완료로 표시된 공고의 매칭은 수수료 전액이 입금된 것으로 이관
+-- (이후 입금 내역으로 정산 상태를 다시 계산해도 정산완료가 유지되도록)
+INSERT INTO payments (matching_id, payer_side, amount, paid_on, payment_method, memo)
+SELECT m.id, 'employer', m.employer_fee_amount, COALESCE(m.completed_at, m.matched_at, m.created_at)::date,
+       'other', '기존 정산완료 내역 이관'
+FROM matchings m
+INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+WHERE jp.settlement_status = 'settled'
+    AND m.employer_fee_amount > 0
+    AND m.matching_status <> 'cancelled'
+    AND m.deleted_at IS NULL;
+
+INSERT INTO payments (matching_id, payer_side, amount, paid_on, payment_method, memo)
+SELECT m.id, 'employee', m.employee_fee_amount, COALESCE(m.completed_at, m.matched_at, m.created_at)::date,
+       'other', '기존 정산완료 내역 이관'
+FROM matchings m
+INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+WHERE jsp.settlement_status = 'settled'
+    AND m.employee_fee_amount > 0
+    AND m.matching_status <> 'cancelled'
+    AND m.deleted_at IS NULL;
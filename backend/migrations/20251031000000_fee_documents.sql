@@ -0,0 +1,43 @@
+-- ================================================
+-- 수수료 청구서/영수증 발행
+-- ================================================
+-- 매칭 한쪽(구인자/구직자)의 수수료에 대한 청구서와 영수증을 PDF 로 만들어 MinIO 에 저장
+-- 문서 번호는 사용자별, 문서 종류별, 연도별로 1부터 빠짐없이 증가
+
+CREATE TABLE document_sequences (
+    user_id BIGINT NOT NULL,
+    document_type VARCHAR(20) NOT NULL,
+    year INTEGER NOT NULL,
+    last_number INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, document_type, year),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+COMMENT ON TABLE document_sequences IS '사용자별 문서 번호 채번';
+COMMENT ON COLUMN document_sequences.last_number IS '마지막으로 발행한 번호';
+
+CREATE TABLE fee_documents (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    matching_id BIGINT NOT NULL,
+    document_type VARCHAR(20) NOT NULL CHECK (document_type IN ('invoice', 'receipt')),
+    payer_side VARCHAR(20) NOT NULL CHECK (payer_side IN ('employer', 'employee')),
+    document_number VARCHAR(30) NOT NULL,
+    issued_on DATE NOT NULL,
+    total_amount DECIMAL(12, 2) NOT NULL,
+    file_path VARCHAR(500) NOT NULL,
+    file_size BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (matching_id) REFERENCES matchings(id) ON DELETE CASCADE,
+    UNIQUE (user_id, document_number)
+);
+
+CREATE INDEX idx_fee_documents_matching_id ON fee_documents(matching_id);
+
+COMMENT ON TABLE fee_documents IS '발행한 수수료 청구서/영수증';
+COMMENT ON COLUMN fee_documents.document_type IS '문서 종류 (invoice: 청구서, receipt: 영수증)';
+COMMENT ON COLUMN fee_documents.payer_side IS '수신자 쪽 (employer: 구인자, employee: 구직자)';
+COMMENT ON COLUMN fee_documents.document_number IS '문서 번호 (예: INV-2025-0001, RCP-2025-0001)';
+COMMENT ON COLUMN fee_documents.total_amount IS '청구서는 청구 금액, 영수증은 영수 금액 (원)';
+COMMENT ON COLUMN fee_documents.file_path IS 'MinIO 객체 경로';
@@ -0,0 +1,48 @@
+-- ================================================
+-- 은행 입금 내역 가져오기
+-- ================================================
+-- 은행에서 내려받은 거래내역 CSV 의 입금 건을 저장하고
+-- 매칭 수수료와 짝지은 입금 건은 payments 에 입금으로 기록
+
+CREATE TABLE bank_imports (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    file_name VARCHAR(255) NULL,
+    total_rows INTEGER NOT NULL,
+    imported_rows INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_bank_imports_user_id ON bank_imports(user_id, created_at DESC);
+
+COMMENT ON TABLE bank_imports IS '은행 거래내역 파일 가져오기 이력';
+COMMENT ON COLUMN bank_imports.total_rows IS '파일의 입금 건수';
+COMMENT ON COLUMN bank_imports.imported_rows IS '이미 가져온 건을 제외하고 새로 저장된 입금 건수';
+
+CREATE TABLE bank_transactions (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    bank_import_id BIGINT NOT NULL,
+    transacted_at TIMESTAMP NOT NULL,
+    depositor VARCHAR(100) NOT NULL,
+    amount DECIMAL(12, 2) NOT NULL CHECK (amount > 0),
+    memo VARCHAR(255) NULL,
+    balance_after DECIMAL(14, 2) NULL,
+    payment_id BIGINT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (bank_import_id) REFERENCES bank_imports(id) ON DELETE CASCADE,
+    FOREIGN KEY (payment_id) REFERENCES payments(id) ON DELETE SET NULL
+);
+
+-- 같은 파일이나 기간이 겹치는 파일을 다시 가져와도 입금 건이 중복 저장되지 않도록 함
+CREATE UNIQUE INDEX idx_bank_transactions_unique
+    ON bank_transactions(user_id, transacted_at, amount, depositor, (COALESCE(balance_after, -1)));
+CREATE INDEX idx_bank_transactions_import_id ON bank_transactions(bank_import_id);
+CREATE UNIQUE INDEX idx_bank_transactions_payment_id ON bank_transactions(payment_id);
+
+COMMENT ON TABLE bank_transactions IS '가져온 은행 입금 건';
+COMMENT ON COLUMN bank_transactions.depositor IS '입금자명 (통장 표시 내용)';
+COMMENT ON COLUMN bank_transactions.balance_after IS '거래 후 잔액';
+COMMENT ON COLUMN bank_transactions.payment_id IS '이 입금으로 기록한 수수료 입금 (없으면 미확정)';
@@ -0,0 +1,74 @@
+-- ================================================
+-- 정산 변경 이력
+-- ================================================
+-- 공고의 정산 상태/금액이 바뀔 때마다 이전 값과 새 값을 기록
+-- 연결된 매칭이 있는 공고의 정산 금액은 매칭 수수료 합계(보증 환불 차감)로,
+-- 정산 상태는 입금 내역으로 자동 계산됨
+
+CREATE TABLE job_posting_settlement_history (
+    id BIGSERIAL PRIMARY KEY,
+    job_posting_id BIGINT NOT NULL,
+    from_status VARCHAR(20) NOT NULL,
+    to_status VARCHAR(20) NOT NULL,
+    from_amount DECIMAL(12, 2) NULL,
+    to_amount DECIMAL(12, 2) NULL,
+    memo TEXT NULL,
+    change_source VARCHAR(20) NOT NULL DEFAULT 'manual' CHECK (change_source IN ('manual', 'payment', 'matching')),
+    changed_by BIGINT NULL,
+    changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (job_posting_id) REFERENCES job_postings(id) ON DELETE CASCADE,
+    FOREIGN KEY (changed_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE TABLE job_seeking_settlement_history (
+    id BIGSERIAL PRIMARY KEY,
+    job_seeking_posting_id BIGINT NOT NULL,
+    from_status VARCHAR(20) NOT NULL,
+    to_status VARCHAR(20) NOT NULL,
+    from_amount DECIMAL(12, 2) NULL,
+    to_amount DECIMAL(12, 2) NULL,
+    memo TEXT NULL,
+    change_source VARCHAR(20) NOT NULL DEFAULT 'manual' CHECK (change_source IN ('manual', 'payment', 'matching')),
+    changed_by BIGINT NULL,
+    changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (job_seeking_posting_id) REFERENCES job_seeking_postings(id) ON DELETE CASCADE,
+    FOREIGN KEY (changed_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_job_posting_settlement_history_posting
+    ON job_posting_settlement_history(job_posting_id, changed_at);
+CREATE INDEX idx_job_seeking_settlement_history_posting
+    ON job_seeking_settlement_history(job_seeking_posting_id, changed_at);
+
+COMMENT ON TABLE job_posting_settlement_history IS '구인 공고 정산 변경 이력';
+COMMENT ON TABLE job_seeking_settlement_history IS '구직 공고 정산 변경 이력';
+COMMENT ON COLUMN job_posting_settlement_history.memo IS '변경 후 정산 메모';
+COMMENT ON COLUMN job_posting_settlement_history.change_source IS '변경 주체 (manual: 사용자, payment: 입금 등록/삭제, matching: 매칭 생성/변경/취소/환불)';
+COMMENT ON COLUMN job_posting_settlement_history.changed_by IS '변경한 사용자. 자동 변경이면 NULL';
+COMMENT ON COLUMN job_seeking_settlement_history.memo IS '변경 후 정산 메모';
+COMMENT ON COLUMN job_seeking_settlement_history.change_source IS '변경 주체 (manual: 사용자, payment: 입금 등록/삭제, matching: 매칭 생성/변경/취소/환불)';
+COMMENT ON COLUMN job_seeking_settlement_history.changed_by IS '변경한 사용자. 자동 변경이면 NULL';
+
+-- 기존 데이터: 연결된 매칭이 있는 공고의 정산 금액을 매칭 수수료 합계로 맞춤
+UPDATE job_postings jp
+SET settlement_amount = fees.total
+FROM (
+    SELECT job_posting_id,
+           SUM(GREATEST(COALESCE(employer_fee_amount, 0) - COALESCE(guarantee_refund_amount, 0), 0)) AS total
+    FROM matchings
+    WHERE matching_status <> 'cancelled' AND deleted_at IS NULL
+    GROUP BY job_posting_id
+) fees
+WHERE jp.id = fees.job_posting_id
+    AND jp.settlement_amount IS DISTINCT FROM fees.total;
+
+UPDATE job_seeking_postings jsp
+SET settlement_amount = fees.total
+FROM (
+    SELECT job_seeking_posting_id, SUM(COALESCE(employee_fee_amount, 0)) AS total
+    FROM matchings
+    WHERE matching_status <> 'cancelled' AND deleted_at IS NULL
+    GROUP BY job_seeking_posting_id
+) fees
+WHERE jsp.id = fees.job_seeking_posting_id
+    AND jsp.settlement_amount IS DISTINCT FROM fees.total;
@@ -0,0 +1,245 @@
+-- ================================================
+-- 대시보드 통계 일별 집계
+-- ================================================
+-- 사용자별·일자별 통계 값을 미리 집계해 두고 대시보드와 분석 API는 이 테이블을 조회
+-- 고객/공고/매칭/입금/공고 상태 이력이 바뀌면 트리거가 영향받는 날짜를 daily_metrics_dirty에 기록하고,
+-- 조회 시 또는 백그라운드 작업이 해당 날짜만 다시 집계함
+
+CREATE TABLE daily_metrics (
+    user_id BIGINT NOT NULL,
+    day DATE NOT NULL,
+    metric VARCHAR(30) NOT NULL,
+    value NUMERIC NOT NULL,
+    refreshed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, day, metric),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- 사용자 삭제 중에도 트리거가 기록할 수 있도록 외래 키를 두지 않음
+CREATE TABLE daily_metrics_dirty (
+    user_id BIGINT NOT NULL,
+    day DATE NOT NULL,
+    marked_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, day)
+);
+
+COMMENT ON TABLE daily_metrics IS '사용자별 일별 통계 집계';
+COMMENT ON COLUMN daily_metrics.metric IS '통계 항목 (revenue_earned, new_customers, fees_charged 등)';
+COMMENT ON TABLE daily_metrics_dirty IS '다시 집계해야 하는 사용자별 날짜';
+
+-- ================================================
+-- 변경 추적 트리거
+-- ================================================
+
+CREATE OR REPLACE FUNCTION mark_daily_metrics_dirty(p_user_id BIGINT, p_day DATE)
+RETURNS VOID AS $$
+BEGIN
+    IF p_user_id IS NOT NULL AND p_day IS NOT NULL THEN
+        INSERT INTO daily_metrics_dirty (user_id, day)
+        VALUES (p_user_id, p_day)
+        ON CONFLICT DO NOTHING;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION customers_mark_daily_metrics()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        PERFORM mark_daily_metrics_dirty(OLD.user_id, OLD.created_at::date);
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        PERFORM mark_daily_metrics_dirty(NEW.user_id, NEW.created_at::date);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER customers_daily_metrics
+AFTER INSERT OR DELETE OR UPDATE OF user_id, created_at, deleted_at ON customers
+FOR EACH ROW EXECUTE FUNCTION customers_mark_daily_metrics();
+
+-- 공고 등록일과 공고가 마감된 날짜
+CREATE OR REPLACE FUNCTION mark_posting_daily_metrics(p_kind TEXT, p_posting_id BIGINT, p_customer_id BIGINT, p_created_at TIMESTAMP)
+RETURNS VOID AS $$
+DECLARE
+    owner_id BIGINT;
+BEGIN
+    SELECT user_id INTO owner_id FROM customers WHERE id = p_customer_id;
+    PERFORM mark_daily_metrics_dirty(owner_id, p_created_at::date);
+
+    IF p_kind = 'job_posting' THEN
+        PERFORM mark_daily_metrics_dirty(owner_id, h.changed_at::date)
+        FROM job_posting_status_history h
+        WHERE h.job_posting_id = p_posting_id AND h.to_status = 'closed';
+    ELSE
+        PERFORM mark_daily_metrics_dirty(owner_id, h.changed_at::date)
+        FROM job_seeking_status_history h
+        WHERE h.job_seeking_posting_id = p_posting_id AND h.to_status = 'closed';
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION postings_mark_daily_metrics()
+RETURNS TRIGGER AS $$
+DECLARE
+    kind TEXT := CASE WHEN TG_TABLE_NAME = 'job_postings' THEN 'job_posting' ELSE 'job_seeking' END;
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        PERFORM mark_posting_daily_metrics(kind, OLD.id, OLD.customer_id, OLD.created_at);
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        PERFORM mark_posting_daily_metrics(kind, NEW.id, NEW.customer_id, NEW.created_at);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER job_postings_daily_metrics
+AFTER INSERT OR DELETE OR UPDATE OF customer_id, created_at, deleted_at ON job_postings
+FOR EACH ROW EXECUTE FUNCTION postings_mark_daily_metrics();
+
+CREATE TRIGGER job_seeking_postings_daily_metrics
+AFTER INSERT OR DELETE OR UPDATE OF customer_id, created_at, deleted_at ON job_seeking_postings
+FOR EACH ROW EXECUTE FUNCTION postings_mark_daily_metrics();
+
+-- 매칭일, 완료일, 취소일, 구인 공고 등록일(채용 전환율)과 매칭에 들어온 입금일
+CREATE OR REPLACE FUNCTION mark_matching_daily_metrics(m matchings)
+RETURNS VOID AS $$
+DECLARE
+    owner_id BIGINT;
+    posted_on DATE;
+BEGIN
+    SELECT c.user_id, jp.created_at::date INTO owner_id, posted_on
+    FROM job_postings jp
+    INNER JOIN customers c ON jp.customer_id = c.id
+    WHERE jp.id = m.job_posting_id;
+
+    PERFORM mark_daily_metrics_dirty(owner_id, COALESCE(m.matched_at, m.created_at)::date);
+    PERFORM mark_daily_metrics_dirty(owner_id, m.completed_at::date);
+    PERFORM mark_daily_metrics_dirty(owner_id, m.cancelled_at::date);
+    PERFORM mark_daily_metrics_dirty(owner_id, posted_on);
+    PERFORM mark_daily_metrics_dirty(owner_id, p.paid_on)
+    FROM payments p
+    WHERE p.matching_id = m.id;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION matchings_mark_daily_metrics()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        PERFORM mark_matching_daily_metrics(OLD);
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        PERFORM mark_matching_daily_metrics(NEW);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER matchings_daily_metrics
+AFTER INSERT OR DELETE OR UPDATE ON matchings
+FOR EACH ROW EXECUTE FUNCTION matchings_mark_daily_metrics();
+
+CREATE OR REPLACE FUNCTION payments_mark_daily_metrics()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        PERFORM mark_daily_metrics_dirty(c.user_id, OLD.paid_on)
+        FROM matchings m
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN customers c ON jp.customer_id = c.id
+        WHERE m.id = OLD.matching_id;
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        PERFORM mark_daily_metrics_dirty(c.user_id, NEW.paid_on)
+        FROM matchings m
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN customers c ON jp.customer_id = c.id
+        WHERE m.id = NEW.matching_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER payments_daily_metrics
+AFTER INSERT OR DELETE OR UPDATE ON payments
+FOR EACH ROW EXECUTE FUNCTION payments_mark_daily_metrics();
+
+CREATE OR REPLACE FUNCTION job_posting_status_history_mark_daily_metrics()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.to_status = 'closed' THEN
+        PERFORM mark_daily_metrics_dirty(c.user_id, NEW.changed_at::date)
+        FROM job_postings jp
+        INNER JOIN customers c ON jp.customer_id = c.id
+        WHERE jp.id = NEW.job_posting_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER job_posting_status_history_daily_metrics
+AFTER INSERT ON job_posting_status_history
+FOR EACH ROW EXECUTE FUNCTION job_posting_status_history_mark_daily_metrics();
+
+CREATE OR REPLACE FUNCTION job_seeking_status_history_mark_daily_metrics()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.to_status = 'closed' THEN
+        PERFORM mark_daily_metrics_dirty(c.user_id, NEW.changed_at::date)
+        FROM job_seeking_postings jsp
+        INNER JOIN customers c ON jsp.customer_id = c.id
+        WHERE jsp.id = NEW.job_seeking_posting_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER job_seeking_status_history_daily_metrics
+AFTER INSERT ON job_seeking_status_history
+FOR EACH ROW EXECUTE FUNCTION job_seeking_status_history_mark_daily_metrics();
+
+-- ================================================
+-- 기존 데이터 집계 대상 등록
+-- ================================================
+
+INSERT INTO daily_metrics_dirty (user_id, day)
+SELECT user_id, day FROM (
+    SELECT user_id, created_at::date AS day FROM customers
+    UNION
+    SELECT c.user_id, jp.created_at::date
+    FROM job_postings jp INNER JOIN customers c ON jp.customer_id = c.id
+    UNION
+    SELECT c.user_id, jsp.created_at::date
+    FROM job_seeking_postings jsp INNER JOIN customers c ON jsp.customer_id = c.id
+    UNION
+    SELECT c.user_id, d.day
+    FROM matchings m
+    INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+    INNER JOIN customers c ON jp.customer_id = c.id
+    CROSS JOIN LATERAL (
+        VALUES (COALESCE(m.matched_at, m.created_at)::date), (m.completed_at::date), (m.cancelled_at::date)
+    ) AS d(day)
+    UNION
+    SELECT c.user_id, p.paid_on
+    FROM payments p
+    INNER JOIN matchings m ON p.matching_id = m.id
+    INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+    INNER JOIN customers c ON jp.customer_id = c.id
+    UNION
+    SELECT c.user_id, h.changed_at::date
+    FROM job_posting_status_history h
+    INNER JOIN job_postings jp ON h.job_posting_id = jp.id
+    INNER JOIN customers c ON jp.customer_id = c.id
+    WHERE h.to_status = 'closed'
+    UNION
+    SELECT c.user_id, h.changed_at::date
+    FROM job_seeking_status_history h
+    INNER JOIN job_seeking_postings jsp ON h.job_seeking_posting_id = jsp.id
+    INNER JOIN customers c ON jsp.customer_id = c.id
+    WHERE h.to_status = 'closed'
+) days
+WHERE day IS NOT NULL
+ON CONFLICT DO NOTHING;
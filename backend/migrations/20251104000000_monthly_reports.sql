@@ -0,0 +1,23 @@
+-- ================================================
+-- 월간 업무 보고서
+-- ================================================
+-- 매월 1일 지난달 보고서(매칭 완료, 수수료 발생/수금, 미수금, 상위 고객, 취소 사유)를
+-- PDF 와 XLSX 로 만들어 MinIO 에 저장. 같은 달을 다시 생성하면 덮어씀
+
+CREATE TABLE monthly_reports (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    year INTEGER NOT NULL,
+    month INTEGER NOT NULL CHECK (month BETWEEN 1 AND 12),
+    pdf_path VARCHAR(500) NOT NULL,
+    pdf_size BIGINT NOT NULL,
+    xlsx_path VARCHAR(500) NOT NULL,
+    xlsx_size BIGINT NOT NULL,
+    generated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE (user_id, year, month)
+);
+
+COMMENT ON TABLE monthly_reports IS '저장된 월간 업무 보고서';
+COMMENT ON COLUMN monthly_reports.pdf_path IS 'PDF 파일의 MinIO 객체 경로';
+COMMENT ON COLUMN monthly_reports.xlsx_path IS 'XLSX 파일의 MinIO 객체 경로';
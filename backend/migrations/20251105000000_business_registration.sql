@@ -0,0 +1,26 @@
+-- ================================================
+-- 사업자 등록 정보 (세금계산서 내보내기)
+-- ================================================
+-- 공급자(중개소) 사업자 정보를 사용자 프로필에, 공급받는자 사업자등록번호를
+-- 구인자 고객에 저장. 사업자등록번호는 하이픈 없는 숫자 10자리
+
+ALTER TABLE users
+    ADD COLUMN business_number VARCHAR(10),
+    ADD COLUMN business_name VARCHAR(100),
+    ADD COLUMN representative_name VARCHAR(50),
+    ADD COLUMN business_address VARCHAR(500),
+    ADD COLUMN business_type VARCHAR(100),
+    ADD COLUMN business_item VARCHAR(100),
+    ADD COLUMN tax_invoice_email VARCHAR(255);
+
+ALTER TABLE customers
+    ADD COLUMN business_number VARCHAR(10);
+
+COMMENT ON COLUMN users.business_number IS '중개소 사업자등록번호 (숫자 10자리)';
+COMMENT ON COLUMN users.business_name IS '상호';
+COMMENT ON COLUMN users.representative_name IS '대표자 성명';
+COMMENT ON COLUMN users.business_address IS '사업장 주소';
+COMMENT ON COLUMN users.business_type IS '업태';
+COMMENT ON COLUMN users.business_item IS '종목';
+COMMENT ON COLUMN users.tax_invoice_email IS '세금계산서 수신 이메일';
+COMMENT ON COLUMN customers.business_number IS '구인자 사업자등록번호 (숫자 10자리, 선택)';
@@ -0,0 +1,15 @@
+-- ================================================
+-- 검색 스니펫 HTML 이스케이프
+-- ================================================
+-- ts_headline 은 원문 그대로에 <mark> 태그를 넣으므로, 메모/설명에 들어 있는
+-- HTML 이 스니펫에 그대로 섞여 나감. 원문을 먼저 이스케이프해서 <mark> 만
+-- 태그로 남게 함. 이스케이프된 엔티티(&amp; 등)는 'simple' 설정에서 색인되지 않아
+-- 강조되지 않음
+
+CREATE OR REPLACE FUNCTION html_escape(input TEXT)
+RETURNS TEXT AS $$
+    SELECT replace(replace(replace(replace(replace(
+        input, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;')
+$$ LANGUAGE sql IMMUTABLE STRICT;
+
+COMMENT ON FUNCTION html_escape(TEXT) IS '검색 스니펫용 HTML 이스케이프 (ts_headline 전에 적용)';
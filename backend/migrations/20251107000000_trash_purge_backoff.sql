@@ -0,0 +1,19 @@
+-- ================================================
+-- 휴지통 파일 영구 삭제 재시도 간격
+-- ================================================
+-- MinIO 객체 삭제가 계속 실패하는 파일이 매번 다시 선택되어 배치를 차지하지
+-- 않도록 실패 횟수와 다음 시도 시각을 기록. 재시도 간격은 1시간부터 두 배씩
+-- 늘어나며 최대 128시간
+
+ALTER TABLE customer_files
+    ADD COLUMN purge_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN purge_retry_at TIMESTAMP NULL;
+
+ALTER TABLE user_files
+    ADD COLUMN purge_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN purge_retry_at TIMESTAMP NULL;
+
+COMMENT ON COLUMN customer_files.purge_attempts IS '영구 삭제 중 저장소 객체 삭제에 실패한 횟수';
+COMMENT ON COLUMN customer_files.purge_retry_at IS '이 시각 이후에 영구 삭제를 다시 시도';
+COMMENT ON COLUMN user_files.purge_attempts IS '영구 삭제 중 저장소 객체 삭제에 실패한 횟수';
+COMMENT ON COLUMN user_files.purge_retry_at IS '이 시각 이후에 영구 삭제를 다시 시도';
@@ -0,0 +1,10 @@
+-- ================================================
+-- 입금 내역 보존
+-- ================================================
+-- 휴지통 영구 삭제가 매칭을 지울 때 입금 내역까지 함께 삭제되지 않도록
+-- 매칭 삭제를 막음. 입금이 있는 매칭은 영구 삭제 대상에서 제외됨
+
+ALTER TABLE payments
+    DROP CONSTRAINT payments_matching_id_fkey,
+    ADD CONSTRAINT payments_matching_id_fkey
+        FOREIGN KEY (matching_id) REFERENCES matchings(id) ON DELETE RESTRICT;
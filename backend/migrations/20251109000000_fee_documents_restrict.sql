@@ -0,0 +1,11 @@
+-- ================================================
+-- 발행 문서 보존
+-- ================================================
+-- 휴지통 영구 삭제가 매칭을 지울 때 발행한 청구서/영수증 기록이 함께 삭제되어
+-- MinIO 의 PDF(documents/{user}/{number}.pdf)가 고아 객체로 남지 않도록
+-- 매칭 삭제를 막음. 문서를 발행한 매칭은 영구 삭제 대상에서 제외됨
+
+ALTER TABLE fee_documents
+    DROP CONSTRAINT fee_documents_matching_id_fkey,
+    ADD CONSTRAINT fee_documents_matching_id_fkey
+        FOREIGN KEY (matching_id) REFERENCES matchings(id) ON DELETE RESTRICT;
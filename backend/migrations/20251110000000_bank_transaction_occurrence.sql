@@ -0,0 +1,18 @@
+-- ================================================
+-- 같은 내용의 입금 건 구분
+-- ================================================
+-- 잔액 열이 없는 거래내역에서는 같은 시각, 같은 입금자, 같은 금액의 입금 두 건이
+-- 하나로 합쳐져 한 건이 사라짐. 한 파일 안에서 같은 내용의 입금이 몇 번째인지를
+-- 중복 판단 키에 포함해, 같은 파일을 다시 가져오면 건너뛰되 실제로 여러 번 들어온
+-- 입금은 모두 저장함
+
+ALTER TABLE bank_transactions
+    ADD COLUMN occurrence INTEGER NOT NULL DEFAULT 1 CHECK (occurrence > 0);
+
+DROP INDEX idx_bank_transactions_unique;
+CREATE UNIQUE INDEX idx_bank_transactions_unique
+    ON bank_transactions(
+        user_id, transacted_at, amount, depositor, (COALESCE(balance_after, -1)), occurrence
+    );
+
+COMMENT ON COLUMN bank_transactions.occurrence IS '한 파일 안에서 같은 시각, 입금자, 금액, 잔액의 입금 중 몇 번째인지 (1부터)';
@@ -0,0 +1,15 @@
+-- ================================================
+-- 구직 공고 등록 수 집계 항목 추가
+-- ================================================
+-- 대시보드의 구직 공고 수를 전체 공고 수에서 구인 공고 수를 빼서 구하지 않고
+-- job_seekings_opened 항목으로 직접 집계함
+-- 기존 집계에는 이 항목이 없으므로 구직 공고가 등록된 날짜를 다시 집계 대상으로 등록
+
+INSERT INTO daily_metrics_dirty (user_id, day)
+SELECT DISTINCT c.user_id, jsp.created_at::date
+FROM job_seeking_postings jsp
+INNER JOIN customers c ON jsp.customer_id = c.id
+WHERE jsp.created_at IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+COMMENT ON COLUMN daily_metrics.metric IS '통계 항목 (revenue_earned, new_customers, job_seekings_opened, fees_charged 등)';
@@ -14,6 +14,12 @@ pub struct Config {
     pub allowed_origins: Vec<String>,
     pub database_max_connections: u32,
     pub run_migrations_on_start: bool,
+    pub trash_retention_days: i64,
+    pub trash_purge_interval_secs: u64,
+    pub posting_expiry_interval_secs: u64,
+    pub analytics_rollup_interval_secs: u64,
+    pub monthly_report_interval_secs: u64,
+    pub pdf_font_path: Option<String>,
 }
 
 impl Config {
@@ -64,6 +70,32 @@ impl Config {
                     )
                 })
                 .unwrap_or(true),
+            trash_retention_days: env::var("TRASH_RETENTION_DAYS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(30),
+            trash_purge_interval_secs: env::var("TRASH_PURGE_INTERVAL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(3600),
+            posting_expiry_interval_secs: env::var("POSTING_EXPIRY_INTERVAL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(600),
+            analytics_rollup_interval_secs: env::var("ANALYTICS_ROLLUP_INTERVAL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(300),
+            monthly_report_interval_secs: env::var("MONTHLY_REPORT_INTERVAL_SECS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(3600),
+            pdf_font_path: env::var("PDF_FONT_PATH").ok(),
         })
     }
 }
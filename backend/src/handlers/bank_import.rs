@@ -0,0 +1,192 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    Json,
+};
+use bytes::BytesMut;
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::middleware::auth::AuthUser;
+use crate::models::bank_import::{
+    BankImportDetail, BankTransactionWithSuggestions, ConfirmBankMatchesRequest, ParsedDeposit,
+};
+use crate::repositories::bank_import::{self, BankImportError};
+use crate::repositories::payment::LedgerError;
+use crate::services::bank_import as statement;
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Import the deposits of a bank statement CSV and suggest the fees each
+/// deposit may be paying
+pub async fn create_bank_import(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<BankImportDetail>), (StatusCode, Json<ErrorResponse>)> {
+    let mut field = multipart
+        .next_field()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read multipart field: {:?}", e);
+            bad_request("파일을 읽을 수 없습니다")
+        })?
+        .ok_or_else(|| bad_request("파일이 없습니다"))?;
+
+    let file_name = field.file_name().map(|s| s.to_string());
+
+    // Read file data with size limit
+    const MAX_STATEMENT_SIZE_BYTES: usize = 5 * 1024 * 1024; // 5 MB
+    let mut data = BytesMut::new();
+    while let Some(chunk) = field.chunk().await.map_err(|e| {
+        tracing::error!("Failed to read file bytes: {:?}", e);
+        bad_request("파일을 읽을 수 없습니다")
+    })? {
+        if data.len() + chunk.len() > MAX_STATEMENT_SIZE_BYTES {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(ErrorResponse {
+                    error: "파일은 5MB 이하여야 합니다".to_string(),
+                }),
+            ));
+        }
+        data.extend_from_slice(&chunk);
+    }
+
+    let deposits = statement::parse_statement(&statement::decode(&data))
+        .map_err(|e| bad_request(&e.to_string()))?;
+
+    let import = bank_import::create_import(&pool, user.user_id, file_name, &deposits)
+        .await
+        .map_err(|e| internal_error(e, "거래내역 가져오기 실패"))?;
+
+    let detail = load_detail(&pool, import.id, user.user_id).await?;
+
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// Get an import with its deposits and suggestions for those not yet confirmed
+pub async fn get_bank_import(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(import_id): Path<i64>,
+) -> Result<Json<BankImportDetail>, (StatusCode, Json<ErrorResponse>)> {
+    let detail = load_detail(&pool, import_id, user.user_id).await?;
+
+    Ok(Json(detail))
+}
+
+/// Record confirmed deposits as fee payments
+pub async fn confirm_bank_matches(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(import_id): Path<i64>,
+    Json(payload): Json<ConfirmBankMatchesRequest>,
+) -> Result<Json<BankImportDetail>, (StatusCode, Json<ErrorResponse>)> {
+    if payload.matches.is_empty() {
+        return Err(bad_request("확정할 입금 내역이 없습니다"));
+    }
+
+    bank_import::confirm_matches(&pool, import_id, user.user_id, &payload.matches)
+        .await
+        .map_err(confirm_error)?;
+
+    let detail = load_detail(&pool, import_id, user.user_id).await?;
+
+    Ok(Json(detail))
+}
+
+async fn load_detail(
+    pool: &PgPool,
+    import_id: i64,
+    user_id: i64,
+) -> Result<BankImportDetail, (StatusCode, Json<ErrorResponse>)> {
+    let import = bank_import::get_import(pool, import_id, user_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "가져온 거래내역을 찾을 수 없습니다".to_string(),
+                }),
+            ),
+            e => internal_error(e, "거래내역 조회 실패"),
+        })?;
+
+    let transactions = bank_import::list_import_transactions(pool, import_id, user_id)
+        .await
+        .map_err(|e| internal_error(e, "거래내역 조회 실패"))?;
+
+    let open_fees = if transactions.iter().any(|t| t.payment_id.is_none()) {
+        bank_import::list_open_fees(pool, user_id)
+            .await
+            .map_err(|e| internal_error(e, "미수 수수료 조회 실패"))?
+    } else {
+        Vec::new()
+    };
+
+    let transactions = transactions
+        .into_iter()
+        .map(|transaction| {
+            let suggestions = if transaction.payment_id.is_none() {
+                statement::suggest(&ParsedDeposit::from(&transaction), &open_fees)
+            } else {
+                Vec::new()
+            };
+            BankTransactionWithSuggestions {
+                transaction,
+                suggestions,
+            }
+        })
+        .collect();
+
+    Ok(BankImportDetail {
+        import,
+        transactions,
+    })
+}
+
+fn confirm_error(e: BankImportError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match &e {
+        BankImportError::TransactionNotFound(_) => StatusCode::NOT_FOUND,
+        BankImportError::AlreadyConfirmed(_) => StatusCode::CONFLICT,
+        BankImportError::Ledger { source, .. } => match source {
+            LedgerError::MatchingNotFound | LedgerError::PaymentNotFound => StatusCode::NOT_FOUND,
+            LedgerError::Payment(_) => StatusCode::BAD_REQUEST,
+            LedgerError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        },
+        BankImportError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    if status == StatusCode::INTERNAL_SERVER_ERROR {
+        tracing::error!("Failed to confirm bank matches: {:?}", e);
+    }
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+fn bad_request(error: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: error.to_string(),
+        }),
+    )
+}
+
+fn internal_error(e: sqlx::Error, context: &str) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("{}: {:?}", context, e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: context.to_string(),
+        }),
+    )
+}
@@ -8,9 +8,14 @@ use sqlx::PgPool;
 
 use crate::middleware::auth::AuthUser;
 use crate::models::customer::{
-    CreateCustomerRequest, Customer, CustomerType, UpdateCustomerRequest,
+    CreateCustomerRequest, Customer, CustomerMergeSummary, CustomerType, MergeCustomerRequest,
+    UpdateCustomerRequest,
 };
-use crate::repositories::customer;
+use crate::repositories::customer::{self, CustomerMergeError};
+use crate::services::business_number::normalize_business_number;
+use crate::services::customer_merge::resolve_merge_selection;
+use crate::services::format::customer_type_label;
+use crate::services::phone::{normalize_phone, PhoneError};
 
 #[derive(Debug, Deserialize)]
 pub struct ListCustomersQuery {
@@ -27,6 +32,18 @@ pub struct SearchCustomersQuery {
     pub offset: Option<i64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DuplicateCustomersQuery {
+    pub phone: String,
+    pub exclude_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateCustomersResponse {
+    pub phone_normalized: String,
+    pub customers: Vec<Customer>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct CustomerResponse {
     pub customer: Customer,
@@ -38,6 +55,13 @@ pub struct CustomersListResponse {
     pub total: usize,
 }
 
+#[derive(Debug, Serialize)]
+pub struct MergeCustomerResponse {
+    pub customer: Customer,
+    pub merged_customer_id: i64,
+    pub moved: CustomerMergeSummary,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub error: String,
@@ -47,9 +71,17 @@ pub struct ErrorResponse {
 pub async fn create_customer(
     user: AuthUser,
     State(pool): State<PgPool>,
-    Json(payload): Json<CreateCustomerRequest>,
+    Json(mut payload): Json<CreateCustomerRequest>,
 ) -> Result<(StatusCode, Json<CustomerResponse>), (StatusCode, Json<ErrorResponse>)> {
-    let customer = customer::create_customer(&pool, user.user_id, payload)
+    let phone_normalized = normalize_phone(&payload.phone).map_err(invalid_phone)?;
+    payload.business_number =
+        check_business_number(payload.business_number.take(), &payload.customer_type)?;
+
+    if !payload.allow_duplicate_phone {
+        ensure_phone_not_taken(&pool, user.user_id, &phone_normalized, None).await?;
+    }
+
+    let customer = customer::create_customer(&pool, user.user_id, payload, &phone_normalized)
         .await
         .map_err(|e| {
             (
@@ -133,24 +165,58 @@ pub async fn update_customer(
     user: AuthUser,
     State(pool): State<PgPool>,
     Path(customer_id): Path<i64>,
-    Json(payload): Json<UpdateCustomerRequest>,
+    Json(mut payload): Json<UpdateCustomerRequest>,
 ) -> Result<Json<CustomerResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let customer = customer::update_customer(&pool, customer_id, user.user_id, payload)
-        .await
-        .map_err(|e| match e {
-            sqlx::Error::RowNotFound => (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: "고객을 찾을 수 없습니다".to_string(),
-                }),
-            ),
-            _ => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("고객 수정 실패: {}", e),
-                }),
-            ),
-        })?;
+    let phone_normalized = payload
+        .phone
+        .as_deref()
+        .map(normalize_phone)
+        .transpose()
+        .map_err(invalid_phone)?;
+
+    match payload.business_number.take() {
+        // An empty value clears the stored number
+        Some(number) if number.trim().is_empty() => payload.business_number = Some(String::new()),
+        Some(number) => {
+            let customer_type = match payload.customer_type.clone() {
+                Some(customer_type) => customer_type,
+                None => {
+                    fetch_customer(&pool, customer_id, user.user_id)
+                        .await?
+                        .customer_type
+                }
+            };
+            payload.business_number = check_business_number(Some(number), &customer_type)?;
+        }
+        None => {}
+    }
+
+    if let (Some(phone_normalized), false) = (&phone_normalized, payload.allow_duplicate_phone) {
+        ensure_phone_not_taken(&pool, user.user_id, phone_normalized, Some(customer_id)).await?;
+    }
+
+    let customer = customer::update_customer(
+        &pool,
+        customer_id,
+        user.user_id,
+        payload,
+        phone_normalized.as_deref(),
+    )
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::RowNotFound => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "고객을 찾을 수 없습니다".to_string(),
+            }),
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("고객 수정 실패: {}", e),
+            }),
+        ),
+    })?;
 
     Ok(Json(CustomerResponse { customer }))
 }
@@ -206,3 +272,197 @@ pub async fn search_customers(
 
     Ok(Json(CustomersListResponse { customers, total }))
 }
+
+/// Merge a duplicate (source) customer into this (target) customer
+pub async fn merge_customer(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(customer_id): Path<i64>,
+    Json(payload): Json<MergeCustomerRequest>,
+) -> Result<Json<MergeCustomerResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if payload.source_customer_id == customer_id {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "같은 고객끼리는 병합할 수 없습니다".to_string(),
+            }),
+        ));
+    }
+
+    let target = fetch_customer(&pool, customer_id, user.user_id).await?;
+    let source = fetch_customer(&pool, payload.source_customer_id, user.user_id).await?;
+
+    let selection =
+        resolve_merge_selection(&target, &source, &payload.fields).map_err(|conflicts| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: format!(
+                        "값이 다른 항목은 유지할 쪽을 선택해주세요: {}",
+                        conflicts.join(", ")
+                    ),
+                }),
+            )
+        })?;
+
+    let (customer, moved) =
+        customer::merge_customers(&pool, user.user_id, target.id, source.id, selection)
+            .await
+            .map_err(|e| match e {
+                CustomerMergeError::DatabaseError(sqlx::Error::RowNotFound) => (
+                    StatusCode::NOT_FOUND,
+                    Json(ErrorResponse {
+                        error: "고객을 찾을 수 없습니다".to_string(),
+                    }),
+                ),
+                CustomerMergeError::IncompatibleType(customer_type) => (
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        error: format!(
+                            "{} 고객은 병합으로 옮겨지는 공고를 가질 수 없습니다. 고객 유형을 구인/구직으로 선택해주세요",
+                            customer_type_label(&customer_type)
+                        ),
+                    }),
+                ),
+                CustomerMergeError::DatabaseError(e) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: format!("고객 병합 실패: {}", e),
+                    }),
+                ),
+            })?;
+
+    Ok(Json(MergeCustomerResponse {
+        customer,
+        merged_customer_id: source.id,
+        moved,
+    }))
+}
+
+/// Find active customers that share the given phone number (in any format)
+pub async fn find_duplicate_customers(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<DuplicateCustomersQuery>,
+) -> Result<Json<DuplicateCustomersResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let phone_normalized = normalize_phone(&params.phone).map_err(invalid_phone)?;
+
+    let customers = customer::find_customers_by_phone(
+        &pool,
+        user.user_id,
+        &phone_normalized,
+        params.exclude_id,
+    )
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("중복 고객 조회 실패: {}", e),
+            }),
+        )
+    })?;
+
+    Ok(Json(DuplicateCustomersResponse {
+        phone_normalized,
+        customers,
+    }))
+}
+
+async fn fetch_customer(
+    pool: &PgPool,
+    customer_id: i64,
+    user_id: i64,
+) -> Result<Customer, (StatusCode, Json<ErrorResponse>)> {
+    customer::get_customer_by_id(pool, customer_id, user_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "고객을 찾을 수 없습니다".to_string(),
+                }),
+            ),
+            _ => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("고객 조회 실패: {}", e),
+                }),
+            ),
+        })
+}
+
+fn invalid_phone(e: PhoneError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+/// Normalize an optional business registration number. Only employer
+/// customers are invoiced, so employees cannot have one.
+fn check_business_number(
+    business_number: Option<String>,
+    customer_type: &CustomerType,
+) -> Result<Option<String>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(number) = business_number.filter(|n| !n.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    if *customer_type == CustomerType::Employee {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "사업자등록번호는 구인자 고객에만 입력할 수 있습니다".to_string(),
+            }),
+        ));
+    }
+
+    normalize_business_number(&number).map(Some).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        )
+    })
+}
+
+/// Reject with 409 when another active customer already uses this phone number
+async fn ensure_phone_not_taken(
+    pool: &PgPool,
+    user_id: i64,
+    phone_normalized: &str,
+    exclude_customer_id: Option<i64>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let duplicates =
+        customer::find_customers_by_phone(pool, user_id, phone_normalized, exclude_customer_id)
+            .await
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: format!("중복 고객 조회 실패: {}", e),
+                    }),
+                )
+            })?;
+
+    if duplicates.is_empty() {
+        return Ok(());
+    }
+
+    let names = duplicates
+        .iter()
+        .map(|c| format!("{} (ID: {})", c.name, c.id))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Err((
+        StatusCode::CONFLICT,
+        Json(ErrorResponse {
+            error: format!("같은 전화번호를 사용하는 고객이 이미 있습니다: {}", names),
+        }),
+    ))
+}
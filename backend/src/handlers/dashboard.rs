@@ -1,9 +1,20 @@
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Local, Months, NaiveDate};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use serde::Serialize;
-use sqlx::{PgPool, Row};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 
 use crate::middleware::auth::AuthUser;
+use crate::models::analytics::{
+    AnalyticsMetric, DashboardAnalytics, Granularity, RollupConsistencyReport,
+};
+use crate::repositories::analytics;
+use crate::services::analytics as series;
 
 #[derive(Debug, Serialize)]
 pub struct DashboardStatsResponse {
@@ -15,6 +26,27 @@ pub struct DashboardStatsResponse {
     pub total_revenue: Decimal,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsQuery {
+    /// 생략하면 종료일 기준 최근 12개월의 첫날
+    pub from: Option<NaiveDate>,
+    /// 생략하면 오늘
+    pub to: Option<NaiveDate>,
+    #[serde(default)]
+    pub granularity: Granularity,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConsistencyQuery {
+    /// 생략하면 종료일 기준 최근 12개월의 첫날
+    pub from: Option<NaiveDate>,
+    /// 생략하면 오늘
+    pub to: Option<NaiveDate>,
+    /// 불일치한 날짜를 다시 집계할지
+    #[serde(default)]
+    pub repair: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub error: String,
@@ -25,119 +57,114 @@ pub async fn get_dashboard_stats(
     user: AuthUser,
     State(pool): State<PgPool>,
 ) -> Result<Json<DashboardStatsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Get customer count
-    let customer_count: i64 = sqlx::query_scalar(
-        r#"
-        SELECT COUNT(*)
-        FROM customers
-        WHERE user_id = $1 AND deleted_at IS NULL
-        "#,
-    )
-    .bind(user.user_id)
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("고객 수 조회 실패: {}", e),
-            }),
-        )
-    })?;
-
-    // Get job postings count
-    let job_postings_count: i64 = sqlx::query_scalar(
-        r#"
-        SELECT COUNT(*)
-        FROM job_postings jp
-        INNER JOIN customers c ON jp.customer_id = c.id
-        WHERE c.user_id = $1 AND jp.deleted_at IS NULL AND c.deleted_at IS NULL
-        "#,
-    )
-    .bind(user.user_id)
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("구인 공고 수 조회 실패: {}", e),
-            }),
-        )
-    })?;
-
-    // Get job seekings count
-    let job_seekings_count: i64 = sqlx::query_scalar(
-        r#"
-        SELECT COUNT(*)
-        FROM job_seeking_postings jsp
-        INNER JOIN customers c ON jsp.customer_id = c.id
-        WHERE c.user_id = $1 AND jsp.deleted_at IS NULL AND c.deleted_at IS NULL
-        "#,
-    )
-    .bind(user.user_id)
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("구직 공고 수 조회 실패: {}", e),
-            }),
-        )
-    })?;
-
-    // Get matchings count and revenue statistics
-    let stats = sqlx::query(
-        r#"
-        SELECT
-            COUNT(*) as count,
-            COALESCE(SUM(
-                COALESCE(m.employer_fee_amount, 0) + COALESCE(m.employee_fee_amount, 0)
-            ), 0) as total_revenue,
-            COALESCE(SUM(
-                CASE
-                    WHEN jp.settlement_status = 'unsettled' THEN COALESCE(m.employer_fee_amount, 0)
-                    ELSE 0
-                END +
-                CASE
-                    WHEN jsp.settlement_status = 'unsettled' THEN COALESCE(m.employee_fee_amount, 0)
-                    ELSE 0
-                END
-            ), 0) as pending_amount
-        FROM matchings m
-        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
-        INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
-        INNER JOIN customers c1 ON jp.customer_id = c1.id
-        WHERE c1.user_id = $1
-            AND m.deleted_at IS NULL
-            AND jp.deleted_at IS NULL
-            AND jsp.deleted_at IS NULL
-            AND c1.deleted_at IS NULL
-        "#,
-    )
-    .bind(user.user_id)
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("매칭 통계 조회 실패: {}", e),
-            }),
-        )
-    })?;
-
-    let matchings_count: i64 = stats.try_get("count").unwrap_or(0);
-    let total_revenue: Decimal = stats.try_get("total_revenue").unwrap_or(Decimal::ZERO);
-    let pending_amount: Decimal = stats.try_get("pending_amount").unwrap_or(Decimal::ZERO);
+    analytics::refresh_dirty_days(&pool, user.user_id)
+        .await
+        .map_err(|e| internal_error(format!("통계 집계 실패: {}", e)))?;
+    let totals = analytics::list_rollup_totals(&pool, user.user_id)
+        .await
+        .map_err(|e| internal_error(format!("대시보드 통계 조회 실패: {}", e)))?;
+
+    let total = |metric| {
+        totals
+            .iter()
+            .find(|total| total.metric == metric)
+            .map(|total| total.value)
+            .unwrap_or_default()
+    };
+    let count = |metric| total(metric).to_i64().unwrap_or_default();
 
+    let total_revenue = total(AnalyticsMetric::FeesCharged);
     Ok(Json(DashboardStatsResponse {
-        total_customers: customer_count,
-        job_postings_count,
-        job_seekings_count,
-        matchings_count,
-        pending_amount,
+        total_customers: count(AnalyticsMetric::NewCustomers),
+        job_postings_count: count(AnalyticsMetric::JobPostingsOpened),
+        job_seekings_count: count(AnalyticsMetric::JobSeekingsOpened),
+        matchings_count: count(AnalyticsMetric::MatchingsCreated),
+        pending_amount: (total_revenue - total(AnalyticsMetric::FeesPaid)).max(Decimal::ZERO),
         total_revenue,
     }))
 }
+
+/// Get revenue, customer, posting and matching activity per period
+pub async fn get_dashboard_analytics(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<AnalyticsQuery>,
+) -> Result<Json<DashboardAnalytics>, (StatusCode, Json<ErrorResponse>)> {
+    let to = params.to.unwrap_or_else(|| Local::now().date_naive());
+    let from = params
+        .from
+        .unwrap_or_else(|| series::period_start(to, Granularity::Month) - Months::new(11));
+    let starts = series::period_starts(from, to, params.granularity)
+        .map_err(|e| bad_request(e.to_string()))?;
+
+    analytics::refresh_dirty_days(&pool, user.user_id)
+        .await
+        .map_err(|e| internal_error(format!("통계 집계 실패: {}", e)))?;
+    let rows = analytics::list_rollup_metrics(&pool, user.user_id, from, to)
+        .await
+        .map_err(|e| internal_error(format!("대시보드 분석 조회 실패: {}", e)))?;
+
+    Ok(Json(series::build_analytics(
+        from,
+        to,
+        params.granularity,
+        &starts,
+        &rows,
+    )))
+}
+
+/// Compare the rolled-up statistics with the live tables day by day. With
+/// `repair=true` the mismatching days are rolled up again.
+pub async fn check_analytics_consistency(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<ConsistencyQuery>,
+) -> Result<Json<RollupConsistencyReport>, (StatusCode, Json<ErrorResponse>)> {
+    let to = params.to.unwrap_or_else(|| Local::now().date_naive());
+    let from = params
+        .from
+        .unwrap_or_else(|| series::period_start(to, Granularity::Month) - Months::new(11));
+    series::period_starts(from, to, Granularity::Day).map_err(|e| bad_request(e.to_string()))?;
+
+    analytics::refresh_dirty_days(&pool, user.user_id)
+        .await
+        .map_err(|e| internal_error(format!("통계 집계 실패: {}", e)))?;
+    let rollup = analytics::list_rollup_metrics(&pool, user.user_id, from, to)
+        .await
+        .map_err(|e| internal_error(format!("집계 통계 조회 실패: {}", e)))?;
+    let live = analytics::list_live_metrics(&pool, user.user_id, from, to)
+        .await
+        .map_err(|e| internal_error(format!("실시간 통계 조회 실패: {}", e)))?;
+
+    let mismatches = series::compare_metrics(&rollup, &live);
+    let repaired = params.repair && !mismatches.is_empty();
+    if repaired {
+        let mut days: Vec<NaiveDate> = mismatches.iter().map(|mismatch| mismatch.day).collect();
+        days.dedup();
+        analytics::mark_days_dirty(&pool, user.user_id, &days)
+            .await
+            .map_err(|e| internal_error(format!("통계 재집계 실패: {}", e)))?;
+        analytics::refresh_dirty_days(&pool, user.user_id)
+            .await
+            .map_err(|e| internal_error(format!("통계 재집계 실패: {}", e)))?;
+    }
+
+    Ok(Json(RollupConsistencyReport {
+        from,
+        to,
+        consistent: mismatches.is_empty(),
+        mismatches,
+        repaired,
+    }))
+}
+
+fn bad_request(error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::BAD_REQUEST, Json(ErrorResponse { error }))
+}
+
+fn internal_error(error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse { error }),
+    )
+}
@@ -0,0 +1,222 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Local, NaiveDate};
+use futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::handlers::job_posting::ListJobPostingsQuery;
+use crate::handlers::job_seeking::ListJobSeekingsQuery;
+use crate::middleware::auth::AuthUser;
+use crate::models::customer::CustomerType;
+use crate::models::export::ExportFormat;
+use crate::models::matching::MatchingStatus;
+use crate::repositories::export;
+use crate::services::export::{csv_header, csv_row, ExportRecord, XlsxExport};
+use crate::services::job_attributes::JobAttributeError;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportCustomersQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub customer_type: Option<CustomerType>,
+    pub tag_ids: Option<String>, // comma-separated tag IDs: "1,2,3"
+}
+
+/// File format of a posting export. The filters are read from the same
+/// query string as the list endpoint's.
+#[derive(Debug, Deserialize)]
+pub struct ExportFormatQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportMatchingsQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub status: Option<MatchingStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportTaxInvoicesQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Completion date range, inclusive
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Export customers (with tags) as CSV or XLSX
+pub async fn export_customers(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<ExportCustomersQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let tag_ids: Option<Vec<i64>> = params.tag_ids.as_ref().and_then(|ids_str| {
+        let ids: Result<Vec<i64>, _> = ids_str
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| s.trim().parse())
+            .collect();
+        ids.ok()
+    });
+
+    let rows = export::stream_customers(pool, user.user_id, params.customer_type, tag_ids);
+
+    export_response(rows, params.format, "customers", "고객").await
+}
+
+/// Export job postings as CSV or XLSX, filtered like the job posting list
+pub async fn export_job_postings(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<ExportFormatQuery>,
+    Query(list): Query<ListJobPostingsQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let filter = list.into_filter().map_err(invalid_filter)?;
+    let rows = export::stream_job_postings(pool, user.user_id, filter);
+
+    export_response(rows, params.format, "job-postings", "구인 공고").await
+}
+
+/// Export job seeking postings as CSV or XLSX, filtered like the job seeking
+/// list
+pub async fn export_job_seekings(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<ExportFormatQuery>,
+    Query(list): Query<ListJobSeekingsQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let filter = list.into_filter().map_err(invalid_filter)?;
+    let rows = export::stream_job_seekings(pool, user.user_id, filter);
+
+    export_response(rows, params.format, "job-seekings", "구직 공고").await
+}
+
+/// Export matchings with fee amounts and settlement status as CSV or XLSX
+pub async fn export_matchings(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<ExportMatchingsQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let rows = export::stream_matchings(pool, user.user_id, params.status);
+
+    export_response(rows, params.format, "matchings", "매칭").await
+}
+
+/// Export fee revenue of completed matchings as tax invoice lines (supplier
+/// and recipient business numbers, supply amount, VAT, issue date) for
+/// e-tax-invoice and accounting software
+pub async fn export_tax_invoices(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<ExportTaxInvoicesQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    if let (Some(from), Some(to)) = (params.from, params.to) {
+        if from > to {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "시작일은 종료일보다 늦을 수 없습니다".to_string(),
+                }),
+            ));
+        }
+    }
+
+    let rows = export::stream_tax_invoices(pool, user.user_id, params.from, params.to);
+
+    export_response(rows, params.format, "tax-invoices", "세금계산서").await
+}
+
+/// Build the download response for a row stream.
+///
+/// CSV is streamed to the client row by row. XLSX rows are streamed from the
+/// database into a constant-memory worksheet and the finished workbook is sent
+/// once it is complete.
+async fn export_response<R>(
+    rows: BoxStream<'static, Result<R, sqlx::Error>>,
+    format: ExportFormat,
+    file_stem: &str,
+    sheet_name: &str,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)>
+where
+    R: ExportRecord + Send + 'static,
+{
+    let file_name = format!(
+        "{}-{}.{}",
+        file_stem,
+        Local::now().format("%Y%m%d"),
+        format.extension()
+    );
+
+    let body = match format {
+        ExportFormat::Csv => {
+            let header = csv_header::<R>().map_err(|e| export_error(e.to_string()))?;
+            let rows = rows.map(|row| match row {
+                Ok(row) => csv_row(&row).map_err(std::io::Error::other),
+                Err(e) => {
+                    tracing::error!("Export stream failed: {:?}", e);
+                    Err(std::io::Error::other(e))
+                }
+            });
+            Body::from_stream(stream::once(async move { Ok(header) }).chain(rows))
+        }
+        ExportFormat::Xlsx => {
+            let mut workbook =
+                XlsxExport::new::<R>(sheet_name).map_err(|e| export_error(e.to_string()))?;
+            let mut rows = rows;
+            while let Some(row) = rows
+                .try_next()
+                .await
+                .map_err(|e| export_error(e.to_string()))?
+            {
+                workbook
+                    .push(&row)
+                    .map_err(|e| export_error(e.to_string()))?;
+            }
+            let bytes = workbook.finish().map_err(|e| export_error(e.to_string()))?;
+            Body::from(bytes)
+        }
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+fn export_error(message: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("내보내기 실패: {}", message),
+        }),
+    )
+}
+
+fn invalid_filter(e: JobAttributeError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
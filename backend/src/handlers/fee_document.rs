@@ -0,0 +1,147 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::middleware::auth::AuthUser;
+use crate::models::fee_document::{CreateFeeDocumentRequest, FeeDocument};
+use crate::repositories::fee_document;
+use crate::repositories::payment::LedgerError;
+use crate::services::fee_document::{self as documents, FeeDocumentError};
+use crate::services::pdf::PdfError;
+use crate::services::storage;
+
+#[derive(Debug, Serialize)]
+pub struct FeeDocumentResponse {
+    pub document: FeeDocument,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeeDocumentsListResponse {
+    pub documents: Vec<FeeDocument>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Issue an invoice or receipt PDF for one side of a matching
+pub async fn create_fee_document(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    Path(matching_id): Path<i64>,
+    Json(payload): Json<CreateFeeDocumentRequest>,
+) -> Result<(StatusCode, Json<FeeDocumentResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let document = documents::issue_document(&pool, &config, matching_id, payload, user.user_id)
+        .await
+        .map_err(document_error)?;
+
+    Ok((StatusCode::CREATED, Json(FeeDocumentResponse { document })))
+}
+
+/// List the invoices and receipts issued for a matching
+pub async fn list_fee_documents(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(matching_id): Path<i64>,
+) -> Result<Json<FeeDocumentsListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let documents = fee_document::list_fee_documents(&pool, matching_id, user.user_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("문서 목록 조회 실패: {}", e),
+                }),
+            )
+        })?;
+
+    let total = documents.len();
+
+    Ok(Json(FeeDocumentsListResponse { documents, total }))
+}
+
+/// Download the PDF of an issued document
+pub async fn download_fee_document(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    Path(document_id): Path<i64>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let document = fee_document::get_fee_document(&pool, document_id, user.user_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "문서를 찾을 수 없습니다".to_string(),
+                }),
+            ),
+            _ => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("문서 조회 실패: {}", e),
+                }),
+            ),
+        })?;
+
+    let bytes = storage::get_object(&config, &document.file_path)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read document from MinIO: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "문서 파일을 읽을 수 없습니다".to_string(),
+                }),
+            )
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.pdf\"", document.document_number),
+            ),
+        ],
+        Body::from(bytes),
+    )
+        .into_response())
+}
+
+fn document_error(e: FeeDocumentError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match &e {
+        FeeDocumentError::Ledger(LedgerError::MatchingNotFound)
+        | FeeDocumentError::DatabaseError(sqlx::Error::RowNotFound) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "매칭을 찾을 수 없습니다".to_string(),
+                }),
+            );
+        }
+        FeeDocumentError::NothingPaid | FeeDocumentError::NothingDue => StatusCode::CONFLICT,
+        FeeDocumentError::Pdf(PdfError::FontNotConfigured) => StatusCode::SERVICE_UNAVAILABLE,
+        _ => {
+            tracing::error!("Failed to issue fee document: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
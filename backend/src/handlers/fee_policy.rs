@@ -0,0 +1,229 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::handlers::helpers::map_sqlx_error;
+use crate::handlers::tag::ErrorResponse;
+use crate::middleware::auth::AuthUser;
+use crate::models::fee_policy::{
+    FeeBreakdown, FeePolicy, FeePolicyRequest, FeePreviewRequest, UserFeePolicies,
+};
+use crate::repositories::fee_policy;
+use crate::services::fee::{self, FeePolicyError};
+use crate::services::salary::{self, SalaryError};
+
+#[derive(Debug, Serialize)]
+pub struct FeePolicyResponse {
+    pub fee_policy: FeePolicy,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeePoliciesListResponse {
+    pub fee_policies: Vec<FeePolicy>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeePreviewResponse {
+    pub breakdown: FeeBreakdown,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserFeePoliciesResponse {
+    pub fee_policies: UserFeePolicies,
+}
+
+/// Create a fee policy
+pub async fn create_fee_policy(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Json(mut payload): Json<FeePolicyRequest>,
+) -> Result<(StatusCode, Json<FeePolicyResponse>), (StatusCode, Json<ErrorResponse>)> {
+    fee::normalize_policy(&mut payload).map_err(invalid_policy)?;
+
+    let fee_policy = fee_policy::create_fee_policy(&pool, user.user_id, payload)
+        .await
+        .map_err(|e| map_sqlx_error(e, "수수료 정책 생성 실패"))?;
+
+    Ok((StatusCode::CREATED, Json(FeePolicyResponse { fee_policy })))
+}
+
+/// List the user's fee policies
+pub async fn list_fee_policies(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+) -> Result<Json<FeePoliciesListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let fee_policies = fee_policy::list_fee_policies(&pool, user.user_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("수수료 정책 목록 조회 실패: {}", e),
+                }),
+            )
+        })?;
+
+    let total = fee_policies.len();
+
+    Ok(Json(FeePoliciesListResponse {
+        fee_policies,
+        total,
+    }))
+}
+
+/// Get a fee policy by ID
+pub async fn get_fee_policy(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(policy_id): Path<i64>,
+) -> Result<Json<FeePolicyResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let fee_policy = fee_policy::get_fee_policy(&pool, policy_id, user.user_id)
+        .await
+        .map_err(|e| policy_lookup_error(e, "수수료 정책 조회 실패"))?;
+
+    Ok(Json(FeePolicyResponse { fee_policy }))
+}
+
+/// Replace a fee policy
+pub async fn update_fee_policy(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(policy_id): Path<i64>,
+    Json(mut payload): Json<FeePolicyRequest>,
+) -> Result<Json<FeePolicyResponse>, (StatusCode, Json<ErrorResponse>)> {
+    fee::normalize_policy(&mut payload).map_err(invalid_policy)?;
+
+    let fee_policy = fee_policy::update_fee_policy(&pool, policy_id, user.user_id, payload)
+        .await
+        .map_err(|e| policy_lookup_error(e, "수수료 정책 수정 실패"))?;
+
+    Ok(Json(FeePolicyResponse { fee_policy }))
+}
+
+/// Delete a fee policy
+pub async fn delete_fee_policy(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(policy_id): Path<i64>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    fee_policy::delete_fee_policy(&pool, policy_id, user.user_id)
+        .await
+        .map_err(|e| policy_lookup_error(e, "수수료 정책 삭제 실패"))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Calculate a fee under a policy without saving anything
+pub async fn preview_fee(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(policy_id): Path<i64>,
+    Json(payload): Json<FeePreviewRequest>,
+) -> Result<Json<FeePreviewResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let policy = fee_policy::get_fee_policy(&pool, policy_id, user.user_id)
+        .await
+        .map_err(|e| policy_lookup_error(e, "수수료 정책 조회 실패"))?;
+
+    let rate = payload.rate.unwrap_or(Decimal::ZERO);
+    fee::validate_rate(rate).map_err(invalid_policy)?;
+    fee::validate_contract_days(payload.contract_days).map_err(invalid_policy)?;
+    salary::validate_weekly_hours(payload.expected_weekly_hours).map_err(invalid_salary)?;
+
+    let fee_base = salary::monthly_equivalent(
+        payload.agreed_salary,
+        payload.salary_basis,
+        payload.expected_weekly_hours,
+        None,
+    );
+    let breakdown = fee::calculate(Some(&policy), fee_base, rate, payload.contract_days);
+
+    Ok(Json(FeePreviewResponse { breakdown }))
+}
+
+/// Get the user's default fee policies
+pub async fn get_user_fee_policies(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+) -> Result<Json<UserFeePoliciesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let fee_policies = fee_policy::get_user_fee_policies(&pool, user.user_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("기본 수수료 정책 조회 실패: {}", e),
+                }),
+            )
+        })?;
+
+    Ok(Json(UserFeePoliciesResponse { fee_policies }))
+}
+
+/// Set the user's default fee policies. A side left empty falls back to the
+/// user's default fee rate.
+pub async fn update_user_fee_policies(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Json(payload): Json<UserFeePolicies>,
+) -> Result<Json<UserFeePoliciesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    for policy_id in [
+        payload.employer_fee_policy_id,
+        payload.employee_fee_policy_id,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        fee_policy::get_fee_policy(&pool, policy_id, user.user_id)
+            .await
+            .map_err(|e| policy_lookup_error(e, "수수료 정책 조회 실패"))?;
+    }
+
+    let fee_policies = fee_policy::set_user_fee_policies(&pool, user.user_id, &payload)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("기본 수수료 정책 설정 실패: {}", e),
+                }),
+            )
+        })?;
+
+    Ok(Json(UserFeePoliciesResponse { fee_policies }))
+}
+
+fn policy_lookup_error(e: sqlx::Error, context: &str) -> (StatusCode, Json<ErrorResponse>) {
+    match e {
+        sqlx::Error::RowNotFound => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "수수료 정책을 찾을 수 없습니다".to_string(),
+            }),
+        ),
+        _ => map_sqlx_error(e, context),
+    }
+}
+
+fn invalid_policy(e: FeePolicyError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+fn invalid_salary(e: SalaryError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
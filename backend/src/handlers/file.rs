@@ -305,7 +305,6 @@ pub async fn upload_customer_profile_photo(
 pub async fn delete_customer_profile_photo(
     AuthUser { user_id, .. }: AuthUser,
     State(pool): State<PgPool>,
-    State(config): State<Config>,
     Path(customer_id): Path<i64>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
     // Verify customer ownership
@@ -342,16 +341,7 @@ pub async fn delete_customer_profile_photo(
             )
         })?;
 
-    // Delete from MinIO
-    delete_from_minio(&config, &profile_photo.file_path)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete from MinIO: {:?}", e);
-            // Continue anyway - soft delete in DB is more important
-        })
-        .ok();
-
-    // Soft delete in database
+    // Soft delete in database; the object stays in MinIO until the trash is purged
     file::delete_customer_file(&pool, profile_photo.id)
         .await
         .map_err(|e| {
@@ -407,7 +397,6 @@ pub async fn list_customer_files(
 pub async fn delete_customer_file(
     AuthUser { user_id, .. }: AuthUser,
     State(pool): State<PgPool>,
-    State(config): State<Config>,
     Path((customer_id, file_id)): Path<(i64, i64)>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
     // Verify customer ownership
@@ -446,16 +435,7 @@ pub async fn delete_customer_file(
         ));
     }
 
-    // Delete from MinIO
-    delete_from_minio(&config, &file_record.file_path)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete from MinIO: {:?}", e);
-            // Continue anyway - soft delete in DB is more important
-        })
-        .ok();
-
-    // Soft delete in database
+    // Soft delete in database; the object stays in MinIO until the trash is purged
     file::delete_customer_file(&pool, file_id)
         .await
         .map_err(|e| {
@@ -500,31 +480,3 @@ async fn upload_to_minio(config: &Config, path: &str, data: &[u8]) -> Result<(),
 
     Ok(())
 }
-
-/// Helper function to delete from MinIO
-async fn delete_from_minio(config: &Config, path: &str) -> Result<(), String> {
-    let credentials = Credentials::new(
-        Some(&config.minio_access_key),
-        Some(&config.minio_secret_key),
-        None,
-        None,
-        None,
-    )
-    .map_err(|e| format!("Failed to create credentials: {}", e))?;
-
-    let region = Region::Custom {
-        region: "us-east-1".to_string(),
-        endpoint: config.minio_endpoint.clone(),
-    };
-
-    let bucket = Bucket::new(&config.minio_bucket, region, credentials)
-        .map_err(|e| format!("Failed to create bucket: {}", e))?
-        .with_path_style();
-
-    bucket
-        .delete_object(path)
-        .await
-        .map_err(|e| format!("Failed to delete object: {}", e))?;
-
-    Ok(())
-}
@@ -3,25 +3,74 @@ use axum::{
     http::StatusCode,
     Json,
 };
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::middleware::auth::AuthUser;
 use crate::models::job_posting::{
-    CreateJobSeekingPostingRequest, JobSeekingPosting, PostingStatus, SettlementStatus,
-    UpdateJobSeekingPostingRequest,
+    CreateJobSeekingPostingRequest, JobCategory, JobSeekingFilter, JobSeekingPosting, PostingKind,
+    PostingStatus, PostingStatusChange, SalaryBasis, SettlementStatus,
+    UpdateJobSeekingPostingRequest, Weekday, WorkArrangement,
 };
-use crate::repositories::{customer, job_seeking};
+use crate::models::suggestion::Suggestion;
+use crate::repositories::fee_policy;
+use crate::repositories::posting_status::{self, PostingStatusError};
+use crate::repositories::{customer, job_seeking, suggestion};
+use crate::services::job_attributes::{self, JobAttributeError};
+use crate::services::salary::{self, SalaryError};
+use crate::services::suggestion as suggestion_service;
 
 #[derive(Debug, Deserialize)]
 pub struct ListJobSeekingsQuery {
     pub status: Option<PostingStatus>,
     pub settlement_status: Option<SettlementStatus>,
     pub preferred_location: Option<String>,
+    pub job_category: Option<JobCategory>,
+    pub region_code: Option<String>,
+    pub work_arrangement: Option<WorkArrangement>,
+    pub salary_basis: Option<SalaryBasis>,
+    pub min_monthly_salary: Option<Decimal>,
+    pub max_monthly_salary: Option<Decimal>,
+    pub work_day: Option<Weekday>,
+    pub start_date_from: Option<NaiveDate>,
+    pub start_date_to: Option<NaiveDate>,
+    pub accepts_pets: Option<bool>,
+    pub household_members: Option<i32>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
+impl ListJobSeekingsQuery {
+    /// The list filters, with the region code normalized. The export accepts
+    /// the same query parameters.
+    pub fn into_filter(self) -> Result<JobSeekingFilter, JobAttributeError> {
+        let region_code = self
+            .region_code
+            .as_deref()
+            .map(job_attributes::normalize_region_code)
+            .transpose()?;
+
+        Ok(JobSeekingFilter {
+            status: self.status,
+            settlement_status: self.settlement_status,
+            preferred_location: self.preferred_location,
+            job_category: self.job_category,
+            region_code,
+            work_arrangement: self.work_arrangement,
+            salary_basis: self.salary_basis,
+            min_monthly_salary: self.min_monthly_salary,
+            max_monthly_salary: self.max_monthly_salary,
+            work_day: self.work_day,
+            start_date_from: self.start_date_from,
+            start_date_to: self.start_date_to,
+            accepts_pets: self.accepts_pets,
+            household_members: self.household_members,
+        })
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct JobSeekingResponse {
     pub job_seeking: JobSeekingPosting,
@@ -33,6 +82,21 @@ pub struct JobSeekingsListResponse {
     pub total: usize,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SuggestionsQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuggestionsResponse {
+    pub suggestions: Vec<Suggestion>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusHistoryResponse {
+    pub history: Vec<PostingStatusChange>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub error: String,
@@ -42,7 +106,7 @@ pub struct ErrorResponse {
 pub async fn create_job_seeking(
     user: AuthUser,
     State(pool): State<PgPool>,
-    Json(payload): Json<CreateJobSeekingPostingRequest>,
+    Json(mut payload): Json<CreateJobSeekingPostingRequest>,
 ) -> Result<(StatusCode, Json<JobSeekingResponse>), (StatusCode, Json<ErrorResponse>)> {
     // Verify the customer belongs to the user
     customer::get_customer_by_id(&pool, payload.customer_id, user.user_id)
@@ -62,6 +126,17 @@ pub async fn create_job_seeking(
             ),
         })?;
 
+    ensure_future_expiry(payload.expires_at)?;
+    fee_policy::ensure_fee_policy(&pool, payload.fee_policy_id, user.user_id)
+        .await
+        .map_err(invalid_fee_policy)?;
+    payload.region_codes =
+        job_attributes::normalize_region_codes(payload.region_codes).map_err(invalid_attributes)?;
+    payload.work_days = job_attributes::normalize_work_days(payload.work_days);
+    job_attributes::validate_household_members(payload.max_household_members)
+        .map_err(invalid_attributes)?;
+    salary::validate_weekly_hours(payload.expected_weekly_hours).map_err(invalid_salary)?;
+
     let job_seeking = job_seeking::create_job_seeking_posting(&pool, payload)
         .await
         .map_err(|e| {
@@ -85,24 +160,20 @@ pub async fn list_job_seekings(
     State(pool): State<PgPool>,
     Query(params): Query<ListJobSeekingsQuery>,
 ) -> Result<Json<JobSeekingsListResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let job_seekings = job_seeking::list_job_seeking_postings(
-        &pool,
-        user.user_id,
-        params.status,
-        params.settlement_status,
-        params.preferred_location,
-        params.limit,
-        params.offset,
-    )
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("구직 공고 목록 조회 실패: {}", e),
-            }),
-        )
-    })?;
+    let (limit, offset) = (params.limit, params.offset);
+    let filter = params.into_filter().map_err(invalid_attributes)?;
+
+    let job_seekings =
+        job_seeking::list_job_seeking_postings(&pool, user.user_id, filter, limit, offset)
+            .await
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: format!("구직 공고 목록 조회 실패: {}", e),
+                    }),
+                )
+            })?;
 
     let total = job_seekings.len();
 
@@ -155,7 +226,7 @@ pub async fn update_job_seeking(
     user: AuthUser,
     State(pool): State<PgPool>,
     Path(job_seeking_id): Path<i64>,
-    Json(payload): Json<UpdateJobSeekingPostingRequest>,
+    Json(mut payload): Json<UpdateJobSeekingPostingRequest>,
 ) -> Result<Json<JobSeekingResponse>, (StatusCode, Json<ErrorResponse>)> {
     // First get the posting to verify ownership
     let existing = job_seeking::get_job_seeking_posting_by_id(&pool, job_seeking_id)
@@ -187,16 +258,81 @@ pub async fn update_job_seeking(
             )
         })?;
 
-    let job_seeking = job_seeking::update_job_seeking_posting(&pool, job_seeking_id, payload)
+    // Settlement changes are checked against the matchings and recorded in the
+    // settlement history, so they go through the settlement endpoint only
+    if payload.settlement_status.is_some()
+        || payload.settlement_amount.is_some()
+        || payload.settlement_memo.is_some()
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "정산 정보는 정산 API(PUT /api/job-seekings/{id}/settlement)로 변경해주세요"
+                    .to_string(),
+            }),
+        ));
+    }
+
+    ensure_future_expiry(payload.expires_at)?;
+    if payload.clear_fee_policy && payload.fee_policy_id.is_some() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "수수료 정책 지정과 해제를 함께 요청할 수 없습니다".to_string(),
+            }),
+        ));
+    }
+    fee_policy::ensure_fee_policy(&pool, payload.fee_policy_id, user.user_id)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("구직 공고 수정 실패: {}", e),
-                }),
-            )
-        })?;
+        .map_err(invalid_fee_policy)?;
+
+    // Reopening a closed posting needs an expiry that has not passed yet
+    let reopening = payload.posting_status == Some(PostingStatus::Published)
+        && existing.posting_status != PostingStatus::Published;
+    let expires_at = payload.expires_at.or(existing.expires_at);
+    if reopening && expires_at.is_some_and(|at| at <= Utc::now().naive_utc()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "만료 시각이 지난 공고입니다. 만료 시각을 변경한 뒤 다시 게시해주세요"
+                    .to_string(),
+            }),
+        ));
+    }
+
+    payload.region_codes = payload
+        .region_codes
+        .map(job_attributes::normalize_region_codes)
+        .transpose()
+        .map_err(invalid_attributes)?;
+    payload.work_days = payload.work_days.map(job_attributes::normalize_work_days);
+    job_attributes::validate_household_members(payload.max_household_members)
+        .map_err(invalid_attributes)?;
+    salary::validate_weekly_hours(payload.expected_weekly_hours).map_err(invalid_salary)?;
+
+    let job_seeking =
+        job_seeking::update_job_seeking_posting(&pool, job_seeking_id, payload, user.user_id)
+            .await
+            .map_err(|e| match e {
+                PostingStatusError::NotFound => (
+                    StatusCode::NOT_FOUND,
+                    Json(ErrorResponse {
+                        error: "구직 공고를 찾을 수 없습니다".to_string(),
+                    }),
+                ),
+                PostingStatusError::InvalidTransition(e) => (
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        error: e.to_string(),
+                    }),
+                ),
+                PostingStatusError::DatabaseError(e) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: format!("구직 공고 수정 실패: {}", e),
+                    }),
+                ),
+            })?;
 
     Ok(Json(JobSeekingResponse { job_seeking }))
 }
@@ -308,6 +444,20 @@ pub async fn toggle_favorite(
             settlement_memo,
             posting_status as "posting_status!: PostingStatus",
             is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory",
+            region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>",
+            work_start_time,
+            work_end_time,
+            start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
+            max_household_members,
+            accepts_pets,
+            expires_at,
+            fee_policy_id,
             created_at as "created_at!",
             updated_at as "updated_at!",
             deleted_at
@@ -329,3 +479,161 @@ pub async fn toggle_favorite(
         job_seeking: updated,
     }))
 }
+
+/// Get the status change history of a job seeking posting
+pub async fn get_job_seeking_status_history(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(job_seeking_id): Path<i64>,
+) -> Result<Json<StatusHistoryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let existing = job_seeking::get_job_seeking_posting_by_id(&pool, job_seeking_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "구직 공고를 찾을 수 없습니다".to_string(),
+                }),
+            ),
+            _ => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("구직 공고 조회 실패: {}", e),
+                }),
+            ),
+        })?;
+
+    // Verify the customer belongs to the user
+    customer::get_customer_by_id(&pool, existing.customer_id, user.user_id)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::FORBIDDEN,
+                Json(ErrorResponse {
+                    error: "접근 권한이 없습니다".to_string(),
+                }),
+            )
+        })?;
+
+    let history =
+        posting_status::list_status_history(&pool, PostingKind::JobSeeking, job_seeking_id)
+            .await
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: format!("상태 변경 이력 조회 실패: {}", e),
+                    }),
+                )
+            })?;
+
+    Ok(Json(StatusHistoryResponse { history }))
+}
+
+/// A posting can only use one of the user's own fee policies
+fn invalid_fee_policy(e: sqlx::Error) -> (StatusCode, Json<ErrorResponse>) {
+    match e {
+        sqlx::Error::RowNotFound => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "수수료 정책을 찾을 수 없습니다".to_string(),
+            }),
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("수수료 정책 조회 실패: {}", e),
+            }),
+        ),
+    }
+}
+
+fn ensure_future_expiry(
+    expires_at: Option<NaiveDateTime>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    match expires_at {
+        Some(at) if at <= Utc::now().naive_utc() => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "만료 시각은 현재 시각 이후로 설정해주세요".to_string(),
+            }),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn invalid_attributes(e: JobAttributeError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+fn invalid_salary(e: SalaryError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+/// Rank open job postings against a job seeking posting
+pub async fn get_job_seeking_suggestions(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(job_seeking_id): Path<i64>,
+    Query(params): Query<SuggestionsQuery>,
+) -> Result<Json<SuggestionsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let target =
+        suggestion::get_profile(&pool, PostingKind::JobSeeking, job_seeking_id, user.user_id)
+            .await
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: format!("구직 공고 조회 실패: {}", e),
+                    }),
+                )
+            })?
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    Json(ErrorResponse {
+                        error: "구직 공고를 찾을 수 없습니다".to_string(),
+                    }),
+                )
+            })?;
+
+    let candidates = suggestion::list_candidates(
+        &pool,
+        PostingKind::JobPosting,
+        user.user_id,
+        target.customer_id,
+    )
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("추천 후보 조회 실패: {}", e),
+            }),
+        )
+    })?;
+
+    let limit = params
+        .limit
+        .unwrap_or(suggestion_service::DEFAULT_LIMIT)
+        .clamp(1, suggestion_service::MAX_LIMIT);
+    let suggestions = suggestion_service::rank(
+        &target,
+        PostingKind::JobSeeking,
+        candidates,
+        Utc::now().naive_utc(),
+        limit,
+    );
+
+    Ok(Json(SuggestionsResponse { suggestions }))
+}
@@ -3,14 +3,23 @@ use axum::{
     http::StatusCode,
     Json,
 };
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::middleware::auth::AuthUser;
 use crate::models::matching::{
-    CreateMatchingRequest, Matching, MatchingStatus, UpdateMatchingRequest,
+    CreateMatchingRequest, GuaranteeRefund, GuaranteeRefundRequest, Matching, MatchingPreview,
+    MatchingStatus, ReplaceMatchingRequest, UpdateMatchingRequest,
 };
+use crate::repositories::matching::MatchingError;
+use crate::repositories::posting_status::PostingStatusError;
 use crate::repositories::{customer, job_posting, job_seeking, matching};
+use crate::services::fee::{self, FeePolicyError};
+use crate::services::format::payer_side_label;
+use crate::services::guarantee::{self, GuaranteeError};
+use crate::services::salary::{self, SalaryError};
 
 #[derive(Debug, Deserialize)]
 pub struct ListMatchingsQuery {
@@ -35,6 +44,27 @@ pub struct MatchingResponse {
     pub matching: Matching,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExpiringGuaranteesQuery {
+    /// 기본 14일, 최대 365일
+    pub within_days: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GuaranteeRefundQuery {
+    pub left_on: NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GuaranteeRefundResponse {
+    pub refund: GuaranteeRefund,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatchingPreviewResponse {
+    pub preview: MatchingPreview,
+}
+
 #[derive(Debug, Serialize)]
 pub struct MatchingsListResponse {
     pub matchings: Vec<Matching>,
@@ -95,31 +125,31 @@ async fn verify_matching_ownership(
     Ok(matching)
 }
 
-/// Create a new matching
-pub async fn create_matching(
-    user: AuthUser,
-    State(pool): State<PgPool>,
-    Json(payload): Json<CreateMatchingRequest>,
-) -> Result<(StatusCode, Json<MatchingResponse>), (StatusCode, Json<ErrorResponse>)> {
-    // Verify the job posting exists and belongs to the user
-    let job_posting = job_posting::get_job_posting_by_id(&pool, payload.job_posting_id)
+/// Helper function to verify the job seeking posting exists and belongs to
+/// the user
+async fn verify_job_seeking_ownership(
+    pool: &PgPool,
+    job_seeking_posting_id: i64,
+    user_id: i64,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let job_seeking = job_seeking::get_job_seeking_posting_by_id(pool, job_seeking_posting_id)
         .await
         .map_err(|e| match e {
             sqlx::Error::RowNotFound => (
                 StatusCode::NOT_FOUND,
                 Json(ErrorResponse {
-                    error: "구인 공고를 찾을 수 없습니다".to_string(),
+                    error: "구직 공고를 찾을 수 없습니다".to_string(),
                 }),
             ),
             _ => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ErrorResponse {
-                    error: format!("구인 공고 조회 실패: {}", e),
+                    error: format!("구직 공고 조회 실패: {}", e),
                 }),
             ),
         })?;
 
-    customer::get_customer_by_id(&pool, job_posting.customer_id, user.user_id)
+    customer::get_customer_by_id(pool, job_seeking.customer_id, user_id)
         .await
         .map_err(|_| {
             (
@@ -130,26 +160,35 @@ pub async fn create_matching(
             )
         })?;
 
-    // Verify the job seeking posting exists and belongs to the user
-    let job_seeking =
-        job_seeking::get_job_seeking_posting_by_id(&pool, payload.job_seeking_posting_id)
-            .await
-            .map_err(|e| match e {
-                sqlx::Error::RowNotFound => (
-                    StatusCode::NOT_FOUND,
-                    Json(ErrorResponse {
-                        error: "구직 공고를 찾을 수 없습니다".to_string(),
-                    }),
-                ),
-                _ => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ErrorResponse {
-                        error: format!("구직 공고 조회 실패: {}", e),
-                    }),
-                ),
-            })?;
-
-    customer::get_customer_by_id(&pool, job_seeking.customer_id, user.user_id)
+    Ok(())
+}
+
+/// Helper function to verify the user owns both postings of a matching
+/// request, fill in the salary terms from the job posting and validate it
+async fn prepare_matching_request(
+    pool: &PgPool,
+    payload: &mut CreateMatchingRequest,
+    user_id: i64,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    // Verify the job posting exists and belongs to the user
+    let job_posting = job_posting::get_job_posting_by_id(pool, payload.job_posting_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "구인 공고를 찾을 수 없습니다".to_string(),
+                }),
+            ),
+            _ => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("구인 공고 조회 실패: {}", e),
+                }),
+            ),
+        })?;
+
+    customer::get_customer_by_id(pool, job_posting.customer_id, user_id)
         .await
         .map_err(|_| {
             (
@@ -160,18 +199,81 @@ pub async fn create_matching(
             )
         })?;
 
-    let matching = matching::create_matching(&pool, payload)
+    verify_job_seeking_ownership(pool, payload.job_seeking_posting_id, user_id).await?;
+
+    // Salary terms default to the job posting's
+    payload.salary_basis.get_or_insert(job_posting.salary_basis);
+    if payload.expected_weekly_hours.is_none() {
+        payload.expected_weekly_hours = job_posting.expected_weekly_hours;
+    }
+    salary::validate_weekly_hours(payload.expected_weekly_hours).map_err(invalid_salary)?;
+    fee::validate_contract_days(payload.contract_days).map_err(invalid_fee)?;
+    validate_fee_rates(payload.employer_fee_rate, payload.employee_fee_rate)?;
+    guarantee::validate_guarantee_days(payload.guarantee_days).map_err(invalid_guarantee)?;
+
+    Ok(())
+}
+
+/// Create a new matching
+pub async fn create_matching(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Json(mut payload): Json<CreateMatchingRequest>,
+) -> Result<(StatusCode, Json<MatchingResponse>), (StatusCode, Json<ErrorResponse>)> {
+    prepare_matching_request(&pool, &mut payload, user.user_id).await?;
+
+    let matching = matching::create_matching(&pool, payload, user.user_id)
+        .await
+        .map_err(|e| matching_error(e, "매칭 생성 실패"))?;
+
+    Ok((StatusCode::CREATED, Json(MatchingResponse { matching })))
+}
+
+/// Show the rates and fees a matching would be created with, without
+/// creating it
+pub async fn preview_matching(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Json(mut payload): Json<CreateMatchingRequest>,
+) -> Result<Json<MatchingPreviewResponse>, (StatusCode, Json<ErrorResponse>)> {
+    prepare_matching_request(&pool, &mut payload, user.user_id).await?;
+
+    let preview = matching::preview_matching(&pool, &payload)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("매칭 수수료 계산 실패: {}", e),
+                }),
+            )
+        })?;
+
+    Ok(Json(MatchingPreviewResponse { preview }))
+}
+
+/// List completed matchings whose guarantee ends soon
+pub async fn list_expiring_guarantees(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<ExpiringGuaranteesQuery>,
+) -> Result<Json<MatchingsListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let within_days = params.within_days.unwrap_or(14).clamp(1, 365);
+
+    let matchings = matching::list_expiring_guarantees(&pool, user.user_id, within_days)
         .await
         .map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ErrorResponse {
-                    error: format!("매칭 생성 실패: {}", e),
+                    error: format!("보증 만료 예정 매칭 조회 실패: {}", e),
                 }),
             )
         })?;
 
-    Ok((StatusCode::CREATED, Json(MatchingResponse { matching })))
+    let total = matchings.len();
+
+    Ok(Json(MatchingsListResponse { matchings, total }))
 }
 
 /// List matchings with filters
@@ -231,16 +333,14 @@ pub async fn update_matching_status(
         ));
     }
 
-    let matching = matching::update_matching_status(&pool, matching_id, payload)
+    salary::validate_weekly_hours(payload.expected_weekly_hours).map_err(invalid_salary)?;
+    fee::validate_contract_days(payload.contract_days).map_err(invalid_fee)?;
+    validate_fee_rates(payload.employer_fee_rate, payload.employee_fee_rate)?;
+    guarantee::validate_guarantee_days(payload.guarantee_days).map_err(invalid_guarantee)?;
+
+    let matching = matching::update_matching_status(&pool, matching_id, payload, user.user_id)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("매칭 상태 수정 실패: {}", e),
-                }),
-            )
-        })?;
+        .map_err(|e| matching_error(e, "매칭 상태 수정 실패"))?;
 
     Ok(Json(MatchingResponse { matching }))
 }
@@ -271,49 +371,9 @@ pub async fn complete_matching(
         ));
     }
 
-    let matching = matching::complete_matching(&pool, matching_id)
+    let matching = matching::complete_matching(&pool, matching_id, user.user_id)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("매칭 완료 처리 실패: {}", e),
-                }),
-            )
-        })?;
-
-    // Automatically update settlement amounts in job postings and job seekings
-    // Update job_posting settlement_amount with employer fee
-    if let Some(employer_fee) = matching.employer_fee_amount {
-        let _ = sqlx::query(
-            r#"
-            UPDATE job_postings
-            SET settlement_amount = COALESCE(settlement_amount, 0) + $1,
-                updated_at = CURRENT_TIMESTAMP
-            WHERE id = $2 AND deleted_at IS NULL
-            "#,
-        )
-        .bind(employer_fee)
-        .bind(matching.job_posting_id)
-        .execute(&pool)
-        .await;
-    }
-
-    // Update job_seeking settlement_amount with employee fee
-    if let Some(employee_fee) = matching.employee_fee_amount {
-        let _ = sqlx::query(
-            r#"
-            UPDATE job_seeking_postings
-            SET settlement_amount = COALESCE(settlement_amount, 0) + $1,
-                updated_at = CURRENT_TIMESTAMP
-            WHERE id = $2 AND deleted_at IS NULL
-            "#,
-        )
-        .bind(employee_fee)
-        .bind(matching.job_seeking_posting_id)
-        .execute(&pool)
-        .await;
-    }
+        .map_err(|e| matching_error(e, "매칭 완료 처리 실패"))?;
 
     Ok(Json(MatchingResponse { matching }))
 }
@@ -347,16 +407,14 @@ pub async fn update_matching(
         ));
     }
 
-    let matching = matching::update_matching_status(&pool, matching_id, payload)
+    salary::validate_weekly_hours(payload.expected_weekly_hours).map_err(invalid_salary)?;
+    fee::validate_contract_days(payload.contract_days).map_err(invalid_fee)?;
+    validate_fee_rates(payload.employer_fee_rate, payload.employee_fee_rate)?;
+    guarantee::validate_guarantee_days(payload.guarantee_days).map_err(invalid_guarantee)?;
+
+    let matching = matching::update_matching_status(&pool, matching_id, payload, user.user_id)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("매칭 정보 수정 실패: {}", e),
-                }),
-            )
-        })?;
+        .map_err(|e| matching_error(e, "매칭 정보 수정 실패"))?;
 
     Ok(Json(MatchingResponse { matching }))
 }
@@ -395,14 +453,140 @@ pub async fn cancel_matching(
         payload.cancellation_reason,
     )
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("매칭 취소 처리 실패: {}", e),
-            }),
-        )
-    })?;
+    .map_err(|e| matching_error(e, "매칭 취소 처리 실패"))?;
 
     Ok(Json(MatchingResponse { matching }))
 }
+
+fn invalid_salary(e: SalaryError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+/// Replace the helper of a completed matching within its guarantee period
+pub async fn replace_matching(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(matching_id): Path<i64>,
+    Json(payload): Json<ReplaceMatchingRequest>,
+) -> Result<(StatusCode, Json<MatchingResponse>), (StatusCode, Json<ErrorResponse>)> {
+    verify_matching_ownership(&pool, matching_id, user.user_id).await?;
+    verify_job_seeking_ownership(&pool, payload.job_seeking_posting_id, user.user_id).await?;
+
+    salary::validate_weekly_hours(payload.expected_weekly_hours).map_err(invalid_salary)?;
+    fee::validate_contract_days(payload.contract_days).map_err(invalid_fee)?;
+    validate_fee_rates(None, payload.employee_fee_rate)?;
+    guarantee::validate_guarantee_days(payload.guarantee_days).map_err(invalid_guarantee)?;
+
+    let matching = matching::replace_matching(&pool, matching_id, payload, user.user_id)
+        .await
+        .map_err(|e| matching_error(e, "교체 매칭 생성 실패"))?;
+
+    Ok((StatusCode::CREATED, Json(MatchingResponse { matching })))
+}
+
+/// Calculate the refund for a broken guarantee without saving it
+pub async fn preview_guarantee_refund(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(matching_id): Path<i64>,
+    Query(params): Query<GuaranteeRefundQuery>,
+) -> Result<Json<GuaranteeRefundResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let matching = verify_matching_ownership(&pool, matching_id, user.user_id).await?;
+
+    let refund = guarantee::calculate_refund(&matching, params.left_on)
+        .map_err(|e| matching_error(e.into(), "환불 계산 실패"))?;
+
+    Ok(Json(GuaranteeRefundResponse { refund }))
+}
+
+/// Refund the employer fee for a broken guarantee
+pub async fn refund_guarantee(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(matching_id): Path<i64>,
+    Json(payload): Json<GuaranteeRefundRequest>,
+) -> Result<Json<MatchingResponse>, (StatusCode, Json<ErrorResponse>)> {
+    verify_matching_ownership(&pool, matching_id, user.user_id).await?;
+
+    let matching = matching::refund_matching(&pool, matching_id, payload.left_on)
+        .await
+        .map_err(|e| matching_error(e, "보증 환불 처리 실패"))?;
+
+    Ok(Json(MatchingResponse { matching }))
+}
+
+fn validate_fee_rates(
+    employer_fee_rate: Option<Decimal>,
+    employee_fee_rate: Option<Decimal>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    for rate in [employer_fee_rate, employee_fee_rate].into_iter().flatten() {
+        fee::validate_rate(rate).map_err(invalid_fee)?;
+    }
+    Ok(())
+}
+
+fn invalid_guarantee(e: GuaranteeError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+fn invalid_fee(e: FeePolicyError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
+
+fn matching_error(e: MatchingError, context: &str) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = match e {
+        MatchingError::NotFound => (StatusCode::NOT_FOUND, "매칭을 찾을 수 없습니다".to_string()),
+        MatchingError::AlreadyInProgress(id) => (
+            StatusCode::CONFLICT,
+            format!("이미 진행중인 매칭이 있습니다 (매칭 #{})", id),
+        ),
+        MatchingError::InvalidTransition(e) => (StatusCode::CONFLICT, e.to_string()),
+        MatchingError::PostingStatus(PostingStatusError::NotFound) => {
+            (StatusCode::NOT_FOUND, "공고를 찾을 수 없습니다".to_string())
+        }
+        MatchingError::PostingStatus(PostingStatusError::InvalidTransition(e)) => {
+            (StatusCode::CONFLICT, e.to_string())
+        }
+        MatchingError::Guarantee(
+            e @ (GuaranteeError::InvalidDays | GuaranteeError::BeforeStart(_)),
+        ) => (StatusCode::BAD_REQUEST, e.to_string()),
+        MatchingError::Guarantee(e) => (StatusCode::CONFLICT, e.to_string()),
+        MatchingError::ReplacementFeeRate => (
+            StatusCode::BAD_REQUEST,
+            "무상 교체 매칭에는 구인자 수수료율을 지정할 수 없습니다".to_string(),
+        ),
+        MatchingError::FeeBelowPaid(side) => (
+            StatusCode::CONFLICT,
+            format!(
+                "{} 수수료를 이미 입금된 금액보다 낮게 변경할 수 없습니다",
+                payer_side_label(&side)
+            ),
+        ),
+        MatchingError::FeeDocumentsIssued => (
+            StatusCode::CONFLICT,
+            "청구서/영수증이 발행된 매칭은 수수료를 변경할 수 없습니다".to_string(),
+        ),
+        MatchingError::PostingStatus(PostingStatusError::DatabaseError(e))
+        | MatchingError::DatabaseError(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{}: {}", context, e),
+        ),
+    };
+
+    (status, Json(ErrorResponse { error }))
+}
@@ -1,6 +1,10 @@
 pub mod auth;
+pub mod bank_import;
 pub mod customer;
 pub mod dashboard;
+pub mod export;
+pub mod fee_document;
+pub mod fee_policy;
 pub mod file;
 pub mod health;
 pub mod helpers;
@@ -8,9 +12,15 @@ pub mod job_posting;
 pub mod job_seeking;
 pub mod matching;
 pub mod memo;
+pub mod payment;
+pub mod report;
+pub mod search;
 pub mod security_question;
 pub mod settlement;
+pub mod statement;
 pub mod tag;
+pub mod timeline;
+pub mod trash;
 pub mod user;
 pub mod user_file;
 pub mod user_memo;
@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::middleware::auth::AuthUser;
+use crate::models::payment::{CreatePaymentRequest, MatchingBalance, Payment};
+use crate::repositories::payment::{self, LedgerError};
+
+#[derive(Debug, Serialize)]
+pub struct PaymentsListResponse {
+    pub payments: Vec<Payment>,
+    pub total: usize,
+    pub balance: MatchingBalance,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaymentResponse {
+    pub payment: Payment,
+    pub balance: MatchingBalance,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BalanceResponse {
+    pub balance: MatchingBalance,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// List the payments of a matching with its outstanding balance
+pub async fn list_payments(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(matching_id): Path<i64>,
+) -> Result<Json<PaymentsListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (payments, balance) = payment::list_payments(&pool, matching_id, user.user_id)
+        .await
+        .map_err(|e| ledger_error(e, "입금 내역 조회 실패"))?;
+
+    let total = payments.len();
+
+    Ok(Json(PaymentsListResponse {
+        payments,
+        total,
+        balance,
+    }))
+}
+
+/// Record a fee payment for one side of a matching
+pub async fn create_payment(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(matching_id): Path<i64>,
+    Json(payload): Json<CreatePaymentRequest>,
+) -> Result<(StatusCode, Json<PaymentResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let (payment, balance) = payment::create_payment(&pool, matching_id, payload, user.user_id)
+        .await
+        .map_err(|e| ledger_error(e, "입금 등록 실패"))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(PaymentResponse { payment, balance }),
+    ))
+}
+
+/// Delete a payment
+pub async fn delete_payment(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path((matching_id, payment_id)): Path<(i64, i64)>,
+) -> Result<Json<BalanceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let balance = payment::delete_payment(&pool, matching_id, payment_id, user.user_id)
+        .await
+        .map_err(|e| ledger_error(e, "입금 삭제 실패"))?;
+
+    Ok(Json(BalanceResponse { balance }))
+}
+
+/// Get the outstanding balance of both sides of a matching
+pub async fn get_matching_balance(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(matching_id): Path<i64>,
+) -> Result<Json<BalanceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let balance = payment::get_matching_balance(&pool, matching_id, user.user_id)
+        .await
+        .map_err(|e| ledger_error(e, "미수금 조회 실패"))?;
+
+    Ok(Json(BalanceResponse { balance }))
+}
+
+fn ledger_error(e: LedgerError, context: &str) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = match e {
+        LedgerError::MatchingNotFound => {
+            (StatusCode::NOT_FOUND, "매칭을 찾을 수 없습니다".to_string())
+        }
+        LedgerError::PaymentNotFound => (
+            StatusCode::NOT_FOUND,
+            "입금 내역을 찾을 수 없습니다".to_string(),
+        ),
+        LedgerError::Payment(e) => (StatusCode::BAD_REQUEST, e.to_string()),
+        LedgerError::DatabaseError(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{}: {}", context, e),
+        ),
+    };
+
+    (status, Json(ErrorResponse { error }))
+}
@@ -0,0 +1,235 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Local, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::middleware::auth::AuthUser;
+use crate::models::monthly_report::{ReportFormat, StoredMonthlyReport};
+use crate::models::report::ReceivablesReport;
+use crate::repositories::{monthly_report, report};
+use crate::services::monthly_report::{self as reports, MonthlyReportError};
+use crate::services::pdf::PdfError;
+use crate::services::{receivables, storage};
+
+#[derive(Debug, Deserialize)]
+pub struct MonthlyReportQuery {
+    /// 생략하면 지난달
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StoredReportQuery {
+    #[serde(default = "default_download_format")]
+    pub format: ReportFormat,
+}
+
+fn default_download_format() -> ReportFormat {
+    ReportFormat::Pdf
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonthlyReportResponse {
+    pub report: StoredMonthlyReport,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonthlyReportsListResponse {
+    pub reports: Vec<StoredMonthlyReport>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Get unpaid fees per customer, bucketed by days since the matching completed
+pub async fn get_receivables(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+) -> Result<Json<ReceivablesReport>, (StatusCode, Json<ErrorResponse>)> {
+    let rows = report::list_receivable_rows(&pool, user.user_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("미수금 조회 실패: {}", e),
+                }),
+            )
+        })?;
+
+    Ok(Json(receivables::build_report(
+        rows,
+        Utc::now().date_naive(),
+    )))
+}
+
+/// Generate a month's business report on demand, as JSON, PDF or XLSX
+pub async fn get_monthly_report(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    Query(params): Query<MonthlyReportQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let (year, month) = report_month(&params);
+    let report = reports::generate_report(&pool, user.user_id, year, month)
+        .await
+        .map_err(report_error)?;
+
+    let bytes = match params.format {
+        ReportFormat::Json => return Ok(Json(report).into_response()),
+        ReportFormat::Pdf => reports::render_pdf(&config, &report)
+            .await
+            .map_err(|e| report_error(e.into()))?,
+        ReportFormat::Xlsx => reports::render_xlsx(&report).map_err(|e| report_error(e.into()))?,
+    };
+
+    Ok(report_file(year, month, params.format, bytes))
+}
+
+/// Generate a month's business report and store it as PDF and XLSX,
+/// replacing an earlier one of the same month
+pub async fn store_monthly_report(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    Query(params): Query<MonthlyReportQuery>,
+) -> Result<(StatusCode, Json<MonthlyReportResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let (year, month) = report_month(&params);
+    let report = reports::store_report(&pool, &config, user.user_id, year, month)
+        .await
+        .map_err(report_error)?;
+
+    Ok((StatusCode::CREATED, Json(MonthlyReportResponse { report })))
+}
+
+/// List the stored monthly reports, latest month first
+pub async fn list_monthly_reports(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+) -> Result<Json<MonthlyReportsListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let reports = monthly_report::list_monthly_reports(&pool, user.user_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("보고서 목록 조회 실패: {}", e),
+                }),
+            )
+        })?;
+
+    let total = reports.len();
+
+    Ok(Json(MonthlyReportsListResponse { reports, total }))
+}
+
+/// Download the PDF or XLSX of a stored monthly report
+pub async fn download_monthly_report(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    Path(report_id): Path<i64>,
+    Query(params): Query<StoredReportQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let stored = monthly_report::get_monthly_report(&pool, report_id, user.user_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "보고서를 찾을 수 없습니다".to_string(),
+                }),
+            ),
+            _ => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("보고서 조회 실패: {}", e),
+                }),
+            ),
+        })?;
+
+    let path = match params.format {
+        ReportFormat::Pdf => &stored.pdf_path,
+        ReportFormat::Xlsx => &stored.xlsx_path,
+        ReportFormat::Json => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "저장된 보고서는 pdf 또는 xlsx 형식으로 내려받을 수 있습니다"
+                        .to_string(),
+                }),
+            ));
+        }
+    };
+    let bytes = storage::get_object(&config, path).await.map_err(|e| {
+        tracing::error!("Failed to read monthly report from MinIO: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "보고서 파일을 읽을 수 없습니다".to_string(),
+            }),
+        )
+    })?;
+
+    Ok(report_file(
+        stored.year,
+        stored.month as u32,
+        params.format,
+        bytes,
+    ))
+}
+
+/// Requested month, last month by default
+fn report_month(params: &MonthlyReportQuery) -> (i32, u32) {
+    let (year, month) = reports::previous_month(Local::now().date_naive());
+    (params.year.unwrap_or(year), params.month.unwrap_or(month))
+}
+
+fn report_file(year: i32, month: u32, format: ReportFormat, bytes: Vec<u8>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"monthly-report-{}-{:02}.{}\"",
+                    year,
+                    month,
+                    format.extension()
+                ),
+            ),
+        ],
+        Body::from(bytes),
+    )
+        .into_response()
+}
+
+fn report_error(e: MonthlyReportError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match &e {
+        MonthlyReportError::InvalidMonth => StatusCode::BAD_REQUEST,
+        MonthlyReportError::Pdf(PdfError::FontNotConfigured) => StatusCode::SERVICE_UNAVAILABLE,
+        _ => {
+            tracing::error!("Failed to generate monthly report: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
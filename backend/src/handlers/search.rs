@@ -0,0 +1,86 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::middleware::auth::AuthUser;
+use crate::models::search::GlobalSearchResults;
+use crate::repositories::{customer, search};
+use crate::services::search::build_prefix_tsquery;
+
+#[derive(Debug, Deserialize)]
+pub struct GlobalSearchQuery {
+    pub q: String,
+    /// Maximum results per group (default 10, max 50)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Search customers, postings, memos and tags at once, grouped by type
+pub async fn global_search(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Query(params): Query<GlobalSearchQuery>,
+) -> Result<Json<GlobalSearchResults>, (StatusCode, Json<ErrorResponse>)> {
+    let term = params.q.trim().to_string();
+    if term.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "검색어를 입력해주세요".to_string(),
+            }),
+        ));
+    }
+
+    let limit = params.limit.unwrap_or(10).clamp(1, 50);
+    let search_error = |e: sqlx::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("통합 검색 실패: {}", e),
+            }),
+        )
+    };
+
+    let customers = customer::search_customers(&pool, user.user_id, &term, Some(limit), None)
+        .await
+        .map_err(search_error)?;
+    let tags = search::search_tags(&pool, user.user_id, &term, limit)
+        .await
+        .map_err(search_error)?;
+
+    let (job_postings, job_seekings, memos) = match build_prefix_tsquery(&term) {
+        Some(tsquery) => (
+            search::search_job_postings(&pool, user.user_id, &tsquery, limit)
+                .await
+                .map_err(search_error)?,
+            search::search_job_seekings(&pool, user.user_id, &tsquery, limit)
+                .await
+                .map_err(search_error)?,
+            search::search_memos(&pool, user.user_id, &tsquery, limit)
+                .await
+                .map_err(search_error)?,
+        ),
+        None => (Vec::new(), Vec::new(), Vec::new()),
+    };
+
+    let total =
+        customers.len() + job_postings.len() + job_seekings.len() + memos.len() + tags.len();
+
+    Ok(Json(GlobalSearchResults {
+        query: term,
+        customers,
+        job_postings,
+        job_seekings,
+        memos,
+        tags,
+        total,
+    }))
+}
@@ -3,26 +3,30 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use sqlx::PgPool;
 
 use crate::middleware::auth::AuthUser;
-use crate::models::job_posting::{JobPosting, SettlementStatus};
+use crate::models::job_posting::{JobPosting, JobSeekingPosting, PostingKind};
+use crate::models::settlement::{SettlementChange, UpdateSettlementRequest};
+use crate::repositories::settlement::{self, SettlementUpdateError};
 use crate::repositories::{customer, job_posting, job_seeking};
 
-#[derive(Debug, Deserialize)]
-pub struct UpdateSettlementRequest {
-    pub settlement_status: Option<SettlementStatus>,
-    pub settlement_amount: Option<Decimal>,
-    pub settlement_memo: Option<String>,
-}
-
 #[derive(Debug, Serialize)]
 pub struct JobPostingResponse {
     pub job_posting: JobPosting,
 }
 
+#[derive(Debug, Serialize)]
+pub struct JobSeekingResponse {
+    pub job_seeking: JobSeekingPosting,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettlementHistoryResponse {
+    pub history: Vec<SettlementChange>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub error: String,
@@ -35,19 +39,86 @@ pub async fn update_job_posting_settlement(
     Path(posting_id): Path<i64>,
     Json(payload): Json<UpdateSettlementRequest>,
 ) -> Result<Json<JobPostingResponse>, (StatusCode, Json<ErrorResponse>)> {
-    if payload.settlement_status.is_none()
-        && payload.settlement_amount.is_none()
-        && payload.settlement_memo.is_none()
-    {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "업데이트할 정산 정보를 제공해주세요".to_string(),
-            }),
-        ));
-    }
-    // Get the posting to verify ownership
-    let existing = job_posting::get_job_posting_by_id(&pool, posting_id)
+    let existing = find_job_posting(&pool, posting_id).await?;
+    verify_owner(&pool, existing.customer_id, user.user_id).await?;
+
+    settlement::update_settlement(
+        &pool,
+        PostingKind::JobPosting,
+        posting_id,
+        &payload,
+        user.user_id,
+    )
+    .await
+    .map_err(update_error)?;
+
+    let job_posting = find_job_posting(&pool, posting_id).await?;
+
+    Ok(Json(JobPostingResponse { job_posting }))
+}
+
+/// Update job seeking settlement
+pub async fn update_job_seeking_settlement(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(seeking_id): Path<i64>,
+    Json(payload): Json<UpdateSettlementRequest>,
+) -> Result<Json<JobSeekingResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let existing = find_job_seeking(&pool, seeking_id).await?;
+    verify_owner(&pool, existing.customer_id, user.user_id).await?;
+
+    settlement::update_settlement(
+        &pool,
+        PostingKind::JobSeeking,
+        seeking_id,
+        &payload,
+        user.user_id,
+    )
+    .await
+    .map_err(update_error)?;
+
+    let job_seeking = find_job_seeking(&pool, seeking_id).await?;
+
+    Ok(Json(JobSeekingResponse { job_seeking }))
+}
+
+/// Get the settlement change history of a job posting
+pub async fn get_job_posting_settlement_history(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(posting_id): Path<i64>,
+) -> Result<Json<SettlementHistoryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let existing = find_job_posting(&pool, posting_id).await?;
+    verify_owner(&pool, existing.customer_id, user.user_id).await?;
+
+    let history = settlement::list_settlement_history(&pool, PostingKind::JobPosting, posting_id)
+        .await
+        .map_err(history_error)?;
+
+    Ok(Json(SettlementHistoryResponse { history }))
+}
+
+/// Get the settlement change history of a job seeking posting
+pub async fn get_job_seeking_settlement_history(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(seeking_id): Path<i64>,
+) -> Result<Json<SettlementHistoryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let existing = find_job_seeking(&pool, seeking_id).await?;
+    verify_owner(&pool, existing.customer_id, user.user_id).await?;
+
+    let history = settlement::list_settlement_history(&pool, PostingKind::JobSeeking, seeking_id)
+        .await
+        .map_err(history_error)?;
+
+    Ok(Json(SettlementHistoryResponse { history }))
+}
+
+async fn find_job_posting(
+    pool: &PgPool,
+    posting_id: i64,
+) -> Result<JobPosting, (StatusCode, Json<ErrorResponse>)> {
+    job_posting::get_job_posting_by_id(pool, posting_id)
         .await
         .map_err(|e| match e {
             sqlx::Error::RowNotFound => (
@@ -62,89 +133,14 @@ pub async fn update_job_posting_settlement(
                     error: format!("구인 공고 조회 실패: {}", e),
                 }),
             ),
-        })?;
-
-    // Verify the customer belongs to the user
-    customer::get_customer_by_id(&pool, existing.customer_id, user.user_id)
-        .await
-        .map_err(|_| {
-            (
-                StatusCode::FORBIDDEN,
-                Json(ErrorResponse {
-                    error: "접근 권한이 없습니다".to_string(),
-                }),
-            )
-        })?;
-
-    // Build update query
-    let mut query_parts = vec!["UPDATE job_postings SET updated_at = NOW()".to_string()];
-    let mut param_count = 1;
-
-    if payload.settlement_status.is_some() {
-        param_count += 1;
-        query_parts.push(format!("settlement_status = ${}", param_count));
-    }
-    if payload.settlement_amount.is_some() {
-        param_count += 1;
-        query_parts.push(format!("settlement_amount = ${}", param_count));
-    }
-    if payload.settlement_memo.is_some() {
-        param_count += 1;
-        query_parts.push(format!("settlement_memo = ${}", param_count));
-    }
-
-    let query = format!(
-        "{}, {} WHERE id = $1 AND deleted_at IS NULL RETURNING *",
-        query_parts[0],
-        query_parts[1..].join(", ")
-    );
-
-    let mut query_builder = sqlx::query_as::<_, JobPosting>(&query).bind(posting_id);
-
-    if let Some(status) = payload.settlement_status {
-        query_builder = query_builder.bind(status);
-    }
-    if let Some(amount) = payload.settlement_amount {
-        query_builder = query_builder.bind(amount);
-    }
-    if let Some(memo) = payload.settlement_memo {
-        query_builder = query_builder.bind(memo);
-    }
-
-    let updated = query_builder.fetch_one(&pool).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("정산 정보 업데이트 실패: {}", e),
-            }),
-        )
-    })?;
-
-    Ok(Json(JobPostingResponse {
-        job_posting: updated,
-    }))
+        })
 }
 
-/// Update job seeking settlement
-pub async fn update_job_seeking_settlement(
-    user: AuthUser,
-    State(pool): State<PgPool>,
-    Path(seeking_id): Path<i64>,
-    Json(payload): Json<UpdateSettlementRequest>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    if payload.settlement_status.is_none()
-        && payload.settlement_amount.is_none()
-        && payload.settlement_memo.is_none()
-    {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "업데이트할 정산 정보를 제공해주세요".to_string(),
-            }),
-        ));
-    }
-    // Get the seeking to verify ownership
-    let existing = job_seeking::get_job_seeking_posting_by_id(&pool, seeking_id)
+async fn find_job_seeking(
+    pool: &PgPool,
+    seeking_id: i64,
+) -> Result<JobSeekingPosting, (StatusCode, Json<ErrorResponse>)> {
+    job_seeking::get_job_seeking_posting_by_id(pool, seeking_id)
         .await
         .map_err(|e| match e {
             sqlx::Error::RowNotFound => (
@@ -159,11 +155,18 @@ pub async fn update_job_seeking_settlement(
                     error: format!("구직 공고 조회 실패: {}", e),
                 }),
             ),
-        })?;
+        })
+}
 
-    // Verify the customer belongs to the user
-    customer::get_customer_by_id(&pool, existing.customer_id, user.user_id)
+/// Verify the customer belongs to the user
+async fn verify_owner(
+    pool: &PgPool,
+    customer_id: i64,
+    user_id: i64,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    customer::get_customer_by_id(pool, customer_id, user_id)
         .await
+        .map(|_| ())
         .map_err(|_| {
             (
                 StatusCode::FORBIDDEN,
@@ -171,53 +174,29 @@ pub async fn update_job_seeking_settlement(
                     error: "접근 권한이 없습니다".to_string(),
                 }),
             )
-        })?;
-
-    // Build update query
-    let mut query_parts = vec!["UPDATE job_seeking_postings SET updated_at = NOW()".to_string()];
-    let mut param_count = 1;
-
-    if payload.settlement_status.is_some() {
-        param_count += 1;
-        query_parts.push(format!("settlement_status = ${}", param_count));
-    }
-    if payload.settlement_amount.is_some() {
-        param_count += 1;
-        query_parts.push(format!("settlement_amount = ${}", param_count));
-    }
-    if payload.settlement_memo.is_some() {
-        param_count += 1;
-        query_parts.push(format!("settlement_memo = ${}", param_count));
-    }
-
-    let query = format!(
-        "{}, {} WHERE id = $1 AND deleted_at IS NULL",
-        query_parts[0],
-        query_parts[1..].join(", ")
-    );
-
-    let mut query_builder = sqlx::query(&query).bind(seeking_id);
-
-    if let Some(status) = payload.settlement_status {
-        query_builder = query_builder.bind(status);
-    }
-    if let Some(amount) = payload.settlement_amount {
-        query_builder = query_builder.bind(amount);
-    }
-    if let Some(memo) = payload.settlement_memo {
-        query_builder = query_builder.bind(memo);
-    }
-
-    query_builder.execute(&pool).await.map_err(|e| {
-        (
+        })
+}
+
+fn update_error(e: SettlementUpdateError) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = match e {
+        SettlementUpdateError::NotFound => {
+            (StatusCode::NOT_FOUND, "공고를 찾을 수 없습니다".to_string())
+        }
+        SettlementUpdateError::Invalid(e) => (StatusCode::BAD_REQUEST, e.to_string()),
+        SettlementUpdateError::DatabaseError(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("정산 정보 업데이트 실패: {}", e),
-            }),
-        )
-    })?;
-
-    Ok(Json(serde_json::json!({
-        "message": "정산 정보가 업데이트되었습니다"
-    })))
+            format!("정산 정보 업데이트 실패: {}", e),
+        ),
+    };
+
+    (status, Json(ErrorResponse { error }))
+}
+
+fn history_error(e: sqlx::Error) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("정산 변경 이력 조회 실패: {}", e),
+        }),
+    )
 }
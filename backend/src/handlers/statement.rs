@@ -0,0 +1,91 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::middleware::auth::AuthUser;
+use crate::models::statement::StatementFormat;
+use crate::services::pdf::PdfError;
+use crate::services::statement::{self as statements, StatementError};
+
+#[derive(Debug, Deserialize)]
+pub struct StatementQuery {
+    /// 생략하면 첫 거래부터
+    pub from: Option<NaiveDate>,
+    /// 생략하면 오늘
+    pub to: Option<NaiveDate>,
+    #[serde(default)]
+    pub format: StatementFormat,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Get a customer's account statement: their matchings, fees charged,
+/// refunds and payments with a running balance, as JSON or PDF
+pub async fn get_customer_statement(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    Path(customer_id): Path<i64>,
+    Query(params): Query<StatementQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let to = params.to.unwrap_or_else(|| Local::now().date_naive());
+    let statement =
+        statements::generate_statement(&pool, customer_id, user.user_id, params.from, to)
+            .await
+            .map_err(statement_error)?;
+
+    match params.format {
+        StatementFormat::Json => Ok(Json(statement).into_response()),
+        StatementFormat::Pdf => {
+            let bytes = statements::render_pdf(&config, &statement)
+                .await
+                .map_err(|e| statement_error(e.into()))?;
+
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "application/pdf".to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!(
+                            "attachment; filename=\"statement-{}-{}.pdf\"",
+                            customer_id,
+                            to.format("%Y%m%d")
+                        ),
+                    ),
+                ],
+                Body::from(bytes),
+            )
+                .into_response())
+        }
+    }
+}
+
+fn statement_error(e: StatementError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match &e {
+        StatementError::InvalidRange => StatusCode::BAD_REQUEST,
+        StatementError::CustomerNotFound => StatusCode::NOT_FOUND,
+        StatementError::Pdf(PdfError::FontNotConfigured) => StatusCode::SERVICE_UNAVAILABLE,
+        _ => {
+            tracing::error!("Failed to build customer statement: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    )
+}
@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::middleware::auth::AuthUser;
+use crate::models::timeline::TimelineEvent;
+use crate::repositories::{customer, timeline};
+
+#[derive(Debug, Deserialize)]
+pub struct TimelineQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimelineResponse {
+    pub events: Vec<TimelineEvent>,
+    pub limit: i64,
+    pub offset: i64,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Get a customer's activity timeline (newest first)
+pub async fn get_customer_timeline(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path(customer_id): Path<i64>,
+    Query(params): Query<TimelineQuery>,
+) -> Result<Json<TimelineResponse>, (StatusCode, Json<ErrorResponse>)> {
+    // Verify customer ownership
+    customer::get_customer_by_id(&pool, customer_id, user.user_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "고객을 찾을 수 없습니다".to_string(),
+                }),
+            ),
+            _ => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("고객 조회 실패: {}", e),
+                }),
+            ),
+        })?;
+
+    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    // Fetch one extra row to know whether another page exists
+    let mut events = timeline::list_customer_timeline(&pool, customer_id, limit + 1, offset)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("고객 타임라인 조회 실패: {}", e),
+                }),
+            )
+        })?;
+
+    let has_more = events.len() as i64 > limit;
+    events.truncate(limit as usize);
+
+    Ok(Json(TimelineResponse {
+        events,
+        limit,
+        offset,
+        has_more,
+    }))
+}
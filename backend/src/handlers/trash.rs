@@ -0,0 +1,122 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::middleware::auth::AuthUser;
+use crate::models::trash::{TrashItem, TrashItemType};
+use crate::repositories::trash::{self, TrashError};
+
+#[derive(Debug, Deserialize)]
+pub struct ListTrashQuery {
+    pub item_type: Option<TrashItemType>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrashListResponse {
+    pub items: Vec<TrashItem>,
+    pub retention_days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoreResponse {
+    pub item_type: TrashItemType,
+    pub id: i64,
+    /// Rows restored, including records deleted together with this one
+    pub restored_count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// List soft-deleted records
+pub async fn list_trash(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    State(config): State<Config>,
+    Query(params): Query<ListTrashQuery>,
+) -> Result<Json<TrashListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let mut items = trash::list_trash(&pool, user.user_id, params.item_type, limit, offset)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("휴지통 조회 실패: {}", e),
+                }),
+            )
+        })?;
+
+    let retention = Duration::days(config.trash_retention_days);
+    for item in &mut items {
+        item.purge_at = Some(item.deleted_at + retention);
+    }
+
+    Ok(Json(TrashListResponse {
+        items,
+        retention_days: config.trash_retention_days,
+    }))
+}
+
+/// Restore a soft-deleted record (and everything deleted together with it)
+pub async fn restore_trash_item(
+    user: AuthUser,
+    State(pool): State<PgPool>,
+    Path((item_type, id)): Path<(TrashItemType, i64)>,
+) -> Result<Json<RestoreResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let restored_count = trash::restore(&pool, user.user_id, item_type, id)
+        .await
+        .map_err(|e| match e {
+            TrashError::NotFound => (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "휴지통에서 항목을 찾을 수 없습니다".to_string(),
+                }),
+            ),
+            TrashError::ParentDeleted(parent) => (
+                StatusCode::CONFLICT,
+                Json(ErrorResponse {
+                    error: format!("{}을(를) 먼저 복원해주세요", parent_label(parent)),
+                }),
+            ),
+            TrashError::Merged => (
+                StatusCode::CONFLICT,
+                Json(ErrorResponse {
+                    error: "다른 고객에 병합된 고객은 복원할 수 없습니다".to_string(),
+                }),
+            ),
+            TrashError::DatabaseError(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("복원 실패: {}", e),
+                }),
+            ),
+        })?;
+
+    Ok(Json(RestoreResponse {
+        item_type,
+        id,
+        restored_count,
+    }))
+}
+
+fn parent_label(item_type: TrashItemType) -> &'static str {
+    match item_type {
+        TrashItemType::Customer => "고객",
+        TrashItemType::JobPosting | TrashItemType::JobSeeking => "공고",
+        TrashItemType::Matching => "매칭",
+        _ => "상위 항목",
+    }
+}
@@ -4,6 +4,7 @@ use sqlx::PgPool;
 
 use crate::middleware::auth::AuthUser;
 use crate::models::user::User;
+use crate::services::business_number::normalize_business_number;
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
 
@@ -19,14 +20,50 @@ pub struct UserProfile {
     pub phone: Option<String>,
     pub default_employer_fee_rate: String,
     pub default_employee_fee_rate: String,
+    pub business_number: Option<String>,
+    pub business_name: Option<String>,
+    pub representative_name: Option<String>,
+    pub business_address: Option<String>,
+    pub business_type: Option<String>,
+    pub business_item: Option<String>,
+    pub tax_invoice_email: Option<String>,
     pub created_at: String,
 }
 
+impl From<User> for UserProfile {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            phone: user.phone,
+            default_employer_fee_rate: user.default_employer_fee_rate.to_string(),
+            default_employee_fee_rate: user.default_employee_fee_rate.to_string(),
+            business_number: user.business_number,
+            business_name: user.business_name,
+            representative_name: user.representative_name,
+            business_address: user.business_address,
+            business_type: user.business_type,
+            business_item: user.business_item,
+            tax_invoice_email: user.tax_invoice_email,
+            created_at: user.created_at.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdateUserProfileRequest {
     pub phone: Option<String>,
     pub default_employer_fee_rate: Option<f64>,
     pub default_employee_fee_rate: Option<f64>,
+    /// Business registration fields printed on tax invoices. An empty string
+    /// clears the field.
+    pub business_number: Option<String>,
+    pub business_name: Option<String>,
+    pub representative_name: Option<String>,
+    pub business_address: Option<String>,
+    pub business_type: Option<String>,
+    pub business_item: Option<String>,
+    pub tax_invoice_email: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -43,6 +80,8 @@ pub async fn get_profile(
         r#"
         SELECT id, username, password_hash, security_question_id, security_answer, phone,
                default_employer_fee_rate, default_employee_fee_rate,
+               business_number, business_name, representative_name, business_address,
+               business_type, business_item, tax_invoice_email,
                last_login_at, created_at, updated_at, deleted_at
         FROM users
         WHERE id = $1 AND deleted_at IS NULL
@@ -61,14 +100,7 @@ pub async fn get_profile(
     })?;
 
     Ok(Json(UserProfileResponse {
-        user: UserProfile {
-            id: db_user.id,
-            username: db_user.username,
-            phone: db_user.phone,
-            default_employer_fee_rate: db_user.default_employer_fee_rate.to_string(),
-            default_employee_fee_rate: db_user.default_employee_fee_rate.to_string(),
-            created_at: db_user.created_at.to_string(),
-        },
+        user: db_user.into(),
     }))
 }
 
@@ -129,6 +161,51 @@ pub async fn update_profile(
         })
         .transpose()?;
 
+    let business_number = payload
+        .business_number
+        .as_deref()
+        .map(|number| match number.trim() {
+            "" => Ok(None),
+            number => normalize_business_number(number).map(Some).map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        error: e.to_string(),
+                    }),
+                )
+            }),
+        })
+        .transpose()?;
+
+    if let Some(email) = payload.tax_invoice_email.as_deref().map(str::trim) {
+        if !email.is_empty() && !email.contains('@') {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "세금계산서 수신 이메일 형식이 올바르지 않습니다".to_string(),
+                }),
+            ));
+        }
+    }
+
+    // Blank business text fields are stored as NULL
+    let business_fields: Vec<(&str, Option<String>)> = [
+        ("business_name", payload.business_name),
+        ("representative_name", payload.representative_name),
+        ("business_address", payload.business_address),
+        ("business_type", payload.business_type),
+        ("business_item", payload.business_item),
+        ("tax_invoice_email", payload.tax_invoice_email),
+    ]
+    .into_iter()
+    .filter_map(|(column, value)| {
+        value.map(|value| {
+            let value = value.trim();
+            (column, (!value.is_empty()).then(|| value.to_string()))
+        })
+    })
+    .collect();
+
     // Build dynamic query based on what fields are being updated
     let mut query = String::from("UPDATE users SET updated_at = NOW()");
     let mut param_count = 1;
@@ -148,7 +225,17 @@ pub async fn update_profile(
         query.push_str(&format!(", default_employee_fee_rate = ${}", param_count));
     }
 
-    query.push_str(" WHERE id = $1 AND deleted_at IS NULL RETURNING id, username, password_hash, security_question_id, security_answer, phone, default_employer_fee_rate, default_employee_fee_rate, last_login_at, created_at, updated_at, deleted_at");
+    if business_number.is_some() {
+        param_count += 1;
+        query.push_str(&format!(", business_number = ${}", param_count));
+    }
+
+    for (column, _) in &business_fields {
+        param_count += 1;
+        query.push_str(&format!(", {} = ${}", column, param_count));
+    }
+
+    query.push_str(" WHERE id = $1 AND deleted_at IS NULL RETURNING id, username, password_hash, security_question_id, security_answer, phone, default_employer_fee_rate, default_employee_fee_rate, business_number, business_name, representative_name, business_address, business_type, business_item, tax_invoice_email, last_login_at, created_at, updated_at, deleted_at");
 
     // Execute query with bound parameters
     let mut query_builder = sqlx::query_as::<_, User>(&query).bind(user.user_id);
@@ -165,6 +252,14 @@ pub async fn update_profile(
         query_builder = query_builder.bind(rate);
     }
 
+    if let Some(number) = business_number {
+        query_builder = query_builder.bind(number);
+    }
+
+    for (_, value) in business_fields {
+        query_builder = query_builder.bind(value);
+    }
+
     let updated_user = query_builder.fetch_one(&pool).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -175,13 +270,6 @@ pub async fn update_profile(
     })?;
 
     Ok(Json(UserProfileResponse {
-        user: UserProfile {
-            id: updated_user.id,
-            username: updated_user.username,
-            phone: updated_user.phone,
-            default_employer_fee_rate: updated_user.default_employer_fee_rate.to_string(),
-            default_employee_fee_rate: updated_user.default_employee_fee_rate.to_string(),
-            created_at: updated_user.created_at.to_string(),
-        },
+        user: updated_user.into(),
     }))
 }
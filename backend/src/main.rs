@@ -8,7 +8,7 @@ use std::net::SocketAddr;
 use tower_http::cors::CorsLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use helpernote_backend::{config, handlers, middleware, AppState};
+use helpernote_backend::{config, handlers, middleware, services, AppState};
 
 #[tokio::main]
 async fn main() {
@@ -47,6 +47,12 @@ async fn main() {
         tracing::warn!("RUN_MIGRATIONS_ON_START=false – skipping database migrations");
     }
 
+    // Background jobs
+    services::trash::spawn_purge_task(db_pool.clone(), config.clone());
+    services::posting_lifecycle::spawn_expiry_task(db_pool.clone(), config.clone());
+    services::analytics::spawn_rollup_task(db_pool.clone(), config.clone());
+    services::monthly_report::spawn_monthly_report_task(db_pool.clone(), config.clone());
+
     // Build application routes
     // Public routes (no authentication required)
     // Auth routes with stricter rate limiting
@@ -75,6 +81,27 @@ async fn main() {
         // User profile routes
         .route("/api/profile", get(handlers::user::get_profile))
         .route("/api/profile", put(handlers::user::update_profile))
+        .route(
+            "/api/profile/fee-policies",
+            get(handlers::fee_policy::get_user_fee_policies)
+                .put(handlers::fee_policy::update_user_fee_policies),
+        )
+        // Fee policies
+        .route(
+            "/api/fee-policies",
+            get(handlers::fee_policy::list_fee_policies)
+                .post(handlers::fee_policy::create_fee_policy),
+        )
+        .route(
+            "/api/fee-policies/{id}",
+            get(handlers::fee_policy::get_fee_policy)
+                .put(handlers::fee_policy::update_fee_policy)
+                .delete(handlers::fee_policy::delete_fee_policy),
+        )
+        .route(
+            "/api/fee-policies/{id}/preview",
+            post(handlers::fee_policy::preview_fee),
+        )
         // User memos
         .route(
             "/api/users/memos",
@@ -110,6 +137,33 @@ async fn main() {
             "/api/dashboard/stats",
             get(handlers::dashboard::get_dashboard_stats),
         )
+        .route(
+            "/api/dashboard/analytics",
+            get(handlers::dashboard::get_dashboard_analytics),
+        )
+        .route(
+            "/api/dashboard/analytics/consistency",
+            get(handlers::dashboard::check_analytics_consistency),
+        )
+        // Reports
+        .route(
+            "/api/reports/receivables",
+            get(handlers::report::get_receivables),
+        )
+        .route(
+            "/api/reports/monthly",
+            get(handlers::report::get_monthly_report).post(handlers::report::store_monthly_report),
+        )
+        .route(
+            "/api/reports/monthly/archive",
+            get(handlers::report::list_monthly_reports),
+        )
+        .route(
+            "/api/reports/monthly/archive/{id}",
+            get(handlers::report::download_monthly_report),
+        )
+        // Global search
+        .route("/api/search", get(handlers::search::global_search))
         // Customer routes
         .route("/api/customers", post(handlers::customer::create_customer))
         .route("/api/customers", get(handlers::customer::list_customers))
@@ -117,6 +171,14 @@ async fn main() {
             "/api/customers/search",
             get(handlers::customer::search_customers),
         )
+        .route(
+            "/api/customers/export",
+            get(handlers::export::export_customers),
+        )
+        .route(
+            "/api/customers/duplicates",
+            get(handlers::customer::find_duplicate_customers),
+        )
         .route("/api/customers/{id}", get(handlers::customer::get_customer))
         .route(
             "/api/customers/{id}",
@@ -126,6 +188,18 @@ async fn main() {
             "/api/customers/{id}",
             delete(handlers::customer::delete_customer),
         )
+        .route(
+            "/api/customers/{id}/merge",
+            post(handlers::customer::merge_customer),
+        )
+        .route(
+            "/api/customers/{id}/timeline",
+            get(handlers::timeline::get_customer_timeline),
+        )
+        .route(
+            "/api/customers/{id}/statement",
+            get(handlers::statement::get_customer_statement),
+        )
         // Customer memos
         .route(
             "/api/customers/{id}/memos",
@@ -183,6 +257,10 @@ async fn main() {
             "/api/job-postings",
             get(handlers::job_posting::list_job_postings),
         )
+        .route(
+            "/api/job-postings/export",
+            get(handlers::export::export_job_postings),
+        )
         .route(
             "/api/job-postings/{id}",
             get(handlers::job_posting::get_job_posting),
@@ -199,10 +277,22 @@ async fn main() {
             "/api/job-postings/{id}/settlement",
             put(handlers::settlement::update_job_posting_settlement),
         )
+        .route(
+            "/api/job-postings/{id}/settlement-history",
+            get(handlers::settlement::get_job_posting_settlement_history),
+        )
         .route(
             "/api/job-postings/{id}/favorite",
             post(handlers::job_posting::toggle_favorite),
         )
+        .route(
+            "/api/job-postings/{id}/status-history",
+            get(handlers::job_posting::get_job_posting_status_history),
+        )
+        .route(
+            "/api/job-postings/{id}/suggestions",
+            get(handlers::job_posting::get_job_posting_suggestions),
+        )
         // Job posting tags
         .route(
             "/api/job-postings/{id}/tags",
@@ -225,6 +315,10 @@ async fn main() {
             "/api/job-seekings",
             get(handlers::job_seeking::list_job_seekings),
         )
+        .route(
+            "/api/job-seekings/export",
+            get(handlers::export::export_job_seekings),
+        )
         .route(
             "/api/job-seekings/{id}",
             get(handlers::job_seeking::get_job_seeking),
@@ -241,10 +335,22 @@ async fn main() {
             "/api/job-seekings/{id}/settlement",
             put(handlers::settlement::update_job_seeking_settlement),
         )
+        .route(
+            "/api/job-seekings/{id}/settlement-history",
+            get(handlers::settlement::get_job_seeking_settlement_history),
+        )
         .route(
             "/api/job-seekings/{id}/favorite",
             post(handlers::job_seeking::toggle_favorite),
         )
+        .route(
+            "/api/job-seekings/{id}/status-history",
+            get(handlers::job_seeking::get_job_seeking_status_history),
+        )
+        .route(
+            "/api/job-seekings/{id}/suggestions",
+            get(handlers::job_seeking::get_job_seeking_suggestions),
+        )
         // Job seeking tags
         .route(
             "/api/job-seekings/{id}/tags",
@@ -261,6 +367,22 @@ async fn main() {
         // Matching routes
         .route("/api/matchings", post(handlers::matching::create_matching))
         .route("/api/matchings", get(handlers::matching::list_matchings))
+        .route(
+            "/api/matchings/preview",
+            post(handlers::matching::preview_matching),
+        )
+        .route(
+            "/api/matchings/guarantees/expiring",
+            get(handlers::matching::list_expiring_guarantees),
+        )
+        .route(
+            "/api/matchings/export",
+            get(handlers::export::export_matchings),
+        )
+        .route(
+            "/api/matchings/tax-invoices/export",
+            get(handlers::export::export_tax_invoices),
+        )
         .route("/api/matchings/{id}", get(handlers::matching::get_matching))
         .route(
             "/api/matchings/{id}",
@@ -278,6 +400,61 @@ async fn main() {
             "/api/matchings/{id}/cancel",
             post(handlers::matching::cancel_matching),
         )
+        .route(
+            "/api/matchings/{id}/replacement",
+            post(handlers::matching::replace_matching),
+        )
+        .route(
+            "/api/matchings/{id}/refund",
+            get(handlers::matching::preview_guarantee_refund),
+        )
+        .route(
+            "/api/matchings/{id}/refund",
+            post(handlers::matching::refund_guarantee),
+        )
+        // Matching payments
+        .route(
+            "/api/matchings/{id}/payments",
+            get(handlers::payment::list_payments),
+        )
+        .route(
+            "/api/matchings/{id}/payments",
+            post(handlers::payment::create_payment),
+        )
+        .route(
+            "/api/matchings/{id}/payments/{payment_id}",
+            delete(handlers::payment::delete_payment),
+        )
+        .route(
+            "/api/matchings/{id}/balance",
+            get(handlers::payment::get_matching_balance),
+        )
+        // Fee documents (invoices, receipts)
+        .route(
+            "/api/matchings/{id}/documents",
+            get(handlers::fee_document::list_fee_documents),
+        )
+        .route(
+            "/api/matchings/{id}/documents",
+            post(handlers::fee_document::create_fee_document),
+        )
+        .route(
+            "/api/documents/{id}/download",
+            get(handlers::fee_document::download_fee_document),
+        )
+        // Bank statement import
+        .route(
+            "/api/bank-imports",
+            post(handlers::bank_import::create_bank_import),
+        )
+        .route(
+            "/api/bank-imports/{id}",
+            get(handlers::bank_import::get_bank_import),
+        )
+        .route(
+            "/api/bank-imports/{id}/confirm",
+            post(handlers::bank_import::confirm_bank_matches),
+        )
         // Matching memos
         .route(
             "/api/matchings/{id}/memos",
@@ -295,6 +472,12 @@ async fn main() {
             "/api/matchings/{id}/memos/{memo_id}",
             delete(handlers::memo::delete_matching_memo),
         )
+        // Trash
+        .route("/api/trash", get(handlers::trash::list_trash))
+        .route(
+            "/api/trash/{type}/{id}/restore",
+            post(handlers::trash::restore_trash_item),
+        )
         // Tag routes
         .route("/api/tags", post(handlers::tag::create_tag))
         .route("/api/tags", get(handlers::tag::list_tags))
@@ -0,0 +1,127 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Length of one period of a time series
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Day,
+    /// 월요일부터 일요일까지
+    Week,
+    #[default]
+    Month,
+}
+
+/// Quantity counted per day or period. `MatchDays` is the total number of
+/// days from job posting to matching of the matchings created, and
+/// `JobPostingsFilled` the job postings opened that now have a completed
+/// matching. `JobPostingsOpened` and `JobSeekingsOpened` split
+/// `PostingsOpened` by posting kind. `FeesCharged` and `FeesPaid` are the fees of the matchings made
+/// that day that are not cancelled and the payments received on them; they
+/// add up to the dashboard's all-time totals.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsMetric {
+    RevenueEarned,
+    RevenueCollected,
+    NewCustomers,
+    PostingsOpened,
+    PostingsClosed,
+    JobPostingsOpened,
+    JobPostingsFilled,
+    JobSeekingsOpened,
+    MatchingsCreated,
+    MatchingsCompleted,
+    MatchingsCancelled,
+    MatchDays,
+    FeesCharged,
+    FeesPaid,
+}
+
+impl AnalyticsMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalyticsMetric::RevenueEarned => "revenue_earned",
+            AnalyticsMetric::RevenueCollected => "revenue_collected",
+            AnalyticsMetric::NewCustomers => "new_customers",
+            AnalyticsMetric::PostingsOpened => "postings_opened",
+            AnalyticsMetric::PostingsClosed => "postings_closed",
+            AnalyticsMetric::JobPostingsOpened => "job_postings_opened",
+            AnalyticsMetric::JobPostingsFilled => "job_postings_filled",
+            AnalyticsMetric::JobSeekingsOpened => "job_seekings_opened",
+            AnalyticsMetric::MatchingsCreated => "matchings_created",
+            AnalyticsMetric::MatchingsCompleted => "matchings_completed",
+            AnalyticsMetric::MatchingsCancelled => "matchings_cancelled",
+            AnalyticsMetric::MatchDays => "match_days",
+            AnalyticsMetric::FeesCharged => "fees_charged",
+            AnalyticsMetric::FeesPaid => "fees_paid",
+        }
+    }
+}
+
+/// Value of one metric on the period or day starting at `bucket`
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MetricRow {
+    pub bucket: NaiveDate,
+    pub metric: AnalyticsMetric,
+    pub value: Decimal,
+}
+
+/// All-time sum of one metric
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MetricTotal {
+    pub metric: AnalyticsMetric,
+    pub value: Decimal,
+}
+
+/// A day whose rolled-up value of a metric differs from the live tables
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MetricMismatch {
+    pub day: NaiveDate,
+    pub metric: AnalyticsMetric,
+    pub rollup: Decimal,
+    pub live: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RollupConsistencyReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub consistent: bool,
+    pub mismatches: Vec<MetricMismatch>,
+    /// 불일치한 날짜를 다시 집계했는지
+    pub repaired: bool,
+}
+
+/// Activity of one period. Revenue earned is the fees of matchings
+/// completed in the period less their guarantee refunds; revenue collected is
+/// the payments received in it.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct AnalyticsPeriod {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub revenue_earned: Decimal,
+    pub revenue_collected: Decimal,
+    pub new_customers: i64,
+    pub postings_opened: i64,
+    pub postings_closed: i64,
+    pub matchings_created: i64,
+    pub matchings_completed: i64,
+    pub matchings_cancelled: i64,
+    /// 기간 중 등록된 구인 공고 중 매칭이 완료된 비율 (%)
+    pub conversion_rate: Option<Decimal>,
+    /// 구인 공고 등록부터 매칭까지 평균 일수
+    pub average_days_to_match: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardAnalytics {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub granularity: Granularity,
+    pub periods: Vec<AnalyticsPeriod>,
+    /// 전체 기간 합계
+    pub totals: AnalyticsPeriod,
+}
@@ -0,0 +1,109 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::payment::PayerSide;
+
+/// One uploaded bank statement file
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BankImport {
+    pub id: i64,
+    pub user_id: i64,
+    pub file_name: Option<String>,
+    pub total_rows: i32,
+    pub imported_rows: i32,
+    pub created_at: NaiveDateTime,
+}
+
+/// A deposit read from a bank statement. `payment_id` is set once it has
+/// been confirmed as a fee payment.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BankTransaction {
+    pub id: i64,
+    pub user_id: i64,
+    pub bank_import_id: i64,
+    pub transacted_at: NaiveDateTime,
+    pub depositor: String,
+    pub amount: Decimal,
+    pub memo: Option<String>,
+    pub balance_after: Option<Decimal>,
+    pub payment_id: Option<i64>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A deposit row parsed from a statement file, before it is stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedDeposit {
+    pub transacted_at: NaiveDateTime,
+    pub depositor: String,
+    pub amount: Decimal,
+    pub memo: Option<String>,
+    pub balance_after: Option<Decimal>,
+}
+
+impl From<&BankTransaction> for ParsedDeposit {
+    fn from(transaction: &BankTransaction) -> Self {
+        ParsedDeposit {
+            transacted_at: transaction.transacted_at,
+            depositor: transaction.depositor.clone(),
+            amount: transaction.amount,
+            memo: transaction.memo.clone(),
+            balance_after: transaction.balance_after,
+        }
+    }
+}
+
+/// Fee of one side of a matching that is not cancelled, with what has been
+/// paid on it. `since` is the day the matching completed, or was made if it
+/// is still in progress.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OpenFeeRow {
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub since: NaiveDate,
+    pub fee_amount: Decimal,
+    pub refunded_amount: Decimal,
+    pub paid_amount: Decimal,
+}
+
+/// A fee a deposit may be paying, with how well each criterion matched
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MatchSuggestion {
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub outstanding_amount: Decimal,
+    /// 0–100
+    pub score: i32,
+    pub amount_score: i32,
+    pub name_score: i32,
+    pub date_score: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BankTransactionWithSuggestions {
+    #[serde(flatten)]
+    pub transaction: BankTransaction,
+    pub suggestions: Vec<MatchSuggestion>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BankImportDetail {
+    pub import: BankImport,
+    pub transactions: Vec<BankTransactionWithSuggestions>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfirmMatch {
+    pub transaction_id: i64,
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfirmBankMatchesRequest {
+    pub matches: Vec<ConfirmMatch>,
+}
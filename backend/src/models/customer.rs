@@ -21,6 +21,8 @@ pub struct Customer {
     pub birth_date: Option<NaiveDate>,
     pub phone: String,
     pub address: Option<String>,
+    /// 사업자등록번호 (구인자만, 숫자 10자리)
+    pub business_number: Option<String>,
     pub profile_photo_id: Option<i64>,
     pub customer_type: CustomerType,
     pub created_at: NaiveDateTime,
@@ -35,6 +37,10 @@ pub struct CreateCustomerRequest {
     pub phone: String,
     pub address: Option<String>,
     pub customer_type: CustomerType,
+    pub business_number: Option<String>,
+    /// Create the customer even if an active customer has the same phone number
+    #[serde(default)]
+    pub allow_duplicate_phone: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -45,4 +51,57 @@ pub struct UpdateCustomerRequest {
     pub address: Option<String>,
     pub profile_photo_id: Option<i64>,
     pub customer_type: Option<CustomerType>,
+    /// An empty string removes the stored number
+    pub business_number: Option<String>,
+    /// Save the new phone number even if another active customer already uses it
+    #[serde(default)]
+    pub allow_duplicate_phone: bool,
+}
+
+/// Which customer's value to keep for a field when merging
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum MergeSide {
+    Target,
+    Source,
+}
+
+/// Explicit choices for fields whose values differ between the two customers
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MergeFieldChoices {
+    pub name: Option<MergeSide>,
+    pub birth_date: Option<MergeSide>,
+    pub phone: Option<MergeSide>,
+    pub address: Option<MergeSide>,
+    pub customer_type: Option<MergeSide>,
+    pub profile_photo: Option<MergeSide>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeCustomerRequest {
+    /// Customer to merge into the target (soft-deleted afterwards)
+    pub source_customer_id: i64,
+    #[serde(default)]
+    pub fields: MergeFieldChoices,
+}
+
+/// Fully resolved field selection applied to the target customer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MergeSelection {
+    pub name: MergeSide,
+    pub birth_date: MergeSide,
+    pub phone: MergeSide,
+    pub address: MergeSide,
+    pub customer_type: MergeSide,
+    pub profile_photo: MergeSide,
+}
+
+/// Number of records moved from the source to the target customer
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CustomerMergeSummary {
+    pub job_postings: u64,
+    pub job_seekings: u64,
+    pub memos: u64,
+    pub files: u64,
+    pub tags: u64,
 }
@@ -0,0 +1,128 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sqlx::types::Json;
+
+use crate::models::customer::CustomerType;
+use crate::models::fee_policy::FeeBreakdown;
+use crate::models::job_posting::{PostingStatus, SettlementStatus};
+use crate::models::matching::MatchingStatus;
+use crate::models::payment::PayerSide;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CustomerExportRow {
+    pub id: i64,
+    pub name: String,
+    pub customer_type: CustomerType,
+    pub phone: String,
+    pub birth_date: Option<NaiveDate>,
+    pub address: Option<String>,
+    pub tags: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobPostingExportRow {
+    pub id: i64,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub salary: Decimal,
+    pub description: String,
+    pub employer_fee_rate: Option<Decimal>,
+    pub posting_status: PostingStatus,
+    pub settlement_status: SettlementStatus,
+    pub settlement_amount: Option<Decimal>,
+    pub settlement_memo: Option<String>,
+    pub is_favorite: bool,
+    pub tags: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobSeekingExportRow {
+    pub id: i64,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub desired_salary: Decimal,
+    pub description: String,
+    pub preferred_location: String,
+    pub employee_fee_rate: Option<Decimal>,
+    pub posting_status: PostingStatus,
+    pub settlement_status: SettlementStatus,
+    pub settlement_amount: Option<Decimal>,
+    pub settlement_memo: Option<String>,
+    pub is_favorite: bool,
+    pub tags: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MatchingExportRow {
+    pub id: i64,
+    pub matched_at: NaiveDateTime,
+    pub job_posting_id: i64,
+    pub employer_name: String,
+    pub job_seeking_posting_id: i64,
+    pub employee_name: String,
+    pub agreed_salary: Decimal,
+    pub employer_fee_rate: Decimal,
+    pub employer_fee_amount: Option<Decimal>,
+    pub employer_settlement_status: SettlementStatus,
+    pub employee_fee_rate: Decimal,
+    pub employee_fee_amount: Option<Decimal>,
+    pub employee_settlement_status: SettlementStatus,
+    pub matching_status: MatchingStatus,
+    pub completed_at: Option<NaiveDateTime>,
+    pub cancelled_at: Option<NaiveDateTime>,
+    pub cancellation_reason: Option<String>,
+}
+
+/// One side of a completed matching with a fee, with the agency as supplier
+/// and the paying customer as recipient of the tax invoice
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TaxInvoiceExportRow {
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub completed_at: NaiveDateTime,
+    pub supplier_business_number: Option<String>,
+    /// 상호, falling back to the username
+    pub supplier_name: String,
+    pub supplier_representative: Option<String>,
+    pub supplier_address: Option<String>,
+    pub supplier_business_type: Option<String>,
+    pub supplier_business_item: Option<String>,
+    pub supplier_email: Option<String>,
+    pub recipient_business_number: Option<String>,
+    pub recipient_name: String,
+    pub recipient_address: Option<String>,
+    pub fee_amount: Decimal,
+    pub refunded_amount: Decimal,
+    pub fee_breakdown: Option<Json<FeeBreakdown>>,
+    pub paid_amount: Decimal,
+}
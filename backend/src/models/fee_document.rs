@@ -0,0 +1,97 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+
+use crate::models::fee_policy::FeeBreakdown;
+use crate::models::job_posting::SalaryBasis;
+use crate::models::payment::PayerSide;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum DocumentType {
+    /// 청구서
+    #[sqlx(rename = "invoice")]
+    Invoice,
+    /// 영수증
+    #[sqlx(rename = "receipt")]
+    Receipt,
+}
+
+impl DocumentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentType::Invoice => "invoice",
+            DocumentType::Receipt => "receipt",
+        }
+    }
+
+    /// Prefix of the document number, e.g. `INV-2025-0001`
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            DocumentType::Invoice => "INV",
+            DocumentType::Receipt => "RCP",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            DocumentType::Invoice => "수수료 청구서",
+            DocumentType::Receipt => "수수료 영수증",
+        }
+    }
+}
+
+/// An issued invoice or receipt, stored as a PDF in MinIO
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FeeDocument {
+    pub id: i64,
+    pub user_id: i64,
+    pub matching_id: i64,
+    pub document_type: DocumentType,
+    pub payer_side: PayerSide,
+    pub document_number: String,
+    pub issued_on: NaiveDate,
+    pub total_amount: Decimal,
+    #[serde(skip_serializing)]
+    pub file_path: String,
+    pub file_size: i64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateFeeDocumentRequest {
+    pub document_type: DocumentType,
+    pub payer_side: PayerSide,
+}
+
+/// Parties and matching terms printed on a document for one side of a matching
+#[derive(Debug, Clone)]
+pub struct FeeDocumentSource {
+    pub agency_name: String,
+    pub agency_phone: Option<String>,
+    pub customer_name: String,
+    pub customer_phone: String,
+    pub customer_address: Option<String>,
+    pub matched_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+    pub agreed_salary: Decimal,
+    pub salary_basis: SalaryBasis,
+    pub fee_rate: Decimal,
+    pub fee_breakdown: Option<Json<FeeBreakdown>>,
+}
+
+/// Row of a document that has been rendered and stored
+#[derive(Debug, Clone)]
+pub struct NewFeeDocument {
+    pub user_id: i64,
+    pub matching_id: i64,
+    pub document_type: DocumentType,
+    pub payer_side: PayerSide,
+    pub document_number: String,
+    pub issued_on: NaiveDate,
+    pub total_amount: Decimal,
+    pub file_path: String,
+    pub file_size: i64,
+}
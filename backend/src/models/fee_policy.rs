@@ -0,0 +1,90 @@
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::job_posting::SalaryBasis;
+
+/// Fee rate applied from `min_monthly_salary` (inclusive) up to the next tier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FeeTier {
+    pub min_monthly_salary: Decimal,
+    pub rate: Decimal,
+}
+
+/// A user's fee rules. Amounts are in won and exclude VAT.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct FeePolicy {
+    pub id: i64,
+    pub user_id: i64,
+    pub name: String,
+    pub minimum_fee: Option<Decimal>,
+    pub short_term_max_days: Option<i32>,
+    pub short_term_fee: Option<Decimal>,
+    pub vat_rate: Decimal,
+    /// Ordered by `min_monthly_salary`
+    #[sqlx(skip)]
+    pub tiers: Vec<FeeTier>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Body of both create and update; an update replaces the whole policy
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeePolicyRequest {
+    pub name: String,
+    pub minimum_fee: Option<Decimal>,
+    pub short_term_max_days: Option<i32>,
+    pub short_term_fee: Option<Decimal>,
+    /// 생략하면 10%
+    pub vat_rate: Option<Decimal>,
+    #[serde(default)]
+    pub tiers: Vec<FeeTier>,
+}
+
+/// Default fee policies of the user per matching side
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserFeePolicies {
+    pub employer_fee_policy_id: Option<i64>,
+    pub employee_fee_policy_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum FeeMethod {
+    /// 매칭 수수료율
+    Rate,
+    /// 정책의 급여 구간 수수료율
+    Tier,
+    /// 단기 근무 고정 수수료
+    ShortTerm,
+}
+
+/// How one side's fee of a matching was calculated.
+/// `total_amount` is what the customer is charged (`supply_amount` + VAT).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    pub fee_policy_id: Option<i64>,
+    pub fee_policy_name: Option<String>,
+    pub method: FeeMethod,
+    /// 월 환산 급여
+    pub fee_base: Decimal,
+    pub rate: Option<Decimal>,
+    pub calculated_fee: Decimal,
+    pub minimum_fee: Option<Decimal>,
+    pub minimum_applied: bool,
+    pub supply_amount: Decimal,
+    pub vat_rate: Decimal,
+    pub vat_amount: Decimal,
+    pub total_amount: Decimal,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeePreviewRequest {
+    pub agreed_salary: Decimal,
+    #[serde(default)]
+    pub salary_basis: SalaryBasis,
+    pub expected_weekly_hours: Option<Decimal>,
+    /// Used when the policy has no tier for the salary; 생략하면 0%
+    pub rate: Option<Decimal>,
+    pub contract_days: Option<i32>,
+}
@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -23,10 +23,106 @@ pub enum PostingStatus {
 pub enum SettlementStatus {
     #[sqlx(rename = "unsettled")]
     Unsettled,
+    /// 수수료 일부만 입금됨
+    #[sqlx(rename = "partially_settled")]
+    PartiallySettled,
     #[sqlx(rename = "settled")]
     Settled,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum JobCategory {
+    /// 가사
+    #[sqlx(rename = "housekeeping")]
+    Housekeeping,
+    /// 육아
+    #[sqlx(rename = "childcare")]
+    Childcare,
+    /// 산후조리
+    #[sqlx(rename = "postpartum")]
+    Postpartum,
+    /// 노인돌봄
+    #[sqlx(rename = "eldercare")]
+    Eldercare,
+    /// 간병
+    #[sqlx(rename = "patient_care")]
+    PatientCare,
+    #[sqlx(rename = "other")]
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum WorkArrangement {
+    /// 입주
+    #[sqlx(rename = "live_in")]
+    LiveIn,
+    /// 출퇴근
+    #[sqlx(rename = "commute")]
+    Commute,
+}
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum Weekday {
+    #[sqlx(rename = "mon")]
+    Monday,
+    #[sqlx(rename = "tue")]
+    Tuesday,
+    #[sqlx(rename = "wed")]
+    Wednesday,
+    #[sqlx(rename = "thu")]
+    Thursday,
+    #[sqlx(rename = "fri")]
+    Friday,
+    #[sqlx(rename = "sat")]
+    Saturday,
+    #[sqlx(rename = "sun")]
+    Sunday,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum SalaryBasis {
+    #[default]
+    #[sqlx(rename = "monthly")]
+    Monthly,
+    #[sqlx(rename = "daily")]
+    Daily,
+    #[sqlx(rename = "hourly")]
+    Hourly,
+}
+
+/// Who or what triggered a posting status change
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum StatusChangeSource {
+    /// 사용자가 직접 변경
+    #[sqlx(rename = "manual")]
+    Manual,
+    /// 만료 시각이 지나 자동 마감
+    #[sqlx(rename = "expiry")]
+    Expiry,
+    /// 매칭 생성/완료/취소에 따라 변경
+    #[sqlx(rename = "matching")]
+    Matching,
+}
+
+/// Posting table a status change applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostingKind {
+    JobPosting,
+    JobSeeking,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct JobPosting {
     pub id: i64,
@@ -39,6 +135,22 @@ pub struct JobPosting {
     pub settlement_memo: Option<String>,
     pub posting_status: PostingStatus,
     pub is_favorite: bool,
+    pub job_category: Option<JobCategory>,
+    pub region_codes: Vec<String>,
+    pub work_arrangement: Option<WorkArrangement>,
+    pub work_days: Vec<Weekday>,
+    pub work_start_time: Option<NaiveTime>,
+    pub work_end_time: Option<NaiveTime>,
+    pub start_date: Option<NaiveDate>,
+    pub salary_basis: SalaryBasis,
+    pub expected_weekly_hours: Option<Decimal>,
+    /// 월 환산 급여 (salary_basis, expected_weekly_hours 기준 자동 계산)
+    pub monthly_salary: Decimal,
+    pub household_members: Option<i32>,
+    pub has_pets: Option<bool>,
+    pub expires_at: Option<NaiveDateTime>,
+    /// 구인자 수수료 정책 (없으면 사용자 기본 정책)
+    pub fee_policy_id: Option<i64>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
@@ -50,6 +162,22 @@ pub struct CreateJobPostingRequest {
     pub salary: Decimal,
     pub description: String,
     pub employer_fee_rate: Option<Decimal>,
+    pub job_category: Option<JobCategory>,
+    #[serde(default)]
+    pub region_codes: Vec<String>,
+    pub work_arrangement: Option<WorkArrangement>,
+    #[serde(default)]
+    pub work_days: Vec<Weekday>,
+    pub work_start_time: Option<NaiveTime>,
+    pub work_end_time: Option<NaiveTime>,
+    pub start_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub salary_basis: SalaryBasis,
+    pub expected_weekly_hours: Option<Decimal>,
+    pub household_members: Option<i32>,
+    pub has_pets: Option<bool>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub fee_policy_id: Option<i64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -62,6 +190,42 @@ pub struct UpdateJobPostingRequest {
     pub settlement_memo: Option<String>,
     pub posting_status: Option<PostingStatus>,
     pub is_favorite: Option<bool>,
+    pub job_category: Option<JobCategory>,
+    pub region_codes: Option<Vec<String>>,
+    pub work_arrangement: Option<WorkArrangement>,
+    pub work_days: Option<Vec<Weekday>>,
+    pub work_start_time: Option<NaiveTime>,
+    pub work_end_time: Option<NaiveTime>,
+    pub start_date: Option<NaiveDate>,
+    pub salary_basis: Option<SalaryBasis>,
+    pub expected_weekly_hours: Option<Decimal>,
+    pub household_members: Option<i32>,
+    pub has_pets: Option<bool>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub fee_policy_id: Option<i64>,
+    /// Remove the posting's fee policy so the user default applies again
+    #[serde(default)]
+    pub clear_fee_policy: bool,
+}
+
+/// Filters for listing job postings
+#[derive(Debug, Clone, Default)]
+pub struct JobPostingFilter {
+    pub status: Option<PostingStatus>,
+    pub settlement_status: Option<SettlementStatus>,
+    pub is_favorite: Option<bool>,
+    pub job_category: Option<JobCategory>,
+    /// 시도/시군구/읍면동 코드. 상위 또는 하위 지역 코드가 겹치는 공고를 포함
+    pub region_code: Option<String>,
+    pub work_arrangement: Option<WorkArrangement>,
+    pub salary_basis: Option<SalaryBasis>,
+    pub min_monthly_salary: Option<Decimal>,
+    pub max_monthly_salary: Option<Decimal>,
+    pub work_day: Option<Weekday>,
+    pub start_date_from: Option<NaiveDate>,
+    pub start_date_to: Option<NaiveDate>,
+    pub has_pets: Option<bool>,
+    pub max_household_members: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -77,6 +241,22 @@ pub struct JobSeekingPosting {
     pub settlement_memo: Option<String>,
     pub posting_status: PostingStatus,
     pub is_favorite: bool,
+    pub job_category: Option<JobCategory>,
+    pub region_codes: Vec<String>,
+    pub work_arrangement: Option<WorkArrangement>,
+    pub work_days: Vec<Weekday>,
+    pub work_start_time: Option<NaiveTime>,
+    pub work_end_time: Option<NaiveTime>,
+    pub start_date: Option<NaiveDate>,
+    pub salary_basis: SalaryBasis,
+    pub expected_weekly_hours: Option<Decimal>,
+    /// 월 환산 급여 (salary_basis, expected_weekly_hours 기준 자동 계산)
+    pub monthly_salary: Decimal,
+    pub max_household_members: Option<i32>,
+    pub accepts_pets: Option<bool>,
+    pub expires_at: Option<NaiveDateTime>,
+    /// 구직자 수수료 정책 (없으면 사용자 기본 정책)
+    pub fee_policy_id: Option<i64>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
@@ -89,6 +269,22 @@ pub struct CreateJobSeekingPostingRequest {
     pub description: String,
     pub preferred_location: String,
     pub employee_fee_rate: Option<Decimal>,
+    pub job_category: Option<JobCategory>,
+    #[serde(default)]
+    pub region_codes: Vec<String>,
+    pub work_arrangement: Option<WorkArrangement>,
+    #[serde(default)]
+    pub work_days: Vec<Weekday>,
+    pub work_start_time: Option<NaiveTime>,
+    pub work_end_time: Option<NaiveTime>,
+    pub start_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub salary_basis: SalaryBasis,
+    pub expected_weekly_hours: Option<Decimal>,
+    pub max_household_members: Option<i32>,
+    pub accepts_pets: Option<bool>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub fee_policy_id: Option<i64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -102,4 +298,53 @@ pub struct UpdateJobSeekingPostingRequest {
     pub settlement_memo: Option<String>,
     pub posting_status: Option<PostingStatus>,
     pub is_favorite: Option<bool>,
+    pub job_category: Option<JobCategory>,
+    pub region_codes: Option<Vec<String>>,
+    pub work_arrangement: Option<WorkArrangement>,
+    pub work_days: Option<Vec<Weekday>>,
+    pub work_start_time: Option<NaiveTime>,
+    pub work_end_time: Option<NaiveTime>,
+    pub start_date: Option<NaiveDate>,
+    pub salary_basis: Option<SalaryBasis>,
+    pub expected_weekly_hours: Option<Decimal>,
+    pub max_household_members: Option<i32>,
+    pub accepts_pets: Option<bool>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub fee_policy_id: Option<i64>,
+    /// Remove the posting's fee policy so the user default applies again
+    #[serde(default)]
+    pub clear_fee_policy: bool,
+}
+
+/// Filters for listing job seeking postings
+#[derive(Debug, Clone, Default)]
+pub struct JobSeekingFilter {
+    pub status: Option<PostingStatus>,
+    pub settlement_status: Option<SettlementStatus>,
+    pub preferred_location: Option<String>,
+    pub job_category: Option<JobCategory>,
+    pub region_code: Option<String>,
+    pub work_arrangement: Option<WorkArrangement>,
+    pub salary_basis: Option<SalaryBasis>,
+    pub min_monthly_salary: Option<Decimal>,
+    pub max_monthly_salary: Option<Decimal>,
+    pub work_day: Option<Weekday>,
+    pub start_date_from: Option<NaiveDate>,
+    pub start_date_to: Option<NaiveDate>,
+    pub accepts_pets: Option<bool>,
+    /// 이 인원 이상의 가정에서 근무 가능한 구직자만 (최대 인원 미지정 포함)
+    pub household_members: Option<i32>,
+}
+
+/// One row of a posting's status history
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PostingStatusChange {
+    pub id: i64,
+    pub posting_id: i64,
+    pub from_status: PostingStatus,
+    pub to_status: PostingStatus,
+    pub change_source: StatusChangeSource,
+    /// 자동 만료처럼 시스템이 변경했으면 None
+    pub changed_by: Option<i64>,
+    pub changed_at: NaiveDateTime,
 }
@@ -1,7 +1,12 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use sqlx::types::Json;
+
+use crate::models::fee_policy::FeeBreakdown;
+use crate::models::job_posting::SalaryBasis;
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
 #[sqlx(type_name = "text", rename_all = "lowercase")]
 #[serde(rename_all = "PascalCase")]
@@ -15,6 +20,41 @@ pub enum MatchingStatus {
     Cancelled,
 }
 
+/// Where a matching's fee rate came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum FeeRateSource {
+    /// 매칭 요청에 입력한 수수료율
+    #[sqlx(rename = "request")]
+    Request,
+    /// 공고에 설정된 수수료율
+    #[sqlx(rename = "posting")]
+    Posting,
+    /// 사용자 기본 수수료율
+    #[sqlx(rename = "user_default")]
+    UserDefault,
+    /// 수수료 정책의 급여 구간 수수료율
+    #[sqlx(rename = "policy")]
+    Policy,
+    /// 보증 기간 내 무상 교체 (수수료 없음)
+    #[sqlx(rename = "replacement")]
+    Replacement,
+}
+
+/// How a broken guarantee was settled
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum GuaranteeResolution {
+    /// 다른 구직자로 무상 교체
+    #[sqlx(rename = "replacement")]
+    Replacement,
+    /// 남은 보증 기간만큼 수수료 환불
+    #[sqlx(rename = "refund")]
+    Refund,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Matching {
     pub id: i64,
@@ -22,15 +62,33 @@ pub struct Matching {
     pub job_seeking_posting_id: i64,
     pub matched_at: NaiveDateTime,
     pub agreed_salary: Decimal,
+    pub salary_basis: SalaryBasis,
+    pub expected_weekly_hours: Option<Decimal>,
+    /// 월 환산 합의 급여 (수수료 산정 기준)
+    pub monthly_salary: Decimal,
     pub employer_fee_rate: Decimal,
     pub employee_fee_rate: Decimal,
     pub employer_fee_amount: Option<Decimal>,
     pub employee_fee_amount: Option<Decimal>,
+    pub employer_fee_rate_source: FeeRateSource,
+    pub employee_fee_rate_source: FeeRateSource,
     pub matching_status: MatchingStatus,
     pub cancellation_reason: Option<String>,
     pub cancelled_at: Option<NaiveDateTime>,
     pub cancelled_by: Option<i64>,
     pub completed_at: Option<NaiveDateTime>,
+    /// 계약 근무 일수 (단기 근무 고정 수수료 판단)
+    pub contract_days: Option<i32>,
+    pub employer_fee_breakdown: Option<Json<FeeBreakdown>>,
+    pub employee_fee_breakdown: Option<Json<FeeBreakdown>>,
+    /// 보증 기간 (일), 매칭 완료일부터
+    pub guarantee_days: Option<i32>,
+    pub guarantee_expires_at: Option<NaiveDateTime>,
+    /// 이 매칭이 무상 교체한 원 매칭
+    pub replaces_matching_id: Option<i64>,
+    pub guarantee_resolution: Option<GuaranteeResolution>,
+    pub guarantee_broken_on: Option<NaiveDate>,
+    pub guarantee_refund_amount: Option<Decimal>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
@@ -41,15 +99,75 @@ pub struct CreateMatchingRequest {
     pub job_posting_id: i64,
     pub job_seeking_posting_id: i64,
     pub agreed_salary: Decimal,
-    pub employer_fee_rate: Decimal,
-    pub employee_fee_rate: Decimal,
+    /// 생략하면 구인 공고의 급여 기준을 사용
+    pub salary_basis: Option<SalaryBasis>,
+    /// 생략하면 구인 공고의 주당 근무시간을 사용
+    pub expected_weekly_hours: Option<Decimal>,
+    /// 생략하면 공고, 사용자 기본 수수료율 순으로 적용
+    pub employer_fee_rate: Option<Decimal>,
+    /// 생략하면 공고, 사용자 기본 수수료율 순으로 적용
+    pub employee_fee_rate: Option<Decimal>,
+    pub contract_days: Option<i32>,
+    pub guarantee_days: Option<i32>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct UpdateMatchingRequest {
     pub agreed_salary: Option<Decimal>,
+    pub salary_basis: Option<SalaryBasis>,
+    pub expected_weekly_hours: Option<Decimal>,
     pub employer_fee_rate: Option<Decimal>,
     pub employee_fee_rate: Option<Decimal>,
+    pub contract_days: Option<i32>,
+    pub guarantee_days: Option<i32>,
     pub matching_status: Option<MatchingStatus>,
     pub cancellation_reason: Option<String>,
 }
+
+/// Replace the helper of a completed matching within its guarantee period.
+/// Omitted terms are taken from the original matching.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplaceMatchingRequest {
+    /// 원 매칭의 근무자가 그만둔 날
+    pub left_on: NaiveDate,
+    pub job_seeking_posting_id: i64,
+    pub agreed_salary: Option<Decimal>,
+    pub salary_basis: Option<SalaryBasis>,
+    pub expected_weekly_hours: Option<Decimal>,
+    pub employee_fee_rate: Option<Decimal>,
+    pub contract_days: Option<i32>,
+    pub guarantee_days: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuaranteeRefundRequest {
+    /// 근무자가 그만둔 날
+    pub left_on: NaiveDate,
+}
+
+/// Employer fee refunded for the part of the guarantee period not worked
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GuaranteeRefund {
+    pub fee_amount: Decimal,
+    pub guarantee_days: i32,
+    pub days_worked: i64,
+    pub remaining_days: i64,
+    pub refund_amount: Decimal,
+}
+
+/// One side's fee rate with where it came from and the resulting fee
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedFee {
+    pub rate: Decimal,
+    pub rate_source: FeeRateSource,
+    pub breakdown: FeeBreakdown,
+}
+
+/// Rates and fees a matching would be created with
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchingPreview {
+    /// 월 환산 합의 급여 (수수료 산정 기준)
+    pub monthly_salary: Decimal,
+    pub employer: ResolvedFee,
+    pub employee: ResolvedFee,
+}
@@ -0,0 +1,111 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::payment::PayerSide;
+use crate::models::report::AgingTotals;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Pdf,
+    Xlsx,
+}
+
+impl ReportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "application/json",
+            ReportFormat::Pdf => "application/pdf",
+            ReportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Pdf => "pdf",
+            ReportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// Customer ranked by the fees earned from them in the month
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TopCustomer {
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub matchings_completed: i64,
+    pub fees_earned: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CancellationReasonCount {
+    /// 사유가 없으면 "사유 미입력"
+    pub reason: String,
+    pub count: i64,
+}
+
+/// Summary of one month of the agency's business. Fees earned are those of
+/// the matchings completed in the month less guarantee refunds; fees
+/// collected are the payments received in it.
+#[derive(Debug, Clone, Serialize)]
+pub struct MonthlyReport {
+    pub year: i32,
+    pub month: u32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub matchings_created: i64,
+    pub matchings_completed: i64,
+    pub matchings_cancelled: i64,
+    pub fees_earned: Decimal,
+    pub fees_collected: Decimal,
+    /// 말일 기준 미수금 (입금은 보고서 생성 시점까지 반영)
+    pub outstanding: AgingTotals,
+    pub top_customers: Vec<TopCustomer>,
+    pub cancellation_reasons: Vec<CancellationReasonCount>,
+}
+
+/// Fee of one side of a matching completed in the month, with the customer
+/// who owes it
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MonthlyFeeRow {
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub fee_amount: Decimal,
+    pub refunded_amount: Decimal,
+}
+
+/// A generated report stored as PDF and XLSX in MinIO
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct StoredMonthlyReport {
+    pub id: i64,
+    pub user_id: i64,
+    pub year: i32,
+    pub month: i32,
+    #[serde(skip_serializing)]
+    pub pdf_path: String,
+    pub pdf_size: i64,
+    #[serde(skip_serializing)]
+    pub xlsx_path: String,
+    pub xlsx_size: i64,
+    pub generated_at: NaiveDateTime,
+}
+
+/// Files of a generated report before they are stored
+#[derive(Debug, Clone)]
+pub struct NewMonthlyReport {
+    pub user_id: i64,
+    pub year: i32,
+    pub month: i32,
+    pub pdf_path: String,
+    pub pdf_size: i64,
+    pub xlsx_path: String,
+    pub xlsx_size: i64,
+}
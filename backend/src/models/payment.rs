@@ -0,0 +1,95 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::job_posting::{PostingKind, SettlementStatus};
+
+/// Side of a matching that pays a fee
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum PayerSide {
+    /// 구인자
+    #[sqlx(rename = "employer")]
+    Employer,
+    /// 구직자
+    #[sqlx(rename = "employee")]
+    Employee,
+}
+
+impl PayerSide {
+    /// Side that pays the fee of a posting's matchings
+    pub fn for_posting(kind: PostingKind) -> Self {
+        match kind {
+            PostingKind::JobPosting => PayerSide::Employer,
+            PostingKind::JobSeeking => PayerSide::Employee,
+        }
+    }
+
+    /// Posting whose settlement this side's payments count towards
+    pub fn posting_kind(self) -> PostingKind {
+        match self {
+            PayerSide::Employer => PostingKind::JobPosting,
+            PayerSide::Employee => PostingKind::JobSeeking,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum PaymentMethod {
+    #[sqlx(rename = "cash")]
+    Cash,
+    #[default]
+    #[sqlx(rename = "bank_transfer")]
+    BankTransfer,
+    #[sqlx(rename = "card")]
+    Card,
+    #[sqlx(rename = "other")]
+    Other,
+}
+
+/// One fee payment received for a matching
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Payment {
+    pub id: i64,
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub amount: Decimal,
+    pub paid_on: NaiveDate,
+    pub payment_method: PaymentMethod,
+    pub memo: Option<String>,
+    pub created_by: Option<i64>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreatePaymentRequest {
+    pub payer_side: PayerSide,
+    pub amount: Decimal,
+    /// 생략하면 오늘
+    pub paid_on: Option<NaiveDate>,
+    #[serde(default)]
+    pub payment_method: PaymentMethod,
+    pub memo: Option<String>,
+}
+
+/// What one side of a matching owes and has paid.
+/// `outstanding_amount` = `fee_amount` - `refunded_amount` - `paid_amount`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SideBalance {
+    pub payer_side: PayerSide,
+    pub fee_amount: Decimal,
+    pub refunded_amount: Decimal,
+    pub paid_amount: Decimal,
+    pub outstanding_amount: Decimal,
+    pub settlement_status: SettlementStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchingBalance {
+    pub matching_id: i64,
+    pub employer: SideBalance,
+    pub employee: SideBalance,
+}
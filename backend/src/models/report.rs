@@ -0,0 +1,79 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::models::payment::PayerSide;
+
+/// Age of an unpaid fee, counted in days since its due date
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum AgingBucket {
+    /// 0–30일
+    #[serde(rename = "0-30")]
+    Current,
+    /// 31–60일
+    #[serde(rename = "31-60")]
+    Days31To60,
+    /// 61–90일
+    #[serde(rename = "61-90")]
+    Days61To90,
+    /// 90일 초과
+    #[serde(rename = "90+")]
+    Over90,
+}
+
+/// Outstanding amounts split by aging bucket
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct AgingTotals {
+    pub current: Decimal,
+    pub days_31_60: Decimal,
+    pub days_61_90: Decimal,
+    pub over_90: Decimal,
+    pub total: Decimal,
+}
+
+/// One unpaid fee of one side of a completed matching
+#[derive(Debug, Clone, Serialize)]
+pub struct Receivable {
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub completed_at: NaiveDateTime,
+    pub due_date: NaiveDate,
+    pub days_outstanding: i64,
+    pub bucket: AgingBucket,
+    pub fee_amount: Decimal,
+    pub refunded_amount: Decimal,
+    pub paid_amount: Decimal,
+    pub outstanding_amount: Decimal,
+}
+
+/// Unpaid fees of one customer
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomerReceivables {
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub phone: String,
+    pub oldest_due_date: NaiveDate,
+    pub totals: AgingTotals,
+    pub receivables: Vec<Receivable>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReceivablesReport {
+    pub as_of: NaiveDate,
+    pub totals: AgingTotals,
+    pub customers: Vec<CustomerReceivables>,
+}
+
+/// Fee of one side of a completed matching with what has been paid on it
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReceivableRow {
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub phone: String,
+    pub completed_at: NaiveDateTime,
+    pub fee_amount: Decimal,
+    pub refunded_amount: Decimal,
+    pub paid_amount: Decimal,
+}
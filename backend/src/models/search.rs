@@ -0,0 +1,79 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::models::customer::Customer;
+use crate::models::job_posting::PostingStatus;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[serde(rename_all = "PascalCase")]
+pub enum MemoKind {
+    #[sqlx(rename = "customer")]
+    Customer,
+    #[sqlx(rename = "matching")]
+    Matching,
+    #[sqlx(rename = "user")]
+    User,
+}
+
+/// A job posting whose description matched. `snippet` is HTML-escaped text
+/// with the matches wrapped in `<mark>`.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct JobPostingSearchHit {
+    pub id: i64,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub posting_status: PostingStatus,
+    pub snippet: String,
+    pub rank: f32,
+    pub created_at: NaiveDateTime,
+}
+
+/// A job seeking posting whose description or preferred location matched
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct JobSeekingSearchHit {
+    pub id: i64,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub preferred_location: String,
+    pub posting_status: PostingStatus,
+    pub snippet: String,
+    pub rank: f32,
+    pub created_at: NaiveDateTime,
+}
+
+/// A customer, matching or user memo that matched.
+/// `customer_id` / `matching_id` point at the record the memo belongs to.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct MemoSearchHit {
+    pub memo_kind: MemoKind,
+    pub id: i64,
+    pub customer_id: Option<i64>,
+    pub customer_name: Option<String>,
+    pub matching_id: Option<i64>,
+    pub snippet: String,
+    pub rank: f32,
+    pub created_at: NaiveDateTime,
+}
+
+/// A tag whose name matched, with how often it is used
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct TagSearchHit {
+    pub id: i64,
+    pub tag_name: String,
+    pub tag_color: String,
+    pub customer_count: i64,
+    pub job_posting_count: i64,
+    pub job_seeking_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GlobalSearchResults {
+    pub query: String,
+    pub customers: Vec<Customer>,
+    pub job_postings: Vec<JobPostingSearchHit>,
+    pub job_seekings: Vec<JobSeekingSearchHit>,
+    pub memos: Vec<MemoSearchHit>,
+    pub tags: Vec<TagSearchHit>,
+    pub total: usize,
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::job_posting::SettlementStatus;
+
+/// Who or what triggered a settlement change
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum SettlementChangeSource {
+    /// 사용자가 직접 변경
+    #[sqlx(rename = "manual")]
+    Manual,
+    /// 입금 등록/삭제에 따라 다시 계산
+    #[sqlx(rename = "payment")]
+    Payment,
+    /// 매칭 생성/변경/취소/환불에 따라 다시 계산
+    #[sqlx(rename = "matching")]
+    Matching,
+}
+
+/// Settlement fields of a job posting or job seeking posting
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostingSettlement {
+    pub settlement_status: SettlementStatus,
+    pub settlement_amount: Option<Decimal>,
+    pub settlement_memo: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateSettlementRequest {
+    pub settlement_status: Option<SettlementStatus>,
+    pub settlement_amount: Option<Decimal>,
+    pub settlement_memo: Option<String>,
+}
+
+/// One row of a posting's settlement history
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SettlementChange {
+    pub id: i64,
+    pub posting_id: i64,
+    pub from_status: SettlementStatus,
+    pub to_status: SettlementStatus,
+    pub from_amount: Option<Decimal>,
+    pub to_amount: Option<Decimal>,
+    pub memo: Option<String>,
+    pub change_source: SettlementChangeSource,
+    /// 입금/매칭에 따른 자동 변경이면 None
+    pub changed_by: Option<i64>,
+    pub changed_at: NaiveDateTime,
+}
@@ -0,0 +1,98 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::matching::MatchingStatus;
+use crate::models::payment::{PayerSide, PaymentMethod};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementFormat {
+    #[default]
+    Json,
+    Pdf,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "PascalCase")]
+pub enum StatementEntryKind {
+    /// 수수료 청구
+    Charge,
+    /// 보증 환불
+    Refund,
+    /// 입금
+    Payment,
+}
+
+/// One line of the ledger. `charge` raises the balance the customer owes,
+/// `credit` (refunds and payments) lowers it.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StatementEntry {
+    pub date: NaiveDate,
+    pub kind: StatementEntryKind,
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub description: String,
+    pub charge: Decimal,
+    pub credit: Decimal,
+    pub balance: Decimal,
+}
+
+/// A matching the customer was one side of, with that side's fee
+#[derive(Debug, Clone, Serialize)]
+pub struct StatementMatching {
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub counterparty_name: String,
+    pub matched_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+    pub matching_status: MatchingStatus,
+    /// 취소된 매칭은 0
+    pub charged_amount: Decimal,
+    pub refunded_amount: Decimal,
+    pub paid_amount: Decimal,
+    pub outstanding_amount: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomerStatement {
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub phone: String,
+    /// 생략하면 첫 거래부터
+    pub from: Option<NaiveDate>,
+    pub to: NaiveDate,
+    /// 시작일 전까지의 잔액
+    pub opening_balance: Decimal,
+    pub total_charged: Decimal,
+    pub total_credited: Decimal,
+    pub closing_balance: Decimal,
+    pub matchings: Vec<StatementMatching>,
+    pub entries: Vec<StatementEntry>,
+}
+
+/// One side of a matching the customer was on
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StatementMatchingRow {
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub counterparty_name: String,
+    pub matched_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+    pub matching_status: MatchingStatus,
+    pub fee_amount: Decimal,
+    pub refunded_amount: Decimal,
+    pub refunded_on: Option<NaiveDate>,
+}
+
+/// A payment the customer made on one of their matchings
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StatementPaymentRow {
+    pub id: i64,
+    pub matching_id: i64,
+    pub payer_side: PayerSide,
+    pub amount: Decimal,
+    pub paid_on: NaiveDate,
+    pub payment_method: PaymentMethod,
+    pub memo: Option<String>,
+}
@@ -0,0 +1,50 @@
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// The parts of a posting that suggestions are scored on.
+///
+/// `location` is the job seeker's preferred location, or the employer
+/// customer's address for job postings. `tags` holds the tag names of both the
+/// posting and its customer.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SuggestionProfile {
+    pub posting_id: i64,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub monthly_salary: Decimal,
+    pub region_codes: Vec<String>,
+    pub location: Option<String>,
+    pub tags: Vec<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum SuggestionFactorKind {
+    Salary,
+    Location,
+    Tags,
+    Recency,
+}
+
+/// How much one factor contributed to a suggestion's score
+#[derive(Debug, Clone, Serialize)]
+pub struct SuggestionFactor {
+    pub factor: SuggestionFactorKind,
+    pub score: f64,
+    pub max_score: f64,
+    pub explanation: String,
+}
+
+/// A candidate posting ranked against a job posting or job seeking posting.
+/// `score` is out of 100 and is the sum of the factor scores.
+#[derive(Debug, Clone, Serialize)]
+pub struct Suggestion {
+    pub posting_id: i64,
+    pub customer_id: i64,
+    pub customer_name: String,
+    pub monthly_salary: Decimal,
+    pub score: f64,
+    pub factors: Vec<SuggestionFactor>,
+}
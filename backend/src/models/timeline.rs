@@ -0,0 +1,57 @@
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::models::job_posting::PostingStatus;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[serde(rename_all = "PascalCase")]
+pub enum TimelineEventType {
+    #[sqlx(rename = "memo_added")]
+    MemoAdded,
+    #[sqlx(rename = "file_uploaded")]
+    FileUploaded,
+    #[sqlx(rename = "job_posting_created")]
+    JobPostingCreated,
+    #[sqlx(rename = "job_posting_status_changed")]
+    JobPostingStatusChanged,
+    #[sqlx(rename = "job_seeking_created")]
+    JobSeekingCreated,
+    #[sqlx(rename = "job_seeking_status_changed")]
+    JobSeekingStatusChanged,
+    #[sqlx(rename = "matching_created")]
+    MatchingCreated,
+    #[sqlx(rename = "matching_completed")]
+    MatchingCompleted,
+    #[sqlx(rename = "matching_cancelled")]
+    MatchingCancelled,
+    #[sqlx(rename = "settlement_recorded")]
+    SettlementRecorded,
+}
+
+/// One entry of a customer's activity timeline.
+///
+/// Only the id fields relevant to the event type are set. `summary` carries a
+/// short human-readable detail (memo excerpt, file name, cancellation reason).
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct TimelineEvent {
+    pub event_type: TimelineEventType,
+    pub occurred_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_posting_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_seeking_posting_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matching_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub posting_status: Option<PostingStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
@@ -0,0 +1,64 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Kind of soft-deleted record. The kebab-case name is also the `{type}`
+/// segment of `/api/trash/{type}/{id}/restore`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "text")]
+#[serde(rename_all = "kebab-case")]
+pub enum TrashItemType {
+    #[sqlx(rename = "customer")]
+    Customer,
+    #[sqlx(rename = "job_posting")]
+    JobPosting,
+    #[sqlx(rename = "job_seeking")]
+    JobSeeking,
+    #[sqlx(rename = "matching")]
+    Matching,
+    #[sqlx(rename = "customer_memo")]
+    CustomerMemo,
+    #[sqlx(rename = "matching_memo")]
+    MatchingMemo,
+    #[sqlx(rename = "user_memo")]
+    UserMemo,
+    #[sqlx(rename = "customer_file")]
+    CustomerFile,
+    #[sqlx(rename = "user_file")]
+    UserFile,
+    #[sqlx(rename = "tag")]
+    Tag,
+}
+
+/// A soft-deleted record shown in the trash.
+///
+/// Records deleted together with their parent (e.g. a customer's postings) are
+/// not listed separately; restoring the parent brings them back.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct TrashItem {
+    pub item_type: TrashItemType,
+    pub id: i64,
+    /// Name, description excerpt, memo excerpt or file name
+    pub title: String,
+    pub customer_id: Option<i64>,
+    pub customer_name: Option<String>,
+    pub deleted_at: NaiveDateTime,
+    /// When the background purge will permanently delete the record
+    #[sqlx(skip)]
+    pub purge_at: Option<NaiveDateTime>,
+}
+
+/// A stored object (customer or user file) whose row is due for purging
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ExpiredFile {
+    pub item_type: TrashItemType,
+    pub id: i64,
+    pub file_path: String,
+    pub thumbnail_path: Option<String>,
+}
+
+/// Number of rows permanently deleted by one purge run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PurgeSummary {
+    pub files: u64,
+    pub rows: u64,
+}
@@ -13,6 +13,14 @@ pub struct User {
     pub phone: Option<String>,
     pub default_employer_fee_rate: rust_decimal::Decimal,
     pub default_employee_fee_rate: rust_decimal::Decimal,
+    /// 세금계산서 공급자 정보
+    pub business_number: Option<String>,
+    pub business_name: Option<String>,
+    pub representative_name: Option<String>,
+    pub business_address: Option<String>,
+    pub business_type: Option<String>,
+    pub business_item: Option<String>,
+    pub tax_invoice_email: Option<String>,
     pub last_login_at: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
@@ -0,0 +1,290 @@
+use chrono::NaiveDate;
+use sqlx::{PgConnection, PgPool};
+
+use crate::models::analytics::{AnalyticsMetric, MetricRow, MetricTotal};
+use crate::services::analytics::contiguous_ranges;
+
+/// Rolled-up daily values of the user's metrics from `from` to `to`
+/// inclusive. Call [`refresh_dirty_days`] first for up-to-date values.
+pub async fn list_rollup_metrics(
+    pool: &PgPool,
+    user_id: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<MetricRow>, sqlx::Error> {
+    sqlx::query_as!(
+        MetricRow,
+        r#"
+        SELECT day AS bucket, metric AS "metric: AnalyticsMetric", value
+        FROM daily_metrics
+        WHERE user_id = $1 AND day >= $2 AND day <= $3
+        ORDER BY day, metric
+        "#,
+        user_id,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// All-time sums of the user's rolled-up metrics
+pub async fn list_rollup_totals(
+    pool: &PgPool,
+    user_id: i64,
+) -> Result<Vec<MetricTotal>, sqlx::Error> {
+    sqlx::query_as!(
+        MetricTotal,
+        r#"
+        SELECT metric AS "metric: AnalyticsMetric", SUM(value) AS "value!"
+        FROM daily_metrics
+        WHERE user_id = $1
+        GROUP BY metric
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Daily values computed from the live tables, bypassing the rollups
+pub async fn list_live_metrics(
+    pool: &PgPool,
+    user_id: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<MetricRow>, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    compute_daily_metrics(&mut conn, user_id, from, to).await
+}
+
+/// Queue days of the user to be rolled up again
+pub async fn mark_days_dirty(
+    pool: &PgPool,
+    user_id: i64,
+    days: &[NaiveDate],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO daily_metrics_dirty (user_id, day)
+        SELECT $1, day FROM UNNEST($2::date[]) AS day
+        ON CONFLICT DO NOTHING
+        "#,
+        user_id,
+        days
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Recompute the rollups of every day of the user marked dirty by the
+/// change-tracking triggers. Returns the number of days refreshed.
+pub async fn refresh_dirty_days(pool: &PgPool, user_id: i64) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let mut days = sqlx::query_scalar!(
+        "DELETE FROM daily_metrics_dirty WHERE user_id = $1 RETURNING day",
+        user_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    if days.is_empty() {
+        return Ok(0);
+    }
+    days.sort();
+
+    sqlx::query!(
+        "DELETE FROM daily_metrics WHERE user_id = $1 AND day = ANY($2)",
+        user_id,
+        &days
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    for (from, to) in contiguous_ranges(&days) {
+        let rows = compute_daily_metrics(&mut tx, user_id, from, to).await?;
+        if rows.is_empty() {
+            continue;
+        }
+
+        let buckets: Vec<NaiveDate> = rows.iter().map(|row| row.bucket).collect();
+        let metrics: Vec<String> = rows
+            .iter()
+            .map(|row| row.metric.as_str().to_string())
+            .collect();
+        let values: Vec<_> = rows.iter().map(|row| row.value).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO daily_metrics (user_id, day, metric, value)
+            SELECT $1, * FROM UNNEST($2::date[], $3::text[], $4::numeric[])
+            ON CONFLICT (user_id, day, metric)
+            DO UPDATE SET value = EXCLUDED.value, refreshed_at = CURRENT_TIMESTAMP
+            "#,
+            user_id,
+            &buckets,
+            &metrics,
+            &values
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(days.len())
+}
+
+/// Refresh the dirty days of every user. Returns the number of days
+/// refreshed.
+pub async fn refresh_all_dirty_days(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let user_ids = sqlx::query_scalar!("SELECT DISTINCT user_id FROM daily_metrics_dirty")
+        .fetch_all(pool)
+        .await?;
+
+    let mut refreshed = 0;
+    for user_id in user_ids {
+        refreshed += refresh_dirty_days(pool, user_id).await?;
+    }
+
+    Ok(refreshed)
+}
+
+/// Daily values of every analytics metric of the user from `from` to `to`
+/// inclusive, computed from the live tables. Days without activity have no
+/// rows.
+pub async fn compute_daily_metrics(
+    conn: &mut PgConnection,
+    user_id: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<MetricRow>, sqlx::Error> {
+    sqlx::query_as!(
+        MetricRow,
+        r#"
+        WITH owned_matchings AS (
+            SELECT m.*, jp.created_at AS job_posting_created_at
+            FROM matchings m
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN customers c ON jp.customer_id = c.id
+            WHERE c.user_id = $1 AND m.deleted_at IS NULL
+        ),
+        owned_postings AS (
+            SELECT jp.id, 'job_posting' AS kind, jp.created_at
+            FROM job_postings jp
+            INNER JOIN customers c ON jp.customer_id = c.id
+            WHERE c.user_id = $1 AND jp.deleted_at IS NULL
+            UNION ALL
+            SELECT jsp.id, 'job_seeking', jsp.created_at
+            FROM job_seeking_postings jsp
+            INNER JOIN customers c ON jsp.customer_id = c.id
+            WHERE c.user_id = $1 AND jsp.deleted_at IS NULL
+        ),
+        metrics AS (
+            SELECT completed_at::date AS bucket, 'revenue_earned' AS metric,
+                SUM(COALESCE(employer_fee_amount, 0) + COALESCE(employee_fee_amount, 0)
+                    - COALESCE(guarantee_refund_amount, 0)) AS value
+            FROM owned_matchings
+            WHERE matching_status = 'completed' AND completed_at >= $2::date AND completed_at < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT p.paid_on, 'revenue_collected', SUM(p.amount)
+            FROM payments p
+            INNER JOIN owned_matchings m ON p.matching_id = m.id
+            WHERE p.paid_on >= $2::date AND p.paid_on <= $3::date
+            GROUP BY 1
+            UNION ALL
+            SELECT created_at::date, 'new_customers', COUNT(*)
+            FROM customers
+            WHERE user_id = $1 AND deleted_at IS NULL
+                AND created_at >= $2::date AND created_at < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT created_at::date, 'postings_opened', COUNT(*)
+            FROM owned_postings
+            WHERE created_at >= $2::date AND created_at < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT h.changed_at::date, 'postings_closed', COUNT(*)
+            FROM (
+                SELECT h.changed_at FROM job_posting_status_history h
+                INNER JOIN owned_postings p ON p.kind = 'job_posting' AND h.job_posting_id = p.id
+                WHERE h.to_status = 'closed'
+                UNION ALL
+                SELECT h.changed_at FROM job_seeking_status_history h
+                INNER JOIN owned_postings p ON p.kind = 'job_seeking' AND h.job_seeking_posting_id = p.id
+                WHERE h.to_status = 'closed'
+            ) h
+            WHERE h.changed_at >= $2::date AND h.changed_at < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT p.created_at::date, 'job_postings_opened', COUNT(*)
+            FROM owned_postings p
+            WHERE p.kind = 'job_posting' AND p.created_at >= $2::date AND p.created_at < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT p.created_at::date, 'job_postings_filled', COUNT(*)
+            FROM owned_postings p
+            WHERE p.kind = 'job_posting' AND p.created_at >= $2::date AND p.created_at < $3::date + 1
+                AND EXISTS (
+                    SELECT 1 FROM owned_matchings m
+                    WHERE m.job_posting_id = p.id AND m.matching_status = 'completed'
+                )
+            GROUP BY 1
+            UNION ALL
+            SELECT p.created_at::date, 'job_seekings_opened', COUNT(*)
+            FROM owned_postings p
+            WHERE p.kind = 'job_seeking' AND p.created_at >= $2::date AND p.created_at < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT COALESCE(matched_at, created_at)::date, 'matchings_created', COUNT(*)
+            FROM owned_matchings
+            WHERE COALESCE(matched_at, created_at) >= $2::date AND COALESCE(matched_at, created_at) < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT COALESCE(matched_at, created_at)::date, 'match_days',
+                SUM(EXTRACT(EPOCH FROM COALESCE(matched_at, created_at) - job_posting_created_at) / 86400)
+            FROM owned_matchings
+            WHERE COALESCE(matched_at, created_at) >= $2::date AND COALESCE(matched_at, created_at) < $3::date + 1
+                AND job_posting_created_at IS NOT NULL
+            GROUP BY 1
+            UNION ALL
+            SELECT completed_at::date, 'matchings_completed', COUNT(*)
+            FROM owned_matchings
+            WHERE matching_status = 'completed' AND completed_at >= $2::date AND completed_at < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT cancelled_at::date, 'matchings_cancelled', COUNT(*)
+            FROM owned_matchings
+            WHERE matching_status = 'cancelled' AND cancelled_at >= $2::date AND cancelled_at < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT COALESCE(matched_at, created_at)::date, 'fees_charged',
+                SUM(GREATEST(COALESCE(employer_fee_amount, 0) - COALESCE(guarantee_refund_amount, 0), 0)
+                    + COALESCE(employee_fee_amount, 0))
+            FROM owned_matchings
+            WHERE matching_status <> 'cancelled'
+                AND COALESCE(matched_at, created_at) >= $2::date AND COALESCE(matched_at, created_at) < $3::date + 1
+            GROUP BY 1
+            UNION ALL
+            SELECT p.paid_on, 'fees_paid', SUM(p.amount)
+            FROM payments p
+            INNER JOIN owned_matchings m ON p.matching_id = m.id
+            WHERE m.matching_status <> 'cancelled' AND p.paid_on >= $2::date AND p.paid_on <= $3::date
+            GROUP BY 1
+        )
+        SELECT
+            bucket AS "bucket!",
+            metric AS "metric!: AnalyticsMetric",
+            value::NUMERIC AS "value!"
+        FROM metrics
+        ORDER BY bucket, metric
+        "#,
+        user_id,
+        from,
+        to
+    )
+    .fetch_all(conn)
+    .await
+}
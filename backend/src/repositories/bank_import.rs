@@ -0,0 +1,263 @@
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::models::bank_import::{
+    BankImport, BankTransaction, ConfirmMatch, OpenFeeRow, ParsedDeposit,
+};
+use crate::models::payment::{CreatePaymentRequest, PayerSide, PaymentMethod};
+use crate::repositories::payment::{self, LedgerError};
+use crate::services::bank_import as statement;
+
+#[derive(Debug, Error)]
+pub enum BankImportError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("입금 내역 #{0}을 찾을 수 없습니다")]
+    TransactionNotFound(i64),
+    #[error("입금 내역 #{0}은 이미 입금으로 기록되었습니다")]
+    AlreadyConfirmed(i64),
+    #[error("입금 내역 #{transaction_id}: {source}")]
+    Ledger {
+        transaction_id: i64,
+        source: LedgerError,
+    },
+}
+
+/// Store the deposits of an uploaded statement. Deposits already imported
+/// from an earlier file are skipped; identical deposits within one file are
+/// all kept.
+pub async fn create_import(
+    pool: &PgPool,
+    user_id: i64,
+    file_name: Option<String>,
+    deposits: &[ParsedDeposit],
+) -> Result<BankImport, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let import_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO bank_imports (user_id, file_name, total_rows, imported_rows)
+        VALUES ($1, $2, $3, 0)
+        RETURNING id
+        "#,
+        user_id,
+        file_name,
+        deposits.len() as i32
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let mut imported_rows = 0;
+    for (deposit, occurrence) in deposits.iter().zip(statement::occurrences(deposits)) {
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO bank_transactions (
+                user_id, bank_import_id, transacted_at, depositor, amount, memo, balance_after,
+                occurrence
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (
+                user_id, transacted_at, amount, depositor, (COALESCE(balance_after, -1)),
+                occurrence
+            )
+            DO NOTHING
+            "#,
+            user_id,
+            import_id,
+            deposit.transacted_at,
+            deposit.depositor,
+            deposit.amount,
+            deposit.memo,
+            deposit.balance_after,
+            occurrence
+        )
+        .execute(&mut *tx)
+        .await?;
+        imported_rows += inserted.rows_affected() as i32;
+    }
+
+    let import = sqlx::query_as!(
+        BankImport,
+        r#"
+        UPDATE bank_imports SET imported_rows = $2
+        WHERE id = $1
+        RETURNING id, user_id, file_name, total_rows, imported_rows, created_at
+        "#,
+        import_id,
+        imported_rows
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(import)
+}
+
+pub async fn get_import(
+    pool: &PgPool,
+    import_id: i64,
+    user_id: i64,
+) -> Result<BankImport, sqlx::Error> {
+    sqlx::query_as!(
+        BankImport,
+        r#"
+        SELECT id, user_id, file_name, total_rows, imported_rows, created_at
+        FROM bank_imports
+        WHERE id = $1 AND user_id = $2
+        "#,
+        import_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Deposits stored by an import, oldest first
+pub async fn list_import_transactions(
+    pool: &PgPool,
+    import_id: i64,
+    user_id: i64,
+) -> Result<Vec<BankTransaction>, sqlx::Error> {
+    sqlx::query_as!(
+        BankTransaction,
+        r#"
+        SELECT
+            id, user_id, bank_import_id, transacted_at, depositor, amount, memo,
+            balance_after, payment_id, created_at
+        FROM bank_transactions
+        WHERE bank_import_id = $1 AND user_id = $2
+        ORDER BY transacted_at, id
+        "#,
+        import_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Employer and employee fees of the user's matchings that are not
+/// cancelled, with the customer who owes each and what has been paid
+pub async fn list_open_fees(pool: &PgPool, user_id: i64) -> Result<Vec<OpenFeeRow>, sqlx::Error> {
+    sqlx::query_as!(
+        OpenFeeRow,
+        r#"
+        WITH open_matchings AS (
+            SELECT
+                m.id, m.employer_fee_amount, m.employee_fee_amount, m.guarantee_refund_amount,
+                COALESCE(m.completed_at, m.matched_at)::date AS since,
+                jp.customer_id AS employer_id, jsp.customer_id AS employee_id
+            FROM matchings m
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+            INNER JOIN customers owner ON jp.customer_id = owner.id
+            WHERE owner.user_id = $1
+                AND m.matching_status <> 'cancelled'
+                AND m.deleted_at IS NULL
+        ),
+        fees AS (
+            SELECT
+                id AS matching_id, 'employer' AS payer_side, employer_id AS customer_id, since,
+                COALESCE(employer_fee_amount, 0) AS fee_amount,
+                COALESCE(guarantee_refund_amount, 0) AS refunded_amount
+            FROM open_matchings
+            UNION ALL
+            SELECT id, 'employee', employee_id, since, COALESCE(employee_fee_amount, 0), 0
+            FROM open_matchings
+        )
+        SELECT
+            f.matching_id AS "matching_id!",
+            f.payer_side AS "payer_side!: PayerSide",
+            f.customer_id AS "customer_id!",
+            c.name AS "customer_name!",
+            f.since AS "since!",
+            f.fee_amount AS "fee_amount!",
+            f.refunded_amount AS "refunded_amount!",
+            COALESCE(
+                (SELECT SUM(p.amount) FROM payments p
+                 WHERE p.matching_id = f.matching_id AND p.payer_side = f.payer_side),
+                0
+            ) AS "paid_amount!"
+        FROM fees f
+        INNER JOIN customers c ON f.customer_id = c.id
+        WHERE c.deleted_at IS NULL AND f.fee_amount > 0
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Record each confirmed deposit as a bank transfer payment of the chosen
+/// fee, all or none. Settlement statuses are updated as the payments are
+/// recorded.
+pub async fn confirm_matches(
+    pool: &PgPool,
+    import_id: i64,
+    user_id: i64,
+    matches: &[ConfirmMatch],
+) -> Result<Vec<BankTransaction>, BankImportError> {
+    let mut tx = pool.begin().await?;
+    let mut confirmed = Vec::with_capacity(matches.len());
+
+    for confirm in matches {
+        let transaction = sqlx::query_as!(
+            BankTransaction,
+            r#"
+            SELECT
+                id, user_id, bank_import_id, transacted_at, depositor, amount, memo,
+                balance_after, payment_id, created_at
+            FROM bank_transactions
+            WHERE id = $1 AND bank_import_id = $2 AND user_id = $3
+            FOR UPDATE
+            "#,
+            confirm.transaction_id,
+            import_id,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(BankImportError::TransactionNotFound(confirm.transaction_id))?;
+        if transaction.payment_id.is_some() {
+            return Err(BankImportError::AlreadyConfirmed(transaction.id));
+        }
+
+        let (recorded, _) = payment::record_payment(
+            &mut tx,
+            confirm.matching_id,
+            CreatePaymentRequest {
+                payer_side: confirm.payer_side,
+                amount: transaction.amount,
+                paid_on: Some(transaction.transacted_at.date()),
+                payment_method: PaymentMethod::BankTransfer,
+                memo: Some(format!("은행 입금: {}", transaction.depositor)),
+            },
+            user_id,
+        )
+        .await
+        .map_err(|source| BankImportError::Ledger {
+            transaction_id: transaction.id,
+            source,
+        })?;
+
+        let transaction = sqlx::query_as!(
+            BankTransaction,
+            r#"
+            UPDATE bank_transactions SET payment_id = $2
+            WHERE id = $1
+            RETURNING
+                id, user_id, bank_import_id, transacted_at, depositor, amount, memo,
+                balance_after, payment_id, created_at
+            "#,
+            transaction.id,
+            recorded.id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        confirmed.push(transaction);
+    }
+
+    tx.commit().await?;
+
+    Ok(confirmed)
+}
@@ -1,21 +1,37 @@
 use crate::models::customer::{
-    CreateCustomerRequest, Customer, CustomerType, UpdateCustomerRequest,
+    CreateCustomerRequest, Customer, CustomerMergeSummary, CustomerType, MergeSelection, MergeSide,
+    UpdateCustomerRequest,
 };
+use crate::repositories::{file, job_posting, job_seeking, memo, tag};
+use crate::services::hangul::{choseong, is_choseong_query};
+use crate::services::phone::phone_search_digits;
 use sqlx::PgPool;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CustomerMergeError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("A {0:?} customer cannot own the merged postings")]
+    IncompatibleType(CustomerType),
+}
 
 /// Create a new customer
 pub async fn create_customer(
     pool: &PgPool,
     user_id: i64,
     req: CreateCustomerRequest,
+    phone_normalized: &str,
 ) -> Result<Customer, sqlx::Error> {
     let customer = sqlx::query_as!(
         Customer,
         r#"
-        INSERT INTO customers (user_id, name, birth_date, phone, address, customer_type)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO customers (
+            user_id, name, birth_date, phone, phone_normalized, address, customer_type, business_number
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         RETURNING
-            id, user_id, name, birth_date, phone, address, profile_photo_id,
+            id, user_id, name, birth_date, phone, address, business_number, profile_photo_id,
             customer_type as "customer_type: CustomerType",
             created_at as "created_at!",
             updated_at as "updated_at!",
@@ -25,8 +41,10 @@ pub async fn create_customer(
         req.name,
         req.birth_date,
         req.phone,
+        phone_normalized,
         req.address,
-        req.customer_type as CustomerType
+        req.customer_type as CustomerType,
+        req.business_number
     )
     .fetch_one(pool)
     .await?;
@@ -44,7 +62,7 @@ pub async fn get_customer_by_id(
         Customer,
         r#"
         SELECT
-            id, user_id, name, birth_date, phone, address, profile_photo_id,
+            id, user_id, name, birth_date, phone, address, business_number, profile_photo_id,
             customer_type as "customer_type: CustomerType",
             created_at as "created_at!",
             updated_at as "updated_at!",
@@ -82,7 +100,7 @@ pub async fn list_customers_by_user(
                         Customer,
                         r#"
                         SELECT DISTINCT
-                            c.id, c.user_id, c.name, c.birth_date, c.phone, c.address, c.profile_photo_id,
+                            c.id, c.user_id, c.name, c.birth_date, c.phone, c.address, c.business_number, c.profile_photo_id,
                             c.customer_type as "customer_type: CustomerType",
                             c.created_at as "created_at!",
                             c.updated_at as "updated_at!",
@@ -108,7 +126,7 @@ pub async fn list_customers_by_user(
                         Customer,
                         r#"
                         SELECT DISTINCT
-                            c.id, c.user_id, c.name, c.birth_date, c.phone, c.address, c.profile_photo_id,
+                            c.id, c.user_id, c.name, c.birth_date, c.phone, c.address, c.business_number, c.profile_photo_id,
                             c.customer_type as "customer_type: CustomerType",
                             c.created_at as "created_at!",
                             c.updated_at as "updated_at!",
@@ -140,7 +158,7 @@ pub async fn list_customers_by_user(
                 Customer,
                 r#"
                 SELECT
-                    id, user_id, name, birth_date, phone, address, profile_photo_id,
+                    id, user_id, name, birth_date, phone, address, business_number, profile_photo_id,
                     customer_type as "customer_type: CustomerType",
                     created_at as "created_at!",
                     updated_at as "updated_at!",
@@ -163,7 +181,7 @@ pub async fn list_customers_by_user(
                 Customer,
                 r#"
                 SELECT
-                    id, user_id, name, birth_date, phone, address, profile_photo_id,
+                    id, user_id, name, birth_date, phone, address, business_number, profile_photo_id,
                     customer_type as "customer_type: CustomerType",
                     created_at as "created_at!",
                     updated_at as "updated_at!",
@@ -185,15 +203,59 @@ pub async fn list_customers_by_user(
     Ok(customers)
 }
 
+/// Find active customers of a user with the given normalized phone number
+pub async fn find_customers_by_phone(
+    pool: &PgPool,
+    user_id: i64,
+    phone_normalized: &str,
+    exclude_customer_id: Option<i64>,
+) -> Result<Vec<Customer>, sqlx::Error> {
+    let customers = sqlx::query_as!(
+        Customer,
+        r#"
+        SELECT
+            id, user_id, name, birth_date, phone, address, business_number, profile_photo_id,
+            customer_type as "customer_type: CustomerType",
+            created_at as "created_at!",
+            updated_at as "updated_at!",
+            deleted_at
+        FROM customers
+        WHERE user_id = $1
+            AND phone_normalized = $2
+            AND ($3::bigint IS NULL OR id <> $3)
+            AND deleted_at IS NULL
+        ORDER BY created_at
+        "#,
+        user_id,
+        phone_normalized,
+        exclude_customer_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(customers)
+}
+
 /// Update customer
+///
+/// `phone_normalized` must be given whenever `req.phone` is set.
 pub async fn update_customer(
     pool: &PgPool,
     customer_id: i64,
     user_id: i64,
     req: UpdateCustomerRequest,
+    phone_normalized: Option<&str>,
 ) -> Result<Customer, sqlx::Error> {
     // First, get the current customer to use existing values for None fields
     let current = get_customer_by_id(pool, customer_id, user_id).await?;
+    let customer_type = req.customer_type.unwrap_or(current.customer_type);
+    // An empty number clears the stored one, and employees are never invoiced
+    let business_number = match req.business_number {
+        _ if customer_type == CustomerType::Employee => None,
+        Some(number) if number.is_empty() => None,
+        Some(number) => Some(number),
+        None => current.business_number,
+    };
 
     let customer = sqlx::query_as!(
         Customer,
@@ -203,12 +265,14 @@ pub async fn update_customer(
             name = $1,
             birth_date = $2,
             phone = $3,
-            address = $4,
-            profile_photo_id = $5,
-            customer_type = $6
-        WHERE id = $7 AND user_id = $8 AND deleted_at IS NULL
+            phone_normalized = COALESCE($4, phone_normalized),
+            address = $5,
+            profile_photo_id = $6,
+            customer_type = $7,
+            business_number = $8
+        WHERE id = $9 AND user_id = $10 AND deleted_at IS NULL
         RETURNING
-            id, user_id, name, birth_date, phone, address, profile_photo_id,
+            id, user_id, name, birth_date, phone, address, business_number, profile_photo_id,
             customer_type as "customer_type: CustomerType",
             created_at as "created_at!",
             updated_at as "updated_at!",
@@ -217,9 +281,11 @@ pub async fn update_customer(
         req.name.unwrap_or(current.name),
         req.birth_date.or(current.birth_date),
         req.phone.unwrap_or(current.phone),
+        phone_normalized,
         req.address.or(current.address),
         req.profile_photo_id.or(current.profile_photo_id),
-        req.customer_type.unwrap_or(current.customer_type) as CustomerType,
+        customer_type as CustomerType,
+        business_number,
         customer_id,
         user_id
     )
@@ -229,28 +295,213 @@ pub async fn update_customer(
     Ok(customer)
 }
 
-/// Soft delete customer
+/// Soft delete customer together with its postings, matchings, memos and files.
+///
+/// Every row gets the same `deleted_at`, which is how a restore from the trash
+/// finds the dependents that were deleted along with the customer.
 pub async fn soft_delete_customer(
     pool: &PgPool,
     customer_id: i64,
     user_id: i64,
 ) -> Result<(), sqlx::Error> {
-    sqlx::query!(
+    let mut tx = pool.begin().await?;
+
+    let deleted_at = sqlx::query_scalar!(
         r#"
         UPDATE customers
         SET deleted_at = CURRENT_TIMESTAMP
         WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+        RETURNING deleted_at as "deleted_at!"
         "#,
         customer_id,
         user_id
     )
-    .execute(pool)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(deleted_at) = deleted_at else {
+        return Ok(());
+    };
+
+    sqlx::query!(
+        r#"
+        UPDATE matching_memos mm
+        SET deleted_at = $2
+        FROM matchings m
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+        WHERE mm.matching_id = m.id
+            AND (jp.customer_id = $1 OR jsp.customer_id = $1)
+            AND m.deleted_at IS NULL AND mm.deleted_at IS NULL
+        "#,
+        customer_id,
+        deleted_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE matchings m
+        SET deleted_at = $2
+        FROM job_postings jp, job_seeking_postings jsp
+        WHERE m.job_posting_id = jp.id AND m.job_seeking_posting_id = jsp.id
+            AND (jp.customer_id = $1 OR jsp.customer_id = $1)
+            AND m.deleted_at IS NULL
+        "#,
+        customer_id,
+        deleted_at
+    )
+    .execute(&mut *tx)
     .await?;
 
+    for query in [
+        sqlx::query!(
+            "UPDATE job_postings SET deleted_at = $2 WHERE customer_id = $1 AND deleted_at IS NULL",
+            customer_id,
+            deleted_at
+        ),
+        sqlx::query!(
+            "UPDATE job_seeking_postings SET deleted_at = $2 WHERE customer_id = $1 AND deleted_at IS NULL",
+            customer_id,
+            deleted_at
+        ),
+        sqlx::query!(
+            "UPDATE customer_memos SET deleted_at = $2 WHERE customer_id = $1 AND deleted_at IS NULL",
+            customer_id,
+            deleted_at
+        ),
+        sqlx::query!(
+            "UPDATE customer_files SET deleted_at = $2 WHERE customer_id = $1 AND deleted_at IS NULL",
+            customer_id,
+            deleted_at
+        ),
+    ] {
+        query.execute(&mut *tx).await?;
+    }
+
+    tx.commit().await?;
+
     Ok(())
 }
 
-/// Search customers by name or phone
+/// Merge `source_id` into `target_id` in a single transaction.
+///
+/// Job postings, job seekings, memos, files and tags move to the target, the
+/// target's fields are updated according to `selection`, and the source is
+/// soft-deleted with `merged_into_id` pointing at the target.
+///
+/// Fails without changing anything when the merged customer's type cannot own
+/// the postings it ends up with. An employee keeps no business number.
+pub async fn merge_customers(
+    pool: &PgPool,
+    user_id: i64,
+    target_id: i64,
+    source_id: i64,
+    selection: MergeSelection,
+) -> Result<(Customer, CustomerMergeSummary), CustomerMergeError> {
+    let mut tx = pool.begin().await?;
+
+    let summary = CustomerMergeSummary {
+        job_postings: job_posting::reassign_job_postings(&mut tx, source_id, target_id).await?,
+        job_seekings: job_seeking::reassign_job_seeking_postings(&mut tx, source_id, target_id)
+            .await?,
+        memos: memo::reassign_customer_memos(&mut tx, source_id, target_id).await?,
+        files: file::reassign_customer_files(
+            &mut tx,
+            source_id,
+            target_id,
+            selection.profile_photo == MergeSide::Source,
+        )
+        .await?,
+        tags: tag::move_customer_tags(&mut tx, source_id, target_id).await?,
+    };
+
+    let customer = sqlx::query_as!(
+        Customer,
+        r#"
+        UPDATE customers t
+        SET
+            name = CASE WHEN $4 THEN s.name ELSE t.name END,
+            birth_date = CASE WHEN $5 THEN s.birth_date ELSE t.birth_date END,
+            phone = CASE WHEN $6 THEN s.phone ELSE t.phone END,
+            phone_normalized = CASE WHEN $6 THEN s.phone_normalized ELSE t.phone_normalized END,
+            address = CASE WHEN $7 THEN s.address ELSE t.address END,
+            customer_type = CASE WHEN $8 THEN s.customer_type ELSE t.customer_type END,
+            profile_photo_id = CASE WHEN $9 THEN s.profile_photo_id ELSE t.profile_photo_id END,
+            business_number = CASE
+                WHEN (CASE WHEN $8 THEN s.customer_type ELSE t.customer_type END) = 'employee'
+                    THEN NULL
+                ELSE COALESCE(t.business_number, s.business_number)
+            END,
+            updated_at = CURRENT_TIMESTAMP
+        FROM customers s
+        WHERE t.id = $1 AND s.id = $2
+            AND t.user_id = $3 AND s.user_id = $3
+            AND t.deleted_at IS NULL AND s.deleted_at IS NULL
+        RETURNING
+            t.id, t.user_id, t.name, t.birth_date, t.phone, t.address, t.business_number, t.profile_photo_id,
+            t.customer_type as "customer_type: CustomerType",
+            t.created_at as "created_at!",
+            t.updated_at as "updated_at!",
+            t.deleted_at
+        "#,
+        target_id,
+        source_id,
+        user_id,
+        selection.name == MergeSide::Source,
+        selection.birth_date == MergeSide::Source,
+        selection.phone == MergeSide::Source,
+        selection.address == MergeSide::Source,
+        selection.customer_type == MergeSide::Source,
+        selection.profile_photo == MergeSide::Source
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let owned = sqlx::query!(
+        r#"
+        SELECT
+            EXISTS (SELECT 1 FROM job_postings WHERE customer_id = $1) AS "job_postings!",
+            EXISTS (SELECT 1 FROM job_seeking_postings WHERE customer_id = $1) AS "job_seekings!"
+        "#,
+        target_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let incompatible = match customer.customer_type {
+        CustomerType::Employer => owned.job_seekings,
+        CustomerType::Employee => owned.job_postings,
+        CustomerType::Both => false,
+    };
+    if incompatible {
+        return Err(CustomerMergeError::IncompatibleType(customer.customer_type));
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE customers
+        SET deleted_at = CURRENT_TIMESTAMP, merged_into_id = $2, profile_photo_id = NULL
+        WHERE id = $1 AND user_id = $3 AND deleted_at IS NULL
+        "#,
+        source_id,
+        target_id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((customer, summary))
+}
+
+/// Search customers by name, initial consonants or phone digits, ranked by relevance
+///
+/// - names match by substring or trigram similarity (typos)
+/// - a term with bare consonants ("ㄱㅁㅅ") matches `name_choseong`
+/// - digits match `phone_normalized` in any format; a suffix match (last four
+///   digits) ranks above a match in the middle of the number
 pub async fn search_customers(
     pool: &PgPool,
     user_id: i64,
@@ -260,26 +511,58 @@ pub async fn search_customers(
 ) -> Result<Vec<Customer>, sqlx::Error> {
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
-    let search_pattern = format!("%{}%", search_term);
+    let term = search_term.trim();
+    let phone_digits = phone_search_digits(term);
+    let choseong_term = is_choseong_query(term).then(|| choseong(term));
 
     let customers = sqlx::query_as!(
         Customer,
         r#"
         SELECT
-            id, user_id, name, birth_date, phone, address, profile_photo_id,
+            id, user_id, name, birth_date, phone, address, business_number, profile_photo_id,
             customer_type as "customer_type: CustomerType",
             created_at as "created_at!",
             updated_at as "updated_at!",
             deleted_at
         FROM customers
         WHERE user_id = $1
-            AND (name ILIKE $2 OR phone ILIKE $2)
             AND deleted_at IS NULL
-        ORDER BY created_at DESC
-        LIMIT $3 OFFSET $4
+            AND (
+                name ILIKE '%' || $2 || '%'
+                OR name % $2
+                OR ($3::text IS NOT NULL AND phone_normalized LIKE '%' || $3 || '%')
+                OR ($4::text IS NOT NULL AND name_choseong LIKE '%' || $4 || '%')
+            )
+        ORDER BY
+            GREATEST(
+                CASE
+                    WHEN name = $2 THEN 1.0
+                    WHEN name ILIKE $2 || '%' THEN 0.9
+                    WHEN name ILIKE '%' || $2 || '%' THEN 0.7
+                    ELSE similarity(name, $2) * 0.6
+                END,
+                CASE
+                    WHEN $3::text IS NULL THEN 0
+                    WHEN phone_normalized = $3 THEN 1.0
+                    WHEN phone_normalized LIKE '%' || $3 THEN 0.85
+                    WHEN phone_normalized LIKE '%' || $3 || '%' THEN 0.5
+                    ELSE 0
+                END,
+                CASE
+                    WHEN $4::text IS NULL THEN 0
+                    WHEN name_choseong = $4 THEN 0.95
+                    WHEN name_choseong LIKE $4 || '%' THEN 0.8
+                    WHEN name_choseong LIKE '%' || $4 || '%' THEN 0.5
+                    ELSE 0
+                END
+            ) DESC,
+            created_at DESC
+        LIMIT $5 OFFSET $6
         "#,
         user_id,
-        search_pattern,
+        term,
+        phone_digits,
+        choseong_term,
         limit,
         offset
     )
@@ -0,0 +1,248 @@
+use chrono::NaiveDate;
+use futures_util::stream::{BoxStream, StreamExt, TryStreamExt};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+use crate::models::customer::CustomerType;
+use crate::models::export::{
+    CustomerExportRow, JobPostingExportRow, JobSeekingExportRow, MatchingExportRow,
+    TaxInvoiceExportRow,
+};
+use crate::models::job_posting::{JobPostingFilter, JobSeekingFilter};
+use crate::models::matching::MatchingStatus;
+use crate::repositories::job_posting::push_job_posting_filter;
+use crate::repositories::job_seeking::push_job_seeking_filter;
+
+/// Stream customers (with their tag names) using the same filters as `list_customers_by_user`
+pub fn stream_customers(
+    pool: PgPool,
+    user_id: i64,
+    customer_type: Option<CustomerType>,
+    tag_ids: Option<Vec<i64>>,
+) -> BoxStream<'static, Result<CustomerExportRow, sqlx::Error>> {
+    let tag_ids = tag_ids.filter(|tags| !tags.is_empty());
+
+    async_stream::try_stream! {
+        let mut rows = sqlx::query_as::<_, CustomerExportRow>(
+            r#"
+            SELECT
+                c.id, c.name, c.customer_type::text AS customer_type, c.phone, c.birth_date, c.address,
+                COALESCE((
+                    SELECT string_agg(t.tag_name, ', ' ORDER BY t.tag_name)
+                    FROM customer_tags ct
+                    INNER JOIN tags t ON ct.tag_id = t.id
+                    WHERE ct.customer_id = c.id AND t.deleted_at IS NULL
+                ), '') AS tags,
+                c.created_at
+            FROM customers c
+            WHERE c.user_id = $1
+                AND c.deleted_at IS NULL
+                AND ($2::text IS NULL OR c.customer_type = $2)
+                AND ($3::bigint[] IS NULL OR EXISTS (
+                    SELECT 1 FROM customer_tags ct
+                    WHERE ct.customer_id = c.id AND ct.tag_id = ANY($3)
+                ))
+            ORDER BY c.created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(customer_type)
+        .bind(tag_ids)
+        .fetch(&pool);
+
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }
+    .boxed()
+}
+
+/// Stream job postings using the same filters as `list_job_postings`
+pub fn stream_job_postings(
+    pool: PgPool,
+    user_id: i64,
+    filter: JobPostingFilter,
+) -> BoxStream<'static, Result<JobPostingExportRow, sqlx::Error>> {
+    async_stream::try_stream! {
+        let mut query = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT
+                jp.id, jp.customer_id, c.name AS customer_name, jp.salary, jp.description,
+                jp.employer_fee_rate, jp.posting_status::text AS posting_status,
+                jp.settlement_status::text AS settlement_status,
+                jp.settlement_amount, jp.settlement_memo, jp.is_favorite,
+                COALESCE((
+                    SELECT string_agg(t.tag_name, ', ' ORDER BY t.tag_name)
+                    FROM job_posting_tags jpt
+                    INNER JOIN tags t ON jpt.tag_id = t.id
+                    WHERE jpt.job_posting_id = jp.id AND t.deleted_at IS NULL
+                ), '') AS tags,
+                jp.created_at
+            FROM job_postings jp
+            INNER JOIN customers c ON jp.customer_id = c.id
+            WHERE c.user_id = "#,
+        );
+        query.push_bind(user_id);
+        query.push(" AND jp.deleted_at IS NULL");
+        push_job_posting_filter(&mut query, filter);
+        query.push(" ORDER BY jp.is_favorite DESC, jp.created_at DESC");
+
+        let mut rows = query.build_query_as::<JobPostingExportRow>().fetch(&pool);
+
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }
+    .boxed()
+}
+
+/// Stream job seeking postings using the same filters as `list_job_seeking_postings`
+pub fn stream_job_seekings(
+    pool: PgPool,
+    user_id: i64,
+    filter: JobSeekingFilter,
+) -> BoxStream<'static, Result<JobSeekingExportRow, sqlx::Error>> {
+    async_stream::try_stream! {
+        let mut query = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT
+                jsp.id, jsp.customer_id, c.name AS customer_name, jsp.desired_salary,
+                jsp.description, jsp.preferred_location, jsp.employee_fee_rate,
+                jsp.posting_status::text AS posting_status,
+                jsp.settlement_status::text AS settlement_status,
+                jsp.settlement_amount, jsp.settlement_memo, jsp.is_favorite,
+                COALESCE((
+                    SELECT string_agg(t.tag_name, ', ' ORDER BY t.tag_name)
+                    FROM job_seeking_posting_tags jspt
+                    INNER JOIN tags t ON jspt.tag_id = t.id
+                    WHERE jspt.job_seeking_posting_id = jsp.id AND t.deleted_at IS NULL
+                ), '') AS tags,
+                jsp.created_at
+            FROM job_seeking_postings jsp
+            INNER JOIN customers c ON jsp.customer_id = c.id
+            WHERE c.user_id = "#,
+        );
+        query.push_bind(user_id);
+        query.push(" AND jsp.deleted_at IS NULL");
+        push_job_seeking_filter(&mut query, filter);
+        query.push(" ORDER BY jsp.is_favorite DESC, jsp.created_at DESC");
+
+        let mut rows = query.build_query_as::<JobSeekingExportRow>().fetch(&pool);
+
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }
+    .boxed()
+}
+
+/// Stream matchings with both parties, fee amounts and settlement status
+pub fn stream_matchings(
+    pool: PgPool,
+    user_id: i64,
+    status: Option<MatchingStatus>,
+) -> BoxStream<'static, Result<MatchingExportRow, sqlx::Error>> {
+    async_stream::try_stream! {
+        let mut rows = sqlx::query_as::<_, MatchingExportRow>(
+            r#"
+            SELECT
+                m.id, m.matched_at, m.job_posting_id, employer.name AS employer_name,
+                m.job_seeking_posting_id, employee.name AS employee_name,
+                m.agreed_salary,
+                m.employer_fee_rate, m.employer_fee_amount,
+                jp.settlement_status::text AS employer_settlement_status,
+                m.employee_fee_rate, m.employee_fee_amount,
+                jsp.settlement_status::text AS employee_settlement_status,
+                m.matching_status::text AS matching_status, m.completed_at, m.cancelled_at, m.cancellation_reason
+            FROM matchings m
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+            INNER JOIN customers employer ON jp.customer_id = employer.id
+            INNER JOIN customers employee ON jsp.customer_id = employee.id
+            WHERE employer.user_id = $1
+                AND m.deleted_at IS NULL
+                AND ($2::text IS NULL OR m.matching_status = $2)
+            ORDER BY m.matched_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(status)
+        .fetch(&pool);
+
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }
+    .boxed()
+}
+
+/// Stream one tax invoice line per side of the user's matchings completed
+/// from `from` to `to` inclusive that was charged a fee, oldest first
+pub fn stream_tax_invoices(
+    pool: PgPool,
+    user_id: i64,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> BoxStream<'static, Result<TaxInvoiceExportRow, sqlx::Error>> {
+    async_stream::try_stream! {
+        let mut rows = sqlx::query_as::<_, TaxInvoiceExportRow>(
+            r#"
+            WITH completed AS (
+                SELECT m.*, jp.customer_id AS employer_id, jsp.customer_id AS employee_id
+                FROM matchings m
+                INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+                INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+                INNER JOIN customers owner ON jp.customer_id = owner.id
+                WHERE owner.user_id = $1
+                    AND m.matching_status = 'completed'
+                    AND ($2::date IS NULL OR m.completed_at >= $2::date)
+                    AND ($3::date IS NULL OR m.completed_at < $3::date + 1)
+                    AND m.deleted_at IS NULL
+            ),
+            sides AS (
+                SELECT
+                    id AS matching_id, 'employer'::text AS payer_side, employer_id AS customer_id,
+                    completed_at, COALESCE(employer_fee_amount, 0) AS fee_amount,
+                    COALESCE(guarantee_refund_amount, 0) AS refunded_amount,
+                    employer_fee_breakdown AS fee_breakdown
+                FROM completed
+                UNION ALL
+                SELECT
+                    id, 'employee', employee_id, completed_at,
+                    COALESCE(employee_fee_amount, 0), 0, employee_fee_breakdown
+                FROM completed
+            )
+            SELECT
+                s.matching_id, s.payer_side, s.completed_at,
+                u.business_number AS supplier_business_number,
+                COALESCE(u.business_name, u.username) AS supplier_name,
+                u.representative_name AS supplier_representative,
+                u.business_address AS supplier_address,
+                u.business_type AS supplier_business_type,
+                u.business_item AS supplier_business_item,
+                u.tax_invoice_email AS supplier_email,
+                c.business_number AS recipient_business_number,
+                c.name AS recipient_name,
+                c.address AS recipient_address,
+                s.fee_amount, s.refunded_amount, s.fee_breakdown,
+                COALESCE((
+                    SELECT SUM(p.amount) FROM payments p
+                    WHERE p.matching_id = s.matching_id AND p.payer_side = s.payer_side
+                ), 0) AS paid_amount
+            FROM sides s
+            INNER JOIN customers c ON s.customer_id = c.id
+            INNER JOIN users u ON c.user_id = u.id
+            WHERE s.fee_amount > s.refunded_amount
+            ORDER BY s.completed_at, s.matching_id, s.payer_side
+            "#,
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch(&pool);
+
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }
+    .boxed()
+}
@@ -0,0 +1,164 @@
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool};
+
+use crate::models::fee_document::{DocumentType, FeeDocument, FeeDocumentSource, NewFeeDocument};
+use crate::models::fee_policy::FeeBreakdown;
+use crate::models::job_posting::SalaryBasis;
+use crate::models::payment::PayerSide;
+
+/// Agency, customer and matching terms printed on a document for `side`
+/// of one of the user's matchings
+pub async fn get_document_source(
+    conn: &mut PgConnection,
+    matching_id: i64,
+    side: PayerSide,
+    user_id: i64,
+) -> Result<FeeDocumentSource, sqlx::Error> {
+    sqlx::query_as!(
+        FeeDocumentSource,
+        r#"
+        SELECT
+            u.username AS agency_name,
+            u.phone AS agency_phone,
+            c.name AS customer_name,
+            c.phone AS customer_phone,
+            c.address AS customer_address,
+            m.matched_at as "matched_at!",
+            m.completed_at,
+            m.agreed_salary,
+            m.salary_basis as "salary_basis: SalaryBasis",
+            CASE WHEN $3 THEN m.employer_fee_rate ELSE m.employee_fee_rate END AS "fee_rate!",
+            CASE WHEN $3 THEN m.employer_fee_breakdown ELSE m.employee_fee_breakdown END
+                AS "fee_breakdown: Json<FeeBreakdown>"
+        FROM matchings m
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+        INNER JOIN customers owner ON jp.customer_id = owner.id
+        INNER JOIN users u ON owner.user_id = u.id
+        INNER JOIN customers c ON c.id = CASE WHEN $3 THEN jp.customer_id ELSE jsp.customer_id END
+        WHERE m.id = $1 AND owner.user_id = $2 AND m.deleted_at IS NULL
+        "#,
+        matching_id,
+        user_id,
+        side == PayerSide::Employer
+    )
+    .fetch_one(conn)
+    .await
+}
+
+/// Take the next number of the user's documents of `document_type` for
+/// `year`. Numbers are gapless as long as the surrounding transaction commits.
+pub async fn next_document_number(
+    conn: &mut PgConnection,
+    user_id: i64,
+    document_type: DocumentType,
+    year: i32,
+) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO document_sequences (user_id, document_type, year, last_number)
+        VALUES ($1, $2, $3, 1)
+        ON CONFLICT (user_id, document_type, year)
+        DO UPDATE SET last_number = document_sequences.last_number + 1
+        RETURNING last_number
+        "#,
+        user_id,
+        document_type.as_str(),
+        year
+    )
+    .fetch_one(conn)
+    .await
+}
+
+pub async fn insert_fee_document(
+    conn: &mut PgConnection,
+    document: &NewFeeDocument,
+) -> Result<FeeDocument, sqlx::Error> {
+    sqlx::query_as!(
+        FeeDocument,
+        r#"
+        INSERT INTO fee_documents (
+            user_id, matching_id, document_type, payer_side, document_number,
+            issued_on, total_amount, file_path, file_size
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING
+            id, user_id, matching_id,
+            document_type as "document_type: DocumentType",
+            payer_side as "payer_side: PayerSide",
+            document_number, issued_on, total_amount, file_path, file_size, created_at
+        "#,
+        document.user_id,
+        document.matching_id,
+        document.document_type as DocumentType,
+        document.payer_side as PayerSide,
+        document.document_number,
+        document.issued_on,
+        document.total_amount,
+        document.file_path,
+        document.file_size
+    )
+    .fetch_one(conn)
+    .await
+}
+
+/// Whether any invoice or receipt has been issued for the matching
+pub async fn has_fee_documents(
+    conn: &mut PgConnection,
+    matching_id: i64,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM fee_documents WHERE matching_id = $1) AS "exists!""#,
+        matching_id
+    )
+    .fetch_one(conn)
+    .await
+}
+
+/// Documents issued for one of the user's matchings, newest first
+pub async fn list_fee_documents(
+    pool: &PgPool,
+    matching_id: i64,
+    user_id: i64,
+) -> Result<Vec<FeeDocument>, sqlx::Error> {
+    sqlx::query_as!(
+        FeeDocument,
+        r#"
+        SELECT
+            id, user_id, matching_id,
+            document_type as "document_type: DocumentType",
+            payer_side as "payer_side: PayerSide",
+            document_number, issued_on, total_amount, file_path, file_size, created_at
+        FROM fee_documents
+        WHERE matching_id = $1 AND user_id = $2
+        ORDER BY created_at DESC, id DESC
+        "#,
+        matching_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_fee_document(
+    pool: &PgPool,
+    document_id: i64,
+    user_id: i64,
+) -> Result<FeeDocument, sqlx::Error> {
+    sqlx::query_as!(
+        FeeDocument,
+        r#"
+        SELECT
+            id, user_id, matching_id,
+            document_type as "document_type: DocumentType",
+            payer_side as "payer_side: PayerSide",
+            document_number, issued_on, total_amount, file_path, file_size, created_at
+        FROM fee_documents
+        WHERE id = $1 AND user_id = $2
+        "#,
+        document_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
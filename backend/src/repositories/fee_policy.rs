@@ -0,0 +1,305 @@
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
+
+use crate::models::fee_policy::{FeePolicy, FeePolicyRequest, FeeTier, UserFeePolicies};
+use crate::services::fee::DEFAULT_VAT_RATE;
+
+const FEE_POLICY_COLUMNS: &str = r#"
+    id, user_id, name, minimum_fee, short_term_max_days, short_term_fee, vat_rate,
+    created_at, updated_at
+"#;
+
+/// Create a fee policy with its tiers
+pub async fn create_fee_policy(
+    pool: &PgPool,
+    user_id: i64,
+    req: FeePolicyRequest,
+) -> Result<FeePolicy, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let mut policy = sqlx::query_as::<_, FeePolicy>(&format!(
+        r#"
+        INSERT INTO fee_policies
+            (user_id, name, minimum_fee, short_term_max_days, short_term_fee, vat_rate)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING {}
+        "#,
+        FEE_POLICY_COLUMNS
+    ))
+    .bind(user_id)
+    .bind(&req.name)
+    .bind(req.minimum_fee)
+    .bind(req.short_term_max_days)
+    .bind(req.short_term_fee)
+    .bind(req.vat_rate.unwrap_or(DEFAULT_VAT_RATE))
+    .fetch_one(&mut *tx)
+    .await?;
+
+    insert_tiers(&mut tx, policy.id, &req.tiers).await?;
+    tx.commit().await?;
+
+    policy.tiers = req.tiers;
+    Ok(policy)
+}
+
+/// List the user's fee policies by name
+pub async fn list_fee_policies(pool: &PgPool, user_id: i64) -> Result<Vec<FeePolicy>, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+
+    let mut policies = sqlx::query_as::<_, FeePolicy>(&format!(
+        "SELECT {} FROM fee_policies WHERE user_id = $1 ORDER BY name",
+        FEE_POLICY_COLUMNS
+    ))
+    .bind(user_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for policy in &mut policies {
+        policy.tiers = list_tiers(&mut conn, policy.id).await?;
+    }
+
+    Ok(policies)
+}
+
+/// Get one of the user's fee policies
+pub async fn get_fee_policy(
+    pool: &PgPool,
+    policy_id: i64,
+    user_id: i64,
+) -> Result<FeePolicy, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+
+    let mut policy = sqlx::query_as::<_, FeePolicy>(&format!(
+        "SELECT {} FROM fee_policies WHERE id = $1 AND user_id = $2",
+        FEE_POLICY_COLUMNS
+    ))
+    .bind(policy_id)
+    .bind(user_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    policy.tiers = list_tiers(&mut conn, policy.id).await?;
+    Ok(policy)
+}
+
+/// Check that an optional fee policy reference names one of the user's policies
+///
+/// Returns `RowNotFound` when the policy does not exist or belongs to another user.
+pub async fn ensure_fee_policy(
+    pool: &PgPool,
+    fee_policy_id: Option<i64>,
+    user_id: i64,
+) -> Result<(), sqlx::Error> {
+    let Some(policy_id) = fee_policy_id else {
+        return Ok(());
+    };
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM fee_policies WHERE id = $1 AND user_id = $2)",
+    )
+    .bind(policy_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    if exists {
+        Ok(())
+    } else {
+        Err(sqlx::Error::RowNotFound)
+    }
+}
+
+/// Replace a fee policy and its tiers
+pub async fn update_fee_policy(
+    pool: &PgPool,
+    policy_id: i64,
+    user_id: i64,
+    req: FeePolicyRequest,
+) -> Result<FeePolicy, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let mut policy = sqlx::query_as::<_, FeePolicy>(&format!(
+        r#"
+        UPDATE fee_policies
+        SET name = $3, minimum_fee = $4, short_term_max_days = $5, short_term_fee = $6,
+            vat_rate = $7
+        WHERE id = $1 AND user_id = $2
+        RETURNING {}
+        "#,
+        FEE_POLICY_COLUMNS
+    ))
+    .bind(policy_id)
+    .bind(user_id)
+    .bind(&req.name)
+    .bind(req.minimum_fee)
+    .bind(req.short_term_max_days)
+    .bind(req.short_term_fee)
+    .bind(req.vat_rate.unwrap_or(DEFAULT_VAT_RATE))
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM fee_policy_tiers WHERE fee_policy_id = $1",
+        policy_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    insert_tiers(&mut tx, policy_id, &req.tiers).await?;
+    tx.commit().await?;
+
+    policy.tiers = req.tiers;
+    Ok(policy)
+}
+
+/// Delete a fee policy. Users and postings using it fall back to their
+/// default rates.
+pub async fn delete_fee_policy(
+    pool: &PgPool,
+    policy_id: i64,
+    user_id: i64,
+) -> Result<(), sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM fee_policies WHERE id = $1 AND user_id = $2",
+        policy_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    Ok(())
+}
+
+/// The user's default fee policies per matching side
+pub async fn get_user_fee_policies(
+    pool: &PgPool,
+    user_id: i64,
+) -> Result<UserFeePolicies, sqlx::Error> {
+    sqlx::query_as!(
+        UserFeePolicies,
+        r#"
+        SELECT employer_fee_policy_id, employee_fee_policy_id
+        FROM users
+        WHERE id = $1 AND deleted_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn set_user_fee_policies(
+    pool: &PgPool,
+    user_id: i64,
+    policies: &UserFeePolicies,
+) -> Result<UserFeePolicies, sqlx::Error> {
+    sqlx::query_as!(
+        UserFeePolicies,
+        r#"
+        UPDATE users
+        SET employer_fee_policy_id = $2, employee_fee_policy_id = $3, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING employer_fee_policy_id, employee_fee_policy_id
+        "#,
+        user_id,
+        policies.employer_fee_policy_id,
+        policies.employee_fee_policy_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Fee policies that apply to a matching between the two postings: each
+/// posting's own policy, otherwise the broker's default for that side.
+/// Returns `(employer, employee)`.
+pub async fn find_matching_policies(
+    conn: &mut PgConnection,
+    job_posting_id: i64,
+    job_seeking_posting_id: i64,
+) -> Result<(Option<FeePolicy>, Option<FeePolicy>), sqlx::Error> {
+    let ids = sqlx::query!(
+        r#"
+        SELECT
+            COALESCE(jp.fee_policy_id, u.employer_fee_policy_id) AS employer_policy_id,
+            COALESCE(jsp.fee_policy_id, u.employee_fee_policy_id) AS employee_policy_id
+        FROM job_postings jp
+        INNER JOIN customers c ON jp.customer_id = c.id
+        INNER JOIN users u ON c.user_id = u.id
+        INNER JOIN job_seeking_postings jsp ON jsp.id = $2
+        WHERE jp.id = $1
+        "#,
+        job_posting_id,
+        job_seeking_posting_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let Some(ids) = ids else {
+        return Ok((None, None));
+    };
+
+    let employer = match ids.employer_policy_id {
+        Some(id) => Some(load_policy(conn, id).await?),
+        None => None,
+    };
+    let employee = match ids.employee_policy_id {
+        Some(id) => Some(load_policy(conn, id).await?),
+        None => None,
+    };
+
+    Ok((employer, employee))
+}
+
+async fn load_policy(conn: &mut PgConnection, policy_id: i64) -> Result<FeePolicy, sqlx::Error> {
+    let mut policy = sqlx::query_as::<_, FeePolicy>(&format!(
+        "SELECT {} FROM fee_policies WHERE id = $1",
+        FEE_POLICY_COLUMNS
+    ))
+    .bind(policy_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    policy.tiers = list_tiers(conn, policy_id).await?;
+    Ok(policy)
+}
+
+async fn list_tiers(conn: &mut PgConnection, policy_id: i64) -> Result<Vec<FeeTier>, sqlx::Error> {
+    sqlx::query_as!(
+        FeeTier,
+        r#"
+        SELECT min_monthly_salary, rate
+        FROM fee_policy_tiers
+        WHERE fee_policy_id = $1
+        ORDER BY min_monthly_salary
+        "#,
+        policy_id
+    )
+    .fetch_all(conn)
+    .await
+}
+
+async fn insert_tiers(
+    conn: &mut PgConnection,
+    policy_id: i64,
+    tiers: &[FeeTier],
+) -> Result<(), sqlx::Error> {
+    let salaries: Vec<Decimal> = tiers.iter().map(|tier| tier.min_monthly_salary).collect();
+    let rates: Vec<Decimal> = tiers.iter().map(|tier| tier.rate).collect();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO fee_policy_tiers (fee_policy_id, min_monthly_salary, rate)
+        SELECT $1, * FROM UNNEST($2::NUMERIC[], $3::NUMERIC[])
+        "#,
+        policy_id,
+        &salaries,
+        &rates
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
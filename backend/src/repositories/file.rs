@@ -2,7 +2,7 @@
 #![allow(dead_code)]
 
 use crate::models::file::{CustomerFile, FileType, UserFile};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 /// Create a new customer file record
 #[allow(clippy::too_many_arguments)]
@@ -195,6 +195,48 @@ pub async fn set_customer_profile_photo(
     Ok(())
 }
 
+/// Move all files of one customer to another (used when merging customers).
+///
+/// Only one profile photo may remain: the source's when `keep_source_profile`
+/// is set, otherwise the target's.
+pub async fn reassign_customer_files(
+    conn: &mut PgConnection,
+    from_customer_id: i64,
+    to_customer_id: i64,
+    keep_source_profile: bool,
+) -> Result<u64, sqlx::Error> {
+    let demoted_customer_id = if keep_source_profile {
+        to_customer_id
+    } else {
+        from_customer_id
+    };
+
+    sqlx::query!(
+        r#"
+        UPDATE customer_files
+        SET is_profile = false, updated_at = CURRENT_TIMESTAMP
+        WHERE customer_id = $1 AND is_profile = true AND deleted_at IS NULL
+        "#,
+        demoted_customer_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE customer_files
+        SET customer_id = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE customer_id = $1 AND deleted_at IS NULL
+        "#,
+        from_customer_id,
+        to_customer_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 /// Delete customer file (soft delete)
 pub async fn delete_customer_file(pool: &PgPool, file_id: i64) -> Result<(), sqlx::Error> {
     sqlx::query!(
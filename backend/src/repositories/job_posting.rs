@@ -2,9 +2,12 @@
 #![allow(dead_code)]
 
 use crate::models::job_posting::{
-    CreateJobPostingRequest, JobPosting, PostingStatus, SettlementStatus, UpdateJobPostingRequest,
+    CreateJobPostingRequest, JobCategory, JobPosting, JobPostingFilter, PostingKind, PostingStatus,
+    SalaryBasis, SettlementStatus, StatusChangeSource, UpdateJobPostingRequest, Weekday,
+    WorkArrangement,
 };
-use sqlx::PgPool;
+use crate::repositories::posting_status::{self, PostingStatusError};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
 
 /// Create a new job posting
 pub async fn create_job_posting(
@@ -14,19 +17,45 @@ pub async fn create_job_posting(
     let job_posting = sqlx::query_as!(
         JobPosting,
         r#"
-        INSERT INTO job_postings (customer_id, salary, description, employer_fee_rate)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO job_postings (
+            customer_id, salary, description, employer_fee_rate,
+            job_category, region_codes, work_arrangement, work_days,
+            work_start_time, work_end_time, start_date, salary_basis,
+            expected_weekly_hours, household_members, has_pets,
+            expires_at, fee_policy_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
         RETURNING
             id, customer_id, salary, description, employer_fee_rate,
             settlement_status as "settlement_status!: SettlementStatus",
             settlement_amount, settlement_memo,
             posting_status as "posting_status!: PostingStatus",
-            is_favorite as "is_favorite!", created_at as "created_at!", updated_at as "updated_at!", deleted_at
+            is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory", region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>", work_start_time, work_end_time, start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours, monthly_salary as "monthly_salary!",
+            household_members, has_pets, expires_at, fee_policy_id,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
         "#,
         req.customer_id,
         req.salary,
         req.description,
-        req.employer_fee_rate
+        req.employer_fee_rate,
+        req.job_category as Option<JobCategory>,
+        &req.region_codes,
+        req.work_arrangement as Option<WorkArrangement>,
+        req.work_days.as_slice() as &[Weekday],
+        req.work_start_time,
+        req.work_end_time,
+        req.start_date,
+        req.salary_basis as SalaryBasis,
+        req.expected_weekly_hours,
+        req.household_members,
+        req.has_pets,
+        req.expires_at,
+        req.fee_policy_id
     )
     .fetch_one(pool)
     .await?;
@@ -47,7 +76,14 @@ pub async fn get_job_posting_by_id(
             settlement_status as "settlement_status!: SettlementStatus",
             settlement_amount, settlement_memo,
             posting_status as "posting_status!: PostingStatus",
-            is_favorite as "is_favorite!", created_at as "created_at!", updated_at as "updated_at!", deleted_at
+            is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory", region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>", work_start_time, work_end_time, start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours, monthly_salary as "monthly_salary!",
+            household_members, has_pets, expires_at, fee_policy_id,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
         FROM job_postings
         WHERE id = $1 AND deleted_at IS NULL
         "#,
@@ -59,240 +95,128 @@ pub async fn get_job_posting_by_id(
     Ok(job_posting)
 }
 
+/// Columns selected into `JobPosting` by runtime queries (enum columns cast to text)
+const JOB_POSTING_COLUMNS: &str = r#"
+    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
+    jp.settlement_status::text AS settlement_status,
+    jp.settlement_amount, jp.settlement_memo,
+    jp.posting_status::text AS posting_status,
+    jp.is_favorite,
+    jp.job_category::text AS job_category, jp.region_codes,
+    jp.work_arrangement::text AS work_arrangement, jp.work_days,
+    jp.work_start_time, jp.work_end_time, jp.start_date,
+    jp.salary_basis::text AS salary_basis,
+    jp.expected_weekly_hours, jp.monthly_salary,
+    jp.household_members, jp.has_pets, jp.expires_at, jp.fee_policy_id,
+    jp.created_at, jp.updated_at, jp.deleted_at
+"#;
+
 /// List job postings with optional filters
 pub async fn list_job_postings(
     pool: &PgPool,
     user_id: i64,
-    status: Option<PostingStatus>,
-    settlement_status: Option<SettlementStatus>,
-    is_favorite: Option<bool>,
+    filter: JobPostingFilter,
     limit: Option<i64>,
     offset: Option<i64>,
 ) -> Result<Vec<JobPosting>, sqlx::Error> {
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
 
-    // Build query based on filters
-    let job_postings = match (status, settlement_status, is_favorite) {
-        (Some(st), Some(ss), Some(fav)) => {
-            sqlx::query_as!(
-                JobPosting,
-                r#"
-                SELECT
-                    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
-                    jp.settlement_status as "settlement_status!: SettlementStatus",
-                    jp.settlement_amount, jp.settlement_memo,
-                    jp.posting_status as "posting_status!: PostingStatus",
-                    jp.is_favorite as "is_favorite!", jp.created_at as "created_at!", jp.updated_at as "updated_at!", jp.deleted_at
-                FROM job_postings jp
-                INNER JOIN customers c ON jp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jp.posting_status = $2
-                    AND jp.settlement_status = $3
-                    AND jp.is_favorite = $4
-                    AND jp.deleted_at IS NULL
-                ORDER BY jp.is_favorite DESC, jp.created_at DESC
-                LIMIT $5 OFFSET $6
-                "#,
-                user_id,
-                st as PostingStatus,
-                ss as SettlementStatus,
-                fav,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (Some(st), Some(ss), None) => {
-            sqlx::query_as!(
-                JobPosting,
-                r#"
-                SELECT
-                    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
-                    jp.settlement_status as "settlement_status!: SettlementStatus",
-                    jp.settlement_amount, jp.settlement_memo,
-                    jp.posting_status as "posting_status!: PostingStatus",
-                    jp.is_favorite as "is_favorite!", jp.created_at as "created_at!", jp.updated_at as "updated_at!", jp.deleted_at
-                FROM job_postings jp
-                INNER JOIN customers c ON jp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jp.posting_status = $2
-                    AND jp.settlement_status = $3
-                    AND jp.deleted_at IS NULL
-                ORDER BY jp.is_favorite DESC, jp.created_at DESC
-                LIMIT $4 OFFSET $5
-                "#,
-                user_id,
-                st as PostingStatus,
-                ss as SettlementStatus,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (Some(st), None, Some(fav)) => {
-            sqlx::query_as!(
-                JobPosting,
-                r#"
-                SELECT
-                    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
-                    jp.settlement_status as "settlement_status!: SettlementStatus",
-                    jp.settlement_amount, jp.settlement_memo,
-                    jp.posting_status as "posting_status!: PostingStatus",
-                    jp.is_favorite as "is_favorite!", jp.created_at as "created_at!", jp.updated_at as "updated_at!", jp.deleted_at
-                FROM job_postings jp
-                INNER JOIN customers c ON jp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jp.posting_status = $2
-                    AND jp.is_favorite = $3
-                    AND jp.deleted_at IS NULL
-                ORDER BY jp.is_favorite DESC, jp.created_at DESC
-                LIMIT $4 OFFSET $5
-                "#,
-                user_id,
-                st as PostingStatus,
-                fav,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (None, Some(ss), Some(fav)) => {
-            sqlx::query_as!(
-                JobPosting,
-                r#"
-                SELECT
-                    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
-                    jp.settlement_status as "settlement_status!: SettlementStatus",
-                    jp.settlement_amount, jp.settlement_memo,
-                    jp.posting_status as "posting_status!: PostingStatus",
-                    jp.is_favorite as "is_favorite!", jp.created_at as "created_at!", jp.updated_at as "updated_at!", jp.deleted_at
-                FROM job_postings jp
-                INNER JOIN customers c ON jp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jp.settlement_status = $2
-                    AND jp.is_favorite = $3
-                    AND jp.deleted_at IS NULL
-                ORDER BY jp.is_favorite DESC, jp.created_at DESC
-                LIMIT $4 OFFSET $5
-                "#,
-                user_id,
-                ss as SettlementStatus,
-                fav,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (Some(st), None, None) => {
-            sqlx::query_as!(
-                JobPosting,
-                r#"
-                SELECT
-                    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
-                    jp.settlement_status as "settlement_status!: SettlementStatus",
-                    jp.settlement_amount, jp.settlement_memo,
-                    jp.posting_status as "posting_status!: PostingStatus",
-                    jp.is_favorite as "is_favorite!", jp.created_at as "created_at!", jp.updated_at as "updated_at!", jp.deleted_at
-                FROM job_postings jp
-                INNER JOIN customers c ON jp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jp.posting_status = $2
-                    AND jp.deleted_at IS NULL
-                ORDER BY jp.is_favorite DESC, jp.created_at DESC
-                LIMIT $3 OFFSET $4
-                "#,
-                user_id,
-                st as PostingStatus,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (None, Some(ss), None) => {
-            sqlx::query_as!(
-                JobPosting,
-                r#"
-                SELECT
-                    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
-                    jp.settlement_status as "settlement_status!: SettlementStatus",
-                    jp.settlement_amount, jp.settlement_memo,
-                    jp.posting_status as "posting_status!: PostingStatus",
-                    jp.is_favorite as "is_favorite!", jp.created_at as "created_at!", jp.updated_at as "updated_at!", jp.deleted_at
-                FROM job_postings jp
-                INNER JOIN customers c ON jp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jp.settlement_status = $2
-                    AND jp.deleted_at IS NULL
-                ORDER BY jp.is_favorite DESC, jp.created_at DESC
-                LIMIT $3 OFFSET $4
-                "#,
-                user_id,
-                ss as SettlementStatus,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (None, None, Some(fav)) => {
-            sqlx::query_as!(
-                JobPosting,
-                r#"
-                SELECT
-                    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
-                    jp.settlement_status as "settlement_status!: SettlementStatus",
-                    jp.settlement_amount, jp.settlement_memo,
-                    jp.posting_status as "posting_status!: PostingStatus",
-                    jp.is_favorite as "is_favorite!", jp.created_at as "created_at!", jp.updated_at as "updated_at!", jp.deleted_at
-                FROM job_postings jp
-                INNER JOIN customers c ON jp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jp.is_favorite = $2
-                    AND jp.deleted_at IS NULL
-                ORDER BY jp.is_favorite DESC, jp.created_at DESC
-                LIMIT $3 OFFSET $4
-                "#,
-                user_id,
-                fav,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (None, None, None) => {
-            sqlx::query_as!(
-                JobPosting,
-                r#"
-                SELECT
-                    jp.id, jp.customer_id, jp.salary, jp.description, jp.employer_fee_rate,
-                    jp.settlement_status as "settlement_status!: SettlementStatus",
-                    jp.settlement_amount, jp.settlement_memo,
-                    jp.posting_status as "posting_status!: PostingStatus",
-                    jp.is_favorite as "is_favorite!", jp.created_at as "created_at!", jp.updated_at as "updated_at!", jp.deleted_at
-                FROM job_postings jp
-                INNER JOIN customers c ON jp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jp.deleted_at IS NULL
-                ORDER BY jp.is_favorite DESC, jp.created_at DESC
-                LIMIT $2 OFFSET $3
-                "#,
-                user_id,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-    };
+    let mut query = QueryBuilder::<Postgres>::new(format!(
+        "SELECT {} FROM job_postings jp INNER JOIN customers c ON jp.customer_id = c.id WHERE c.user_id = ",
+        JOB_POSTING_COLUMNS
+    ));
+    query.push_bind(user_id);
+    query.push(" AND jp.deleted_at IS NULL");
 
-    Ok(job_postings)
+    push_job_posting_filter(&mut query, filter);
+
+    query.push(" ORDER BY jp.is_favorite DESC, jp.created_at DESC LIMIT ");
+    query.push_bind(limit);
+    query.push(" OFFSET ");
+    query.push_bind(offset);
+
+    query.build_query_as::<JobPosting>().fetch_all(pool).await
+}
+
+/// Append the list filters as `AND` conditions on `jp`; the export uses
+/// the same conditions
+pub(crate) fn push_job_posting_filter(
+    query: &mut QueryBuilder<'_, Postgres>,
+    filter: JobPostingFilter,
+) {
+    if let Some(status) = filter.status {
+        query.push(" AND jp.posting_status = ").push_bind(status);
+    }
+    if let Some(settlement_status) = filter.settlement_status {
+        query
+            .push(" AND jp.settlement_status = ")
+            .push_bind(settlement_status);
+    }
+    if let Some(is_favorite) = filter.is_favorite {
+        query.push(" AND jp.is_favorite = ").push_bind(is_favorite);
+    }
+    if let Some(job_category) = filter.job_category {
+        query
+            .push(" AND jp.job_category = ")
+            .push_bind(job_category);
+    }
+    if let Some(region_code) = filter.region_code {
+        push_region_filter(query, "jp.region_codes", region_code);
+    }
+    if let Some(work_arrangement) = filter.work_arrangement {
+        query
+            .push(" AND jp.work_arrangement = ")
+            .push_bind(work_arrangement);
+    }
+    if let Some(salary_basis) = filter.salary_basis {
+        query
+            .push(" AND jp.salary_basis = ")
+            .push_bind(salary_basis);
+    }
+    if let Some(min) = filter.min_monthly_salary {
+        query.push(" AND jp.monthly_salary >= ").push_bind(min);
+    }
+    if let Some(max) = filter.max_monthly_salary {
+        query.push(" AND jp.monthly_salary <= ").push_bind(max);
+    }
+    if let Some(work_day) = filter.work_day {
+        query
+            .push(" AND ")
+            .push_bind(work_day)
+            .push(" = ANY(jp.work_days)");
+    }
+    if let Some(from) = filter.start_date_from {
+        query.push(" AND jp.start_date >= ").push_bind(from);
+    }
+    if let Some(to) = filter.start_date_to {
+        query.push(" AND jp.start_date <= ").push_bind(to);
+    }
+    if let Some(has_pets) = filter.has_pets {
+        query.push(" AND jp.has_pets = ").push_bind(has_pets);
+    }
+    if let Some(max_members) = filter.max_household_members {
+        query
+            .push(" AND jp.household_members <= ")
+            .push_bind(max_members);
+    }
+}
+
+/// Match postings whose region codes overlap the given code in either direction,
+/// so "11" (서울) finds "11680" (강남구) and vice versa.
+pub(crate) fn push_region_filter(
+    query: &mut QueryBuilder<'_, Postgres>,
+    column: &str,
+    region_code: String,
+) {
+    query.push(format!(
+        " AND EXISTS (SELECT 1 FROM unnest({}) AS rc WHERE rc LIKE ",
+        column
+    ));
+    query.push_bind(format!("{}%", region_code));
+    query.push(" OR ");
+    query.push_bind(region_code);
+    query.push(" LIKE rc || '%')");
 }
 
 /// Update job posting
@@ -300,9 +224,31 @@ pub async fn update_job_posting(
     pool: &PgPool,
     job_posting_id: i64,
     req: UpdateJobPostingRequest,
-) -> Result<JobPosting, sqlx::Error> {
+    changed_by: i64,
+) -> Result<JobPosting, PostingStatusError> {
+    let mut tx = pool.begin().await?;
+
+    if let Some(status) = req.posting_status {
+        posting_status::transition(
+            &mut tx,
+            PostingKind::JobPosting,
+            job_posting_id,
+            status,
+            StatusChangeSource::Manual,
+            Some(changed_by),
+        )
+        .await?;
+    }
+
     // Get current posting for default values
     let current = get_job_posting_by_id(pool, job_posting_id).await?;
+    let region_codes = req.region_codes.unwrap_or(current.region_codes);
+    let work_days = req.work_days.unwrap_or(current.work_days);
+    let fee_policy_id = if req.clear_fee_policy {
+        None
+    } else {
+        req.fee_policy_id.or(current.fee_policy_id)
+    };
 
     let job_posting = sqlx::query_as!(
         JobPosting,
@@ -312,32 +258,59 @@ pub async fn update_job_posting(
             salary = $1,
             description = $2,
             employer_fee_rate = $3,
-            settlement_status = $4,
-            settlement_amount = $5,
-            settlement_memo = $6,
-            posting_status = $7,
-            is_favorite = $8
-        WHERE id = $9 AND deleted_at IS NULL
+            is_favorite = $4,
+            job_category = $5,
+            region_codes = $6,
+            work_arrangement = $7,
+            work_days = $8,
+            work_start_time = $9,
+            work_end_time = $10,
+            start_date = $11,
+            salary_basis = $12,
+            expected_weekly_hours = $13,
+            household_members = $14,
+            has_pets = $15,
+            expires_at = $16,
+            fee_policy_id = $17
+        WHERE id = $18 AND deleted_at IS NULL
         RETURNING
             id, customer_id, salary, description, employer_fee_rate,
             settlement_status as "settlement_status!: SettlementStatus",
             settlement_amount, settlement_memo,
             posting_status as "posting_status!: PostingStatus",
-            is_favorite as "is_favorite!", created_at as "created_at!", updated_at as "updated_at!", deleted_at
+            is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory", region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>", work_start_time, work_end_time, start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours, monthly_salary as "monthly_salary!",
+            household_members, has_pets, expires_at, fee_policy_id,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
         "#,
         req.salary.unwrap_or(current.salary),
         req.description.unwrap_or(current.description),
         req.employer_fee_rate.or(current.employer_fee_rate),
-        req.settlement_status.unwrap_or(current.settlement_status) as SettlementStatus,
-        req.settlement_amount.or(current.settlement_amount),
-        req.settlement_memo.or(current.settlement_memo),
-        req.posting_status.unwrap_or(current.posting_status) as PostingStatus,
         req.is_favorite.unwrap_or(current.is_favorite),
+        req.job_category.or(current.job_category) as Option<JobCategory>,
+        &region_codes,
+        req.work_arrangement.or(current.work_arrangement) as Option<WorkArrangement>,
+        work_days.as_slice() as &[Weekday],
+        req.work_start_time.or(current.work_start_time),
+        req.work_end_time.or(current.work_end_time),
+        req.start_date.or(current.start_date),
+        req.salary_basis.unwrap_or(current.salary_basis) as SalaryBasis,
+        req.expected_weekly_hours.or(current.expected_weekly_hours),
+        req.household_members.or(current.household_members),
+        req.has_pets.or(current.has_pets),
+        req.expires_at.or(current.expires_at),
+        fee_policy_id,
         job_posting_id
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
     Ok(job_posting)
 }
 
@@ -357,6 +330,27 @@ pub async fn delete_job_posting(pool: &PgPool, job_posting_id: i64) -> Result<()
     Ok(())
 }
 
+/// Move all job postings of one customer to another (used when merging customers)
+pub async fn reassign_job_postings(
+    conn: &mut PgConnection,
+    from_customer_id: i64,
+    to_customer_id: i64,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE job_postings
+        SET customer_id = $2
+        WHERE customer_id = $1 AND deleted_at IS NULL
+        "#,
+        from_customer_id,
+        to_customer_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 /// List job postings by customer
 pub async fn list_job_postings_by_customer(
     pool: &PgPool,
@@ -375,7 +369,14 @@ pub async fn list_job_postings_by_customer(
             settlement_status as "settlement_status!: SettlementStatus",
             settlement_amount, settlement_memo,
             posting_status as "posting_status!: PostingStatus",
-            is_favorite as "is_favorite!", created_at as "created_at!", updated_at as "updated_at!", deleted_at
+            is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory", region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>", work_start_time, work_end_time, start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours, monthly_salary as "monthly_salary!",
+            household_members, has_pets, expires_at, fee_policy_id,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
         FROM job_postings
         WHERE customer_id = $1 AND deleted_at IS NULL
         ORDER BY created_at DESC
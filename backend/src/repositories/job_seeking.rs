@@ -2,10 +2,13 @@
 #![allow(dead_code)]
 
 use crate::models::job_posting::{
-    CreateJobSeekingPostingRequest, JobSeekingPosting, PostingStatus, SettlementStatus,
-    UpdateJobSeekingPostingRequest,
+    CreateJobSeekingPostingRequest, JobCategory, JobSeekingFilter, JobSeekingPosting, PostingKind,
+    PostingStatus, SalaryBasis, SettlementStatus, StatusChangeSource,
+    UpdateJobSeekingPostingRequest, Weekday, WorkArrangement,
 };
-use sqlx::PgPool;
+use crate::repositories::job_posting::push_region_filter;
+use crate::repositories::posting_status::{self, PostingStatusError};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
 
 /// Create a new job seeking posting
 pub async fn create_job_seeking_posting(
@@ -15,21 +18,46 @@ pub async fn create_job_seeking_posting(
     let job_seeking = sqlx::query_as!(
         JobSeekingPosting,
         r#"
-        INSERT INTO job_seeking_postings
-            (customer_id, desired_salary, description, preferred_location, employee_fee_rate)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO job_seeking_postings (
+            customer_id, desired_salary, description, preferred_location, employee_fee_rate,
+            job_category, region_codes, work_arrangement, work_days,
+            work_start_time, work_end_time, start_date, salary_basis,
+            expected_weekly_hours, max_household_members, accepts_pets,
+            expires_at, fee_policy_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
         RETURNING
             id, customer_id, desired_salary, description, preferred_location, employee_fee_rate,
             settlement_status as "settlement_status!: SettlementStatus",
             settlement_amount, settlement_memo,
             posting_status as "posting_status!: PostingStatus",
-            is_favorite as "is_favorite!", created_at as "created_at!", updated_at as "updated_at!", deleted_at
+            is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory", region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>", work_start_time, work_end_time, start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours, monthly_salary as "monthly_salary!",
+            max_household_members, accepts_pets, expires_at, fee_policy_id,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
         "#,
         req.customer_id,
         req.desired_salary,
         req.description,
         req.preferred_location,
-        req.employee_fee_rate
+        req.employee_fee_rate,
+        req.job_category as Option<JobCategory>,
+        &req.region_codes,
+        req.work_arrangement as Option<WorkArrangement>,
+        req.work_days.as_slice() as &[Weekday],
+        req.work_start_time,
+        req.work_end_time,
+        req.start_date,
+        req.salary_basis as SalaryBasis,
+        req.expected_weekly_hours,
+        req.max_household_members,
+        req.accepts_pets,
+        req.expires_at,
+        req.fee_policy_id
     )
     .fetch_one(pool)
     .await?;
@@ -50,7 +78,14 @@ pub async fn get_job_seeking_posting_by_id(
             settlement_status as "settlement_status!: SettlementStatus",
             settlement_amount, settlement_memo,
             posting_status as "posting_status!: PostingStatus",
-            is_favorite as "is_favorite!", created_at as "created_at!", updated_at as "updated_at!", deleted_at
+            is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory", region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>", work_start_time, work_end_time, start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours, monthly_salary as "monthly_salary!",
+            max_household_members, accepts_pets, expires_at, fee_policy_id,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
         FROM job_seeking_postings
         WHERE id = $1 AND deleted_at IS NULL
         "#,
@@ -62,252 +97,120 @@ pub async fn get_job_seeking_posting_by_id(
     Ok(job_seeking)
 }
 
+/// Columns selected into `JobSeekingPosting` by runtime queries (enum columns cast to text)
+const JOB_SEEKING_COLUMNS: &str = r#"
+    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
+    jsp.preferred_location, jsp.employee_fee_rate,
+    jsp.settlement_status::text AS settlement_status,
+    jsp.settlement_amount, jsp.settlement_memo,
+    jsp.posting_status::text AS posting_status,
+    jsp.is_favorite,
+    jsp.job_category::text AS job_category, jsp.region_codes,
+    jsp.work_arrangement::text AS work_arrangement, jsp.work_days,
+    jsp.work_start_time, jsp.work_end_time, jsp.start_date,
+    jsp.salary_basis::text AS salary_basis,
+    jsp.expected_weekly_hours, jsp.monthly_salary,
+    jsp.max_household_members, jsp.accepts_pets, jsp.expires_at, jsp.fee_policy_id,
+    jsp.created_at, jsp.updated_at, jsp.deleted_at
+"#;
+
 /// List job seeking postings with optional filters
 pub async fn list_job_seeking_postings(
     pool: &PgPool,
     user_id: i64,
-    status: Option<PostingStatus>,
-    settlement_status: Option<SettlementStatus>,
-    preferred_location: Option<String>,
+    filter: JobSeekingFilter,
     limit: Option<i64>,
     offset: Option<i64>,
 ) -> Result<Vec<JobSeekingPosting>, sqlx::Error> {
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
 
-    // Build query based on filters
-    let job_seekings = match (status, settlement_status, preferred_location) {
-        (Some(st), Some(ss), Some(loc)) => {
-            let location_pattern = format!("%{}%", loc);
-            sqlx::query_as!(
-                JobSeekingPosting,
-                r#"
-                SELECT
-                    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
-                    jsp.preferred_location, jsp.employee_fee_rate,
-                    jsp.settlement_status as "settlement_status!: SettlementStatus",
-                    jsp.settlement_amount, jsp.settlement_memo,
-                    jsp.posting_status as "posting_status!: PostingStatus",
-                    jsp.is_favorite as "is_favorite!", jsp.created_at as "created_at!", jsp.updated_at as "updated_at!", jsp.deleted_at
-                FROM job_seeking_postings jsp
-                INNER JOIN customers c ON jsp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jsp.posting_status = $2
-                    AND jsp.settlement_status = $3
-                    AND jsp.preferred_location ILIKE $4
-                    AND jsp.deleted_at IS NULL
-                ORDER BY jsp.is_favorite DESC, jsp.created_at DESC
-                LIMIT $5 OFFSET $6
-                "#,
-                user_id,
-                st as PostingStatus,
-                ss as SettlementStatus,
-                location_pattern,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (Some(st), Some(ss), None) => {
-            sqlx::query_as!(
-                JobSeekingPosting,
-                r#"
-                SELECT
-                    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
-                    jsp.preferred_location, jsp.employee_fee_rate,
-                    jsp.settlement_status as "settlement_status!: SettlementStatus",
-                    jsp.settlement_amount, jsp.settlement_memo,
-                    jsp.posting_status as "posting_status!: PostingStatus",
-                    jsp.is_favorite as "is_favorite!", jsp.created_at as "created_at!", jsp.updated_at as "updated_at!", jsp.deleted_at
-                FROM job_seeking_postings jsp
-                INNER JOIN customers c ON jsp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jsp.posting_status = $2
-                    AND jsp.settlement_status = $3
-                    AND jsp.deleted_at IS NULL
-                ORDER BY jsp.is_favorite DESC, jsp.created_at DESC
-                LIMIT $4 OFFSET $5
-                "#,
-                user_id,
-                st as PostingStatus,
-                ss as SettlementStatus,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (Some(st), None, Some(loc)) => {
-            let location_pattern = format!("%{}%", loc);
-            sqlx::query_as!(
-                JobSeekingPosting,
-                r#"
-                SELECT
-                    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
-                    jsp.preferred_location, jsp.employee_fee_rate,
-                    jsp.settlement_status as "settlement_status!: SettlementStatus",
-                    jsp.settlement_amount, jsp.settlement_memo,
-                    jsp.posting_status as "posting_status!: PostingStatus",
-                    jsp.is_favorite as "is_favorite!", jsp.created_at as "created_at!", jsp.updated_at as "updated_at!", jsp.deleted_at
-                FROM job_seeking_postings jsp
-                INNER JOIN customers c ON jsp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jsp.posting_status = $2
-                    AND jsp.preferred_location ILIKE $3
-                    AND jsp.deleted_at IS NULL
-                ORDER BY jsp.is_favorite DESC, jsp.created_at DESC
-                LIMIT $4 OFFSET $5
-                "#,
-                user_id,
-                st as PostingStatus,
-                location_pattern,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (None, Some(ss), Some(loc)) => {
-            let location_pattern = format!("%{}%", loc);
-            sqlx::query_as!(
-                JobSeekingPosting,
-                r#"
-                SELECT
-                    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
-                    jsp.preferred_location, jsp.employee_fee_rate,
-                    jsp.settlement_status as "settlement_status!: SettlementStatus",
-                    jsp.settlement_amount, jsp.settlement_memo,
-                    jsp.posting_status as "posting_status!: PostingStatus",
-                    jsp.is_favorite as "is_favorite!", jsp.created_at as "created_at!", jsp.updated_at as "updated_at!", jsp.deleted_at
-                FROM job_seeking_postings jsp
-                INNER JOIN customers c ON jsp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jsp.settlement_status = $2
-                    AND jsp.preferred_location ILIKE $3
-                    AND jsp.deleted_at IS NULL
-                ORDER BY jsp.is_favorite DESC, jsp.created_at DESC
-                LIMIT $4 OFFSET $5
-                "#,
-                user_id,
-                ss as SettlementStatus,
-                location_pattern,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (Some(st), None, None) => {
-            sqlx::query_as!(
-                JobSeekingPosting,
-                r#"
-                SELECT
-                    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
-                    jsp.preferred_location, jsp.employee_fee_rate,
-                    jsp.settlement_status as "settlement_status!: SettlementStatus",
-                    jsp.settlement_amount, jsp.settlement_memo,
-                    jsp.posting_status as "posting_status!: PostingStatus",
-                    jsp.is_favorite as "is_favorite!", jsp.created_at as "created_at!", jsp.updated_at as "updated_at!", jsp.deleted_at
-                FROM job_seeking_postings jsp
-                INNER JOIN customers c ON jsp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jsp.posting_status = $2
-                    AND jsp.deleted_at IS NULL
-                ORDER BY jsp.is_favorite DESC, jsp.created_at DESC
-                LIMIT $3 OFFSET $4
-                "#,
-                user_id,
-                st as PostingStatus,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (None, Some(ss), None) => {
-            sqlx::query_as!(
-                JobSeekingPosting,
-                r#"
-                SELECT
-                    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
-                    jsp.preferred_location, jsp.employee_fee_rate,
-                    jsp.settlement_status as "settlement_status!: SettlementStatus",
-                    jsp.settlement_amount, jsp.settlement_memo,
-                    jsp.posting_status as "posting_status!: PostingStatus",
-                    jsp.is_favorite as "is_favorite!", jsp.created_at as "created_at!", jsp.updated_at as "updated_at!", jsp.deleted_at
-                FROM job_seeking_postings jsp
-                INNER JOIN customers c ON jsp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jsp.settlement_status = $2
-                    AND jsp.deleted_at IS NULL
-                ORDER BY jsp.is_favorite DESC, jsp.created_at DESC
-                LIMIT $3 OFFSET $4
-                "#,
-                user_id,
-                ss as SettlementStatus,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (None, None, Some(loc)) => {
-            let location_pattern = format!("%{}%", loc);
-            sqlx::query_as!(
-                JobSeekingPosting,
-                r#"
-                SELECT
-                    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
-                    jsp.preferred_location, jsp.employee_fee_rate,
-                    jsp.settlement_status as "settlement_status!: SettlementStatus",
-                    jsp.settlement_amount, jsp.settlement_memo,
-                    jsp.posting_status as "posting_status!: PostingStatus",
-                    jsp.is_favorite as "is_favorite!", jsp.created_at as "created_at!", jsp.updated_at as "updated_at!", jsp.deleted_at
-                FROM job_seeking_postings jsp
-                INNER JOIN customers c ON jsp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jsp.preferred_location ILIKE $2
-                    AND jsp.deleted_at IS NULL
-                ORDER BY jsp.is_favorite DESC, jsp.created_at DESC
-                LIMIT $3 OFFSET $4
-                "#,
-                user_id,
-                location_pattern,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-        (None, None, None) => {
-            sqlx::query_as!(
-                JobSeekingPosting,
-                r#"
-                SELECT
-                    jsp.id, jsp.customer_id, jsp.desired_salary, jsp.description,
-                    jsp.preferred_location, jsp.employee_fee_rate,
-                    jsp.settlement_status as "settlement_status!: SettlementStatus",
-                    jsp.settlement_amount, jsp.settlement_memo,
-                    jsp.posting_status as "posting_status!: PostingStatus",
-                    jsp.is_favorite as "is_favorite!", jsp.created_at as "created_at!", jsp.updated_at as "updated_at!", jsp.deleted_at
-                FROM job_seeking_postings jsp
-                INNER JOIN customers c ON jsp.customer_id = c.id
-                WHERE c.user_id = $1
-                    AND jsp.deleted_at IS NULL
-                ORDER BY jsp.is_favorite DESC, jsp.created_at DESC
-                LIMIT $2 OFFSET $3
-                "#,
-                user_id,
-                limit,
-                offset
-            )
-            .fetch_all(pool)
-            .await?
-        }
-    };
+    let mut query = QueryBuilder::<Postgres>::new(format!(
+        "SELECT {} FROM job_seeking_postings jsp INNER JOIN customers c ON jsp.customer_id = c.id WHERE c.user_id = ",
+        JOB_SEEKING_COLUMNS
+    ));
+    query.push_bind(user_id);
+    query.push(" AND jsp.deleted_at IS NULL");
 
-    Ok(job_seekings)
+    push_job_seeking_filter(&mut query, filter);
+
+    query.push(" ORDER BY jsp.is_favorite DESC, jsp.created_at DESC LIMIT ");
+    query.push_bind(limit);
+    query.push(" OFFSET ");
+    query.push_bind(offset);
+
+    query
+        .build_query_as::<JobSeekingPosting>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Append the list filters as `AND` conditions on `jsp`; the export uses
+/// the same conditions
+pub(crate) fn push_job_seeking_filter(
+    query: &mut QueryBuilder<'_, Postgres>,
+    filter: JobSeekingFilter,
+) {
+    if let Some(status) = filter.status {
+        query.push(" AND jsp.posting_status = ").push_bind(status);
+    }
+    if let Some(settlement_status) = filter.settlement_status {
+        query
+            .push(" AND jsp.settlement_status = ")
+            .push_bind(settlement_status);
+    }
+    if let Some(location) = filter.preferred_location {
+        query
+            .push(" AND jsp.preferred_location ILIKE ")
+            .push_bind(format!("%{}%", location));
+    }
+    if let Some(job_category) = filter.job_category {
+        query
+            .push(" AND jsp.job_category = ")
+            .push_bind(job_category);
+    }
+    if let Some(region_code) = filter.region_code {
+        push_region_filter(query, "jsp.region_codes", region_code);
+    }
+    if let Some(work_arrangement) = filter.work_arrangement {
+        query
+            .push(" AND jsp.work_arrangement = ")
+            .push_bind(work_arrangement);
+    }
+    if let Some(salary_basis) = filter.salary_basis {
+        query
+            .push(" AND jsp.salary_basis = ")
+            .push_bind(salary_basis);
+    }
+    if let Some(min) = filter.min_monthly_salary {
+        query.push(" AND jsp.monthly_salary >= ").push_bind(min);
+    }
+    if let Some(max) = filter.max_monthly_salary {
+        query.push(" AND jsp.monthly_salary <= ").push_bind(max);
+    }
+    if let Some(work_day) = filter.work_day {
+        query
+            .push(" AND ")
+            .push_bind(work_day)
+            .push(" = ANY(jsp.work_days)");
+    }
+    if let Some(from) = filter.start_date_from {
+        query.push(" AND jsp.start_date >= ").push_bind(from);
+    }
+    if let Some(to) = filter.start_date_to {
+        query.push(" AND jsp.start_date <= ").push_bind(to);
+    }
+    if let Some(accepts_pets) = filter.accepts_pets {
+        query
+            .push(" AND jsp.accepts_pets = ")
+            .push_bind(accepts_pets);
+    }
+    if let Some(members) = filter.household_members {
+        query
+            .push(" AND (jsp.max_household_members IS NULL OR jsp.max_household_members >= ")
+            .push_bind(members)
+            .push(")");
+    }
 }
 
 /// Update job seeking posting
@@ -315,9 +218,31 @@ pub async fn update_job_seeking_posting(
     pool: &PgPool,
     job_seeking_id: i64,
     req: UpdateJobSeekingPostingRequest,
-) -> Result<JobSeekingPosting, sqlx::Error> {
+    changed_by: i64,
+) -> Result<JobSeekingPosting, PostingStatusError> {
+    let mut tx = pool.begin().await?;
+
+    if let Some(status) = req.posting_status {
+        posting_status::transition(
+            &mut tx,
+            PostingKind::JobSeeking,
+            job_seeking_id,
+            status,
+            StatusChangeSource::Manual,
+            Some(changed_by),
+        )
+        .await?;
+    }
+
     // Get current posting for default values
     let current = get_job_seeking_posting_by_id(pool, job_seeking_id).await?;
+    let region_codes = req.region_codes.unwrap_or(current.region_codes);
+    let work_days = req.work_days.unwrap_or(current.work_days);
+    let fee_policy_id = if req.clear_fee_policy {
+        None
+    } else {
+        req.fee_policy_id.or(current.fee_policy_id)
+    };
 
     let job_seeking = sqlx::query_as!(
         JobSeekingPosting,
@@ -328,33 +253,60 @@ pub async fn update_job_seeking_posting(
             description = $2,
             preferred_location = $3,
             employee_fee_rate = $4,
-            settlement_status = $5,
-            settlement_amount = $6,
-            settlement_memo = $7,
-            posting_status = $8,
-            is_favorite = $9
-        WHERE id = $10 AND deleted_at IS NULL
+            is_favorite = $5,
+            job_category = $6,
+            region_codes = $7,
+            work_arrangement = $8,
+            work_days = $9,
+            work_start_time = $10,
+            work_end_time = $11,
+            start_date = $12,
+            salary_basis = $13,
+            expected_weekly_hours = $14,
+            max_household_members = $15,
+            accepts_pets = $16,
+            expires_at = $17,
+            fee_policy_id = $18
+        WHERE id = $19 AND deleted_at IS NULL
         RETURNING
             id, customer_id, desired_salary, description, preferred_location, employee_fee_rate,
             settlement_status as "settlement_status!: SettlementStatus",
             settlement_amount, settlement_memo,
             posting_status as "posting_status!: PostingStatus",
-            is_favorite as "is_favorite!", created_at as "created_at!", updated_at as "updated_at!", deleted_at
+            is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory", region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>", work_start_time, work_end_time, start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours, monthly_salary as "monthly_salary!",
+            max_household_members, accepts_pets, expires_at, fee_policy_id,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
         "#,
         req.desired_salary.unwrap_or(current.desired_salary),
         req.description.unwrap_or(current.description),
         req.preferred_location.unwrap_or(current.preferred_location),
         req.employee_fee_rate.or(current.employee_fee_rate),
-        req.settlement_status.unwrap_or(current.settlement_status) as SettlementStatus,
-        req.settlement_amount.or(current.settlement_amount),
-        req.settlement_memo.or(current.settlement_memo),
-        req.posting_status.unwrap_or(current.posting_status) as PostingStatus,
         req.is_favorite.unwrap_or(current.is_favorite),
+        req.job_category.or(current.job_category) as Option<JobCategory>,
+        &region_codes,
+        req.work_arrangement.or(current.work_arrangement) as Option<WorkArrangement>,
+        work_days.as_slice() as &[Weekday],
+        req.work_start_time.or(current.work_start_time),
+        req.work_end_time.or(current.work_end_time),
+        req.start_date.or(current.start_date),
+        req.salary_basis.unwrap_or(current.salary_basis) as SalaryBasis,
+        req.expected_weekly_hours.or(current.expected_weekly_hours),
+        req.max_household_members.or(current.max_household_members),
+        req.accepts_pets.or(current.accepts_pets),
+        req.expires_at.or(current.expires_at),
+        fee_policy_id,
         job_seeking_id
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
     Ok(job_seeking)
 }
 
@@ -377,6 +329,27 @@ pub async fn delete_job_seeking_posting(
     Ok(())
 }
 
+/// Move all job seeking postings of one customer to another (used when merging customers)
+pub async fn reassign_job_seeking_postings(
+    conn: &mut PgConnection,
+    from_customer_id: i64,
+    to_customer_id: i64,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE job_seeking_postings
+        SET customer_id = $2
+        WHERE customer_id = $1 AND deleted_at IS NULL
+        "#,
+        from_customer_id,
+        to_customer_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 /// List job seeking postings by customer
 pub async fn list_job_seeking_postings_by_customer(
     pool: &PgPool,
@@ -395,7 +368,14 @@ pub async fn list_job_seeking_postings_by_customer(
             settlement_status as "settlement_status!: SettlementStatus",
             settlement_amount, settlement_memo,
             posting_status as "posting_status!: PostingStatus",
-            is_favorite as "is_favorite!", created_at as "created_at!", updated_at as "updated_at!", deleted_at
+            is_favorite as "is_favorite!",
+            job_category as "job_category: JobCategory", region_codes as "region_codes!",
+            work_arrangement as "work_arrangement: WorkArrangement",
+            work_days as "work_days!: Vec<Weekday>", work_start_time, work_end_time, start_date,
+            salary_basis as "salary_basis!: SalaryBasis",
+            expected_weekly_hours, monthly_salary as "monthly_salary!",
+            max_household_members, accepts_pets, expires_at, fee_policy_id,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
         FROM job_seeking_postings
         WHERE customer_id = $1 AND deleted_at IS NULL
         ORDER BY created_at DESC
@@ -1,50 +1,393 @@
 // Allow unused code - these functions are used for future features
 #![allow(dead_code)]
 
+use crate::models::fee_policy::FeeBreakdown;
+use crate::models::job_posting::{PostingKind, PostingStatus, SalaryBasis, StatusChangeSource};
 use crate::models::matching::{
-    CreateMatchingRequest, Matching, MatchingStatus, UpdateMatchingRequest,
+    CreateMatchingRequest, FeeRateSource, GuaranteeResolution, Matching, MatchingPreview,
+    MatchingStatus, ReplaceMatchingRequest, ResolvedFee, UpdateMatchingRequest,
 };
-use chrono::Utc;
+use crate::models::payment::PayerSide;
+use crate::models::settlement::SettlementChangeSource;
+use crate::repositories::payment::{self, LedgerError};
+use crate::repositories::posting_status::{self, PostingStatusError};
+use crate::repositories::{fee_document, fee_policy, settlement};
+use crate::services::guarantee::{self, GuaranteeError};
+use crate::services::matching_lifecycle::{self, InvalidTransition};
+use crate::services::{fee, salary};
+use chrono::{NaiveDate, Utc};
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool};
+use thiserror::Error;
 
-/// Create a new matching with automatic fee calculation
+#[derive(Debug, Error)]
+pub enum MatchingError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Matching not found")]
+    NotFound,
+    #[error("Matching {0} is already in progress")]
+    AlreadyInProgress(i64),
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidTransition),
+    #[error(transparent)]
+    PostingStatus(#[from] PostingStatusError),
+    #[error(transparent)]
+    Guarantee(#[from] GuaranteeError),
+    #[error("A replacement matching charges the employer no fee")]
+    ReplacementFeeRate,
+    #[error("The {0:?} fee would fall below what has been paid")]
+    FeeBelowPaid(PayerSide),
+    #[error("Fee documents have been issued for the matching")]
+    FeeDocumentsIssued,
+}
+
+/// Create a new matching with automatic fee calculation.
+///
+/// Both postings move to 진행중 in the same transaction. Fails when the job
+/// posting or the job seeker already has a matching in progress.
 pub async fn create_matching(
     pool: &PgPool,
     req: CreateMatchingRequest,
-) -> Result<Matching, sqlx::Error> {
-    // Calculate fees
-    let employer_fee = (req.agreed_salary * req.employer_fee_rate) / Decimal::from(100);
-    let employee_fee = (req.agreed_salary * req.employee_fee_rate) / Decimal::from(100);
+    user_id: i64,
+) -> Result<Matching, MatchingError> {
+    let mut tx = pool.begin().await?;
+    let matching = insert_matching(&mut tx, req, user_id, None).await?;
+    tx.commit().await?;
+
+    Ok(matching)
+}
+
+/// Insert a matching, moving both postings to 진행중. A replacement of
+/// `replaces` charges the employer nothing.
+async fn insert_matching(
+    conn: &mut PgConnection,
+    req: CreateMatchingRequest,
+    user_id: i64,
+    replaces: Option<&Matching>,
+) -> Result<Matching, MatchingError> {
+    let salary_basis = req.salary_basis.unwrap_or_default();
+
+    // Locks both postings, so concurrent matchings on them are serialized
+    // before the duplicate check below
+    for (kind, posting_id) in [
+        (PostingKind::JobPosting, req.job_posting_id),
+        (PostingKind::JobSeeking, req.job_seeking_posting_id),
+    ] {
+        posting_status::transition(
+            &mut *conn,
+            kind,
+            posting_id,
+            PostingStatus::InProgress,
+            StatusChangeSource::Matching,
+            Some(user_id),
+        )
+        .await?;
+    }
+
+    if let Some(existing) =
+        find_in_progress_matching(&mut *conn, req.job_posting_id, req.job_seeking_posting_id)
+            .await?
+    {
+        return Err(MatchingError::AlreadyInProgress(existing));
+    }
+
+    let fee_base = salary::monthly_equivalent(
+        req.agreed_salary,
+        salary_basis,
+        req.expected_weekly_hours,
+        None,
+    );
+    let (mut employer_fee, employee_fee) = resolve_matching_fees(
+        &mut *conn,
+        req.job_posting_id,
+        req.job_seeking_posting_id,
+        fee_base,
+        [
+            (req.employer_fee_rate, FeeRateSource::Request),
+            (req.employee_fee_rate, FeeRateSource::Request),
+        ],
+        req.contract_days,
+    )
+    .await?;
+    if replaces.is_some() {
+        employer_fee = replacement_fee(fee_base, req.contract_days);
+    }
 
     let matching = sqlx::query_as!(
         Matching,
         r#"
         INSERT INTO matchings
-            (job_posting_id, job_seeking_posting_id, agreed_salary,
-             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+            (job_posting_id, job_seeking_posting_id, agreed_salary, salary_basis, expected_weekly_hours,
+             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+             contract_days, employer_fee_breakdown, employee_fee_breakdown,
+             employer_fee_rate_source, employee_fee_rate_source, guarantee_days, replaces_matching_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
         RETURNING
             id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
             matching_status as "matching_status!: MatchingStatus",
-            cancellation_reason, cancelled_at, cancelled_by, completed_at,
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
             created_at as "created_at!", updated_at as "updated_at!", deleted_at
         "#,
         req.job_posting_id,
         req.job_seeking_posting_id,
         req.agreed_salary,
-        req.employer_fee_rate,
-        req.employee_fee_rate,
-        employer_fee,
-        employee_fee
+        salary_basis as SalaryBasis,
+        req.expected_weekly_hours,
+        employer_fee.rate,
+        employee_fee.rate,
+        employer_fee.breakdown.total_amount,
+        employee_fee.breakdown.total_amount,
+        req.contract_days,
+        Json(&employer_fee.breakdown) as _,
+        Json(&employee_fee.breakdown) as _,
+        employer_fee.rate_source as FeeRateSource,
+        employee_fee.rate_source as FeeRateSource,
+        req.guarantee_days,
+        replaces.map(|original| original.id)
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *conn)
     .await?;
 
+    sync_settlements(conn, &matching).await?;
+
     Ok(matching)
 }
 
+/// Resolve the fee rate of each side and calculate the fees on the monthly
+/// equivalent salary `fee_base`, under the fee policies that apply to the two
+/// postings.
+///
+/// A side's rate is the `given` one when set, otherwise the posting's rate,
+/// otherwise the broker's default. A policy tier for the salary replaces the
+/// posting or default rate, but not a `given` one.
+async fn resolve_matching_fees(
+    conn: &mut PgConnection,
+    job_posting_id: i64,
+    job_seeking_posting_id: i64,
+    fee_base: Decimal,
+    given: [(Option<Decimal>, FeeRateSource); 2],
+    contract_days: Option<i32>,
+) -> Result<(ResolvedFee, ResolvedFee), sqlx::Error> {
+    let defaults = sqlx::query!(
+        r#"
+        SELECT
+            jp.employer_fee_rate AS employer_posting_rate,
+            jsp.employee_fee_rate AS employee_posting_rate,
+            u.default_employer_fee_rate,
+            u.default_employee_fee_rate
+        FROM job_postings jp
+        INNER JOIN customers c ON jp.customer_id = c.id
+        INNER JOIN users u ON c.user_id = u.id
+        INNER JOIN job_seeking_postings jsp ON jsp.id = $2
+        WHERE jp.id = $1
+        "#,
+        job_posting_id,
+        job_seeking_posting_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let [employer_given, employee_given] = given;
+    let employer_rate = fee::resolve_rate(&[
+        employer_given,
+        (defaults.employer_posting_rate, FeeRateSource::Posting),
+        (
+            defaults.default_employer_fee_rate,
+            FeeRateSource::UserDefault,
+        ),
+    ]);
+    let employee_rate = fee::resolve_rate(&[
+        employee_given,
+        (defaults.employee_posting_rate, FeeRateSource::Posting),
+        (
+            defaults.default_employee_fee_rate,
+            FeeRateSource::UserDefault,
+        ),
+    ]);
+
+    let (employer_policy, employee_policy) =
+        fee_policy::find_matching_policies(conn, job_posting_id, job_seeking_posting_id).await?;
+
+    Ok((
+        fee::calculate_resolved(
+            employer_policy.as_ref(),
+            fee_base,
+            employer_rate,
+            contract_days,
+        ),
+        fee::calculate_resolved(
+            employee_policy.as_ref(),
+            fee_base,
+            employee_rate,
+            contract_days,
+        ),
+    ))
+}
+
+/// The employer side of a free replacement: no fee, and no fee policy so
+/// neither a minimum nor a short-term fee applies
+fn replacement_fee(fee_base: Decimal, contract_days: Option<i32>) -> ResolvedFee {
+    ResolvedFee {
+        rate: Decimal::ZERO,
+        rate_source: FeeRateSource::Replacement,
+        breakdown: fee::calculate(None, fee_base, Decimal::ZERO, contract_days),
+    }
+}
+
+/// Rates and fees `create_matching` would use for the request, without
+/// saving anything or checking for matchings in progress
+pub async fn preview_matching(
+    pool: &PgPool,
+    req: &CreateMatchingRequest,
+) -> Result<MatchingPreview, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+
+    let monthly_salary = salary::monthly_equivalent(
+        req.agreed_salary,
+        req.salary_basis.unwrap_or_default(),
+        req.expected_weekly_hours,
+        None,
+    );
+    let (employer, employee) = resolve_matching_fees(
+        &mut conn,
+        req.job_posting_id,
+        req.job_seeking_posting_id,
+        monthly_salary,
+        [
+            (req.employer_fee_rate, FeeRateSource::Request),
+            (req.employee_fee_rate, FeeRateSource::Request),
+        ],
+        req.contract_days,
+    )
+    .await?;
+
+    Ok(MatchingPreview {
+        monthly_salary,
+        employer,
+        employee,
+    })
+}
+
+/// An in-progress matching on the job posting or on any posting of the
+/// job seeker behind `job_seeking_posting_id`.
+///
+/// Locks the job seeker's customer row so two matchings on different
+/// postings of the same seeker cannot be created concurrently.
+async fn find_in_progress_matching(
+    conn: &mut PgConnection,
+    job_posting_id: i64,
+    job_seeking_posting_id: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    let seeker_id = sqlx::query_scalar!(
+        r#"
+        SELECT c.id
+        FROM customers c
+        INNER JOIN job_seeking_postings jsp ON jsp.customer_id = c.id
+        WHERE jsp.id = $1
+        FOR UPDATE OF c
+        "#,
+        job_seeking_posting_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query_scalar!(
+        r#"
+        SELECT m.id
+        FROM matchings m
+        INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+        WHERE m.matching_status = 'in_progress'
+            AND m.deleted_at IS NULL
+            AND (m.job_posting_id = $1 OR jsp.customer_id = $2)
+        ORDER BY m.id
+        LIMIT 1
+        "#,
+        job_posting_id,
+        seeker_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+}
+
+/// Lock a matching row for the rest of the transaction
+async fn lock_matching(
+    conn: &mut PgConnection,
+    matching_id: i64,
+) -> Result<Matching, MatchingError> {
+    sqlx::query_as!(
+        Matching,
+        r#"
+        SELECT
+            id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
+            employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
+            matching_status as "matching_status!: MatchingStatus",
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
+        FROM matchings
+        WHERE id = $1 AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
+        matching_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(MatchingError::NotFound)
+}
+
+/// Bring the linked postings in line with a matching that just left 진행중:
+/// 완료 closes them and 취소 puts them back to 게시중. Postings the user has
+/// moved out of 진행중 by hand are left alone.
+async fn sync_postings(
+    conn: &mut PgConnection,
+    matching: &Matching,
+    user_id: i64,
+) -> Result<(), PostingStatusError> {
+    let to = match matching.matching_status {
+        MatchingStatus::Completed => PostingStatus::Closed,
+        MatchingStatus::Cancelled => PostingStatus::Published,
+        MatchingStatus::InProgress => return Ok(()),
+    };
+
+    for (kind, posting_id) in [
+        (PostingKind::JobPosting, matching.job_posting_id),
+        (PostingKind::JobSeeking, matching.job_seeking_posting_id),
+    ] {
+        posting_status::transition_if(
+            conn,
+            kind,
+            posting_id,
+            PostingStatus::InProgress,
+            to.clone(),
+            StatusChangeSource::Matching,
+            Some(user_id),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 /// Get matching by ID
 pub async fn get_matching_by_id(pool: &PgPool, matching_id: i64) -> Result<Matching, sqlx::Error> {
     let matching = sqlx::query_as!(
@@ -52,9 +395,18 @@ pub async fn get_matching_by_id(pool: &PgPool, matching_id: i64) -> Result<Match
         r#"
         SELECT
             id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
             matching_status as "matching_status!: MatchingStatus",
-            cancellation_reason, cancelled_at, cancelled_by, completed_at,
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
             created_at as "created_at!", updated_at as "updated_at!", deleted_at
         FROM matchings
         WHERE id = $1 AND deleted_at IS NULL
@@ -85,9 +437,18 @@ pub async fn list_matchings(
                 r#"
                 SELECT
                     m.id, m.job_posting_id, m.job_seeking_posting_id, m.matched_at as "matched_at!", m.agreed_salary,
+            m.salary_basis as "salary_basis!: SalaryBasis", m.expected_weekly_hours,
+            m.monthly_salary as "monthly_salary!",
                     m.employer_fee_rate, m.employee_fee_rate, m.employer_fee_amount, m.employee_fee_amount,
+                    m.employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+                    m.employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
                     m.matching_status as "matching_status!: MatchingStatus",
-                    m.cancellation_reason, m.cancelled_at, m.cancelled_by, m.completed_at,
+                    m.cancellation_reason, m.cancelled_at, m.cancelled_by, m.completed_at, m.contract_days,
+                    m.employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+                    m.employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+                    m.guarantee_days, m.guarantee_expires_at, m.replaces_matching_id,
+                    m.guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+                    m.guarantee_broken_on, m.guarantee_refund_amount,
                     m.created_at as "created_at!", m.updated_at as "updated_at!", m.deleted_at
                 FROM matchings m
                 INNER JOIN job_postings jp ON m.job_posting_id = jp.id
@@ -112,9 +473,18 @@ pub async fn list_matchings(
                 r#"
                 SELECT
                     m.id, m.job_posting_id, m.job_seeking_posting_id, m.matched_at as "matched_at!", m.agreed_salary,
+            m.salary_basis as "salary_basis!: SalaryBasis", m.expected_weekly_hours,
+            m.monthly_salary as "monthly_salary!",
                     m.employer_fee_rate, m.employee_fee_rate, m.employer_fee_amount, m.employee_fee_amount,
+                    m.employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+                    m.employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
                     m.matching_status as "matching_status!: MatchingStatus",
-                    m.cancellation_reason, m.cancelled_at, m.cancelled_by, m.completed_at,
+                    m.cancellation_reason, m.cancelled_at, m.cancelled_by, m.completed_at, m.contract_days,
+                    m.employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+                    m.employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+                    m.guarantee_days, m.guarantee_expires_at, m.replaces_matching_id,
+                    m.guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+                    m.guarantee_broken_on, m.guarantee_refund_amount,
                     m.created_at as "created_at!", m.updated_at as "updated_at!", m.deleted_at
                 FROM matchings m
                 INNER JOIN job_postings jp ON m.job_posting_id = jp.id
@@ -141,22 +511,104 @@ pub async fn update_matching_status(
     pool: &PgPool,
     matching_id: i64,
     req: UpdateMatchingRequest,
-) -> Result<Matching, sqlx::Error> {
+    user_id: i64,
+) -> Result<Matching, MatchingError> {
+    let mut tx = pool.begin().await?;
+
     // Get current matching for default values
-    let current = get_matching_by_id(pool, matching_id).await?;
+    let current = lock_matching(&mut tx, matching_id).await?;
 
     // Determine final values
     let final_salary = req.agreed_salary.unwrap_or(current.agreed_salary);
-    let final_employer_rate = req.employer_fee_rate.unwrap_or(current.employer_fee_rate);
-    let final_employee_rate = req.employee_fee_rate.unwrap_or(current.employee_fee_rate);
+    let final_basis = req.salary_basis.unwrap_or(current.salary_basis);
+    let final_weekly_hours = req.expected_weekly_hours.or(current.expected_weekly_hours);
+    let final_contract_days = req.contract_days.or(current.contract_days);
 
-    // Recalculate fees if any of the values changed
-    let employer_fee = (final_salary * final_employer_rate) / Decimal::from(100);
-    let employee_fee = (final_salary * final_employee_rate) / Decimal::from(100);
+    // Recalculate fees on the monthly equivalent if any of the values changed.
+    // Otherwise keep the stored fees so later policy edits do not touch them.
+    let fee_inputs_changed = req.agreed_salary.is_some()
+        || req.salary_basis.is_some()
+        || req.expected_weekly_hours.is_some()
+        || req.employer_fee_rate.is_some()
+        || req.employee_fee_rate.is_some()
+        || req.contract_days.is_some();
+    let replacement = current.employer_fee_rate_source == FeeRateSource::Replacement;
+    if replacement && req.employer_fee_rate.is_some() {
+        return Err(MatchingError::ReplacementFeeRate);
+    }
+    let (employer_fee, employee_fee) = if fee_inputs_changed {
+        let fee_base =
+            salary::monthly_equivalent(final_salary, final_basis, final_weekly_hours, None);
+        let (mut employer, employee) = resolve_matching_fees(
+            &mut tx,
+            current.job_posting_id,
+            current.job_seeking_posting_id,
+            fee_base,
+            [
+                kept_rate(
+                    req.employer_fee_rate,
+                    current.employer_fee_rate,
+                    current.employer_fee_rate_source,
+                ),
+                kept_rate(
+                    req.employee_fee_rate,
+                    current.employee_fee_rate,
+                    current.employee_fee_rate_source,
+                ),
+            ],
+            final_contract_days,
+        )
+        .await?;
+        if replacement {
+            employer = replacement_fee(fee_base, final_contract_days);
+        }
+        (
+            (
+                employer.rate,
+                employer.rate_source,
+                Some(employer.breakdown.total_amount),
+                Some(Json(employer.breakdown)),
+            ),
+            (
+                employee.rate,
+                employee.rate_source,
+                Some(employee.breakdown.total_amount),
+                Some(Json(employee.breakdown)),
+            ),
+        )
+    } else {
+        (
+            (
+                current.employer_fee_rate,
+                current.employer_fee_rate_source,
+                current.employer_fee_amount,
+                current.employer_fee_breakdown,
+            ),
+            (
+                current.employee_fee_rate,
+                current.employee_fee_rate_source,
+                current.employee_fee_amount,
+                current.employee_fee_breakdown,
+            ),
+        )
+    };
+    let (employer_rate, employer_rate_source, employer_amount, employer_breakdown) = employer_fee;
+    let (employee_rate, employee_rate_source, employee_amount, employee_breakdown) = employee_fee;
+    if fee_inputs_changed {
+        ensure_fees_cover_ledger(
+            &mut tx,
+            matching_id,
+            user_id,
+            [employer_amount, employee_amount],
+        )
+        .await?;
+    }
 
     let next_status = req
         .matching_status
         .unwrap_or(current.matching_status.clone());
+    matching_lifecycle::ensure_transition(&current.matching_status, &next_status)?;
+    let status_changed = next_status != current.matching_status;
 
     let new_completed_at = if next_status == MatchingStatus::Completed {
         current
@@ -166,45 +618,157 @@ pub async fn update_matching_status(
         current.completed_at
     };
 
-    let matching = sqlx::query_as::<_, Matching>(
+    let matching = sqlx::query_as!(
+        Matching,
         r#"
         UPDATE matchings
         SET
             agreed_salary = $1,
-            employer_fee_rate = $2,
-            employee_fee_rate = $3,
-            employer_fee_amount = $4,
-            employee_fee_amount = $5,
-            matching_status = $6,
-            cancellation_reason = $7,
-            completed_at = $8,
+            salary_basis = $2,
+            expected_weekly_hours = $3,
+            employer_fee_rate = $4,
+            employee_fee_rate = $5,
+            employer_fee_amount = $6,
+            employee_fee_amount = $7,
+            matching_status = $8,
+            cancellation_reason = $9,
+            completed_at = $10,
+            contract_days = $11,
+            employer_fee_breakdown = $12,
+            employee_fee_breakdown = $13,
+            employer_fee_rate_source = $14,
+            employee_fee_rate_source = $15,
+            guarantee_days = $16,
             updated_at = CURRENT_TIMESTAMP
-        WHERE id = $9 AND deleted_at IS NULL
+        WHERE id = $17 AND deleted_at IS NULL
         RETURNING
             id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
             matching_status as "matching_status!: MatchingStatus",
-            cancellation_reason, cancelled_at, cancelled_by, completed_at,
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
             created_at as "created_at!", updated_at as "updated_at!", deleted_at
         "#,
+        final_salary,
+        final_basis as SalaryBasis,
+        final_weekly_hours,
+        employer_rate,
+        employee_rate,
+        employer_amount,
+        employee_amount,
+        next_status as MatchingStatus,
+        req.cancellation_reason.or(current.cancellation_reason),
+        new_completed_at,
+        final_contract_days,
+        employer_breakdown as _,
+        employee_breakdown as _,
+        employer_rate_source as FeeRateSource,
+        employee_rate_source as FeeRateSource,
+        req.guarantee_days.or(current.guarantee_days),
+        matching_id
     )
-    .bind(final_salary)
-    .bind(final_employer_rate)
-    .bind(final_employee_rate)
-    .bind(employer_fee)
-    .bind(employee_fee)
-    .bind(next_status)
-    .bind(req.cancellation_reason.or(current.cancellation_reason))
-    .bind(new_completed_at)
-    .bind(matching_id)
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    if status_changed {
+        sync_postings(&mut tx, &matching, user_id).await?;
+    }
+    if status_changed || fee_inputs_changed {
+        sync_settlements(&mut tx, &matching).await?;
+    }
+
+    tx.commit().await?;
+
     Ok(matching)
 }
 
-/// Complete a matching
-pub async fn complete_matching(pool: &PgPool, matching_id: i64) -> Result<Matching, sqlx::Error> {
+/// Settlement status of both postings, after the matching's fees changed
+async fn sync_settlements(conn: &mut PgConnection, matching: &Matching) -> Result<(), sqlx::Error> {
+    settlement::sync_posting_settlement(
+        conn,
+        PostingKind::JobPosting,
+        matching.job_posting_id,
+        SettlementChangeSource::Matching,
+    )
+    .await?;
+    settlement::sync_posting_settlement(
+        conn,
+        PostingKind::JobSeeking,
+        matching.job_seeking_posting_id,
+        SettlementChangeSource::Matching,
+    )
+    .await
+}
+
+/// Recalculated fees must still cover what each side has paid, and must not
+/// change once an invoice or receipt has been issued for them
+async fn ensure_fees_cover_ledger(
+    conn: &mut PgConnection,
+    matching_id: i64,
+    user_id: i64,
+    [employer_amount, employee_amount]: [Option<Decimal>; 2],
+) -> Result<(), MatchingError> {
+    if fee_document::has_fee_documents(&mut *conn, matching_id).await? {
+        return Err(MatchingError::FeeDocumentsIssued);
+    }
+
+    let (_, balance) = payment::lock_payments(conn, matching_id, user_id)
+        .await
+        .map_err(|e| match e {
+            LedgerError::DatabaseError(e) => MatchingError::DatabaseError(e),
+            _ => MatchingError::NotFound,
+        })?;
+    for (amount, side) in [
+        (employer_amount, &balance.employer),
+        (employee_amount, &balance.employee),
+    ] {
+        let amount = amount.unwrap_or(Decimal::ZERO);
+        if amount - side.refunded_amount < side.paid_amount {
+            return Err(MatchingError::FeeBelowPaid(side.payer_side));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rate to resolve an updated matching's fee with: the requested rate, else
+/// the stored one. A stored policy tier rate is resolved again, as the salary
+/// may now fall into another tier.
+fn kept_rate(
+    requested: Option<Decimal>,
+    current: Decimal,
+    current_source: FeeRateSource,
+) -> (Option<Decimal>, FeeRateSource) {
+    match (requested, current_source) {
+        (Some(rate), _) => (Some(rate), FeeRateSource::Request),
+        (None, FeeRateSource::Policy) => (None, FeeRateSource::Request),
+        (None, source) => (Some(current), source),
+    }
+}
+
+/// Complete a matching, closing both postings and adding the fees to their
+/// settlement amounts
+pub async fn complete_matching(
+    pool: &PgPool,
+    matching_id: i64,
+    user_id: i64,
+) -> Result<Matching, MatchingError> {
+    let mut tx = pool.begin().await?;
+
+    let current = lock_matching(&mut tx, matching_id).await?;
+    if current.matching_status == MatchingStatus::Completed {
+        return Ok(current);
+    }
+    matching_lifecycle::ensure_transition(&current.matching_status, &MatchingStatus::Completed)?;
+
     let matching = sqlx::query_as!(
         Matching,
         r#"
@@ -215,26 +779,47 @@ pub async fn complete_matching(pool: &PgPool, matching_id: i64) -> Result<Matchi
         WHERE id = $1 AND deleted_at IS NULL
         RETURNING
             id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
             matching_status as "matching_status!: MatchingStatus",
-            cancellation_reason, cancelled_at, cancelled_by, completed_at,
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
             created_at as "created_at!", updated_at as "updated_at!", deleted_at
         "#,
         matching_id
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    sync_postings(&mut tx, &matching, user_id).await?;
+
+    tx.commit().await?;
+
     Ok(matching)
 }
 
-/// Cancel a matching
+/// Cancel a matching and put its postings back to 게시중
 pub async fn cancel_matching(
     pool: &PgPool,
     matching_id: i64,
     cancelled_by: i64,
     reason: Option<String>,
-) -> Result<Matching, sqlx::Error> {
+) -> Result<Matching, MatchingError> {
+    let mut tx = pool.begin().await?;
+
+    let current = lock_matching(&mut tx, matching_id).await?;
+    if current.matching_status == MatchingStatus::Cancelled {
+        return Ok(current);
+    }
+    matching_lifecycle::ensure_transition(&current.matching_status, &MatchingStatus::Cancelled)?;
+
     let matching = sqlx::query_as!(
         Matching,
         r#"
@@ -247,21 +832,225 @@ pub async fn cancel_matching(
         WHERE id = $1 AND deleted_at IS NULL
         RETURNING
             id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
             matching_status as "matching_status!: MatchingStatus",
-            cancellation_reason, cancelled_at, cancelled_by, completed_at,
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
             created_at as "created_at!", updated_at as "updated_at!", deleted_at
         "#,
         matching_id,
         cancelled_by,
         reason
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sync_postings(&mut tx, &matching, cancelled_by).await?;
+    sync_settlements(&mut tx, &matching).await?;
+
+    tx.commit().await?;
+
+    Ok(matching)
+}
+
+/// Replace the helper of a completed matching whose guarantee is still open.
+///
+/// The job posting is reopened and matched with the new job seeker in the
+/// same transaction. The guarantee is the employer's, so only the employer
+/// is not charged again; the new job seeker pays the usual fee.
+pub async fn replace_matching(
+    pool: &PgPool,
+    matching_id: i64,
+    req: ReplaceMatchingRequest,
+    user_id: i64,
+) -> Result<Matching, MatchingError> {
+    let mut tx = pool.begin().await?;
+
+    let original = lock_matching(&mut tx, matching_id).await?;
+    guarantee::ensure_open(&original, req.left_on)?;
+    if let Some(existing) = find_active_replacement(&mut tx, original.id).await? {
+        return Err(GuaranteeError::AlreadyReplaced(existing).into());
+    }
+
+    // Completing the original closed the job posting
+    posting_status::transition_if(
+        &mut tx,
+        PostingKind::JobPosting,
+        original.job_posting_id,
+        PostingStatus::Closed,
+        PostingStatus::Published,
+        StatusChangeSource::Matching,
+        Some(user_id),
+    )
+    .await?;
+
+    let replacement = insert_matching(
+        &mut tx,
+        CreateMatchingRequest {
+            job_posting_id: original.job_posting_id,
+            job_seeking_posting_id: req.job_seeking_posting_id,
+            agreed_salary: req.agreed_salary.unwrap_or(original.agreed_salary),
+            salary_basis: Some(req.salary_basis.unwrap_or(original.salary_basis)),
+            expected_weekly_hours: req.expected_weekly_hours.or(original.expected_weekly_hours),
+            employer_fee_rate: None,
+            employee_fee_rate: req.employee_fee_rate,
+            contract_days: req.contract_days,
+            guarantee_days: req.guarantee_days.or(original.guarantee_days),
+        },
+        user_id,
+        Some(&original),
+    )
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE matchings
+        SET guarantee_resolution = 'replacement', guarantee_broken_on = $2,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#,
+        original.id,
+        req.left_on
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(replacement)
+}
+
+/// Refund the employer for the guarantee days the helper did not work. The
+/// refund comes off the job posting's settlement amount.
+pub async fn refund_matching(
+    pool: &PgPool,
+    matching_id: i64,
+    left_on: NaiveDate,
+) -> Result<Matching, MatchingError> {
+    let mut tx = pool.begin().await?;
+
+    let current = lock_matching(&mut tx, matching_id).await?;
+    let refund = guarantee::calculate_refund(&current, left_on)?;
+    if let Some(existing) = find_active_replacement(&mut tx, current.id).await? {
+        return Err(GuaranteeError::AlreadyReplaced(existing).into());
+    }
+
+    let matching = sqlx::query_as!(
+        Matching,
+        r#"
+        UPDATE matchings
+        SET
+            guarantee_resolution = 'refund',
+            guarantee_broken_on = $2,
+            guarantee_refund_amount = $3,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING
+            id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
+            employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
+            matching_status as "matching_status!: MatchingStatus",
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
+            created_at as "created_at!", updated_at as "updated_at!", deleted_at
+        "#,
+        matching_id,
+        left_on,
+        refund.refund_amount
+    )
+    .fetch_one(&mut *tx)
     .await?;
 
+    settlement::sync_posting_settlement(
+        &mut tx,
+        PostingKind::JobPosting,
+        matching.job_posting_id,
+        SettlementChangeSource::Matching,
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(matching)
 }
 
+/// A replacement of the matching that is not cancelled
+async fn find_active_replacement(
+    conn: &mut PgConnection,
+    matching_id: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT id
+        FROM matchings
+        WHERE replaces_matching_id = $1
+            AND matching_status <> 'cancelled'
+            AND deleted_at IS NULL
+        "#,
+        matching_id
+    )
+    .fetch_optional(conn)
+    .await
+}
+
+/// Completed matchings of the user whose guarantee is open and ends within
+/// `within_days` days, soonest first
+pub async fn list_expiring_guarantees(
+    pool: &PgPool,
+    user_id: i64,
+    within_days: i32,
+) -> Result<Vec<Matching>, sqlx::Error> {
+    sqlx::query_as!(
+        Matching,
+        r#"
+        SELECT
+            m.id, m.job_posting_id, m.job_seeking_posting_id, m.matched_at as "matched_at!", m.agreed_salary,
+            m.salary_basis as "salary_basis!: SalaryBasis", m.expected_weekly_hours,
+            m.monthly_salary as "monthly_salary!",
+            m.employer_fee_rate, m.employee_fee_rate, m.employer_fee_amount, m.employee_fee_amount,
+            m.employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            m.employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
+            m.matching_status as "matching_status!: MatchingStatus",
+            m.cancellation_reason, m.cancelled_at, m.cancelled_by, m.completed_at, m.contract_days,
+            m.employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            m.employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            m.guarantee_days, m.guarantee_expires_at, m.replaces_matching_id,
+            m.guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            m.guarantee_broken_on, m.guarantee_refund_amount,
+            m.created_at as "created_at!", m.updated_at as "updated_at!", m.deleted_at
+        FROM matchings m
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN customers c ON jp.customer_id = c.id
+        WHERE c.user_id = $1
+            AND m.matching_status = 'completed'
+            AND m.guarantee_resolution IS NULL
+            AND m.deleted_at IS NULL
+            AND m.guarantee_expires_at > LOCALTIMESTAMP
+            AND m.guarantee_expires_at <= LOCALTIMESTAMP + make_interval(days => $2)
+        ORDER BY m.guarantee_expires_at
+        "#,
+        user_id,
+        within_days
+    )
+    .fetch_all(pool)
+    .await
+}
+
 /// Calculate total fees for a matching
 pub async fn calculate_fees(
     pool: &PgPool,
@@ -291,9 +1080,18 @@ pub async fn list_matchings_by_job_posting(
         r#"
         SELECT
             id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
             matching_status as "matching_status!: MatchingStatus",
-            cancellation_reason, cancelled_at, cancelled_by, completed_at,
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
             created_at as "created_at!", updated_at as "updated_at!", deleted_at
         FROM matchings
         WHERE job_posting_id = $1 AND deleted_at IS NULL
@@ -325,9 +1123,18 @@ pub async fn list_matchings_by_job_seeking(
         r#"
         SELECT
             id, job_posting_id, job_seeking_posting_id, matched_at as "matched_at!", agreed_salary,
+            salary_basis as "salary_basis!: SalaryBasis", expected_weekly_hours,
+            monthly_salary as "monthly_salary!",
             employer_fee_rate, employee_fee_rate, employer_fee_amount, employee_fee_amount,
+            employer_fee_rate_source as "employer_fee_rate_source!: FeeRateSource",
+            employee_fee_rate_source as "employee_fee_rate_source!: FeeRateSource",
             matching_status as "matching_status!: MatchingStatus",
-            cancellation_reason, cancelled_at, cancelled_by, completed_at,
+            cancellation_reason, cancelled_at, cancelled_by, completed_at, contract_days,
+            employer_fee_breakdown as "employer_fee_breakdown: Json<FeeBreakdown>",
+            employee_fee_breakdown as "employee_fee_breakdown: Json<FeeBreakdown>",
+            guarantee_days, guarantee_expires_at, replaces_matching_id,
+            guarantee_resolution as "guarantee_resolution: GuaranteeResolution",
+            guarantee_broken_on, guarantee_refund_amount,
             created_at as "created_at!", updated_at as "updated_at!", deleted_at
         FROM matchings
         WHERE job_seeking_posting_id = $1 AND deleted_at IS NULL
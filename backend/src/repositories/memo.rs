@@ -5,7 +5,7 @@ use crate::models::memo::{
     CreateCustomerMemoRequest, CreateMatchingMemoRequest, CreateUserMemoRequest, CustomerMemo,
     MatchingMemo, UpdateMemoRequest, UserMemo,
 };
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 // ========================================
 // Customer Memos
@@ -123,6 +123,27 @@ pub async fn delete_customer_memo(pool: &PgPool, memo_id: i64) -> Result<(), sql
     Ok(())
 }
 
+/// Move all customer memos of one customer to another (used when merging customers)
+pub async fn reassign_customer_memos(
+    conn: &mut PgConnection,
+    from_customer_id: i64,
+    to_customer_id: i64,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE customer_memos
+        SET customer_id = $2
+        WHERE customer_id = $1 AND deleted_at IS NULL
+        "#,
+        from_customer_id,
+        to_customer_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 // ========================================
 // User Memos
 // ========================================
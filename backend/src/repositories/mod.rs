@@ -1,8 +1,23 @@
+pub mod analytics;
+pub mod bank_import;
 pub mod customer;
+pub mod export;
+pub mod fee_document;
+pub mod fee_policy;
 pub mod file;
 pub mod job_posting;
 pub mod job_seeking;
 pub mod matching;
 pub mod memo;
+pub mod monthly_report;
+pub mod payment;
+pub mod posting_status;
+pub mod report;
+pub mod search;
+pub mod settlement;
+pub mod statement;
+pub mod suggestion;
 pub mod tag;
+pub mod timeline;
+pub mod trash;
 pub mod user;
@@ -0,0 +1,188 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use crate::models::monthly_report::{MonthlyFeeRow, NewMonthlyReport, StoredMonthlyReport};
+use crate::models::payment::PayerSide;
+
+/// Employer and employee fees of the user's matchings completed from `from`
+/// to `to` inclusive
+pub async fn list_monthly_fee_rows(
+    pool: &PgPool,
+    user_id: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<MonthlyFeeRow>, sqlx::Error> {
+    sqlx::query_as!(
+        MonthlyFeeRow,
+        r#"
+        WITH completed AS (
+            SELECT m.*, jp.customer_id AS employer_id, jsp.customer_id AS employee_id
+            FROM matchings m
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+            INNER JOIN customers owner ON jp.customer_id = owner.id
+            WHERE owner.user_id = $1
+                AND m.matching_status = 'completed'
+                AND m.completed_at >= $2::date AND m.completed_at < $3::date + 1
+                AND m.deleted_at IS NULL
+        ),
+        fees AS (
+            SELECT
+                id AS matching_id, 'employer' AS payer_side, employer_id AS customer_id,
+                COALESCE(employer_fee_amount, 0) AS fee_amount,
+                COALESCE(guarantee_refund_amount, 0) AS refunded_amount
+            FROM completed
+            UNION ALL
+            SELECT id, 'employee', employee_id, COALESCE(employee_fee_amount, 0), 0
+            FROM completed
+        )
+        SELECT
+            f.matching_id AS "matching_id!",
+            f.payer_side AS "payer_side!: PayerSide",
+            f.customer_id AS "customer_id!",
+            c.name AS "customer_name!",
+            f.fee_amount AS "fee_amount!",
+            f.refunded_amount AS "refunded_amount!"
+        FROM fees f
+        INNER JOIN customers c ON f.customer_id = c.id
+        ORDER BY f.matching_id, f.payer_side
+        "#,
+        user_id,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Number of the user's matchings cancelled from `from` to `to` inclusive per
+/// cancellation reason, most frequent first. Blank reasons are `None`.
+pub async fn list_cancellation_reasons(
+    pool: &PgPool,
+    user_id: i64,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<(Option<String>, i64)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            NULLIF(TRIM(m.cancellation_reason), '') AS reason,
+            COUNT(*) AS "count!"
+        FROM matchings m
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN customers owner ON jp.customer_id = owner.id
+        WHERE owner.user_id = $1
+            AND m.matching_status = 'cancelled'
+            AND m.cancelled_at >= $2::date AND m.cancelled_at < $3::date + 1
+            AND m.deleted_at IS NULL
+        GROUP BY 1
+        ORDER BY 2 DESC, 1
+        "#,
+        user_id,
+        from,
+        to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.reason, row.count))
+        .collect())
+}
+
+/// Record a generated report, replacing the one stored for the same month
+pub async fn upsert_monthly_report(
+    pool: &PgPool,
+    report: &NewMonthlyReport,
+) -> Result<StoredMonthlyReport, sqlx::Error> {
+    sqlx::query_as!(
+        StoredMonthlyReport,
+        r#"
+        INSERT INTO monthly_reports (user_id, year, month, pdf_path, pdf_size, xlsx_path, xlsx_size)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (user_id, year, month) DO UPDATE SET
+            pdf_path = EXCLUDED.pdf_path,
+            pdf_size = EXCLUDED.pdf_size,
+            xlsx_path = EXCLUDED.xlsx_path,
+            xlsx_size = EXCLUDED.xlsx_size,
+            generated_at = CURRENT_TIMESTAMP
+        RETURNING id, user_id, year, month, pdf_path, pdf_size, xlsx_path, xlsx_size, generated_at
+        "#,
+        report.user_id,
+        report.year,
+        report.month,
+        report.pdf_path,
+        report.pdf_size,
+        report.xlsx_path,
+        report.xlsx_size
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Stored reports of the user, latest month first
+pub async fn list_monthly_reports(
+    pool: &PgPool,
+    user_id: i64,
+) -> Result<Vec<StoredMonthlyReport>, sqlx::Error> {
+    sqlx::query_as!(
+        StoredMonthlyReport,
+        r#"
+        SELECT id, user_id, year, month, pdf_path, pdf_size, xlsx_path, xlsx_size, generated_at
+        FROM monthly_reports
+        WHERE user_id = $1
+        ORDER BY year DESC, month DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_monthly_report(
+    pool: &PgPool,
+    report_id: i64,
+    user_id: i64,
+) -> Result<StoredMonthlyReport, sqlx::Error> {
+    sqlx::query_as!(
+        StoredMonthlyReport,
+        r#"
+        SELECT id, user_id, year, month, pdf_path, pdf_size, xlsx_path, xlsx_size, generated_at
+        FROM monthly_reports
+        WHERE id = $1 AND user_id = $2
+        "#,
+        report_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Users who had signed up by the end of the month and have no stored report
+/// for it yet
+pub async fn list_users_missing_report(
+    pool: &PgPool,
+    year: i32,
+    month: i32,
+    period_end: NaiveDate,
+) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT u.id
+        FROM users u
+        WHERE u.deleted_at IS NULL
+            AND u.created_at < $3::date + 1
+            AND NOT EXISTS (
+                SELECT 1 FROM monthly_reports r
+                WHERE r.user_id = u.id AND r.year = $1 AND r.month = $2
+            )
+        ORDER BY u.id
+        "#,
+        year,
+        month,
+        period_end
+    )
+    .fetch_all(pool)
+    .await
+}
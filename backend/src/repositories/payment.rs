@@ -0,0 +1,286 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
+use thiserror::Error;
+
+use crate::models::payment::{
+    CreatePaymentRequest, MatchingBalance, PayerSide, Payment, PaymentMethod,
+};
+use crate::models::settlement::SettlementChangeSource;
+use crate::repositories::settlement;
+use crate::services::payment::{self, PaymentError};
+
+#[derive(Debug, Error)]
+pub enum LedgerError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Matching not found")]
+    MatchingNotFound,
+    #[error("Payment not found")]
+    PaymentNotFound,
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+}
+
+/// Fee amounts of a matching the ledger is kept against
+struct MatchingFees {
+    id: i64,
+    job_posting_id: i64,
+    job_seeking_posting_id: i64,
+    employer_fee_amount: Option<Decimal>,
+    employee_fee_amount: Option<Decimal>,
+    guarantee_refund_amount: Option<Decimal>,
+}
+
+impl MatchingFees {
+    fn posting_id(&self, side: PayerSide) -> i64 {
+        match side {
+            PayerSide::Employer => self.job_posting_id,
+            PayerSide::Employee => self.job_seeking_posting_id,
+        }
+    }
+}
+
+/// Payments of one of the user's matchings, oldest first, with its balance
+pub async fn list_payments(
+    pool: &PgPool,
+    matching_id: i64,
+    user_id: i64,
+) -> Result<(Vec<Payment>, MatchingBalance), LedgerError> {
+    let mut conn = pool.acquire().await?;
+
+    let fees = find_matching(&mut conn, matching_id, user_id).await?;
+    let payments = matching_payments(&mut conn, matching_id).await?;
+    let balance = matching_balance(&mut conn, &fees).await?;
+
+    Ok((payments, balance))
+}
+
+/// `list_payments` within an open transaction, locking the matching so no
+/// payment is recorded or deleted on it until the transaction ends
+pub async fn lock_payments(
+    conn: &mut PgConnection,
+    matching_id: i64,
+    user_id: i64,
+) -> Result<(Vec<Payment>, MatchingBalance), LedgerError> {
+    let fees = lock_matching(conn, matching_id, user_id).await?;
+    let payments = matching_payments(conn, matching_id).await?;
+    let balance = matching_balance(conn, &fees).await?;
+
+    Ok((payments, balance))
+}
+
+/// Outstanding balance of both sides of one of the user's matchings
+pub async fn get_matching_balance(
+    pool: &PgPool,
+    matching_id: i64,
+    user_id: i64,
+) -> Result<MatchingBalance, LedgerError> {
+    let mut conn = pool.acquire().await?;
+
+    let fees = find_matching(&mut conn, matching_id, user_id).await?;
+    Ok(matching_balance(&mut conn, &fees).await?)
+}
+
+/// Record a payment against the outstanding fee of one side of a matching
+/// and update the settlement status of that side's posting
+pub async fn create_payment(
+    pool: &PgPool,
+    matching_id: i64,
+    req: CreatePaymentRequest,
+    user_id: i64,
+) -> Result<(Payment, MatchingBalance), LedgerError> {
+    let mut tx = pool.begin().await?;
+    let recorded = record_payment(&mut tx, matching_id, req, user_id).await?;
+    tx.commit().await?;
+
+    Ok(recorded)
+}
+
+/// `create_payment` within an open transaction
+pub async fn record_payment(
+    conn: &mut PgConnection,
+    matching_id: i64,
+    req: CreatePaymentRequest,
+    user_id: i64,
+) -> Result<(Payment, MatchingBalance), LedgerError> {
+    let fees = lock_matching(conn, matching_id, user_id).await?;
+    let balance = matching_balance(conn, &fees).await?;
+    let side_balance = match req.payer_side {
+        PayerSide::Employer => &balance.employer,
+        PayerSide::Employee => &balance.employee,
+    };
+    payment::ensure_payable(side_balance, req.amount)?;
+
+    let created = sqlx::query_as!(
+        Payment,
+        r#"
+        INSERT INTO payments (matching_id, payer_side, amount, paid_on, payment_method, memo, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING
+            id, matching_id, payer_side as "payer_side: PayerSide", amount, paid_on,
+            payment_method as "payment_method: PaymentMethod", memo, created_by, created_at
+        "#,
+        matching_id,
+        req.payer_side as PayerSide,
+        req.amount,
+        req.paid_on.unwrap_or_else(|| Utc::now().date_naive()),
+        req.payment_method as PaymentMethod,
+        req.memo,
+        user_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    settlement::sync_posting_settlement(
+        conn,
+        req.payer_side.posting_kind(),
+        fees.posting_id(req.payer_side),
+        SettlementChangeSource::Payment,
+    )
+    .await?;
+    let balance = matching_balance(conn, &fees).await?;
+
+    Ok((created, balance))
+}
+
+/// Delete a payment recorded by mistake
+pub async fn delete_payment(
+    pool: &PgPool,
+    matching_id: i64,
+    payment_id: i64,
+    user_id: i64,
+) -> Result<MatchingBalance, LedgerError> {
+    let mut tx = pool.begin().await?;
+
+    let fees = lock_matching(&mut tx, matching_id, user_id).await?;
+    let payer_side = sqlx::query_scalar!(
+        r#"
+        DELETE FROM payments
+        WHERE id = $1 AND matching_id = $2
+        RETURNING payer_side::text as "payer_side!: PayerSide"
+        "#,
+        payment_id,
+        matching_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(LedgerError::PaymentNotFound)?;
+
+    settlement::sync_posting_settlement(
+        &mut tx,
+        payer_side.posting_kind(),
+        fees.posting_id(payer_side),
+        SettlementChangeSource::Payment,
+    )
+    .await?;
+    let balance = matching_balance(&mut tx, &fees).await?;
+
+    tx.commit().await?;
+
+    Ok(balance)
+}
+
+async fn find_matching(
+    conn: &mut PgConnection,
+    matching_id: i64,
+    user_id: i64,
+) -> Result<MatchingFees, LedgerError> {
+    sqlx::query_as!(
+        MatchingFees,
+        r#"
+        SELECT
+            m.id, m.job_posting_id, m.job_seeking_posting_id,
+            m.employer_fee_amount, m.employee_fee_amount, m.guarantee_refund_amount
+        FROM matchings m
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN customers c ON jp.customer_id = c.id
+        WHERE m.id = $1 AND c.user_id = $2 AND m.deleted_at IS NULL
+        "#,
+        matching_id,
+        user_id
+    )
+    .fetch_optional(conn)
+    .await?
+    .ok_or(LedgerError::MatchingNotFound)
+}
+
+/// Lock the matching so concurrent payments on it are checked one by one
+async fn lock_matching(
+    conn: &mut PgConnection,
+    matching_id: i64,
+    user_id: i64,
+) -> Result<MatchingFees, LedgerError> {
+    sqlx::query_as!(
+        MatchingFees,
+        r#"
+        SELECT
+            m.id, m.job_posting_id, m.job_seeking_posting_id,
+            m.employer_fee_amount, m.employee_fee_amount, m.guarantee_refund_amount
+        FROM matchings m
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN customers c ON jp.customer_id = c.id
+        WHERE m.id = $1 AND c.user_id = $2 AND m.deleted_at IS NULL
+        FOR UPDATE OF m
+        "#,
+        matching_id,
+        user_id
+    )
+    .fetch_optional(conn)
+    .await?
+    .ok_or(LedgerError::MatchingNotFound)
+}
+
+async fn matching_payments(
+    conn: &mut PgConnection,
+    matching_id: i64,
+) -> Result<Vec<Payment>, sqlx::Error> {
+    sqlx::query_as!(
+        Payment,
+        r#"
+        SELECT
+            id, matching_id, payer_side as "payer_side: PayerSide", amount, paid_on,
+            payment_method as "payment_method: PaymentMethod", memo, created_by, created_at
+        FROM payments
+        WHERE matching_id = $1
+        ORDER BY paid_on, id
+        "#,
+        matching_id
+    )
+    .fetch_all(conn)
+    .await
+}
+
+async fn matching_balance(
+    conn: &mut PgConnection,
+    fees: &MatchingFees,
+) -> Result<MatchingBalance, sqlx::Error> {
+    let paid = sqlx::query!(
+        r#"
+        SELECT
+            COALESCE(SUM(amount) FILTER (WHERE payer_side = 'employer'), 0) AS "employer_paid!",
+            COALESCE(SUM(amount) FILTER (WHERE payer_side = 'employee'), 0) AS "employee_paid!"
+        FROM payments
+        WHERE matching_id = $1
+        "#,
+        fees.id
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(MatchingBalance {
+        matching_id: fees.id,
+        employer: payment::side_balance(
+            PayerSide::Employer,
+            fees.employer_fee_amount.unwrap_or(Decimal::ZERO),
+            fees.guarantee_refund_amount.unwrap_or(Decimal::ZERO),
+            paid.employer_paid,
+        ),
+        employee: payment::side_balance(
+            PayerSide::Employee,
+            fees.employee_fee_amount.unwrap_or(Decimal::ZERO),
+            Decimal::ZERO,
+            paid.employee_paid,
+        ),
+    })
+}
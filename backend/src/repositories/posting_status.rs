@@ -0,0 +1,189 @@
+use chrono::NaiveDateTime;
+use sqlx::{PgConnection, PgPool};
+use thiserror::Error;
+
+use crate::models::job_posting::{
+    PostingKind, PostingStatus, PostingStatusChange, StatusChangeSource,
+};
+use crate::services::posting_lifecycle::{self, InvalidTransition};
+
+#[derive(Debug, Error)]
+pub enum PostingStatusError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Posting not found")]
+    NotFound,
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidTransition),
+}
+
+/// Move a posting to `to` after checking the transition rules, and record the
+/// change in the posting's status history.
+///
+/// The posting row is locked for the rest of the caller's transaction.
+/// Returns the previous status; nothing is written when it already equals `to`.
+pub async fn transition(
+    conn: &mut PgConnection,
+    kind: PostingKind,
+    posting_id: i64,
+    to: PostingStatus,
+    source: StatusChangeSource,
+    changed_by: Option<i64>,
+) -> Result<PostingStatus, PostingStatusError> {
+    let from = lock_status(conn, kind, posting_id).await?;
+    if from != to {
+        posting_lifecycle::ensure_transition(&from, &to)?;
+        write_transition(conn, kind, posting_id, &from, &to, source, changed_by).await?;
+    }
+
+    Ok(from)
+}
+
+/// Move a posting to `to` only while it is still in `expected`, e.g. put it
+/// back to 게시중 when a matching is cancelled unless the user has already
+/// changed it by hand. Returns whether the status was changed.
+pub async fn transition_if(
+    conn: &mut PgConnection,
+    kind: PostingKind,
+    posting_id: i64,
+    expected: PostingStatus,
+    to: PostingStatus,
+    source: StatusChangeSource,
+    changed_by: Option<i64>,
+) -> Result<bool, PostingStatusError> {
+    let from = lock_status(conn, kind, posting_id).await?;
+    if from != expected || from == to {
+        return Ok(false);
+    }
+    posting_lifecycle::ensure_transition(&from, &to)?;
+    write_transition(conn, kind, posting_id, &from, &to, source, changed_by).await?;
+
+    Ok(true)
+}
+
+async fn lock_status(
+    conn: &mut PgConnection,
+    kind: PostingKind,
+    posting_id: i64,
+) -> Result<PostingStatus, PostingStatusError> {
+    let (table, _, _) = tables(kind);
+
+    sqlx::query_scalar(&format!(
+        "SELECT posting_status::text FROM {} WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+        table
+    ))
+    .bind(posting_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(PostingStatusError::NotFound)
+}
+
+async fn write_transition(
+    conn: &mut PgConnection,
+    kind: PostingKind,
+    posting_id: i64,
+    from: &PostingStatus,
+    to: &PostingStatus,
+    source: StatusChangeSource,
+    changed_by: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    let (table, history_table, history_fk) = tables(kind);
+
+    sqlx::query(&format!(
+        "UPDATE {} SET posting_status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+        table
+    ))
+    .bind(posting_id)
+    .bind(to)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO {} ({}, from_status, to_status, change_source, changed_by)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        history_table, history_fk
+    ))
+    .bind(posting_id)
+    .bind(from)
+    .bind(to)
+    .bind(source)
+    .bind(changed_by)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Status history of a posting, oldest first
+pub async fn list_status_history(
+    pool: &PgPool,
+    kind: PostingKind,
+    posting_id: i64,
+) -> Result<Vec<PostingStatusChange>, sqlx::Error> {
+    let (_, history_table, history_fk) = tables(kind);
+
+    sqlx::query_as::<_, PostingStatusChange>(&format!(
+        r#"
+        SELECT
+            id, {fk} AS posting_id,
+            from_status::text AS from_status, to_status::text AS to_status,
+            change_source::text AS change_source, changed_by, changed_at
+        FROM {table}
+        WHERE {fk} = $1
+        ORDER BY changed_at, id
+        "#,
+        fk = history_fk,
+        table = history_table
+    ))
+    .bind(posting_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Close published postings whose `expires_at` has passed, recording each in
+/// the status history. Returns the number of postings closed.
+pub async fn close_expired_postings(pool: &PgPool, now: NaiveDateTime) -> Result<u64, sqlx::Error> {
+    let mut closed = 0;
+
+    for kind in [PostingKind::JobPosting, PostingKind::JobSeeking] {
+        let (table, history_table, history_fk) = tables(kind);
+        let result = sqlx::query(&format!(
+            r#"
+            WITH expired AS (
+                UPDATE {table}
+                SET posting_status = 'closed', updated_at = CURRENT_TIMESTAMP
+                WHERE posting_status = 'published'
+                    AND expires_at <= $1
+                    AND deleted_at IS NULL
+                RETURNING id
+            )
+            INSERT INTO {history_table} ({history_fk}, from_status, to_status, change_source)
+            SELECT id, 'published', 'closed', 'expiry' FROM expired
+            "#,
+        ))
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        closed += result.rows_affected();
+    }
+
+    Ok(closed)
+}
+
+fn tables(kind: PostingKind) -> (&'static str, &'static str, &'static str) {
+    match kind {
+        PostingKind::JobPosting => (
+            "job_postings",
+            "job_posting_status_history",
+            "job_posting_id",
+        ),
+        PostingKind::JobSeeking => (
+            "job_seeking_postings",
+            "job_seeking_status_history",
+            "job_seeking_posting_id",
+        ),
+    }
+}
@@ -0,0 +1,61 @@
+use sqlx::PgPool;
+
+use crate::models::payment::PayerSide;
+use crate::models::report::ReceivableRow;
+
+/// Employer and employee fees of the user's completed matchings, with the
+/// customer who owes each and the amounts paid and refunded so far
+pub async fn list_receivable_rows(
+    pool: &PgPool,
+    user_id: i64,
+) -> Result<Vec<ReceivableRow>, sqlx::Error> {
+    sqlx::query_as!(
+        ReceivableRow,
+        r#"
+        WITH completed AS (
+            SELECT m.*, jp.customer_id AS employer_id, jsp.customer_id AS employee_id
+            FROM matchings m
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+            INNER JOIN customers owner ON jp.customer_id = owner.id
+            WHERE owner.user_id = $1
+                AND m.matching_status = 'completed'
+                AND m.completed_at IS NOT NULL
+                AND m.deleted_at IS NULL
+        ),
+        fees AS (
+            SELECT
+                id AS matching_id, 'employer' AS payer_side, employer_id AS customer_id,
+                completed_at, COALESCE(employer_fee_amount, 0) AS fee_amount,
+                COALESCE(guarantee_refund_amount, 0) AS refunded_amount
+            FROM completed
+            UNION ALL
+            SELECT
+                id, 'employee', employee_id,
+                completed_at, COALESCE(employee_fee_amount, 0), 0
+            FROM completed
+        )
+        SELECT
+            f.matching_id AS "matching_id!",
+            f.payer_side AS "payer_side!: PayerSide",
+            f.customer_id AS "customer_id!",
+            c.name AS "customer_name!",
+            c.phone AS "phone!",
+            f.completed_at AS "completed_at!",
+            f.fee_amount AS "fee_amount!",
+            f.refunded_amount AS "refunded_amount!",
+            COALESCE(
+                (SELECT SUM(p.amount) FROM payments p
+                 WHERE p.matching_id = f.matching_id AND p.payer_side = f.payer_side),
+                0
+            ) AS "paid_amount!"
+        FROM fees f
+        INNER JOIN customers c ON f.customer_id = c.id
+        WHERE c.deleted_at IS NULL AND f.fee_amount > 0
+        ORDER BY f.completed_at, f.matching_id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
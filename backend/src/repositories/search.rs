@@ -0,0 +1,169 @@
+use sqlx::PgPool;
+
+use crate::models::search::{
+    JobPostingSearchHit, JobSeekingSearchHit, MemoSearchHit, TagSearchHit,
+};
+use crate::services::search::HEADLINE_OPTIONS;
+
+/// Full-text search over job posting descriptions
+pub async fn search_job_postings(
+    pool: &PgPool,
+    user_id: i64,
+    tsquery: &str,
+    limit: i64,
+) -> Result<Vec<JobPostingSearchHit>, sqlx::Error> {
+    sqlx::query_as::<_, JobPostingSearchHit>(
+        r#"
+        SELECT
+            jp.id, jp.customer_id, c.name AS customer_name, jp.posting_status::text AS posting_status,
+            ts_headline('simple', html_escape(jp.description), q.query, $4) AS snippet,
+            ts_rank(to_tsvector('simple', jp.description), q.query) AS rank,
+            jp.created_at
+        FROM job_postings jp
+        INNER JOIN customers c ON jp.customer_id = c.id
+        CROSS JOIN to_tsquery('simple', $2) AS q(query)
+        WHERE c.user_id = $1
+            AND jp.deleted_at IS NULL
+            AND c.deleted_at IS NULL
+            AND to_tsvector('simple', jp.description) @@ q.query
+        ORDER BY rank DESC, jp.created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(user_id)
+    .bind(tsquery)
+    .bind(limit)
+    .bind(HEADLINE_OPTIONS)
+    .fetch_all(pool)
+    .await
+}
+
+/// Full-text search over job seeking descriptions and preferred locations
+pub async fn search_job_seekings(
+    pool: &PgPool,
+    user_id: i64,
+    tsquery: &str,
+    limit: i64,
+) -> Result<Vec<JobSeekingSearchHit>, sqlx::Error> {
+    sqlx::query_as::<_, JobSeekingSearchHit>(
+        r#"
+        SELECT
+            jsp.id, jsp.customer_id, c.name AS customer_name,
+            jsp.preferred_location, jsp.posting_status::text AS posting_status,
+            ts_headline(
+                'simple', html_escape(jsp.description || ' ' || jsp.preferred_location), q.query, $4
+            ) AS snippet,
+            ts_rank(
+                to_tsvector('simple', jsp.description || ' ' || jsp.preferred_location), q.query
+            ) AS rank,
+            jsp.created_at
+        FROM job_seeking_postings jsp
+        INNER JOIN customers c ON jsp.customer_id = c.id
+        CROSS JOIN to_tsquery('simple', $2) AS q(query)
+        WHERE c.user_id = $1
+            AND jsp.deleted_at IS NULL
+            AND c.deleted_at IS NULL
+            AND to_tsvector('simple', jsp.description || ' ' || jsp.preferred_location) @@ q.query
+        ORDER BY rank DESC, jsp.created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(user_id)
+    .bind(tsquery)
+    .bind(limit)
+    .bind(HEADLINE_OPTIONS)
+    .fetch_all(pool)
+    .await
+}
+
+/// Full-text search over customer, matching and user memos
+pub async fn search_memos(
+    pool: &PgPool,
+    user_id: i64,
+    tsquery: &str,
+    limit: i64,
+) -> Result<Vec<MemoSearchHit>, sqlx::Error> {
+    sqlx::query_as::<_, MemoSearchHit>(
+        r#"
+        WITH q AS (SELECT to_tsquery('simple', $2) AS query),
+        memos AS (
+            SELECT
+                'customer' AS memo_kind, cm.id, c.id AS customer_id, c.name AS customer_name,
+                NULL::bigint AS matching_id, cm.memo_content, cm.created_at
+            FROM customer_memos cm
+            INNER JOIN customers c ON cm.customer_id = c.id
+            WHERE c.user_id = $1 AND cm.deleted_at IS NULL AND c.deleted_at IS NULL
+                AND to_tsvector('simple', cm.memo_content) @@ (SELECT query FROM q)
+
+            UNION ALL
+
+            SELECT
+                'matching', mm.id, employer.id, employer.name, m.id,
+                mm.memo_content, mm.created_at
+            FROM matching_memos mm
+            INNER JOIN matchings m ON mm.matching_id = m.id
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN customers employer ON jp.customer_id = employer.id
+            WHERE employer.user_id = $1 AND mm.deleted_at IS NULL AND m.deleted_at IS NULL
+                AND to_tsvector('simple', mm.memo_content) @@ (SELECT query FROM q)
+
+            UNION ALL
+
+            SELECT
+                'user', um.id, NULL, NULL, NULL, um.memo_content, um.created_at
+            FROM user_memos um
+            WHERE um.user_id = $1 AND um.deleted_at IS NULL
+                AND to_tsvector('simple', um.memo_content) @@ (SELECT query FROM q)
+        )
+        SELECT
+            memos.memo_kind, memos.id, memos.customer_id, memos.customer_name, memos.matching_id,
+            ts_headline('simple', html_escape(memos.memo_content), q.query, $4) AS snippet,
+            ts_rank(to_tsvector('simple', memos.memo_content), q.query) AS rank,
+            memos.created_at
+        FROM memos, q
+        ORDER BY rank DESC, memos.created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(user_id)
+    .bind(tsquery)
+    .bind(limit)
+    .bind(HEADLINE_OPTIONS)
+    .fetch_all(pool)
+    .await
+}
+
+/// Search tag names (substring match) with usage counts
+pub async fn search_tags(
+    pool: &PgPool,
+    user_id: i64,
+    term: &str,
+    limit: i64,
+) -> Result<Vec<TagSearchHit>, sqlx::Error> {
+    sqlx::query_as::<_, TagSearchHit>(
+        r#"
+        SELECT
+            t.id, t.tag_name, COALESCE(t.tag_color, '#6B7280') AS tag_color,
+            (SELECT COUNT(*) FROM customer_tags ct
+                INNER JOIN customers c ON ct.customer_id = c.id
+                WHERE ct.tag_id = t.id AND c.deleted_at IS NULL) AS customer_count,
+            (SELECT COUNT(*) FROM job_posting_tags jpt
+                INNER JOIN job_postings jp ON jpt.job_posting_id = jp.id
+                WHERE jpt.tag_id = t.id AND jp.deleted_at IS NULL) AS job_posting_count,
+            (SELECT COUNT(*) FROM job_seeking_posting_tags jspt
+                INNER JOIN job_seeking_postings jsp ON jspt.job_seeking_posting_id = jsp.id
+                WHERE jspt.tag_id = t.id AND jsp.deleted_at IS NULL) AS job_seeking_count
+        FROM tags t
+        WHERE t.user_id = $1
+            AND t.deleted_at IS NULL
+            AND t.tag_name ILIKE '%' || $2 || '%'
+        ORDER BY (t.tag_name = $2) DESC, similarity(t.tag_name, $2) DESC, t.tag_name
+        LIMIT $3
+        "#,
+    )
+    .bind(user_id)
+    .bind(term)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
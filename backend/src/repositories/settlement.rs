@@ -0,0 +1,258 @@
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
+use thiserror::Error;
+
+use crate::models::job_posting::{PostingKind, SettlementStatus};
+use crate::models::payment::{PayerSide, SideBalance};
+use crate::models::settlement::{
+    PostingSettlement, SettlementChange, SettlementChangeSource, UpdateSettlementRequest,
+};
+use crate::services::payment;
+use crate::services::settlement::{self, SettlementError};
+
+#[derive(Debug, Error)]
+pub enum SettlementUpdateError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Posting not found")]
+    NotFound,
+    #[error(transparent)]
+    Invalid(#[from] SettlementError),
+}
+
+/// Update a posting's settlement by hand after checking it against the
+/// posting's matchings, and record the change in its settlement history
+pub async fn update_settlement(
+    pool: &PgPool,
+    kind: PostingKind,
+    posting_id: i64,
+    req: &UpdateSettlementRequest,
+    changed_by: i64,
+) -> Result<(), SettlementUpdateError> {
+    let mut tx = pool.begin().await?;
+
+    let current = lock_settlement(&mut tx, kind, posting_id)
+        .await?
+        .ok_or(SettlementUpdateError::NotFound)?;
+    let balances = linked_balances(&mut tx, kind, posting_id).await?;
+    let expected = settlement::expected_settlement(&balances);
+    let next = settlement::resolve_update(&current, req, expected.as_ref())?;
+    write_change(
+        &mut tx,
+        kind,
+        posting_id,
+        &current,
+        &next,
+        SettlementChangeSource::Manual,
+        Some(changed_by),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Derive the settlement status and amount of a posting from the balances of
+/// all its matchings that are not cancelled, recording any change in its
+/// settlement history. A posting left with no such matching goes back to
+/// unsettled with no amount.
+pub async fn sync_posting_settlement(
+    conn: &mut PgConnection,
+    kind: PostingKind,
+    posting_id: i64,
+    source: SettlementChangeSource,
+) -> Result<(), sqlx::Error> {
+    let Some(current) = lock_settlement(conn, kind, posting_id).await? else {
+        return Ok(());
+    };
+    let balances = linked_balances(conn, kind, posting_id).await?;
+    let next = match settlement::expected_settlement(&balances) {
+        Some(expected) => PostingSettlement {
+            settlement_status: expected.settlement_status,
+            settlement_amount: Some(expected.settlement_amount),
+            settlement_memo: current.settlement_memo.clone(),
+        },
+        None => PostingSettlement {
+            settlement_status: SettlementStatus::Unsettled,
+            settlement_amount: None,
+            settlement_memo: current.settlement_memo.clone(),
+        },
+    };
+
+    write_change(conn, kind, posting_id, &current, &next, source, None).await
+}
+
+/// Settlement history of a posting, oldest first
+pub async fn list_settlement_history(
+    pool: &PgPool,
+    kind: PostingKind,
+    posting_id: i64,
+) -> Result<Vec<SettlementChange>, sqlx::Error> {
+    let (_, history_table, history_fk) = tables(kind);
+
+    sqlx::query_as::<_, SettlementChange>(&format!(
+        r#"
+        SELECT
+            id, {fk} AS posting_id,
+            from_status::text AS from_status, to_status::text AS to_status,
+            from_amount, to_amount, memo,
+            change_source::text AS change_source, changed_by, changed_at
+        FROM {table}
+        WHERE {fk} = $1
+        ORDER BY changed_at, id
+        "#,
+        fk = history_fk,
+        table = history_table
+    ))
+    .bind(posting_id)
+    .fetch_all(pool)
+    .await
+}
+
+async fn lock_settlement(
+    conn: &mut PgConnection,
+    kind: PostingKind,
+    posting_id: i64,
+) -> Result<Option<PostingSettlement>, sqlx::Error> {
+    let (table, _, _) = tables(kind);
+
+    let row = sqlx::query_as::<_, (SettlementStatus, Option<Decimal>, Option<String>)>(&format!(
+        r#"
+        SELECT settlement_status::text, settlement_amount, settlement_memo
+        FROM {table}
+        WHERE id = $1
+        FOR UPDATE
+        "#
+    ))
+    .bind(posting_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(row.map(
+        |(settlement_status, settlement_amount, settlement_memo)| PostingSettlement {
+            settlement_status,
+            settlement_amount,
+            settlement_memo,
+        },
+    ))
+}
+
+/// Balances of the posting's side of each of its matchings that are not
+/// cancelled: the employer side for a job posting, the employee side for a
+/// job seeking posting
+async fn linked_balances(
+    conn: &mut PgConnection,
+    kind: PostingKind,
+    posting_id: i64,
+) -> Result<Vec<SideBalance>, sqlx::Error> {
+    let side = PayerSide::for_posting(kind);
+    let (fk, fee_column, refund) = match side {
+        PayerSide::Employer => (
+            "job_posting_id",
+            "employer_fee_amount",
+            "m.guarantee_refund_amount",
+        ),
+        PayerSide::Employee => (
+            "job_seeking_posting_id",
+            "employee_fee_amount",
+            "NULL::NUMERIC",
+        ),
+    };
+
+    let rows = sqlx::query_as::<_, (Option<Decimal>, Option<Decimal>, Decimal)>(&format!(
+        r#"
+        SELECT
+            m.{fee_column},
+            {refund},
+            COALESCE(
+                (SELECT SUM(p.amount) FROM payments p
+                 WHERE p.matching_id = m.id AND p.payer_side = $2),
+                0
+            )
+        FROM matchings m
+        WHERE m.{fk} = $1 AND m.matching_status <> 'cancelled' AND m.deleted_at IS NULL
+        "#
+    ))
+    .bind(posting_id)
+    .bind(side)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(fee, refunded, paid)| {
+            payment::side_balance(
+                side,
+                fee.unwrap_or(Decimal::ZERO),
+                refunded.unwrap_or(Decimal::ZERO),
+                paid,
+            )
+        })
+        .collect())
+}
+
+async fn write_change(
+    conn: &mut PgConnection,
+    kind: PostingKind,
+    posting_id: i64,
+    from: &PostingSettlement,
+    to: &PostingSettlement,
+    source: SettlementChangeSource,
+    changed_by: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    if from == to {
+        return Ok(());
+    }
+    let (table, history_table, history_fk) = tables(kind);
+
+    sqlx::query(&format!(
+        r#"
+        UPDATE {table}
+        SET settlement_status = $2, settlement_amount = $3, settlement_memo = $4,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#
+    ))
+    .bind(posting_id)
+    .bind(&to.settlement_status)
+    .bind(to.settlement_amount)
+    .bind(&to.settlement_memo)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO {history_table}
+            ({history_fk}, from_status, to_status, from_amount, to_amount, memo, change_source, changed_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#
+    ))
+    .bind(posting_id)
+    .bind(&from.settlement_status)
+    .bind(&to.settlement_status)
+    .bind(from.settlement_amount)
+    .bind(to.settlement_amount)
+    .bind(&to.settlement_memo)
+    .bind(source)
+    .bind(changed_by)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+fn tables(kind: PostingKind) -> (&'static str, &'static str, &'static str) {
+    match kind {
+        PostingKind::JobPosting => (
+            "job_postings",
+            "job_posting_settlement_history",
+            "job_posting_id",
+        ),
+        PostingKind::JobSeeking => (
+            "job_seeking_postings",
+            "job_seeking_settlement_history",
+            "job_seeking_posting_id",
+        ),
+    }
+}
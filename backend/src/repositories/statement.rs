@@ -0,0 +1,95 @@
+use sqlx::PgPool;
+
+use crate::models::matching::MatchingStatus;
+use crate::models::payment::{PayerSide, PaymentMethod};
+use crate::models::statement::{StatementMatchingRow, StatementPaymentRow};
+
+/// Every side of the user's matchings the customer was on: the employer side
+/// of matchings of their job postings and the employee side of matchings of
+/// their job seeking postings
+pub async fn list_statement_matchings(
+    pool: &PgPool,
+    customer_id: i64,
+    user_id: i64,
+) -> Result<Vec<StatementMatchingRow>, sqlx::Error> {
+    sqlx::query_as!(
+        StatementMatchingRow,
+        r#"
+        WITH owned AS (
+            SELECT
+                m.*, jp.customer_id AS employer_id, jsp.customer_id AS employee_id
+            FROM matchings m
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+            INNER JOIN customers owner ON jp.customer_id = owner.id
+            WHERE owner.user_id = $2 AND m.deleted_at IS NULL
+                AND (jp.customer_id = $1 OR jsp.customer_id = $1)
+        ),
+        sides AS (
+            SELECT
+                id AS matching_id, 'employer' AS payer_side, employee_id AS counterparty_id,
+                COALESCE(employer_fee_amount, 0) AS fee_amount,
+                COALESCE(guarantee_refund_amount, 0) AS refunded_amount
+            FROM owned
+            WHERE employer_id = $1
+            UNION ALL
+            SELECT id, 'employee', employer_id, COALESCE(employee_fee_amount, 0), 0
+            FROM owned
+            WHERE employee_id = $1
+        )
+        SELECT
+            s.matching_id AS "matching_id!",
+            s.payer_side AS "payer_side!: PayerSide",
+            c.name AS "counterparty_name!",
+            COALESCE(m.matched_at, m.created_at) AS "matched_at!",
+            m.completed_at,
+            m.matching_status AS "matching_status!: MatchingStatus",
+            s.fee_amount AS "fee_amount!",
+            s.refunded_amount AS "refunded_amount!",
+            m.guarantee_broken_on AS refunded_on
+        FROM sides s
+        INNER JOIN owned m ON s.matching_id = m.id
+        INNER JOIN customers c ON s.counterparty_id = c.id
+        ORDER BY 4, s.matching_id
+        "#,
+        customer_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Payments the customer made on their side of the user's matchings, oldest
+/// first
+pub async fn list_statement_payments(
+    pool: &PgPool,
+    customer_id: i64,
+    user_id: i64,
+) -> Result<Vec<StatementPaymentRow>, sqlx::Error> {
+    sqlx::query_as!(
+        StatementPaymentRow,
+        r#"
+        SELECT
+            p.id, p.matching_id,
+            p.payer_side AS "payer_side: PayerSide",
+            p.amount, p.paid_on,
+            p.payment_method AS "payment_method: PaymentMethod",
+            p.memo
+        FROM payments p
+        INNER JOIN matchings m ON p.matching_id = m.id
+        INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+        INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+        INNER JOIN customers owner ON jp.customer_id = owner.id
+        WHERE owner.user_id = $2 AND m.deleted_at IS NULL
+            AND (
+                (p.payer_side = 'employer' AND jp.customer_id = $1)
+                OR (p.payer_side = 'employee' AND jsp.customer_id = $1)
+            )
+        ORDER BY p.paid_on, p.id
+        "#,
+        customer_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
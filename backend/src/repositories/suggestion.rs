@@ -0,0 +1,90 @@
+use sqlx::PgPool;
+
+use crate::models::job_posting::PostingKind;
+use crate::models::suggestion::SuggestionProfile;
+
+/// Scoring profile of one of the user's postings
+pub async fn get_profile(
+    pool: &PgPool,
+    kind: PostingKind,
+    posting_id: i64,
+    user_id: i64,
+) -> Result<Option<SuggestionProfile>, sqlx::Error> {
+    sqlx::query_as::<_, SuggestionProfile>(&format!("{} AND p.id = $2", profile_query(kind)))
+        .bind(user_id)
+        .bind(posting_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Scoring profiles of the user's published postings of `kind`, leaving out
+/// `exclude_customer_id` and customers already in an in-progress matching
+pub async fn list_candidates(
+    pool: &PgPool,
+    kind: PostingKind,
+    user_id: i64,
+    exclude_customer_id: i64,
+) -> Result<Vec<SuggestionProfile>, sqlx::Error> {
+    sqlx::query_as::<_, SuggestionProfile>(&format!(
+        r#"
+        {}
+            AND p.posting_status = 'published'
+            AND c.id <> $2
+            AND NOT EXISTS (
+                SELECT 1
+                FROM matchings m
+                INNER JOIN job_postings ajp ON m.job_posting_id = ajp.id
+                INNER JOIN job_seeking_postings ajs ON m.job_seeking_posting_id = ajs.id
+                WHERE m.matching_status = 'in_progress'
+                    AND m.deleted_at IS NULL
+                    AND c.id IN (ajp.customer_id, ajs.customer_id)
+            )
+        "#,
+        profile_query(kind)
+    ))
+    .bind(user_id)
+    .bind(exclude_customer_id)
+    .fetch_all(pool)
+    .await
+}
+
+fn profile_query(kind: PostingKind) -> String {
+    let (table, tag_table, tag_fk, location) = match kind {
+        PostingKind::JobPosting => (
+            "job_postings",
+            "job_posting_tags",
+            "job_posting_id",
+            "c.address",
+        ),
+        PostingKind::JobSeeking => (
+            "job_seeking_postings",
+            "job_seeking_posting_tags",
+            "job_seeking_posting_id",
+            "p.preferred_location",
+        ),
+    };
+
+    format!(
+        r#"
+        SELECT
+            p.id AS posting_id, c.id AS customer_id, c.name AS customer_name,
+            p.monthly_salary, p.region_codes, {location} AS location,
+            ARRAY(
+                SELECT DISTINCT t.tag_name::text
+                FROM tags t
+                WHERE t.deleted_at IS NULL
+                    AND (
+                        t.id IN (SELECT pt.tag_id FROM {tag_table} pt WHERE pt.{tag_fk} = p.id)
+                        OR t.id IN (SELECT ct.tag_id FROM customer_tags ct WHERE ct.customer_id = c.id)
+                    )
+                ORDER BY t.tag_name::text
+            ) AS tags,
+            COALESCE(p.created_at, LOCALTIMESTAMP) AS created_at
+        FROM {table} p
+        INNER JOIN customers c ON p.customer_id = c.id
+        WHERE c.user_id = $1
+            AND p.deleted_at IS NULL
+            AND c.deleted_at IS NULL
+        "#
+    )
+}
@@ -2,7 +2,7 @@
 #![allow(dead_code)]
 
 use crate::models::tag::{CreateTagRequest, Tag, UpdateTagRequest};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 // ========================================
 // Tag Management
@@ -154,6 +154,38 @@ pub async fn attach_tag_to_customer(
     Ok(())
 }
 
+/// Copy the tags of one customer onto another and remove them from the source
+/// (used when merging customers). Returns the number of tags newly added to the target.
+pub async fn move_customer_tags(
+    conn: &mut PgConnection,
+    from_customer_id: i64,
+    to_customer_id: i64,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO customer_tags (customer_id, tag_id)
+        SELECT $2, tag_id FROM customer_tags WHERE customer_id = $1
+        ON CONFLICT (customer_id, tag_id) DO NOTHING
+        "#,
+        from_customer_id,
+        to_customer_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM customer_tags
+        WHERE customer_id = $1
+        "#,
+        from_customer_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 /// Detach tag from customer
 pub async fn detach_tag_from_customer(
     pool: &PgPool,
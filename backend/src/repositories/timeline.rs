@@ -0,0 +1,125 @@
+use sqlx::PgPool;
+
+use crate::models::timeline::TimelineEvent;
+
+/// List a customer's activity events, newest first.
+///
+/// Events are derived from memos, files, postings, posting status history and
+/// matchings (on either side). Settlement only keeps the current state, so it
+/// is reported once, at the posting's last update. Events at the same time
+/// are ordered by type and then by their source row, newest first.
+pub async fn list_customer_timeline(
+    pool: &PgPool,
+    customer_id: i64,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<TimelineEvent>, sqlx::Error> {
+    sqlx::query_as::<_, TimelineEvent>(
+        r#"
+        WITH customer_matchings AS (
+            SELECT m.*, jp.customer_id AS employer_id, jsp.customer_id AS employee_id
+            FROM matchings m
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+            WHERE (jp.customer_id = $1 OR jsp.customer_id = $1) AND m.deleted_at IS NULL
+        ),
+        events AS (
+            SELECT
+                'memo_added' AS event_type, cm.created_at AS occurred_at,
+                NULL::bigint AS job_posting_id, NULL::bigint AS job_seeking_posting_id,
+                NULL::bigint AS matching_id, cm.id AS memo_id, NULL::bigint AS file_id,
+                NULL::text AS posting_status, NULL::numeric AS amount,
+                left(cm.memo_content, 100) AS summary, cm.id AS source_id
+            FROM customer_memos cm
+            WHERE cm.customer_id = $1 AND cm.deleted_at IS NULL
+
+            UNION ALL
+            SELECT
+                'file_uploaded', cf.created_at, NULL, NULL, NULL, NULL, cf.id,
+                NULL, NULL, cf.original_filename, cf.id
+            FROM customer_files cf
+            WHERE cf.customer_id = $1 AND cf.deleted_at IS NULL
+
+            UNION ALL
+            SELECT
+                'job_posting_created', jp.created_at, jp.id, NULL, NULL, NULL, NULL,
+                'published', jp.salary, left(jp.description, 100), jp.id
+            FROM job_postings jp
+            WHERE jp.customer_id = $1 AND jp.deleted_at IS NULL
+
+            UNION ALL
+            SELECT
+                'job_posting_status_changed', h.changed_at, jp.id, NULL, NULL, NULL, NULL,
+                h.to_status, NULL, NULL, h.id
+            FROM job_posting_status_history h
+            INNER JOIN job_postings jp ON h.job_posting_id = jp.id
+            WHERE jp.customer_id = $1 AND jp.deleted_at IS NULL
+
+            UNION ALL
+            SELECT
+                'settlement_recorded', jp.updated_at, jp.id, NULL, NULL, NULL, NULL,
+                NULL, jp.settlement_amount, jp.settlement_memo, jp.id
+            FROM job_postings jp
+            WHERE jp.customer_id = $1 AND jp.deleted_at IS NULL
+                AND jp.settlement_status = 'settled'
+
+            UNION ALL
+            SELECT
+                'job_seeking_created', jsp.created_at, NULL, jsp.id, NULL, NULL, NULL,
+                'published', jsp.desired_salary, left(jsp.description, 100), jsp.id
+            FROM job_seeking_postings jsp
+            WHERE jsp.customer_id = $1 AND jsp.deleted_at IS NULL
+
+            UNION ALL
+            SELECT
+                'job_seeking_status_changed', h.changed_at, NULL, jsp.id, NULL, NULL, NULL,
+                h.to_status, NULL, NULL, h.id
+            FROM job_seeking_status_history h
+            INNER JOIN job_seeking_postings jsp ON h.job_seeking_posting_id = jsp.id
+            WHERE jsp.customer_id = $1 AND jsp.deleted_at IS NULL
+
+            UNION ALL
+            SELECT
+                'settlement_recorded', jsp.updated_at, NULL, jsp.id, NULL, NULL, NULL,
+                NULL, jsp.settlement_amount, jsp.settlement_memo, jsp.id
+            FROM job_seeking_postings jsp
+            WHERE jsp.customer_id = $1 AND jsp.deleted_at IS NULL
+                AND jsp.settlement_status = 'settled'
+
+            UNION ALL
+            SELECT
+                'matching_created', cm.matched_at, cm.job_posting_id, cm.job_seeking_posting_id,
+                cm.id, NULL, NULL, NULL, cm.agreed_salary, NULL, cm.id
+            FROM customer_matchings cm
+
+            UNION ALL
+            SELECT
+                'matching_completed', cm.completed_at, cm.job_posting_id,
+                cm.job_seeking_posting_id, cm.id, NULL, NULL, NULL,
+                CASE WHEN cm.employer_id = $1 THEN cm.employer_fee_amount
+                     ELSE cm.employee_fee_amount END,
+                NULL, cm.id
+            FROM customer_matchings cm
+            WHERE cm.completed_at IS NOT NULL
+
+            UNION ALL
+            SELECT
+                'matching_cancelled', cm.cancelled_at, cm.job_posting_id,
+                cm.job_seeking_posting_id, cm.id, NULL, NULL, NULL, NULL,
+                cm.cancellation_reason, cm.id
+            FROM customer_matchings cm
+            WHERE cm.cancelled_at IS NOT NULL
+        )
+        SELECT *
+        FROM events
+        WHERE occurred_at IS NOT NULL
+        ORDER BY occurred_at DESC, event_type, source_id DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(customer_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,576 @@
+use chrono::NaiveDateTime;
+use sqlx::{PgConnection, PgPool, Row};
+use thiserror::Error;
+
+use crate::models::trash::{ExpiredFile, TrashItem, TrashItemType};
+
+#[derive(Debug, Error)]
+pub enum TrashError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Item not found in trash")]
+    NotFound,
+    #[error("Parent record is deleted: {0:?}")]
+    ParentDeleted(TrashItemType),
+    #[error("Merged customers cannot be restored")]
+    Merged,
+}
+
+/// List soft-deleted records of a user, most recently deleted first
+pub async fn list_trash(
+    pool: &PgPool,
+    user_id: i64,
+    item_type: Option<TrashItemType>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<TrashItem>, sqlx::Error> {
+    sqlx::query_as::<_, TrashItem>(
+        r#"
+        SELECT * FROM (
+            SELECT 'customer' AS item_type, c.id, c.name AS title,
+                c.id AS customer_id, c.name AS customer_name, c.deleted_at
+            FROM customers c
+            WHERE c.user_id = $1 AND c.deleted_at IS NOT NULL AND c.merged_into_id IS NULL
+
+            UNION ALL
+            SELECT 'job_posting', jp.id, left(jp.description, 100), c.id, c.name, jp.deleted_at
+            FROM job_postings jp
+            INNER JOIN customers c ON jp.customer_id = c.id
+            WHERE c.user_id = $1 AND jp.deleted_at IS NOT NULL
+                AND c.deleted_at IS DISTINCT FROM jp.deleted_at
+
+            UNION ALL
+            SELECT 'job_seeking', jsp.id, left(jsp.description, 100), c.id, c.name, jsp.deleted_at
+            FROM job_seeking_postings jsp
+            INNER JOIN customers c ON jsp.customer_id = c.id
+            WHERE c.user_id = $1 AND jsp.deleted_at IS NOT NULL
+                AND c.deleted_at IS DISTINCT FROM jsp.deleted_at
+
+            UNION ALL
+            SELECT 'matching', m.id, employer.name || ' - ' || employee.name,
+                employer.id, employer.name, m.deleted_at
+            FROM matchings m
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+            INNER JOIN customers employer ON jp.customer_id = employer.id
+            INNER JOIN customers employee ON jsp.customer_id = employee.id
+            WHERE employer.user_id = $1 AND m.deleted_at IS NOT NULL
+                AND jp.deleted_at IS DISTINCT FROM m.deleted_at
+                AND jsp.deleted_at IS DISTINCT FROM m.deleted_at
+
+            UNION ALL
+            SELECT 'customer_memo', cm.id, left(cm.memo_content, 100), c.id, c.name, cm.deleted_at
+            FROM customer_memos cm
+            INNER JOIN customers c ON cm.customer_id = c.id
+            WHERE c.user_id = $1 AND cm.deleted_at IS NOT NULL
+                AND c.deleted_at IS DISTINCT FROM cm.deleted_at
+
+            UNION ALL
+            SELECT 'matching_memo', mm.id, left(mm.memo_content, 100), c.id, c.name, mm.deleted_at
+            FROM matching_memos mm
+            INNER JOIN matchings m ON mm.matching_id = m.id
+            INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+            INNER JOIN customers c ON jp.customer_id = c.id
+            WHERE c.user_id = $1 AND mm.deleted_at IS NOT NULL
+                AND m.deleted_at IS DISTINCT FROM mm.deleted_at
+
+            UNION ALL
+            SELECT 'user_memo', um.id, left(um.memo_content, 100), NULL, NULL, um.deleted_at
+            FROM user_memos um
+            WHERE um.user_id = $1 AND um.deleted_at IS NOT NULL
+
+            UNION ALL
+            SELECT 'customer_file', cf.id, COALESCE(cf.original_filename, cf.file_path),
+                c.id, c.name, cf.deleted_at
+            FROM customer_files cf
+            INNER JOIN customers c ON cf.customer_id = c.id
+            WHERE c.user_id = $1 AND cf.deleted_at IS NOT NULL
+                AND c.deleted_at IS DISTINCT FROM cf.deleted_at
+
+            UNION ALL
+            SELECT 'user_file', uf.id, COALESCE(uf.original_filename, uf.file_path),
+                NULL, NULL, uf.deleted_at
+            FROM user_files uf
+            WHERE uf.user_id = $1 AND uf.deleted_at IS NOT NULL
+
+            UNION ALL
+            SELECT 'tag', t.id, t.tag_name, NULL, NULL, t.deleted_at
+            FROM tags t
+            WHERE t.user_id = $1 AND t.deleted_at IS NOT NULL
+        ) AS trash
+        WHERE $2::text IS NULL OR trash.item_type = $2
+        ORDER BY trash.deleted_at DESC, trash.item_type, trash.id
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(user_id)
+    .bind(item_type)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
+
+/// Restore a soft-deleted record and everything deleted together with it.
+///
+/// Returns the number of rows restored. Fails with `ParentDeleted` when the
+/// record belongs to a parent that is still in the trash.
+pub async fn restore(
+    pool: &PgPool,
+    user_id: i64,
+    item_type: TrashItemType,
+    id: i64,
+) -> Result<u64, TrashError> {
+    let mut tx = pool.begin().await?;
+
+    let restored = match item_type {
+        TrashItemType::Customer => restore_customer(&mut tx, user_id, id).await?,
+        TrashItemType::JobPosting => {
+            let deleted_at = locate(
+                &mut tx,
+                r#"
+                SELECT jp.deleted_at, c.deleted_at IS NULL AS parent_alive
+                FROM job_postings jp
+                INNER JOIN customers c ON jp.customer_id = c.id
+                WHERE jp.id = $1 AND c.user_id = $2 AND jp.deleted_at IS NOT NULL
+                FOR UPDATE OF jp
+                "#,
+                id,
+                user_id,
+                TrashItemType::Customer,
+            )
+            .await?;
+            undelete(&mut tx, "job_postings", id).await?
+                + restore_matchings(&mut tx, "m.job_posting_id = $1", id, deleted_at).await?
+        }
+        TrashItemType::JobSeeking => {
+            let deleted_at = locate(
+                &mut tx,
+                r#"
+                SELECT jsp.deleted_at, c.deleted_at IS NULL AS parent_alive
+                FROM job_seeking_postings jsp
+                INNER JOIN customers c ON jsp.customer_id = c.id
+                WHERE jsp.id = $1 AND c.user_id = $2 AND jsp.deleted_at IS NOT NULL
+                FOR UPDATE OF jsp
+                "#,
+                id,
+                user_id,
+                TrashItemType::Customer,
+            )
+            .await?;
+            undelete(&mut tx, "job_seeking_postings", id).await?
+                + restore_matchings(&mut tx, "m.job_seeking_posting_id = $1", id, deleted_at)
+                    .await?
+        }
+        TrashItemType::Matching => {
+            let deleted_at = locate(
+                &mut tx,
+                r#"
+                SELECT m.deleted_at, (jp.deleted_at IS NULL AND jsp.deleted_at IS NULL) AS parent_alive
+                FROM matchings m
+                INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+                INNER JOIN job_seeking_postings jsp ON m.job_seeking_posting_id = jsp.id
+                INNER JOIN customers c ON jp.customer_id = c.id
+                WHERE m.id = $1 AND c.user_id = $2 AND m.deleted_at IS NOT NULL
+                FOR UPDATE OF m
+                "#,
+                id,
+                user_id,
+                TrashItemType::JobPosting,
+            )
+            .await?;
+            restore_matchings(&mut tx, "m.id = $1", id, deleted_at).await?
+        }
+        TrashItemType::CustomerMemo => {
+            locate(
+                &mut tx,
+                r#"
+                SELECT cm.deleted_at, c.deleted_at IS NULL AS parent_alive
+                FROM customer_memos cm
+                INNER JOIN customers c ON cm.customer_id = c.id
+                WHERE cm.id = $1 AND c.user_id = $2 AND cm.deleted_at IS NOT NULL
+                "#,
+                id,
+                user_id,
+                TrashItemType::Customer,
+            )
+            .await?;
+            undelete(&mut tx, "customer_memos", id).await?
+        }
+        TrashItemType::MatchingMemo => {
+            locate(
+                &mut tx,
+                r#"
+                SELECT mm.deleted_at, m.deleted_at IS NULL AS parent_alive
+                FROM matching_memos mm
+                INNER JOIN matchings m ON mm.matching_id = m.id
+                INNER JOIN job_postings jp ON m.job_posting_id = jp.id
+                INNER JOIN customers c ON jp.customer_id = c.id
+                WHERE mm.id = $1 AND c.user_id = $2 AND mm.deleted_at IS NOT NULL
+                "#,
+                id,
+                user_id,
+                TrashItemType::Matching,
+            )
+            .await?;
+            undelete(&mut tx, "matching_memos", id).await?
+        }
+        TrashItemType::CustomerFile => {
+            locate(
+                &mut tx,
+                r#"
+                SELECT cf.deleted_at, c.deleted_at IS NULL AS parent_alive
+                FROM customer_files cf
+                INNER JOIN customers c ON cf.customer_id = c.id
+                WHERE cf.id = $1 AND c.user_id = $2 AND cf.deleted_at IS NOT NULL
+                "#,
+                id,
+                user_id,
+                TrashItemType::Customer,
+            )
+            .await?;
+            undelete(&mut tx, "customer_files", id).await?
+        }
+        TrashItemType::UserMemo | TrashItemType::UserFile | TrashItemType::Tag => {
+            let table = table_name(item_type);
+            locate(
+                &mut tx,
+                &format!(
+                    "SELECT deleted_at, TRUE AS parent_alive FROM {} \
+                     WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL",
+                    table
+                ),
+                id,
+                user_id,
+                item_type,
+            )
+            .await?;
+            undelete(&mut tx, table, id).await?
+        }
+    };
+
+    tx.commit().await?;
+
+    Ok(restored)
+}
+
+async fn restore_customer(
+    conn: &mut PgConnection,
+    user_id: i64,
+    customer_id: i64,
+) -> Result<u64, TrashError> {
+    let row = sqlx::query(
+        r#"
+        SELECT deleted_at, merged_into_id
+        FROM customers
+        WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+        FOR UPDATE
+        "#,
+    )
+    .bind(customer_id)
+    .bind(user_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(TrashError::NotFound)?;
+
+    if row.get::<Option<i64>, _>("merged_into_id").is_some() {
+        return Err(TrashError::Merged);
+    }
+    let deleted_at: NaiveDateTime = row.get("deleted_at");
+
+    let mut restored = undelete(conn, "customers", customer_id).await?;
+
+    for table in [
+        "job_postings",
+        "job_seeking_postings",
+        "customer_memos",
+        "customer_files",
+    ] {
+        restored += sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = NULL WHERE customer_id = $1 AND deleted_at = $2",
+            table
+        ))
+        .bind(customer_id)
+        .bind(deleted_at)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+    }
+
+    restored += restore_matchings(
+        conn,
+        "(jp.customer_id = $1 OR jsp.customer_id = $1)",
+        customer_id,
+        deleted_at,
+    )
+    .await?;
+
+    Ok(restored)
+}
+
+/// Restore matchings (and their memos) matched by `condition` that were
+/// deleted at `deleted_at`, as long as both postings are live again
+async fn restore_matchings(
+    conn: &mut PgConnection,
+    condition: &str,
+    id: i64,
+    deleted_at: NaiveDateTime,
+) -> Result<u64, sqlx::Error> {
+    let matching_ids: Vec<i64> = sqlx::query_scalar(&format!(
+        r#"
+        UPDATE matchings m
+        SET deleted_at = NULL
+        FROM job_postings jp, job_seeking_postings jsp
+        WHERE m.job_posting_id = jp.id AND m.job_seeking_posting_id = jsp.id
+            AND {}
+            AND m.deleted_at = $2
+            AND jp.deleted_at IS NULL AND jsp.deleted_at IS NULL
+        RETURNING m.id
+        "#,
+        condition
+    ))
+    .bind(id)
+    .bind(deleted_at)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let memos = sqlx::query(
+        r#"
+        UPDATE matching_memos
+        SET deleted_at = NULL
+        WHERE matching_id = ANY($1) AND deleted_at = $2
+        "#,
+    )
+    .bind(&matching_ids)
+    .bind(deleted_at)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    Ok(matching_ids.len() as u64 + memos)
+}
+
+/// Find a trashed record owned by the user, returning its `deleted_at`
+async fn locate(
+    conn: &mut PgConnection,
+    sql: &str,
+    id: i64,
+    user_id: i64,
+    parent_type: TrashItemType,
+) -> Result<NaiveDateTime, TrashError> {
+    let row = sqlx::query(sql)
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(TrashError::NotFound)?;
+
+    if !row.get::<bool, _>("parent_alive") {
+        return Err(TrashError::ParentDeleted(parent_type));
+    }
+
+    Ok(row.get("deleted_at"))
+}
+
+async fn undelete(conn: &mut PgConnection, table: &str, id: i64) -> Result<u64, sqlx::Error> {
+    Ok(sqlx::query(&format!(
+        "UPDATE {} SET deleted_at = NULL WHERE id = $1",
+        table
+    ))
+    .bind(id)
+    .execute(conn)
+    .await?
+    .rows_affected())
+}
+
+fn table_name(item_type: TrashItemType) -> &'static str {
+    match item_type {
+        TrashItemType::Customer => "customers",
+        TrashItemType::JobPosting => "job_postings",
+        TrashItemType::JobSeeking => "job_seeking_postings",
+        TrashItemType::Matching => "matchings",
+        TrashItemType::CustomerMemo => "customer_memos",
+        TrashItemType::MatchingMemo => "matching_memos",
+        TrashItemType::UserMemo => "user_memos",
+        TrashItemType::CustomerFile => "customer_files",
+        TrashItemType::UserFile => "user_files",
+        TrashItemType::Tag => "tags",
+    }
+}
+
+// ========================================
+// Purge
+// ========================================
+
+/// Files whose rows are due for purging: deleted before `cutoff`, or belonging
+/// to a customer deleted before `cutoff`. Files whose storage removal failed
+/// are skipped until their retry time and come after the others, oldest first.
+pub async fn list_expired_files(
+    pool: &PgPool,
+    cutoff: NaiveDateTime,
+    limit: i64,
+) -> Result<Vec<ExpiredFile>, sqlx::Error> {
+    sqlx::query_as::<_, ExpiredFile>(
+        r#"
+        SELECT item_type, id, file_path, thumbnail_path
+        FROM (
+            SELECT
+                'customer_file' AS item_type, cf.id, cf.file_path, cf.thumbnail_path,
+                cf.purge_attempts, LEAST(cf.deleted_at, c.deleted_at) AS expired_at
+            FROM customer_files cf
+            INNER JOIN customers c ON cf.customer_id = c.id
+            WHERE (cf.deleted_at < $1 OR c.deleted_at < $1)
+                AND (cf.purge_retry_at IS NULL OR cf.purge_retry_at <= LOCALTIMESTAMP)
+
+            UNION ALL
+            SELECT
+                'user_file', uf.id, uf.file_path, uf.thumbnail_path,
+                uf.purge_attempts, uf.deleted_at
+            FROM user_files uf
+            WHERE uf.deleted_at < $1
+                AND (uf.purge_retry_at IS NULL OR uf.purge_retry_at <= LOCALTIMESTAMP)
+        ) expired
+        ORDER BY purge_attempts, expired_at, item_type, id
+        LIMIT $2
+        "#,
+    )
+    .bind(cutoff)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Count a failed storage removal and postpone the next attempt, doubling
+/// the wait from one hour up to 128 hours
+pub async fn record_file_purge_failure(
+    pool: &PgPool,
+    file: &ExpiredFile,
+) -> Result<(), sqlx::Error> {
+    let table = if file.item_type == TrashItemType::CustomerFile {
+        "customer_files"
+    } else {
+        "user_files"
+    };
+
+    sqlx::query(&format!(
+        r#"
+        UPDATE {table}
+        SET purge_attempts = purge_attempts + 1,
+            purge_retry_at = LOCALTIMESTAMP + INTERVAL '1 hour' * power(2, LEAST(purge_attempts, 7))
+        WHERE id = $1
+        "#
+    ))
+    .bind(file.id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Permanently delete a file row (after its object was removed from storage)
+pub async fn hard_delete_file(pool: &PgPool, file: &ExpiredFile) -> Result<(), sqlx::Error> {
+    if file.item_type == TrashItemType::CustomerFile {
+        // The FK clears profile_photo_id on the owning customer
+        sqlx::query("DELETE FROM customer_files WHERE id = $1")
+            .bind(file.id)
+            .execute(pool)
+            .await?;
+    } else {
+        sqlx::query("DELETE FROM user_files WHERE id = $1")
+            .bind(file.id)
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Permanently delete rows soft-deleted before `cutoff`.
+///
+/// Parents are only removed once nothing live (or any file, whose storage
+/// object must be removed first) still depends on them. Matchings with
+/// payments or issued documents are kept for the ledger, and so are the
+/// postings and customers they would be deleted with.
+pub async fn purge_expired_rows(pool: &PgPool, cutoff: NaiveDateTime) -> Result<u64, sqlx::Error> {
+    let statements = [
+        "DELETE FROM matching_memos WHERE deleted_at < $1",
+        "DELETE FROM customer_memos WHERE deleted_at < $1",
+        "DELETE FROM user_memos WHERE deleted_at < $1",
+        "DELETE FROM tags WHERE deleted_at < $1",
+        r#"
+        DELETE FROM matchings m
+        WHERE m.deleted_at < $1
+            AND NOT EXISTS (
+                SELECT 1 FROM matching_memos mm
+                WHERE mm.matching_id = m.id AND mm.deleted_at IS NULL
+            )
+            AND NOT EXISTS (SELECT 1 FROM payments p WHERE p.matching_id = m.id)
+            AND NOT EXISTS (SELECT 1 FROM fee_documents fd WHERE fd.matching_id = m.id)
+        "#,
+        r#"
+        DELETE FROM job_postings jp
+        WHERE jp.deleted_at < $1
+            AND NOT EXISTS (
+                SELECT 1 FROM matchings m
+                WHERE m.job_posting_id = jp.id
+                    AND (
+                        m.deleted_at IS NULL
+                        OR EXISTS (SELECT 1 FROM payments p WHERE p.matching_id = m.id)
+                        OR EXISTS (SELECT 1 FROM fee_documents fd WHERE fd.matching_id = m.id)
+                    )
+            )
+        "#,
+        r#"
+        DELETE FROM job_seeking_postings jsp
+        WHERE jsp.deleted_at < $1
+            AND NOT EXISTS (
+                SELECT 1 FROM matchings m
+                WHERE m.job_seeking_posting_id = jsp.id
+                    AND (
+                        m.deleted_at IS NULL
+                        OR EXISTS (SELECT 1 FROM payments p WHERE p.matching_id = m.id)
+                        OR EXISTS (SELECT 1 FROM fee_documents fd WHERE fd.matching_id = m.id)
+                    )
+            )
+        "#,
+        r#"
+        DELETE FROM customers c
+        WHERE c.deleted_at < $1
+            AND NOT EXISTS (SELECT 1 FROM customer_files cf WHERE cf.customer_id = c.id)
+            AND NOT EXISTS (
+                SELECT 1 FROM job_postings jp
+                WHERE jp.customer_id = c.id AND jp.deleted_at IS NULL
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM job_seeking_postings jsp
+                WHERE jsp.customer_id = c.id AND jsp.deleted_at IS NULL
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM customer_memos cm
+                WHERE cm.customer_id = c.id AND cm.deleted_at IS NULL
+            )
+            AND NOT EXISTS (
+                SELECT 1
+                FROM matchings m
+                LEFT JOIN job_postings jp ON jp.id = m.job_posting_id
+                LEFT JOIN job_seeking_postings jsp ON jsp.id = m.job_seeking_posting_id
+                WHERE (jp.customer_id = c.id OR jsp.customer_id = c.id)
+                    AND (
+                        EXISTS (SELECT 1 FROM payments p WHERE p.matching_id = m.id)
+                        OR EXISTS (SELECT 1 FROM fee_documents fd WHERE fd.matching_id = m.id)
+                    )
+            )
+        "#,
+    ];
+
+    let mut purged = 0;
+    for statement in statements {
+        purged += sqlx::query(statement)
+            .bind(cutoff)
+            .execute(pool)
+            .await?
+            .rows_affected();
+    }
+
+    Ok(purged)
+}
@@ -72,6 +72,13 @@ impl UserRepository {
                 phone,
                 default_employer_fee_rate,
                 default_employee_fee_rate,
+                business_number,
+                business_name,
+                representative_name,
+                business_address,
+                business_type,
+                business_item,
+                tax_invoice_email,
                 last_login_at,
                 created_at,
                 updated_at,
@@ -105,6 +112,13 @@ impl UserRepository {
                 phone,
                 default_employer_fee_rate,
                 default_employee_fee_rate,
+                business_number,
+                business_name,
+                representative_name,
+                business_address,
+                business_type,
+                business_item,
+                tax_invoice_email,
                 last_login_at,
                 created_at,
                 updated_at,
@@ -133,6 +147,13 @@ impl UserRepository {
                 phone,
                 default_employer_fee_rate,
                 default_employee_fee_rate,
+                business_number,
+                business_name,
+                representative_name,
+                business_address,
+                business_type,
+                business_item,
+                tax_invoice_email,
                 last_login_at,
                 created_at,
                 updated_at,
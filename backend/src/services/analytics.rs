@@ -0,0 +1,324 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::models::analytics::{
+    AnalyticsMetric, AnalyticsPeriod, DashboardAnalytics, Granularity, MetricMismatch, MetricRow,
+};
+use crate::repositories::analytics;
+
+/// Longest series returned, e.g. a little over a year of days
+pub const MAX_PERIODS: usize = 400;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AnalyticsError {
+    #[error("시작일은 종료일보다 늦을 수 없습니다")]
+    InvalidRange,
+    #[error("조회 기간이 너무 깁니다. 최대 {0}개 구간까지 조회할 수 있습니다")]
+    TooManyPeriods(usize),
+}
+
+/// First day of the period `date` falls in
+pub fn period_start(date: NaiveDate, granularity: Granularity) -> NaiveDate {
+    match granularity {
+        Granularity::Day => date,
+        Granularity::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        Granularity::Month => date.with_day(1).expect("every month has a first day"),
+    }
+}
+
+fn next_period_start(start: NaiveDate, granularity: Granularity) -> NaiveDate {
+    match granularity {
+        Granularity::Day => start + Duration::days(1),
+        Granularity::Week => start + Duration::weeks(1),
+        Granularity::Month => start + Months::new(1),
+    }
+}
+
+/// Start of every period from the one containing `from` to the one
+/// containing `to`
+pub fn period_starts(
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: Granularity,
+) -> Result<Vec<NaiveDate>, AnalyticsError> {
+    if from > to {
+        return Err(AnalyticsError::InvalidRange);
+    }
+
+    let mut starts = Vec::new();
+    let mut start = period_start(from, granularity);
+    while start <= to {
+        if starts.len() == MAX_PERIODS {
+            return Err(AnalyticsError::TooManyPeriods(MAX_PERIODS));
+        }
+        starts.push(start);
+        start = next_period_start(start, granularity);
+    }
+
+    Ok(starts)
+}
+
+/// Sum the metric rows of each period into a series from `from` to `to`.
+/// The first and last periods are clipped to the range.
+pub fn build_analytics(
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: Granularity,
+    starts: &[NaiveDate],
+    rows: &[MetricRow],
+) -> DashboardAnalytics {
+    let mut per_period: Vec<HashMap<AnalyticsMetric, Decimal>> = vec![HashMap::new(); starts.len()];
+    let mut overall: HashMap<AnalyticsMetric, Decimal> = HashMap::new();
+
+    for row in rows {
+        let start = period_start(row.bucket, granularity);
+        let Ok(index) = starts.binary_search(&start) else {
+            continue;
+        };
+        *per_period[index].entry(row.metric).or_default() += row.value;
+        *overall.entry(row.metric).or_default() += row.value;
+    }
+
+    let periods = starts
+        .iter()
+        .zip(&per_period)
+        .map(|(start, values)| {
+            let end = next_period_start(*start, granularity) - Duration::days(1);
+            summarize((*start).max(from), end.min(to), values)
+        })
+        .collect();
+
+    DashboardAnalytics {
+        from,
+        to,
+        granularity,
+        periods,
+        totals: summarize(from, to, &overall),
+    }
+}
+
+fn summarize(
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    values: &HashMap<AnalyticsMetric, Decimal>,
+) -> AnalyticsPeriod {
+    let value = |metric| values.get(&metric).copied().unwrap_or_default();
+    let count = |metric| value(metric).to_i64().unwrap_or_default();
+    let ratio = |numerator: Decimal, denominator: Decimal| {
+        (!denominator.is_zero()).then(|| numerator / denominator)
+    };
+
+    AnalyticsPeriod {
+        period_start,
+        period_end,
+        revenue_earned: value(AnalyticsMetric::RevenueEarned),
+        revenue_collected: value(AnalyticsMetric::RevenueCollected),
+        new_customers: count(AnalyticsMetric::NewCustomers),
+        postings_opened: count(AnalyticsMetric::PostingsOpened),
+        postings_closed: count(AnalyticsMetric::PostingsClosed),
+        matchings_created: count(AnalyticsMetric::MatchingsCreated),
+        matchings_completed: count(AnalyticsMetric::MatchingsCompleted),
+        matchings_cancelled: count(AnalyticsMetric::MatchingsCancelled),
+        conversion_rate: ratio(
+            value(AnalyticsMetric::JobPostingsFilled) * Decimal::ONE_HUNDRED,
+            value(AnalyticsMetric::JobPostingsOpened),
+        )
+        .map(|rate| rate.round_dp(1)),
+        average_days_to_match: ratio(
+            value(AnalyticsMetric::MatchDays),
+            value(AnalyticsMetric::MatchingsCreated),
+        )
+        .map(|days| days.round_dp(1)),
+    }
+}
+
+/// Group sorted days into runs of consecutive days, as `(first, last)`
+pub fn contiguous_ranges(days: &[NaiveDate]) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut ranges: Vec<(NaiveDate, NaiveDate)> = Vec::new();
+    for &day in days {
+        match ranges.last_mut() {
+            Some((_, last)) if day <= *last + Duration::days(1) => *last = (*last).max(day),
+            _ => ranges.push((day, day)),
+        }
+    }
+    ranges
+}
+
+/// Days and metrics whose rolled-up value differs from the live one. A
+/// missing row counts as zero.
+pub fn compare_metrics(rollup: &[MetricRow], live: &[MetricRow]) -> Vec<MetricMismatch> {
+    let mut values: BTreeMap<(NaiveDate, &str), (AnalyticsMetric, Decimal, Decimal)> =
+        BTreeMap::new();
+    for row in rollup {
+        values
+            .entry((row.bucket, row.metric.as_str()))
+            .or_insert((row.metric, Decimal::ZERO, Decimal::ZERO))
+            .1 += row.value;
+    }
+    for row in live {
+        values
+            .entry((row.bucket, row.metric.as_str()))
+            .or_insert((row.metric, Decimal::ZERO, Decimal::ZERO))
+            .2 += row.value;
+    }
+
+    values
+        .into_iter()
+        .filter(|(_, (_, rollup, live))| rollup != live)
+        .map(|((day, _), (metric, rollup, live))| MetricMismatch {
+            day,
+            metric,
+            rollup,
+            live,
+        })
+        .collect()
+}
+
+/// Periodically roll up the days marked dirty by writes, so dashboards
+/// rarely have to wait for it.
+pub fn spawn_rollup_task(pool: PgPool, config: Config) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            config.analytics_rollup_interval_secs,
+        ));
+
+        loop {
+            interval.tick().await;
+            match analytics::refresh_all_dirty_days(&pool).await {
+                Ok(refreshed) if refreshed > 0 => {
+                    tracing::info!("Rolled up {} days of dashboard statistics", refreshed);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Analytics rollup failed: {:?}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn row(bucket: NaiveDate, metric: AnalyticsMetric, value: i64) -> MetricRow {
+        MetricRow {
+            bucket,
+            metric,
+            value: Decimal::new(value, 0),
+        }
+    }
+
+    #[test]
+    fn test_period_starts() {
+        assert_eq!(
+            period_starts(date(2025, 1, 15), date(2025, 3, 2), Granularity::Month),
+            Ok(vec![date(2025, 1, 1), date(2025, 2, 1), date(2025, 3, 1)])
+        );
+        // 2025-03-05 is a Wednesday
+        assert_eq!(
+            period_starts(date(2025, 3, 5), date(2025, 3, 10), Granularity::Week),
+            Ok(vec![date(2025, 3, 3), date(2025, 3, 10)])
+        );
+        assert_eq!(
+            period_starts(date(2025, 3, 2), date(2025, 3, 1), Granularity::Day),
+            Err(AnalyticsError::InvalidRange)
+        );
+        assert_eq!(
+            period_starts(date(2020, 1, 1), date(2025, 1, 1), Granularity::Day),
+            Err(AnalyticsError::TooManyPeriods(MAX_PERIODS))
+        );
+    }
+
+    #[test]
+    fn test_build_analytics_sums_days_into_periods() {
+        let from = date(2025, 1, 15);
+        let to = date(2025, 2, 10);
+        let starts = period_starts(from, to, Granularity::Month).unwrap();
+        let rows = [
+            row(date(2025, 1, 20), AnalyticsMetric::RevenueEarned, 300_000),
+            row(date(2025, 1, 31), AnalyticsMetric::RevenueEarned, 200_000),
+            row(date(2025, 1, 20), AnalyticsMetric::JobPostingsOpened, 4),
+            row(date(2025, 1, 20), AnalyticsMetric::JobPostingsFilled, 1),
+            row(date(2025, 2, 3), AnalyticsMetric::MatchingsCreated, 2),
+            row(date(2025, 2, 3), AnalyticsMetric::MatchDays, 7),
+        ];
+
+        let analytics = build_analytics(from, to, Granularity::Month, &starts, &rows);
+        let january = &analytics.periods[0];
+        assert_eq!(january.period_start, from);
+        assert_eq!(january.period_end, date(2025, 1, 31));
+        assert_eq!(january.revenue_earned, Decimal::new(500_000, 0));
+        assert_eq!(january.conversion_rate, Some(Decimal::new(250, 1)));
+        assert_eq!(january.average_days_to_match, None);
+
+        let february = &analytics.periods[1];
+        assert_eq!(february.period_end, to);
+        assert_eq!(february.matchings_created, 2);
+        assert_eq!(february.average_days_to_match, Some(Decimal::new(35, 1)));
+        assert_eq!(february.conversion_rate, None);
+
+        assert_eq!(analytics.totals.revenue_earned, Decimal::new(500_000, 0));
+        assert_eq!(analytics.totals.matchings_created, 2);
+    }
+
+    #[test]
+    fn test_contiguous_ranges() {
+        let days = [
+            date(2025, 1, 30),
+            date(2025, 1, 31),
+            date(2025, 2, 1),
+            date(2025, 2, 3),
+            date(2025, 2, 3),
+        ];
+        assert_eq!(
+            contiguous_ranges(&days),
+            vec![
+                (date(2025, 1, 30), date(2025, 2, 1)),
+                (date(2025, 2, 3), date(2025, 2, 3)),
+            ]
+        );
+        assert!(contiguous_ranges(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_compare_metrics_treats_missing_rows_as_zero() {
+        let day = date(2025, 3, 4);
+        let rollup = [
+            row(day, AnalyticsMetric::NewCustomers, 2),
+            row(day, AnalyticsMetric::FeesCharged, 250_000),
+            row(day, AnalyticsMetric::MatchingsCancelled, 0),
+        ];
+        let live = [
+            row(day, AnalyticsMetric::NewCustomers, 2),
+            row(day, AnalyticsMetric::FeesCharged, 350_000),
+            row(day, AnalyticsMetric::FeesPaid, 100_000),
+        ];
+
+        assert_eq!(
+            compare_metrics(&rollup, &live),
+            vec![
+                MetricMismatch {
+                    day,
+                    metric: AnalyticsMetric::FeesCharged,
+                    rollup: Decimal::new(250_000, 0),
+                    live: Decimal::new(350_000, 0),
+                },
+                MetricMismatch {
+                    day,
+                    metric: AnalyticsMetric::FeesPaid,
+                    rollup: Decimal::ZERO,
+                    live: Decimal::new(100_000, 0),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::models::bank_import::{MatchSuggestion, OpenFeeRow, ParsedDeposit};
+use crate::services::payment;
+
+/// Rows searched for the header line; exports often start with account details
+const HEADER_SEARCH_ROWS: usize = 30;
+/// Suggestions scoring below this are not shown
+const MIN_SUGGESTION_SCORE: i32 = 30;
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum BankStatementError {
+    #[error("파일을 읽을 수 없습니다: {0}")]
+    Csv(String),
+    #[error("거래일시와 입금액 열을 찾을 수 없습니다")]
+    HeaderNotFound,
+    #[error("{line}번째 줄의 금액을 읽을 수 없습니다: {value}")]
+    InvalidAmount { line: usize, value: String },
+}
+
+/// Header names used by Korean banks, most specific first
+const DATE_HEADERS: &[&str] = &["거래일시", "거래일자", "거래일", "거래날짜", "일자", "날짜"];
+const TIME_HEADERS: &[&str] = &["거래시간", "시간"];
+const DEPOSIT_HEADERS: &[&str] = &["입금액", "맡기신금액", "입금금액", "입금"];
+const AMOUNT_HEADERS: &[&str] = &["거래금액", "금액"];
+const KIND_HEADERS: &[&str] = &["입출금구분", "거래구분", "구분"];
+const DEPOSITOR_HEADERS: &[&str] = &[
+    "입금자명",
+    "보낸분",
+    "의뢰인",
+    "기재내용",
+    "받는분/보낸분",
+    "거래내용",
+    "내용",
+    "적요",
+];
+const MEMO_HEADERS: &[&str] = &["적요", "메모", "거래점", "비고"];
+const BALANCE_HEADERS: &[&str] = &["거래후잔액", "잔액"];
+
+/// Decode a statement file. Bank exports are usually EUC-KR (CP949); UTF-8
+/// files, with or without a byte order mark, are accepted as well.
+pub fn decode(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => encoding_rs::EUC_KR.decode(bytes).0.into_owned(),
+    }
+}
+
+fn normalize_header(cell: &str) -> String {
+    cell.chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .replace("(원)", "")
+}
+
+/// Columns of a statement, found from its header row
+#[derive(Debug, Default)]
+struct Columns {
+    date: usize,
+    time: Option<usize>,
+    deposit: Option<usize>,
+    /// Signed or unsigned amount with a separate deposit/withdrawal column
+    amount: Option<(usize, usize)>,
+    depositor: Option<usize>,
+    memo: Option<usize>,
+    balance: Option<usize>,
+}
+
+impl Columns {
+    fn from_header(header: &[String]) -> Option<Self> {
+        let cells: Vec<String> = header.iter().map(|cell| normalize_header(cell)).collect();
+        let find = |names: &[&str], taken: &[usize]| {
+            names.iter().find_map(|name| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .position(|(i, cell)| cell == name && !taken.contains(&i))
+            })
+        };
+
+        let date = find(DATE_HEADERS, &[])?;
+        let deposit = find(DEPOSIT_HEADERS, &[date]);
+        let amount = match deposit {
+            Some(_) => None,
+            None => Some((find(AMOUNT_HEADERS, &[date])?, find(KIND_HEADERS, &[date])?)),
+        };
+        let depositor = find(DEPOSITOR_HEADERS, &[date]);
+        let taken: Vec<usize> = [Some(date), depositor].into_iter().flatten().collect();
+
+        Some(Columns {
+            date,
+            time: find(TIME_HEADERS, &[date]),
+            deposit,
+            amount,
+            depositor,
+            memo: find(MEMO_HEADERS, &taken),
+            balance: find(BALANCE_HEADERS, &[date]),
+        })
+    }
+}
+
+/// Empty cells and a lone "-" (no transaction on that side) read as zero
+fn parse_amount(value: &str) -> Option<Decimal> {
+    let cleaned: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',' && *c != '원')
+        .collect();
+    if cleaned.is_empty() || cleaned == "-" {
+        return Some(Decimal::ZERO);
+    }
+    cleaned.parse().ok()
+}
+
+/// Parse "2025-03-02 14:03:11", "2025.03.02", "2025/03/02 14:03", "20250302"
+/// and the like from the digits of `date` (and of `time` if separate)
+fn parse_datetime(date: &str, time: Option<&str>) -> Option<NaiveDateTime> {
+    let digits: String = date
+        .chars()
+        .chain(time.unwrap_or_default().chars())
+        .filter(|c| c.is_ascii_digit())
+        .collect();
+    if digits.len() < 8 {
+        return None;
+    }
+
+    let date = NaiveDate::from_ymd_opt(
+        digits[0..4].parse().ok()?,
+        digits[4..6].parse().ok()?,
+        digits[6..8].parse().ok()?,
+    )?;
+    let part = |range: std::ops::Range<usize>| {
+        digits
+            .get(range)
+            .and_then(|value| value.parse::<u32>().ok())
+            .unwrap_or(0)
+    };
+    let time = NaiveTime::from_hms_opt(part(8..10), part(10..12), part(12..14))?;
+
+    Some(date.and_time(time))
+}
+
+/// Read the deposits of a bank statement CSV. Withdrawals and rows without a
+/// date (summaries, footers) are skipped.
+pub fn parse_statement(text: &str) -> Result<Vec<ParsedDeposit>, BankStatementError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let rows = reader
+        .records()
+        .map(|record| {
+            record
+                .map(|record| record.iter().map(|cell| cell.trim().to_string()).collect())
+                .map_err(|e| BankStatementError::Csv(e.to_string()))
+        })
+        .collect::<Result<Vec<Vec<String>>, _>>()?;
+
+    let (header_index, columns) = rows
+        .iter()
+        .take(HEADER_SEARCH_ROWS)
+        .enumerate()
+        .find_map(|(i, row)| Columns::from_header(row).map(|columns| (i, columns)))
+        .ok_or(BankStatementError::HeaderNotFound)?;
+
+    let mut deposits = Vec::new();
+    for (i, row) in rows.iter().enumerate().skip(header_index + 1) {
+        let line = i + 1;
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|index| row.get(index))
+                .map(String::as_str)
+                .unwrap_or_default()
+        };
+        let Some(transacted_at) = parse_datetime(
+            cell(Some(columns.date)),
+            columns.time.map(|_| cell(columns.time)),
+        ) else {
+            continue;
+        };
+
+        let raw_amount = match (columns.deposit, columns.amount) {
+            (Some(deposit), _) => cell(Some(deposit)),
+            (None, Some((amount, kind))) if cell(Some(kind)).contains("입금") => {
+                cell(Some(amount))
+            }
+            _ => continue,
+        };
+        let amount = parse_amount(raw_amount).ok_or_else(|| BankStatementError::InvalidAmount {
+            line,
+            value: raw_amount.to_string(),
+        })?;
+        if amount <= Decimal::ZERO {
+            continue;
+        }
+
+        let memo = cell(columns.memo);
+        deposits.push(ParsedDeposit {
+            transacted_at,
+            depositor: cell(columns.depositor).to_string(),
+            amount,
+            memo: (!memo.is_empty()).then(|| memo.to_string()),
+            balance_after: parse_amount(cell(columns.balance))
+                .filter(|_| columns.balance.is_some()),
+        });
+    }
+
+    Ok(deposits)
+}
+
+/// For each deposit, how many deposits before it in the same file (itself
+/// included) have the same time, depositor, amount and balance. Identical
+/// deposits in one statement are separate transfers, so this keeps them apart
+/// when stored while a re-imported file still matches the stored rows.
+pub fn occurrences(deposits: &[ParsedDeposit]) -> Vec<i32> {
+    let mut seen = HashMap::new();
+    deposits
+        .iter()
+        .map(|deposit| {
+            let count = seen
+                .entry((
+                    deposit.transacted_at,
+                    deposit.depositor.as_str(),
+                    deposit.amount,
+                    deposit.balance_after,
+                ))
+                .or_insert(0);
+            *count += 1;
+            *count
+        })
+        .collect()
+}
+
+/// Reduce a name to the characters compared: no spaces, punctuation or
+/// company markers ("(주)", "주식회사")
+fn normalize_name(name: &str) -> String {
+    name.replace("(주)", "")
+        .replace("주식회사", "")
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Similarity of a depositor name to a customer name, 0.0–1.0. Bank
+/// statements often truncate names or add a note, so containment counts as
+/// a strong match; otherwise character bigrams are compared.
+pub fn name_similarity(depositor: &str, customer_name: &str) -> f64 {
+    let a = normalize_name(depositor);
+    let b = normalize_name(customer_name);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    let (shorter, longer) = if a.chars().count() <= b.chars().count() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    if shorter.chars().count() >= 2 && longer.contains(shorter.as_str()) {
+        return 0.8;
+    }
+
+    let bigrams = |s: &str| -> HashSet<(char, char)> {
+        let chars: Vec<char> = s.chars().collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    };
+    let (a, b) = (bigrams(&a), bigrams(&b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    2.0 * a.intersection(&b).count() as f64 / (a.len() + b.len()) as f64
+}
+
+/// Score how likely a deposit pays `fee`; `None` when it cannot (the amount
+/// is larger than what is outstanding, or it came long before the matching).
+///
+/// Amount is worth up to 50 points (exact outstanding amount; 20 for a
+/// smaller instalment), the depositor name up to 40 and the date up to 10.
+pub fn score(deposit: &ParsedDeposit, fee: &OpenFeeRow) -> Option<MatchSuggestion> {
+    let balance = payment::side_balance(
+        fee.payer_side,
+        fee.fee_amount,
+        fee.refunded_amount,
+        fee.paid_amount,
+    );
+    let outstanding = balance.outstanding_amount;
+    if outstanding <= Decimal::ZERO || deposit.amount > outstanding {
+        return None;
+    }
+
+    let days = (deposit.transacted_at.date() - fee.since).num_days();
+    let date_score = match days {
+        ..-7 => return None,
+        -7..=30 => 10,
+        31..=90 => 5,
+        _ => 0,
+    };
+    let amount_score = if deposit.amount == outstanding {
+        50
+    } else {
+        20
+    };
+    let name_score =
+        (name_similarity(&deposit.depositor, &fee.customer_name) * 40.0).round() as i32;
+
+    Some(MatchSuggestion {
+        matching_id: fee.matching_id,
+        payer_side: fee.payer_side,
+        customer_id: fee.customer_id,
+        customer_name: fee.customer_name.clone(),
+        outstanding_amount: outstanding,
+        score: amount_score + name_score + date_score,
+        amount_score,
+        name_score,
+        date_score,
+    })
+}
+
+/// Best few fees a deposit may be paying, highest score first
+pub fn suggest(deposit: &ParsedDeposit, fees: &[OpenFeeRow]) -> Vec<MatchSuggestion> {
+    let mut suggestions: Vec<MatchSuggestion> = fees
+        .iter()
+        .filter_map(|fee| score(deposit, fee))
+        .filter(|suggestion| suggestion.score >= MIN_SUGGESTION_SCORE)
+        .collect();
+    suggestions
+        .sort_by_key(|suggestion| (std::cmp::Reverse(suggestion.score), suggestion.matching_id));
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::payment::PayerSide;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn deposit(depositor: &str, amount: i64, on: &str) -> ParsedDeposit {
+        ParsedDeposit {
+            transacted_at: date(on).and_hms_opt(9, 0, 0).unwrap(),
+            depositor: depositor.to_string(),
+            amount: Decimal::new(amount, 0),
+            memo: None,
+            balance_after: None,
+        }
+    }
+
+    fn fee(matching_id: i64, name: &str, fee: i64, paid: i64, since: &str) -> OpenFeeRow {
+        OpenFeeRow {
+            matching_id,
+            payer_side: PayerSide::Employer,
+            customer_id: matching_id,
+            customer_name: name.to_string(),
+            since: date(since),
+            fee_amount: Decimal::new(fee, 0),
+            refunded_amount: Decimal::ZERO,
+            paid_amount: Decimal::new(paid, 0),
+        }
+    }
+
+    #[test]
+    fn test_parse_euc_kr_statement_with_preamble() {
+        let csv = "계좌번호,123-456-789\n\
+                   조회기간,2025.03.01~2025.03.31\n\
+                   거래일시,적요,기재내용,찾으신금액,맡기신금액,거래후 잔액,거래점\n\
+                   2025.03.02 14:03:11,타행이체,김민수,0,\"330,000\",\"1,330,000\",국민\n\
+                   2025.03.03 09:10:00,카드,관리비,\"50,000\",0,\"1,280,000\",\n\
+                   합계,,,\"50,000\",\"330,000\",,\n";
+        let (encoded, _, _) = encoding_rs::EUC_KR.encode(csv);
+
+        let deposits = parse_statement(&decode(&encoded)).unwrap();
+        assert_eq!(
+            deposits,
+            vec![ParsedDeposit {
+                transacted_at: date("2025-03-02").and_hms_opt(14, 3, 11).unwrap(),
+                depositor: "김민수".to_string(),
+                amount: Decimal::new(330_000, 0),
+                memo: Some("타행이체".to_string()),
+                balance_after: Some(Decimal::new(1_330_000, 0)),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_with_kind_column() {
+        let csv = "\u{feff}거래일자,거래시간,구분,거래금액,입금자명,잔액\n\
+                   20250305,1530,입금,200000,박영희(구직),700000\n\
+                   20250306,0900,출금,100000,월세,600000\n";
+
+        let deposits = parse_statement(&decode(csv.as_bytes())).unwrap();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(
+            deposits[0].transacted_at,
+            date("2025-03-05").and_hms_opt(15, 30, 0).unwrap()
+        );
+        assert_eq!(deposits[0].depositor, "박영희(구직)");
+        assert_eq!(deposits[0].amount, Decimal::new(200_000, 0));
+    }
+
+    #[test]
+    fn test_parse_statement_with_dash_for_no_amount() {
+        let csv = "거래일시,출금액,입금액,내용\n\
+                   2025-03-02 10:00,-,\"150,000\",이영수\n\
+                   2025-03-02 11:00,\"20,000\",-,수수료\n";
+
+        let deposits = parse_statement(&decode(csv.as_bytes())).unwrap();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].depositor, "이영수");
+        assert_eq!(deposits[0].amount, Decimal::new(150_000, 0));
+    }
+
+    #[test]
+    fn test_parse_statement_errors() {
+        assert_eq!(
+            parse_statement("a,b,c\n1,2,3\n"),
+            Err(BankStatementError::HeaderNotFound)
+        );
+        assert_eq!(
+            parse_statement("거래일시,입금액\n2025-03-02,abc\n"),
+            Err(BankStatementError::InvalidAmount {
+                line: 2,
+                value: "abc".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_occurrences_count_identical_deposits() {
+        let deposits = [
+            deposit("김민수", 330_000, "2025-03-02"),
+            deposit("김민수", 330_000, "2025-03-02"),
+            deposit("이영희", 330_000, "2025-03-02"),
+            deposit("김민수", 330_000, "2025-03-02"),
+        ];
+        assert_eq!(occurrences(&deposits), vec![1, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_name_similarity() {
+        assert_eq!(name_similarity("김민수", "김 민수"), 1.0);
+        assert_eq!(name_similarity("(주)행복상사", "행복상사"), 1.0);
+        assert_eq!(name_similarity("박영희(구직)", "박영희"), 0.8);
+        assert_eq!(name_similarity("김민호", "김민수"), 0.5);
+        assert_eq!(name_similarity("이영희", "김민수"), 0.0);
+        assert_eq!(name_similarity("", "김민수"), 0.0);
+    }
+
+    #[test]
+    fn test_suggest_ranks_by_amount_name_and_date() {
+        let fees = [
+            fee(1, "김민수", 330_000, 0, "2025-02-20"),
+            fee(2, "이영희", 330_000, 0, "2025-02-25"),
+            fee(3, "김민수", 500_000, 0, "2024-12-15"),
+            // 미수금보다 큰 입금은 후보가 아님
+            fee(4, "김민수", 300_000, 0, "2025-02-20"),
+        ];
+
+        let suggestions = suggest(&deposit("김민수", 330_000, "2025-03-02"), &fees);
+        let ids: Vec<i64> = suggestions.iter().map(|s| s.matching_id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+        assert_eq!(suggestions[0].score, 100);
+        assert_eq!(suggestions[1].amount_score, 20);
+        assert_eq!(suggestions[1].date_score, 5);
+        assert_eq!(suggestions[2].name_score, 0);
+    }
+
+    #[test]
+    fn test_suggest_skips_deposits_before_matching() {
+        let fees = [fee(1, "김민수", 330_000, 0, "2025-03-20")];
+        assert!(suggest(&deposit("김민수", 330_000, "2025-03-02"), &fees).is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use thiserror::Error;
+
+/// Weights of the first nine digits in the registration number checksum
+const CHECKSUM_WEIGHTS: [u32; 9] = [1, 3, 7, 1, 3, 7, 1, 3, 5];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum BusinessNumberError {
+    #[error("사업자등록번호를 입력해주세요")]
+    Empty,
+    #[error("사업자등록번호에 사용할 수 없는 문자가 포함되어 있습니다")]
+    InvalidCharacters,
+    #[error("사업자등록번호는 숫자 10자리여야 합니다")]
+    InvalidLength,
+    #[error("유효하지 않은 사업자등록번호입니다")]
+    InvalidChecksum,
+}
+
+/// Normalize a Korean business registration number to its 10 digits.
+///
+/// Accepts "123-45-67890", "123 45 67890" or "1234567890" and verifies the
+/// check digit.
+pub fn normalize_business_number(input: &str) -> Result<String, BusinessNumberError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(BusinessNumberError::Empty);
+    }
+
+    if !trimmed
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '-' | ' '))
+    {
+        return Err(BusinessNumberError::InvalidCharacters);
+    }
+
+    let digits: Vec<u32> = trimmed.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() != 10 {
+        return Err(BusinessNumberError::InvalidLength);
+    }
+
+    let mut sum: u32 = digits
+        .iter()
+        .zip(CHECKSUM_WEIGHTS)
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+    sum += digits[8] * 5 / 10;
+
+    if (10 - sum % 10) % 10 != digits[9] {
+        return Err(BusinessNumberError::InvalidChecksum);
+    }
+
+    Ok(digits
+        .iter()
+        .map(|d| char::from_digit(*d, 10).unwrap())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_business_number() {
+        for input in ["220-81-62517", "2208162517", " 220 81 62517 "] {
+            assert_eq!(
+                normalize_business_number(input).unwrap(),
+                "2208162517",
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_rejects_invalid_numbers() {
+        assert_eq!(
+            normalize_business_number(" "),
+            Err(BusinessNumberError::Empty)
+        );
+        assert_eq!(
+            normalize_business_number("220-81-6251a"),
+            Err(BusinessNumberError::InvalidCharacters)
+        );
+        assert_eq!(
+            normalize_business_number("220-81-6251"),
+            Err(BusinessNumberError::InvalidLength)
+        );
+        assert_eq!(
+            normalize_business_number("220-81-62518"),
+            Err(BusinessNumberError::InvalidChecksum)
+        );
+    }
+}
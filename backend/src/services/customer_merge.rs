@@ -0,0 +1,174 @@
+use crate::models::customer::{Customer, MergeFieldChoices, MergeSelection, MergeSide};
+use crate::services::phone::normalize_phone;
+
+/// Decide, field by field, whose value the merged customer keeps.
+///
+/// Values that are equal or only present on one side resolve automatically.
+/// Fields that differ on both sides need an explicit choice; their names are
+/// returned as the error when the choice is missing.
+pub fn resolve_merge_selection(
+    target: &Customer,
+    source: &Customer,
+    choices: &MergeFieldChoices,
+) -> Result<MergeSelection, Vec<&'static str>> {
+    let mut conflicts = Vec::new();
+
+    let selection = MergeSelection {
+        name: pick(
+            "name",
+            Some(&target.name),
+            Some(&source.name),
+            choices.name,
+            &mut conflicts,
+        ),
+        birth_date: pick(
+            "birth_date",
+            target.birth_date.as_ref(),
+            source.birth_date.as_ref(),
+            choices.birth_date,
+            &mut conflicts,
+        ),
+        phone: pick_phone(target, source, choices.phone, &mut conflicts),
+        address: pick(
+            "address",
+            target.address.as_ref().filter(|a| !a.trim().is_empty()),
+            source.address.as_ref().filter(|a| !a.trim().is_empty()),
+            choices.address,
+            &mut conflicts,
+        ),
+        customer_type: pick(
+            "customer_type",
+            Some(&target.customer_type),
+            Some(&source.customer_type),
+            choices.customer_type,
+            &mut conflicts,
+        ),
+        profile_photo: pick(
+            "profile_photo",
+            target.profile_photo_id.as_ref(),
+            source.profile_photo_id.as_ref(),
+            choices.profile_photo,
+            &mut conflicts,
+        ),
+    };
+
+    if conflicts.is_empty() {
+        Ok(selection)
+    } else {
+        Err(conflicts)
+    }
+}
+
+fn pick<T: PartialEq>(
+    field: &'static str,
+    target: Option<&T>,
+    source: Option<&T>,
+    choice: Option<MergeSide>,
+    conflicts: &mut Vec<&'static str>,
+) -> MergeSide {
+    if let Some(choice) = choice {
+        return choice;
+    }
+
+    match (target, source) {
+        (_, None) => MergeSide::Target,
+        (None, Some(_)) => MergeSide::Source,
+        (Some(t), Some(s)) if t == s => MergeSide::Target,
+        _ => {
+            conflicts.push(field);
+            MergeSide::Target
+        }
+    }
+}
+
+/// Both customers always have a phone number; they only conflict when the
+/// numbers differ once normalized
+fn pick_phone(
+    target: &Customer,
+    source: &Customer,
+    choice: Option<MergeSide>,
+    conflicts: &mut Vec<&'static str>,
+) -> MergeSide {
+    if let Some(choice) = choice {
+        return choice;
+    }
+
+    let same_phone = match (
+        normalize_phone(&target.phone),
+        normalize_phone(&source.phone),
+    ) {
+        (Ok(t), Ok(s)) => t == s,
+        _ => target.phone.trim() == source.phone.trim(),
+    };
+
+    if !same_phone {
+        conflicts.push("phone");
+    }
+    MergeSide::Target
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::customer::CustomerType;
+    use chrono::NaiveDate;
+
+    fn customer(id: i64, name: &str, phone: &str) -> Customer {
+        let now = NaiveDate::from_ymd_opt(2025, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        Customer {
+            id,
+            user_id: 1,
+            name: name.to_string(),
+            birth_date: None,
+            phone: phone.to_string(),
+            address: None,
+            business_number: None,
+            profile_photo_id: None,
+            customer_type: CustomerType::Employer,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_missing_values_are_filled_from_source() {
+        let target = customer(1, "김민수", "010-1234-5678");
+        let mut source = customer(2, "김민수", "+82 10 1234 5678");
+        source.address = Some("서울시 강남구".to_string());
+        source.profile_photo_id = Some(10);
+
+        let selection =
+            resolve_merge_selection(&target, &source, &MergeFieldChoices::default()).unwrap();
+
+        assert_eq!(selection.name, MergeSide::Target);
+        assert_eq!(selection.phone, MergeSide::Target);
+        assert_eq!(selection.address, MergeSide::Source);
+        assert_eq!(selection.profile_photo, MergeSide::Source);
+    }
+
+    #[test]
+    fn test_conflicts_require_explicit_choice() {
+        let target = customer(1, "김민수", "010-1234-5678");
+        let mut source = customer(2, "김민수(중복)", "010-9999-8888");
+        source.customer_type = CustomerType::Both;
+
+        let conflicts =
+            resolve_merge_selection(&target, &source, &MergeFieldChoices::default()).unwrap_err();
+        assert_eq!(conflicts, vec!["name", "phone", "customer_type"]);
+
+        let choices = MergeFieldChoices {
+            name: Some(MergeSide::Target),
+            phone: Some(MergeSide::Source),
+            customer_type: Some(MergeSide::Source),
+            ..Default::default()
+        };
+        let selection = resolve_merge_selection(&target, &source, &choices).unwrap();
+        assert_eq!(selection.name, MergeSide::Target);
+        assert_eq!(selection.phone, MergeSide::Source);
+        assert_eq!(selection.customer_type, MergeSide::Source);
+    }
+}
@@ -0,0 +1,492 @@
+use bytes::Bytes;
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+use crate::models::export::{
+    CustomerExportRow, JobPostingExportRow, JobSeekingExportRow, MatchingExportRow,
+    TaxInvoiceExportRow,
+};
+use crate::models::fee_policy::FeeBreakdown;
+use crate::services::format::{
+    customer_type_label, format_won, matching_status_label, payer_side_label, posting_status_label,
+    settlement_status_label,
+};
+
+/// UTF-8 byte order mark so that Excel (Korean locale) opens the CSV as UTF-8
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// A single cell value of an exported row
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportCell {
+    Text(String),
+    Number(Decimal),
+    Empty,
+}
+
+impl From<String> for ExportCell {
+    fn from(value: String) -> Self {
+        ExportCell::Text(value)
+    }
+}
+
+impl From<&str> for ExportCell {
+    fn from(value: &str) -> Self {
+        ExportCell::Text(value.to_string())
+    }
+}
+
+impl From<i64> for ExportCell {
+    fn from(value: i64) -> Self {
+        ExportCell::Number(Decimal::from(value))
+    }
+}
+
+impl From<Decimal> for ExportCell {
+    fn from(value: Decimal) -> Self {
+        ExportCell::Number(value)
+    }
+}
+
+impl From<NaiveDate> for ExportCell {
+    fn from(value: NaiveDate) -> Self {
+        ExportCell::Text(value.format("%Y-%m-%d").to_string())
+    }
+}
+
+impl From<NaiveDateTime> for ExportCell {
+    fn from(value: NaiveDateTime) -> Self {
+        ExportCell::Text(value.format("%Y-%m-%d %H:%M:%S").to_string())
+    }
+}
+
+impl<T: Into<ExportCell>> From<Option<T>> for ExportCell {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(ExportCell::Empty)
+    }
+}
+
+impl ExportCell {
+    fn to_csv_field(&self) -> String {
+        match self {
+            ExportCell::Text(text) => text.clone(),
+            ExportCell::Number(number) => number.normalize().to_string(),
+            ExportCell::Empty => String::new(),
+        }
+    }
+}
+
+/// A row type that can be written to an export file
+pub trait ExportRecord {
+    /// Column headers, in the same order as `cells`
+    const HEADERS: &'static [&'static str];
+
+    fn cells(&self) -> Vec<ExportCell>;
+}
+
+/// Encode the CSV header line, prefixed with a UTF-8 BOM
+pub fn csv_header<R: ExportRecord>() -> Result<Bytes, csv::Error> {
+    let mut buffer = UTF8_BOM.to_vec();
+    buffer.extend_from_slice(&encode_csv_record(
+        R::HEADERS.iter().map(|h| h.to_string()),
+    )?);
+    Ok(Bytes::from(buffer))
+}
+
+/// Encode a single record as one CSV line
+pub fn csv_row<R: ExportRecord>(record: &R) -> Result<Bytes, csv::Error> {
+    let cells = record.cells();
+    Ok(Bytes::from(encode_csv_record(
+        cells.iter().map(ExportCell::to_csv_field),
+    )?))
+}
+
+fn encode_csv_record(fields: impl Iterator<Item = String>) -> Result<Vec<u8>, csv::Error> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    writer.write_record(fields)?;
+    writer
+        .into_inner()
+        .map_err(|e| csv::Error::from(e.into_error()))
+}
+
+/// XLSX workbook that writes rows to disk as they are pushed (constant memory mode)
+pub struct XlsxExport {
+    workbook: Workbook,
+    next_row: u32,
+}
+
+impl XlsxExport {
+    pub fn new<R: ExportRecord>(sheet_name: &str) -> Result<Self, XlsxError> {
+        let mut workbook = Workbook::new();
+        let header_format = Format::new().set_bold();
+
+        let worksheet = workbook.add_worksheet_with_constant_memory();
+        worksheet.set_name(sheet_name)?;
+        worksheet.set_freeze_panes(1, 0)?;
+        for (col, header) in R::HEADERS.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+        }
+
+        Ok(Self {
+            workbook,
+            next_row: 1,
+        })
+    }
+
+    pub fn push<R: ExportRecord>(&mut self, record: &R) -> Result<(), XlsxError> {
+        let worksheet = self.workbook.worksheet_from_index(0)?;
+        for (col, cell) in record.cells().into_iter().enumerate() {
+            let col = col as u16;
+            match cell {
+                ExportCell::Text(text) => {
+                    worksheet.write_string(self.next_row, col, text)?;
+                }
+                ExportCell::Number(number) => {
+                    worksheet.write_number(self.next_row, col, number.to_f64().unwrap_or(0.0))?;
+                }
+                ExportCell::Empty => {}
+            }
+        }
+        self.next_row += 1;
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<Vec<u8>, XlsxError> {
+        self.workbook.save_to_buffer()
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "Y"
+    } else {
+        "N"
+    }
+}
+
+impl ExportRecord for CustomerExportRow {
+    const HEADERS: &'static [&'static str] = &[
+        "고객 ID",
+        "이름",
+        "고객 유형",
+        "전화번호",
+        "생년월일",
+        "주소",
+        "태그",
+        "등록일",
+    ];
+
+    fn cells(&self) -> Vec<ExportCell> {
+        vec![
+            self.id.into(),
+            self.name.clone().into(),
+            customer_type_label(&self.customer_type).into(),
+            self.phone.clone().into(),
+            self.birth_date.into(),
+            self.address.clone().into(),
+            self.tags.clone().into(),
+            self.created_at.into(),
+        ]
+    }
+}
+
+impl ExportRecord for JobPostingExportRow {
+    const HEADERS: &'static [&'static str] = &[
+        "구인 공고 ID",
+        "고객 ID",
+        "고객명",
+        "급여",
+        "설명",
+        "구인자 수수료율(%)",
+        "공고 상태",
+        "정산 상태",
+        "정산 금액",
+        "정산 메모",
+        "즐겨찾기",
+        "태그",
+        "등록일",
+    ];
+
+    fn cells(&self) -> Vec<ExportCell> {
+        vec![
+            self.id.into(),
+            self.customer_id.into(),
+            self.customer_name.clone().into(),
+            self.salary.into(),
+            self.description.clone().into(),
+            self.employer_fee_rate.into(),
+            posting_status_label(&self.posting_status).into(),
+            settlement_status_label(&self.settlement_status).into(),
+            self.settlement_amount.into(),
+            self.settlement_memo.clone().into(),
+            yes_no(self.is_favorite).into(),
+            self.tags.clone().into(),
+            self.created_at.into(),
+        ]
+    }
+}
+
+impl ExportRecord for JobSeekingExportRow {
+    const HEADERS: &'static [&'static str] = &[
+        "구직 공고 ID",
+        "고객 ID",
+        "고객명",
+        "희망 급여",
+        "설명",
+        "희망 지역",
+        "구직자 수수료율(%)",
+        "공고 상태",
+        "정산 상태",
+        "정산 금액",
+        "정산 메모",
+        "즐겨찾기",
+        "태그",
+        "등록일",
+    ];
+
+    fn cells(&self) -> Vec<ExportCell> {
+        vec![
+            self.id.into(),
+            self.customer_id.into(),
+            self.customer_name.clone().into(),
+            self.desired_salary.into(),
+            self.description.clone().into(),
+            self.preferred_location.clone().into(),
+            self.employee_fee_rate.into(),
+            posting_status_label(&self.posting_status).into(),
+            settlement_status_label(&self.settlement_status).into(),
+            self.settlement_amount.into(),
+            self.settlement_memo.clone().into(),
+            yes_no(self.is_favorite).into(),
+            self.tags.clone().into(),
+            self.created_at.into(),
+        ]
+    }
+}
+
+impl ExportRecord for MatchingExportRow {
+    const HEADERS: &'static [&'static str] = &[
+        "매칭 ID",
+        "매칭일",
+        "구인 공고 ID",
+        "구인자",
+        "구직 공고 ID",
+        "구직자",
+        "합의 급여",
+        "구인자 수수료율(%)",
+        "구인자 수수료",
+        "구인자 정산 상태",
+        "구직자 수수료율(%)",
+        "구직자 수수료",
+        "구직자 정산 상태",
+        "매칭 상태",
+        "완료일",
+        "취소일",
+        "취소 사유",
+    ];
+
+    fn cells(&self) -> Vec<ExportCell> {
+        vec![
+            self.id.into(),
+            self.matched_at.into(),
+            self.job_posting_id.into(),
+            self.employer_name.clone().into(),
+            self.job_seeking_posting_id.into(),
+            self.employee_name.clone().into(),
+            self.agreed_salary.into(),
+            self.employer_fee_rate.into(),
+            self.employer_fee_amount.into(),
+            settlement_status_label(&self.employer_settlement_status).into(),
+            self.employee_fee_rate.into(),
+            self.employee_fee_amount.into(),
+            settlement_status_label(&self.employee_settlement_status).into(),
+            matching_status_label(&self.matching_status).into(),
+            self.completed_at.into(),
+            self.cancelled_at.into(),
+            self.cancellation_reason.clone().into(),
+        ]
+    }
+}
+
+/// Split a fee (VAT included) into supply amount and VAT.
+///
+/// The recorded breakdown is used as is while the fee still matches it. A fee
+/// reduced by a guarantee refund is split at the breakdown's VAT rate, and fees
+/// recorded without a breakdown carry no VAT.
+pub fn split_supply_and_vat(
+    amount: Decimal,
+    breakdown: Option<&FeeBreakdown>,
+) -> (Decimal, Decimal) {
+    match breakdown {
+        Some(breakdown) if breakdown.total_amount == amount => {
+            (breakdown.supply_amount, breakdown.vat_amount)
+        }
+        Some(breakdown) => {
+            let supply = (amount * Decimal::ONE_HUNDRED
+                / (Decimal::ONE_HUNDRED + breakdown.vat_rate))
+                .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+            (supply, amount - supply)
+        }
+        None => (amount, Decimal::ZERO),
+    }
+}
+
+impl ExportRecord for TaxInvoiceExportRow {
+    const HEADERS: &'static [&'static str] = &[
+        "작성일자",
+        "공급자 등록번호",
+        "공급자 상호",
+        "공급자 성명",
+        "공급자 사업장주소",
+        "공급자 업태",
+        "공급자 종목",
+        "공급자 이메일",
+        "공급받는자 등록번호",
+        "공급받는자 상호",
+        "공급받는자 성명",
+        "공급받는자 주소",
+        "공급가액",
+        "세액",
+        "합계금액",
+        "품목",
+        "영수/청구",
+        "매칭 ID",
+        "구분",
+        "입금액",
+        "비고",
+    ];
+
+    fn cells(&self) -> Vec<ExportCell> {
+        let amount = self.fee_amount - self.refunded_amount;
+        let (supply, vat) = split_supply_and_vat(amount, self.fee_breakdown.as_ref().map(|b| &b.0));
+        let receipt = if self.paid_amount >= amount {
+            "영수"
+        } else {
+            "청구"
+        };
+        let memo = (self.refunded_amount > Decimal::ZERO)
+            .then(|| format!("보증 환불 {}원 차감", format_won(self.refunded_amount)));
+
+        vec![
+            self.completed_at.date().into(),
+            self.supplier_business_number.clone().into(),
+            self.supplier_name.clone().into(),
+            self.supplier_representative.clone().into(),
+            self.supplier_address.clone().into(),
+            self.supplier_business_type.clone().into(),
+            self.supplier_business_item.clone().into(),
+            self.supplier_email.clone().into(),
+            self.recipient_business_number.clone().into(),
+            self.recipient_name.clone().into(),
+            self.recipient_name.clone().into(),
+            self.recipient_address.clone().into(),
+            supply.into(),
+            vat.into(),
+            amount.into(),
+            format!("{} 직업소개 수수료", payer_side_label(&self.payer_side)).into(),
+            receipt.into(),
+            self.matching_id.into(),
+            payer_side_label(&self.payer_side).into(),
+            self.paid_amount.into(),
+            memo.into(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::customer::CustomerType;
+    use crate::models::fee_policy::FeeMethod;
+
+    fn sample_customer() -> CustomerExportRow {
+        CustomerExportRow {
+            id: 7,
+            name: "김민수".to_string(),
+            customer_type: CustomerType::Employer,
+            phone: "010-1234-5678".to_string(),
+            birth_date: NaiveDate::from_ymd_opt(1980, 5, 1),
+            address: Some("서울시 강남구, 역삼동".to_string()),
+            tags: "VIP, 장기".to_string(),
+            created_at: NaiveDate::from_ymd_opt(2025, 1, 2)
+                .unwrap()
+                .and_hms_opt(9, 30, 0)
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_csv_header_starts_with_bom() {
+        let header = csv_header::<CustomerExportRow>().unwrap();
+
+        assert!(header.starts_with(UTF8_BOM));
+        let text = std::str::from_utf8(&header[UTF8_BOM.len()..]).unwrap();
+        assert!(text.starts_with("고객 ID,이름,고객 유형"));
+        assert!(text.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_csv_row_quotes_fields_with_commas() {
+        let row = csv_row(&sample_customer()).unwrap();
+        let text = std::str::from_utf8(&row).unwrap();
+
+        assert_eq!(
+            text,
+            "7,김민수,구인자,010-1234-5678,1980-05-01,\"서울시 강남구, 역삼동\",\"VIP, 장기\",2025-01-02 09:30:00\n"
+        );
+    }
+
+    #[test]
+    fn test_number_cells_are_normalized() {
+        let cell = ExportCell::from(Decimal::new(250000000, 2));
+        assert_eq!(cell.to_csv_field(), "2500000");
+
+        let empty = ExportCell::from(None::<Decimal>);
+        assert_eq!(empty.to_csv_field(), "");
+    }
+
+    #[test]
+    fn test_split_supply_and_vat() {
+        let breakdown = FeeBreakdown {
+            fee_policy_id: None,
+            fee_policy_name: None,
+            method: FeeMethod::Rate,
+            fee_base: Decimal::new(3_000_000, 0),
+            rate: Some(Decimal::new(1000, 2)),
+            calculated_fee: Decimal::new(300_000, 0),
+            minimum_fee: None,
+            minimum_applied: false,
+            supply_amount: Decimal::new(300_000, 0),
+            vat_rate: Decimal::new(1000, 2),
+            vat_amount: Decimal::new(30_000, 0),
+            total_amount: Decimal::new(330_000, 0),
+        };
+
+        assert_eq!(
+            split_supply_and_vat(Decimal::new(330_000, 0), Some(&breakdown)),
+            (Decimal::new(300_000, 0), Decimal::new(30_000, 0))
+        );
+        // 보증 환불 후 남은 수수료는 같은 세율로 나눔
+        assert_eq!(
+            split_supply_and_vat(Decimal::new(110_001, 0), Some(&breakdown)),
+            (Decimal::new(100_001, 0), Decimal::new(10_000, 0))
+        );
+        assert_eq!(
+            split_supply_and_vat(Decimal::new(250_000, 0), None),
+            (Decimal::new(250_000, 0), Decimal::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_xlsx_export_produces_zip_archive() {
+        let mut export = XlsxExport::new::<CustomerExportRow>("고객").unwrap();
+        export.push(&sample_customer()).unwrap();
+        let bytes = export.finish().unwrap();
+
+        assert!(bytes.starts_with(b"PK"));
+    }
+}
@@ -0,0 +1,448 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use thiserror::Error;
+
+use crate::models::fee_policy::{FeeBreakdown, FeeMethod, FeePolicy, FeePolicyRequest, FeeTier};
+use crate::models::matching::{FeeRateSource, ResolvedFee};
+
+/// VAT added by policies that do not set one
+pub const DEFAULT_VAT_RATE: Decimal = Decimal::from_parts(10, 0, 0, false, 0);
+const MAX_RATE: Decimal = Decimal::ONE_HUNDRED;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum FeePolicyError {
+    #[error("정책 이름을 입력해주세요")]
+    EmptyName,
+    #[error("수수료율은 0% 이상 100% 이하로 입력해주세요")]
+    InvalidRate,
+    #[error("부가세율은 0% 이상 100% 이하로 입력해주세요")]
+    InvalidVatRate,
+    #[error("수수료 금액은 0원 이상으로 입력해주세요")]
+    NegativeAmount,
+    #[error("같은 급여 구간이 중복되었습니다")]
+    DuplicateTier,
+    #[error("단기 근무 일수와 고정 수수료를 함께 입력해주세요")]
+    IncompleteShortTerm,
+    #[error("근무 일수는 1일 이상으로 입력해주세요")]
+    InvalidContractDays,
+}
+
+/// Check a policy request and sort its tiers by salary band
+pub fn normalize_policy(req: &mut FeePolicyRequest) -> Result<(), FeePolicyError> {
+    req.name = req.name.trim().to_string();
+    if req.name.is_empty() {
+        return Err(FeePolicyError::EmptyName);
+    }
+
+    if req
+        .vat_rate
+        .is_some_and(|rate| rate < Decimal::ZERO || rate > MAX_RATE)
+    {
+        return Err(FeePolicyError::InvalidVatRate);
+    }
+
+    let amounts = [req.minimum_fee, req.short_term_fee];
+    if amounts
+        .iter()
+        .flatten()
+        .any(|amount| *amount < Decimal::ZERO)
+    {
+        return Err(FeePolicyError::NegativeAmount);
+    }
+
+    match (req.short_term_max_days, req.short_term_fee) {
+        (Some(days), Some(_)) => validate_contract_days(Some(days))?,
+        (None, None) => {}
+        _ => return Err(FeePolicyError::IncompleteShortTerm),
+    }
+
+    for tier in &req.tiers {
+        validate_rate(tier.rate)?;
+        if tier.min_monthly_salary < Decimal::ZERO {
+            return Err(FeePolicyError::NegativeAmount);
+        }
+    }
+    req.tiers.sort_by_key(|tier| tier.min_monthly_salary);
+    if req
+        .tiers
+        .windows(2)
+        .any(|pair| pair[0].min_monthly_salary == pair[1].min_monthly_salary)
+    {
+        return Err(FeePolicyError::DuplicateTier);
+    }
+
+    Ok(())
+}
+
+pub fn validate_rate(rate: Decimal) -> Result<(), FeePolicyError> {
+    if rate < Decimal::ZERO || rate > MAX_RATE {
+        Err(FeePolicyError::InvalidRate)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_contract_days(days: Option<i32>) -> Result<(), FeePolicyError> {
+    match days {
+        Some(days) if days < 1 => Err(FeePolicyError::InvalidContractDays),
+        _ => Ok(()),
+    }
+}
+
+/// Calculate one side's fee on the monthly equivalent salary `fee_base`.
+///
+/// 1. A contract of at most `short_term_max_days` days is charged the fixed
+///    short-term fee.
+/// 2. Otherwise the rate of the highest tier at or below `fee_base` applies,
+///    or `rate` when no tier does, raised to `minimum_fee`.
+/// 3. VAT is added on top; every amount is rounded to the won.
+///
+/// Without a policy the fee is `fee_base * rate / 100` with no VAT.
+pub fn calculate(
+    policy: Option<&FeePolicy>,
+    fee_base: Decimal,
+    rate: Decimal,
+    contract_days: Option<i32>,
+) -> FeeBreakdown {
+    breakdown(policy, fee_base, rate, contract_days, true)
+}
+
+fn breakdown(
+    policy: Option<&FeePolicy>,
+    fee_base: Decimal,
+    rate: Decimal,
+    contract_days: Option<i32>,
+    apply_tiers: bool,
+) -> FeeBreakdown {
+    let short_term_fee = policy.and_then(|policy| {
+        let max_days = policy.short_term_max_days?;
+        let fee = policy.short_term_fee?;
+        contract_days
+            .is_some_and(|days| days <= max_days)
+            .then_some(fee)
+    });
+
+    let (method, applied_rate, calculated_fee) = match short_term_fee {
+        Some(fee) => (FeeMethod::ShortTerm, None, fee),
+        None => {
+            let tier = policy
+                .filter(|_| apply_tiers)
+                .and_then(|policy| tier_for(&policy.tiers, fee_base));
+            let (method, rate) = match tier {
+                Some(tier) => (FeeMethod::Tier, tier.rate),
+                None => (FeeMethod::Rate, rate),
+            };
+            (
+                method,
+                Some(rate),
+                won(fee_base * rate / Decimal::ONE_HUNDRED),
+            )
+        }
+    };
+
+    let minimum_fee = policy.and_then(|policy| policy.minimum_fee);
+    let minimum_applied = method != FeeMethod::ShortTerm
+        && minimum_fee.is_some_and(|minimum| calculated_fee < minimum);
+    let supply_amount = match minimum_fee {
+        Some(minimum) if minimum_applied => won(minimum),
+        _ => won(calculated_fee),
+    };
+
+    let vat_rate = policy.map_or(Decimal::ZERO, |policy| policy.vat_rate);
+    let vat_amount = won(supply_amount * vat_rate / Decimal::ONE_HUNDRED);
+
+    FeeBreakdown {
+        fee_policy_id: policy.map(|policy| policy.id),
+        fee_policy_name: policy.map(|policy| policy.name.clone()),
+        method,
+        fee_base,
+        rate: applied_rate,
+        calculated_fee,
+        minimum_fee,
+        minimum_applied,
+        supply_amount,
+        vat_rate,
+        vat_amount,
+        total_amount: supply_amount + vat_amount,
+    }
+}
+
+/// The first rate that is set among `candidates`, in order of precedence.
+/// Falls back to 0% from the user default when none is.
+pub fn resolve_rate(candidates: &[(Option<Decimal>, FeeRateSource)]) -> (Decimal, FeeRateSource) {
+    candidates
+        .iter()
+        .find_map(|(rate, source)| rate.map(|rate| (rate, *source)))
+        .unwrap_or((Decimal::ZERO, FeeRateSource::UserDefault))
+}
+
+/// Calculate one side's fee with the resolved `rate`. A policy tier replaces
+/// a rate taken from the posting or the user default, and the source becomes
+/// the policy; a rate entered on the request is kept as is.
+pub fn calculate_resolved(
+    policy: Option<&FeePolicy>,
+    fee_base: Decimal,
+    (rate, rate_source): (Decimal, FeeRateSource),
+    contract_days: Option<i32>,
+) -> ResolvedFee {
+    let apply_tiers = matches!(
+        rate_source,
+        FeeRateSource::Posting | FeeRateSource::UserDefault
+    );
+    let breakdown = breakdown(policy, fee_base, rate, contract_days, apply_tiers);
+    let (rate, rate_source) = match (breakdown.method, breakdown.rate) {
+        (FeeMethod::Tier, Some(tier_rate)) => (tier_rate, FeeRateSource::Policy),
+        _ => (rate, rate_source),
+    };
+
+    ResolvedFee {
+        rate,
+        rate_source,
+        breakdown,
+    }
+}
+
+fn tier_for(tiers: &[FeeTier], fee_base: Decimal) -> Option<&FeeTier> {
+    tiers
+        .iter()
+        .filter(|tier| tier.min_monthly_salary <= fee_base)
+        .max_by(|a, b| a.min_monthly_salary.cmp(&b.min_monthly_salary))
+}
+
+fn won(amount: Decimal) -> Decimal {
+    amount.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn policy() -> FeePolicy {
+        FeePolicy {
+            id: 1,
+            user_id: 1,
+            name: "기본".to_string(),
+            minimum_fee: Some(Decimal::new(200_000, 0)),
+            short_term_max_days: Some(7),
+            short_term_fee: Some(Decimal::new(50_000, 0)),
+            vat_rate: DEFAULT_VAT_RATE,
+            tiers: vec![
+                FeeTier {
+                    min_monthly_salary: Decimal::ZERO,
+                    rate: Decimal::new(10, 0),
+                },
+                FeeTier {
+                    min_monthly_salary: Decimal::new(3_000_000, 0),
+                    rate: Decimal::new(8, 0),
+                },
+            ],
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn test_without_policy_uses_rate_without_vat() {
+        let breakdown = calculate(None, Decimal::new(2_607_000, 0), Decimal::new(125, 1), None);
+        assert_eq!(breakdown.method, FeeMethod::Rate);
+        // 325,875
+        assert_eq!(breakdown.supply_amount, Decimal::new(325_875, 0));
+        assert_eq!(breakdown.vat_amount, Decimal::ZERO);
+        assert_eq!(breakdown.total_amount, Decimal::new(325_875, 0));
+    }
+
+    #[test]
+    fn test_tiers_by_salary_band() {
+        let policy = policy();
+
+        let low = calculate(
+            Some(&policy),
+            Decimal::new(2_500_000, 0),
+            Decimal::ZERO,
+            None,
+        );
+        assert_eq!(low.method, FeeMethod::Tier);
+        assert_eq!(low.rate, Some(Decimal::new(10, 0)));
+        assert_eq!(low.supply_amount, Decimal::new(250_000, 0));
+        assert_eq!(low.vat_amount, Decimal::new(25_000, 0));
+        assert_eq!(low.total_amount, Decimal::new(275_000, 0));
+
+        let high = calculate(
+            Some(&policy),
+            Decimal::new(3_000_000, 0),
+            Decimal::ZERO,
+            None,
+        );
+        assert_eq!(high.rate, Some(Decimal::new(8, 0)));
+        assert_eq!(high.supply_amount, Decimal::new(240_000, 0));
+    }
+
+    #[test]
+    fn test_minimum_fee_and_rate_fallback() {
+        let mut policy = policy();
+        policy.tiers.clear();
+
+        let breakdown = calculate(
+            Some(&policy),
+            Decimal::new(1_000_000, 0),
+            Decimal::new(10, 0),
+            Some(30),
+        );
+        assert_eq!(breakdown.method, FeeMethod::Rate);
+        assert_eq!(breakdown.calculated_fee, Decimal::new(100_000, 0));
+        assert!(breakdown.minimum_applied);
+        assert_eq!(breakdown.supply_amount, Decimal::new(200_000, 0));
+        assert_eq!(breakdown.total_amount, Decimal::new(220_000, 0));
+    }
+
+    #[test]
+    fn test_short_term_fixed_fee() {
+        let breakdown = calculate(
+            Some(&policy()),
+            Decimal::new(2_500_000, 0),
+            Decimal::ZERO,
+            Some(5),
+        );
+        assert_eq!(breakdown.method, FeeMethod::ShortTerm);
+        assert_eq!(breakdown.rate, None);
+        // 최저 수수료는 고정 수수료에 적용하지 않음
+        assert!(!breakdown.minimum_applied);
+        assert_eq!(breakdown.supply_amount, Decimal::new(50_000, 0));
+        assert_eq!(breakdown.total_amount, Decimal::new(55_000, 0));
+    }
+
+    #[test]
+    fn test_vat_rounds_to_won() {
+        let mut policy = policy();
+        policy.minimum_fee = None;
+        policy.tiers = vec![FeeTier {
+            min_monthly_salary: Decimal::ZERO,
+            rate: Decimal::new(9, 0),
+        }];
+
+        // 1,234,567 x 9% = 111,111.03 → 111,111, 부가세 11,111.1 → 11,111
+        let breakdown = calculate(
+            Some(&policy),
+            Decimal::new(1_234_567, 0),
+            Decimal::ZERO,
+            None,
+        );
+        assert_eq!(breakdown.supply_amount, Decimal::new(111_111, 0));
+        assert_eq!(breakdown.vat_amount, Decimal::new(11_111, 0));
+    }
+
+    #[test]
+    fn test_resolve_rate_precedence() {
+        let posting = Some(Decimal::new(8, 0));
+        let user_default = Some(Decimal::new(5, 0));
+
+        assert_eq!(
+            resolve_rate(&[
+                (Some(Decimal::new(12, 0)), FeeRateSource::Request),
+                (posting, FeeRateSource::Posting),
+                (user_default, FeeRateSource::UserDefault),
+            ]),
+            (Decimal::new(12, 0), FeeRateSource::Request)
+        );
+        assert_eq!(
+            resolve_rate(&[
+                (None, FeeRateSource::Request),
+                (posting, FeeRateSource::Posting),
+                (user_default, FeeRateSource::UserDefault),
+            ]),
+            (Decimal::new(8, 0), FeeRateSource::Posting)
+        );
+        assert_eq!(
+            resolve_rate(&[
+                (None, FeeRateSource::Request),
+                (None, FeeRateSource::Posting),
+                (user_default, FeeRateSource::UserDefault),
+            ]),
+            (Decimal::new(5, 0), FeeRateSource::UserDefault)
+        );
+        assert_eq!(
+            resolve_rate(&[(None, FeeRateSource::Request)]),
+            (Decimal::ZERO, FeeRateSource::UserDefault)
+        );
+    }
+
+    #[test]
+    fn test_policy_tier_overrides_resolved_rate() {
+        let resolved = calculate_resolved(
+            Some(&policy()),
+            Decimal::new(2_500_000, 0),
+            (Decimal::new(5, 0), FeeRateSource::Posting),
+            None,
+        );
+        assert_eq!(resolved.rate, Decimal::new(10, 0));
+        assert_eq!(resolved.rate_source, FeeRateSource::Policy);
+
+        // 단기 근무 고정 수수료는 수수료율을 쓰지 않으므로 출처 유지
+        let resolved = calculate_resolved(
+            Some(&policy()),
+            Decimal::new(2_500_000, 0),
+            (Decimal::new(5, 0), FeeRateSource::Posting),
+            Some(3),
+        );
+        assert_eq!(resolved.rate, Decimal::new(5, 0));
+        assert_eq!(resolved.rate_source, FeeRateSource::Posting);
+
+        // 매칭 요청에 직접 입력한 수수료율은 구간보다 우선하고, 최저 수수료와 VAT는 적용
+        let resolved = calculate_resolved(
+            Some(&policy()),
+            Decimal::new(2_500_000, 0),
+            (Decimal::new(5, 0), FeeRateSource::Request),
+            None,
+        );
+        assert_eq!(resolved.rate, Decimal::new(5, 0));
+        assert_eq!(resolved.rate_source, FeeRateSource::Request);
+        assert_eq!(resolved.breakdown.method, FeeMethod::Rate);
+        assert_eq!(resolved.breakdown.calculated_fee, Decimal::new(125_000, 0));
+        assert!(resolved.breakdown.minimum_applied);
+        assert_eq!(resolved.breakdown.supply_amount, Decimal::new(200_000, 0));
+    }
+
+    #[test]
+    fn test_normalize_policy() {
+        let mut req = FeePolicyRequest {
+            name: "  구간제 ".to_string(),
+            minimum_fee: None,
+            short_term_max_days: None,
+            short_term_fee: None,
+            vat_rate: None,
+            tiers: vec![
+                FeeTier {
+                    min_monthly_salary: Decimal::new(3_000_000, 0),
+                    rate: Decimal::new(8, 0),
+                },
+                FeeTier {
+                    min_monthly_salary: Decimal::ZERO,
+                    rate: Decimal::new(10, 0),
+                },
+            ],
+        };
+        assert!(normalize_policy(&mut req).is_ok());
+        assert_eq!(req.name, "구간제");
+        assert_eq!(req.tiers[0].min_monthly_salary, Decimal::ZERO);
+
+        req.tiers[1].min_monthly_salary = Decimal::ZERO;
+        assert_eq!(
+            normalize_policy(&mut req),
+            Err(FeePolicyError::DuplicateTier)
+        );
+
+        req.tiers.clear();
+        req.short_term_max_days = Some(7);
+        assert_eq!(
+            normalize_policy(&mut req),
+            Err(FeePolicyError::IncompleteShortTerm)
+        );
+
+        req.short_term_fee = Some(Decimal::new(50_000, 0));
+        req.vat_rate = Some(Decimal::new(101, 0));
+        assert_eq!(
+            normalize_policy(&mut req),
+            Err(FeePolicyError::InvalidVatRate)
+        );
+    }
+}
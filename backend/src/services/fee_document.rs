@@ -0,0 +1,413 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, Transaction};
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::models::fee_document::{
+    CreateFeeDocumentRequest, DocumentType, FeeDocument, FeeDocumentSource, NewFeeDocument,
+};
+use crate::models::fee_policy::FeeMethod;
+use crate::models::payment::{PayerSide, Payment, SideBalance};
+use crate::repositories::payment::LedgerError;
+use crate::repositories::{fee_document, payment};
+use crate::services::format::{
+    format_won, payer_side_label, payment_method_label, salary_basis_label,
+};
+use crate::services::pdf::{self, PdfError, PdfLine};
+use crate::services::storage;
+
+#[derive(Debug, Error)]
+pub enum FeeDocumentError {
+    #[error("입금 내역이 없어 영수증을 발행할 수 없습니다")]
+    NothingPaid,
+    #[error("청구할 미수금이 없습니다")]
+    NothingDue,
+    #[error(transparent)]
+    Ledger(#[from] LedgerError),
+    #[error(transparent)]
+    Pdf(#[from] PdfError),
+    #[error("문서 저장 실패: {0}")]
+    Storage(String),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+/// What is printed on one document
+pub struct FeeDocumentContent<'a> {
+    pub document_type: DocumentType,
+    pub document_number: &'a str,
+    pub issued_on: NaiveDate,
+    pub matching_id: i64,
+    pub source: &'a FeeDocumentSource,
+    pub balance: &'a SideBalance,
+    /// Payments of the document's side only
+    pub payments: &'a [Payment],
+}
+
+/// e.g. `INV-2025-0001`
+pub fn document_number(document_type: DocumentType, year: i32, number: i32) -> String {
+    format!("{}-{}-{:04}", document_type.prefix(), year, number)
+}
+
+/// Amount an invoice claims (the outstanding fee) or a receipt acknowledges
+/// (the payments received)
+pub fn total_amount(
+    document_type: DocumentType,
+    balance: &SideBalance,
+) -> Result<Decimal, FeeDocumentError> {
+    match document_type {
+        DocumentType::Invoice if balance.outstanding_amount <= Decimal::ZERO => {
+            Err(FeeDocumentError::NothingDue)
+        }
+        DocumentType::Invoice => Ok(balance.outstanding_amount),
+        DocumentType::Receipt if balance.paid_amount <= Decimal::ZERO => {
+            Err(FeeDocumentError::NothingPaid)
+        }
+        DocumentType::Receipt => Ok(balance.paid_amount),
+    }
+}
+
+fn won(amount: Decimal) -> String {
+    format!("{}원", format_won(amount))
+}
+
+fn percent(rate: Decimal) -> String {
+    format!("{}%", rate.normalize())
+}
+
+pub fn document_lines(content: &FeeDocumentContent) -> Vec<PdfLine> {
+    let source = content.source;
+    let balance = content.balance;
+    let mut lines = vec![
+        PdfLine::Title(content.document_type.title().to_string()),
+        PdfLine::pair("문서 번호", content.document_number),
+        PdfLine::pair("발행일", content.issued_on.format("%Y-%m-%d").to_string()),
+        PdfLine::Rule,
+        PdfLine::Heading("공급자".to_string()),
+        PdfLine::pair("상호", source.agency_name.as_str()),
+    ];
+    if let Some(phone) = &source.agency_phone {
+        lines.push(PdfLine::pair("연락처", phone.as_str()));
+    }
+
+    lines.push(PdfLine::Heading(format!(
+        "공급받는 자 ({})",
+        payer_side_label(&balance.payer_side)
+    )));
+    lines.push(PdfLine::pair("성명", source.customer_name.as_str()));
+    lines.push(PdfLine::pair("연락처", source.customer_phone.as_str()));
+    if let Some(address) = &source.customer_address {
+        lines.push(PdfLine::pair("주소", address.as_str()));
+    }
+    lines.push(PdfLine::Rule);
+
+    lines.push(PdfLine::Heading("매칭 내역".to_string()));
+    lines.push(PdfLine::pair(
+        "매칭 번호",
+        format!("#{}", content.matching_id),
+    ));
+    lines.push(PdfLine::pair(
+        "매칭일",
+        source.matched_at.format("%Y-%m-%d").to_string(),
+    ));
+    if let Some(completed_at) = source.completed_at {
+        lines.push(PdfLine::pair(
+            "완료일",
+            completed_at.format("%Y-%m-%d").to_string(),
+        ));
+    }
+    lines.push(PdfLine::pair(
+        "합의 급여",
+        format!(
+            "{} ({})",
+            won(source.agreed_salary),
+            salary_basis_label(&source.salary_basis)
+        ),
+    ));
+
+    lines.push(PdfLine::Heading("수수료".to_string()));
+    match &source.fee_breakdown {
+        Some(breakdown) => {
+            let rate = match breakdown.method {
+                FeeMethod::ShortTerm => "단기 근무 고정 수수료".to_string(),
+                _ => percent(breakdown.rate.unwrap_or(source.fee_rate)),
+            };
+            lines.push(PdfLine::pair("수수료율", rate));
+            lines.push(PdfLine::pair("공급가액", won(breakdown.supply_amount)));
+            lines.push(PdfLine::pair(
+                format!("부가세 ({})", percent(breakdown.vat_rate)),
+                won(breakdown.vat_amount),
+            ));
+        }
+        None => lines.push(PdfLine::pair("수수료율", percent(source.fee_rate))),
+    }
+    lines.push(PdfLine::pair("수수료 합계", won(balance.fee_amount)));
+    if balance.refunded_amount > Decimal::ZERO {
+        lines.push(PdfLine::pair(
+            "보증 환불",
+            format!("-{}", won(balance.refunded_amount)),
+        ));
+    }
+
+    lines.push(PdfLine::Heading("입금 내역".to_string()));
+    if content.payments.is_empty() {
+        lines.push(PdfLine::Text("입금 내역이 없습니다".to_string()));
+    } else {
+        lines.push(PdfLine::Row(
+            ["입금일", "입금 방법", "금액", "메모"]
+                .map(String::from)
+                .to_vec(),
+        ));
+        for payment in content.payments {
+            lines.push(PdfLine::Row(vec![
+                payment.paid_on.format("%Y-%m-%d").to_string(),
+                payment_method_label(&payment.payment_method).to_string(),
+                won(payment.amount),
+                payment.memo.clone().unwrap_or_default(),
+            ]));
+        }
+    }
+    lines.push(PdfLine::pair("입금 합계", won(balance.paid_amount)));
+    lines.push(PdfLine::Rule);
+
+    match content.document_type {
+        DocumentType::Invoice => {
+            lines.push(PdfLine::pair("청구 금액", won(balance.outstanding_amount)));
+        }
+        DocumentType::Receipt => {
+            lines.push(PdfLine::pair("영수 금액", won(balance.paid_amount)));
+            lines.push(PdfLine::Blank);
+            lines.push(PdfLine::Text("위 금액을 정히 영수합니다.".to_string()));
+        }
+    }
+
+    lines
+}
+
+/// Render an invoice or receipt for one side of a matching, store the PDF in
+/// MinIO and record it under the user's next document number
+///
+/// The matching stays locked from reading its balance until the document is
+/// recorded, so the amounts cannot change under the document.
+pub async fn issue_document(
+    pool: &PgPool,
+    config: &Config,
+    matching_id: i64,
+    req: CreateFeeDocumentRequest,
+    user_id: i64,
+) -> Result<FeeDocument, FeeDocumentError> {
+    let font = pdf::load_font(config).await?;
+
+    let issued_on = Utc::now().date_naive();
+    let mut tx = pool.begin().await?;
+
+    let (payments, balance) = payment::lock_payments(&mut tx, matching_id, user_id).await?;
+    let balance = match req.payer_side {
+        PayerSide::Employer => balance.employer,
+        PayerSide::Employee => balance.employee,
+    };
+    let payments: Vec<Payment> = payments
+        .into_iter()
+        .filter(|payment| payment.payer_side == req.payer_side)
+        .collect();
+    let total_amount = total_amount(req.document_type, &balance)?;
+
+    let source =
+        fee_document::get_document_source(&mut tx, matching_id, req.payer_side, user_id).await?;
+    let number =
+        fee_document::next_document_number(&mut tx, user_id, req.document_type, issued_on.year())
+            .await?;
+    let number = document_number(req.document_type, issued_on.year(), number);
+
+    let lines = document_lines(&FeeDocumentContent {
+        document_type: req.document_type,
+        document_number: &number,
+        issued_on,
+        matching_id,
+        source: &source,
+        balance: &balance,
+        payments: &payments,
+    });
+    let bytes = pdf::render(req.document_type.title(), &lines, &font)?;
+
+    let file_path = format!("documents/{}/{}.pdf", user_id, number);
+    storage::put_object(config, &file_path, &bytes, "application/pdf")
+        .await
+        .map_err(FeeDocumentError::Storage)?;
+
+    let document = NewFeeDocument {
+        user_id,
+        matching_id,
+        document_type: req.document_type,
+        payer_side: req.payer_side,
+        document_number: number,
+        issued_on,
+        total_amount,
+        file_path,
+        file_size: bytes.len() as i64,
+    };
+    match record_document(tx, &document).await {
+        Ok(document) => Ok(document),
+        Err(e) => {
+            // The number is rolled back, so the stored PDF would be orphaned
+            // and overwritten by the next document that takes the number
+            if let Err(delete_error) = storage::delete_object(config, &document.file_path).await {
+                tracing::warn!(
+                    "Failed to delete {} from MinIO: {}",
+                    document.file_path,
+                    delete_error
+                );
+            }
+            Err(e.into())
+        }
+    }
+}
+
+async fn record_document(
+    mut tx: Transaction<'_, Postgres>,
+    document: &NewFeeDocument,
+) -> Result<FeeDocument, sqlx::Error> {
+    let document = fee_document::insert_fee_document(&mut tx, document).await?;
+    tx.commit().await?;
+
+    Ok(document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fee_policy::FeeBreakdown;
+    use crate::models::job_posting::SalaryBasis;
+    use crate::models::payment::PaymentMethod;
+    use crate::services::payment::side_balance;
+    use chrono::NaiveDateTime;
+    use sqlx::types::Json;
+
+    fn source() -> FeeDocumentSource {
+        FeeDocumentSource {
+            agency_name: "행복 직업소개소".to_string(),
+            agency_phone: Some("02-123-4567".to_string()),
+            customer_name: "김구인".to_string(),
+            customer_phone: "010-1111-2222".to_string(),
+            customer_address: None,
+            matched_at: NaiveDateTime::default(),
+            completed_at: None,
+            agreed_salary: Decimal::new(3_000_000, 0),
+            salary_basis: SalaryBasis::Monthly,
+            fee_rate: Decimal::new(1000, 2),
+            fee_breakdown: Some(Json(FeeBreakdown {
+                fee_policy_id: None,
+                fee_policy_name: None,
+                method: FeeMethod::Rate,
+                fee_base: Decimal::new(3_000_000, 0),
+                rate: Some(Decimal::new(1000, 2)),
+                calculated_fee: Decimal::new(300_000, 0),
+                minimum_fee: None,
+                minimum_applied: false,
+                supply_amount: Decimal::new(300_000, 0),
+                vat_rate: Decimal::new(1000, 2),
+                vat_amount: Decimal::new(30_000, 0),
+                total_amount: Decimal::new(330_000, 0),
+            })),
+        }
+    }
+
+    fn payment(amount: i64) -> Payment {
+        Payment {
+            id: 1,
+            matching_id: 1,
+            payer_side: PayerSide::Employer,
+            amount: Decimal::new(amount, 0),
+            paid_on: NaiveDate::from_ymd_opt(2025, 3, 2).unwrap(),
+            payment_method: PaymentMethod::BankTransfer,
+            memo: None,
+            created_by: None,
+            created_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn test_document_number() {
+        assert_eq!(
+            document_number(DocumentType::Invoice, 2025, 7),
+            "INV-2025-0007"
+        );
+        assert_eq!(
+            document_number(DocumentType::Receipt, 2025, 12345),
+            "RCP-2025-12345"
+        );
+    }
+
+    #[test]
+    fn test_total_amount() {
+        let unpaid = side_balance(
+            PayerSide::Employer,
+            Decimal::new(330_000, 0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+        );
+        assert_eq!(
+            total_amount(DocumentType::Invoice, &unpaid).unwrap(),
+            Decimal::new(330_000, 0)
+        );
+        assert!(matches!(
+            total_amount(DocumentType::Receipt, &unpaid),
+            Err(FeeDocumentError::NothingPaid)
+        ));
+
+        let paid = side_balance(
+            PayerSide::Employer,
+            Decimal::new(330_000, 0),
+            Decimal::ZERO,
+            Decimal::new(330_000, 0),
+        );
+        assert_eq!(
+            total_amount(DocumentType::Receipt, &paid).unwrap(),
+            Decimal::new(330_000, 0)
+        );
+        assert!(matches!(
+            total_amount(DocumentType::Invoice, &paid),
+            Err(FeeDocumentError::NothingDue)
+        ));
+    }
+
+    #[test]
+    fn test_receipt_lines() {
+        let source = source();
+        let balance = side_balance(
+            PayerSide::Employer,
+            Decimal::new(330_000, 0),
+            Decimal::ZERO,
+            Decimal::new(100_000, 0),
+        );
+        let payments = [payment(100_000)];
+        let lines = document_lines(&FeeDocumentContent {
+            document_type: DocumentType::Receipt,
+            document_number: "RCP-2025-0001",
+            issued_on: NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+            matching_id: 42,
+            source: &source,
+            balance: &balance,
+            payments: &payments,
+        });
+
+        assert_eq!(lines[0], PdfLine::Title("수수료 영수증".to_string()));
+        for expected in [
+            PdfLine::pair("문서 번호", "RCP-2025-0001"),
+            PdfLine::pair("매칭 번호", "#42"),
+            PdfLine::pair("합의 급여", "3,000,000원 (월급)"),
+            PdfLine::pair("수수료율", "10%"),
+            PdfLine::pair("부가세 (10%)", "30,000원"),
+            PdfLine::pair("수수료 합계", "330,000원"),
+            PdfLine::Row(
+                ["2025-03-02", "계좌이체", "100,000원", ""]
+                    .map(String::from)
+                    .to_vec(),
+            ),
+            PdfLine::pair("영수 금액", "100,000원"),
+        ] {
+            assert!(lines.contains(&expected), "missing {:?}", expected);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::models::customer::CustomerType;
+use crate::models::job_posting::{PostingStatus, SalaryBasis, SettlementStatus};
+use crate::models::matching::MatchingStatus;
+use crate::models::payment::{PayerSide, PaymentMethod};
+
+// Korean display labels and amount formatting shared by exports, PDFs and
+// user-facing messages
+
+pub fn customer_type_label(customer_type: &CustomerType) -> &'static str {
+    match customer_type {
+        CustomerType::Employer => "구인자",
+        CustomerType::Employee => "구직자",
+        CustomerType::Both => "구인/구직",
+    }
+}
+
+pub fn posting_status_label(status: &PostingStatus) -> &'static str {
+    match status {
+        PostingStatus::Published => "게시중",
+        PostingStatus::InProgress => "진행중",
+        PostingStatus::Closed => "마감",
+        PostingStatus::Cancelled => "취소",
+    }
+}
+
+pub fn settlement_status_label(status: &SettlementStatus) -> &'static str {
+    match status {
+        SettlementStatus::Unsettled => "미정산",
+        SettlementStatus::PartiallySettled => "부분정산",
+        SettlementStatus::Settled => "정산완료",
+    }
+}
+
+pub fn matching_status_label(status: &MatchingStatus) -> &'static str {
+    match status {
+        MatchingStatus::InProgress => "진행중",
+        MatchingStatus::Completed => "완료",
+        MatchingStatus::Cancelled => "취소",
+    }
+}
+
+pub fn salary_basis_label(basis: &SalaryBasis) -> &'static str {
+    match basis {
+        SalaryBasis::Monthly => "월급",
+        SalaryBasis::Daily => "일급",
+        SalaryBasis::Hourly => "시급",
+    }
+}
+
+pub fn payer_side_label(side: &PayerSide) -> &'static str {
+    match side {
+        PayerSide::Employer => "구인자",
+        PayerSide::Employee => "구직자",
+    }
+}
+
+pub fn payment_method_label(method: &PaymentMethod) -> &'static str {
+    match method {
+        PaymentMethod::Cash => "현금",
+        PaymentMethod::BankTransfer => "계좌이체",
+        PaymentMethod::Card => "카드",
+        PaymentMethod::Other => "기타",
+    }
+}
+
+/// Format a won amount with thousands separators, e.g. `2,500,000`.
+/// Fractions of a won are rounded away.
+pub fn format_won(amount: Decimal) -> String {
+    let rounded = amount
+        .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+        .abs()
+        .to_string();
+    let mut grouped = String::with_capacity(rounded.len() + rounded.len() / 3);
+    for (i, digit) in rounded.chars().enumerate() {
+        if i > 0 && (rounded.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    if amount.is_sign_negative() && !grouped.chars().all(|c| c == '0') {
+        format!("-{}", grouped)
+    } else {
+        grouped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_won() {
+        assert_eq!(format_won(Decimal::new(2_500_000, 0)), "2,500,000");
+        assert_eq!(format_won(Decimal::new(99_950, 2)), "1,000");
+        assert_eq!(format_won(Decimal::new(-123_456, 0)), "-123,456");
+        assert_eq!(format_won(Decimal::ZERO), "0");
+    }
+}
@@ -0,0 +1,194 @@
+use chrono::NaiveDate;
+use rust_decimal::{Decimal, RoundingStrategy};
+use thiserror::Error;
+
+use crate::models::matching::{GuaranteeRefund, GuaranteeResolution, Matching, MatchingStatus};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum GuaranteeError {
+    #[error("보증 기간은 1일 이상으로 입력해주세요")]
+    InvalidDays,
+    #[error("완료된 매칭만 보증 처리할 수 있습니다")]
+    NotCompleted,
+    #[error("보증 기간이 설정되지 않은 매칭입니다")]
+    NoGuarantee,
+    #[error("퇴사일이 매칭 완료일({0})보다 빠릅니다")]
+    BeforeStart(NaiveDate),
+    #[error("보증 기간이 {0}에 끝났습니다")]
+    Expired(NaiveDate),
+    #[error("이미 환불 처리된 매칭입니다")]
+    AlreadyRefunded,
+    #[error("이미 교체 매칭이 있습니다 (매칭 #{0})")]
+    AlreadyReplaced(i64),
+}
+
+pub fn validate_guarantee_days(days: Option<i32>) -> Result<(), GuaranteeError> {
+    match days {
+        Some(days) if days < 1 => Err(GuaranteeError::InvalidDays),
+        _ => Ok(()),
+    }
+}
+
+/// Check that the helper of `matching` leaving on `left_on` breaks an open
+/// guarantee. Returns the guarantee days and the day the matching completed.
+///
+/// The guarantee covers `guarantee_days` days from the completion date; the
+/// last covered day is the day before `guarantee_expires_at`.
+pub fn ensure_open(
+    matching: &Matching,
+    left_on: NaiveDate,
+) -> Result<(i32, NaiveDate), GuaranteeError> {
+    if matching.matching_status != MatchingStatus::Completed {
+        return Err(GuaranteeError::NotCompleted);
+    }
+    if matching.guarantee_resolution == Some(GuaranteeResolution::Refund) {
+        return Err(GuaranteeError::AlreadyRefunded);
+    }
+    let (Some(guarantee_days), Some(completed_at)) =
+        (matching.guarantee_days, matching.completed_at)
+    else {
+        return Err(GuaranteeError::NoGuarantee);
+    };
+
+    let started_on = completed_at.date();
+    if left_on < started_on {
+        return Err(GuaranteeError::BeforeStart(started_on));
+    }
+    let expires_on = started_on + chrono::Duration::days(i64::from(guarantee_days));
+    if left_on >= expires_on {
+        return Err(GuaranteeError::Expired(expires_on));
+    }
+
+    Ok((guarantee_days, started_on))
+}
+
+/// Refund of the employer fee in proportion to the guarantee days left
+/// after `left_on`, rounded to the won.
+pub fn calculate_refund(
+    matching: &Matching,
+    left_on: NaiveDate,
+) -> Result<GuaranteeRefund, GuaranteeError> {
+    let (guarantee_days, started_on) = ensure_open(matching, left_on)?;
+
+    let fee_amount = matching.employer_fee_amount.unwrap_or(Decimal::ZERO);
+    let days_worked = (left_on - started_on).num_days();
+    let remaining_days = i64::from(guarantee_days) - days_worked;
+    let refund_amount = (fee_amount * Decimal::from(remaining_days)
+        / Decimal::from(guarantee_days))
+    .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+
+    Ok(GuaranteeRefund {
+        fee_amount,
+        guarantee_days,
+        days_worked,
+        remaining_days,
+        refund_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::job_posting::SalaryBasis;
+    use crate::models::matching::FeeRateSource;
+    use chrono::NaiveDateTime;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn completed_matching() -> Matching {
+        Matching {
+            id: 1,
+            job_posting_id: 1,
+            job_seeking_posting_id: 1,
+            matched_at: NaiveDateTime::default(),
+            agreed_salary: Decimal::new(3_000_000, 0),
+            salary_basis: SalaryBasis::Monthly,
+            expected_weekly_hours: None,
+            monthly_salary: Decimal::new(3_000_000, 0),
+            employer_fee_rate: Decimal::new(10, 0),
+            employee_fee_rate: Decimal::ZERO,
+            employer_fee_amount: Some(Decimal::new(300_000, 0)),
+            employee_fee_amount: Some(Decimal::ZERO),
+            employer_fee_rate_source: FeeRateSource::Request,
+            employee_fee_rate_source: FeeRateSource::Request,
+            matching_status: MatchingStatus::Completed,
+            cancellation_reason: None,
+            cancelled_at: None,
+            cancelled_by: None,
+            completed_at: Some(date("2025-01-01").and_hms_opt(15, 0, 0).unwrap()),
+            contract_days: None,
+            employer_fee_breakdown: None,
+            employee_fee_breakdown: None,
+            guarantee_days: Some(90),
+            guarantee_expires_at: None,
+            replaces_matching_id: None,
+            guarantee_resolution: None,
+            guarantee_broken_on: None,
+            guarantee_refund_amount: None,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_refund_in_proportion_to_days_left() {
+        // 30일 근무 후 퇴사 → 90일 중 60일분 환불
+        let refund = calculate_refund(&completed_matching(), date("2025-01-31")).unwrap();
+        assert_eq!(refund.days_worked, 30);
+        assert_eq!(refund.remaining_days, 60);
+        assert_eq!(refund.refund_amount, Decimal::new(200_000, 0));
+
+        // 100,000 x 89 / 90 = 98,888.9 → 98,889
+        let mut matching = completed_matching();
+        matching.employer_fee_amount = Some(Decimal::new(100_000, 0));
+        let refund = calculate_refund(&matching, date("2025-01-02")).unwrap();
+        assert_eq!(refund.refund_amount, Decimal::new(98_889, 0));
+    }
+
+    #[test]
+    fn test_guarantee_window() {
+        let matching = completed_matching();
+        assert_eq!(
+            calculate_refund(&matching, date("2025-01-01"))
+                .unwrap()
+                .refund_amount,
+            Decimal::new(300_000, 0)
+        );
+        assert!(ensure_open(&matching, date("2025-03-31")).is_ok());
+        assert_eq!(
+            ensure_open(&matching, date("2025-04-01")),
+            Err(GuaranteeError::Expired(date("2025-04-01")))
+        );
+        assert_eq!(
+            ensure_open(&matching, date("2024-12-31")),
+            Err(GuaranteeError::BeforeStart(date("2025-01-01")))
+        );
+    }
+
+    #[test]
+    fn test_guarantee_requires_completed_matching() {
+        let mut matching = completed_matching();
+        matching.guarantee_days = None;
+        assert_eq!(
+            ensure_open(&matching, date("2025-01-10")),
+            Err(GuaranteeError::NoGuarantee)
+        );
+
+        let mut matching = completed_matching();
+        matching.matching_status = MatchingStatus::InProgress;
+        assert_eq!(
+            ensure_open(&matching, date("2025-01-10")),
+            Err(GuaranteeError::NotCompleted)
+        );
+
+        let mut matching = completed_matching();
+        matching.guarantee_resolution = Some(GuaranteeResolution::Refund);
+        assert_eq!(
+            ensure_open(&matching, date("2025-01-10")),
+            Err(GuaranteeError::AlreadyRefunded)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+/// Initial consonants (compatibility jamo) in Unicode syllable order
+const CHOSEONG: [char; 19] = [
+    'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ',
+    'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+const SYLLABLE_START: u32 = 0xAC00; // 가
+const SYLLABLE_END: u32 = 0xD7A3; // 힣
+const SYLLABLES_PER_CHOSEONG: u32 = 21 * 28;
+
+/// Replace each Hangul syllable with its initial consonant ("김민수" → "ㄱㅁㅅ").
+///
+/// Whitespace is dropped and other characters are kept as they are. Must stay
+/// in sync with the `hangul_choseong` SQL function behind `customers.name_choseong`.
+pub fn choseong(input: &str) -> String {
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            let code = c as u32;
+            if (SYLLABLE_START..=SYLLABLE_END).contains(&code) {
+                CHOSEONG[((code - SYLLABLE_START) / SYLLABLES_PER_CHOSEONG) as usize]
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Whether the term contains a bare consonant ("ㄱㅁㅅ", "김ㅁ"), i.e. the user
+/// is searching by initial consonants
+pub fn is_choseong_query(term: &str) -> bool {
+    term.chars().any(|c| CHOSEONG.contains(&c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choseong() {
+        assert_eq!(choseong("김민수"), "ㄱㅁㅅ");
+        assert_eq!(choseong("홍 길동"), "ㅎㄱㄷ");
+        assert_eq!(choseong("빵집 Kim2"), "ㅃㅈKim2");
+        assert_eq!(choseong("ㄱㅁㅅ"), "ㄱㅁㅅ");
+    }
+
+    #[test]
+    fn test_is_choseong_query() {
+        assert!(is_choseong_query("ㄱㅁㅅ"));
+        assert!(is_choseong_query("김ㅁ"));
+        assert!(!is_choseong_query("김민수"));
+        assert!(!is_choseong_query("5678"));
+    }
+}
@@ -0,0 +1,111 @@
+use thiserror::Error;
+
+use crate::models::job_posting::Weekday;
+
+const MAX_HOUSEHOLD_MEMBERS: i32 = 30;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum JobAttributeError {
+    #[error("올바른 지역 코드가 아닙니다: {0}")]
+    InvalidRegionCode(String),
+    #[error("가족 구성원 수는 1명 이상 {MAX_HOUSEHOLD_MEMBERS}명 이하로 입력해주세요")]
+    InvalidHouseholdMembers,
+}
+
+/// Validate a single administrative region code.
+///
+/// Accepts 시도 (2), 시군구 (5) and 읍면동 (8 or 10 digit) prefixes of the
+/// 법정동 코드, e.g. "11" (서울), "11680" (강남구).
+pub fn normalize_region_code(code: &str) -> Result<String, JobAttributeError> {
+    let trimmed = code.trim();
+    let valid_length = matches!(trimmed.len(), 2 | 5 | 8 | 10);
+    if valid_length && trimmed.chars().all(|c| c.is_ascii_digit()) {
+        Ok(trimmed.to_string())
+    } else {
+        Err(JobAttributeError::InvalidRegionCode(code.to_string()))
+    }
+}
+
+/// Validate region codes and drop duplicates, keeping the given order
+pub fn normalize_region_codes(codes: Vec<String>) -> Result<Vec<String>, JobAttributeError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(codes.len());
+    for code in codes {
+        let code = normalize_region_code(&code)?;
+        if !normalized.contains(&code) {
+            normalized.push(code);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Sort working days Monday first and drop duplicates
+pub fn normalize_work_days(mut days: Vec<Weekday>) -> Vec<Weekday> {
+    days.sort();
+    days.dedup();
+    days
+}
+
+pub fn validate_household_members(members: Option<i32>) -> Result<(), JobAttributeError> {
+    match members {
+        Some(n) if !(1..=MAX_HOUSEHOLD_MEMBERS).contains(&n) => {
+            Err(JobAttributeError::InvalidHouseholdMembers)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_region_codes() {
+        let codes = vec![
+            "11680".to_string(),
+            " 11 ".to_string(),
+            "11680".to_string(),
+            "4113510300".to_string(),
+        ];
+        assert_eq!(
+            normalize_region_codes(codes).unwrap(),
+            vec!["11680", "11", "4113510300"]
+        );
+
+        for invalid in ["1", "116", "11a80", "서울", ""] {
+            assert_eq!(
+                normalize_region_code(invalid),
+                Err(JobAttributeError::InvalidRegionCode(invalid.to_string())),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_work_days() {
+        let days = vec![
+            Weekday::Friday,
+            Weekday::Monday,
+            Weekday::Friday,
+            Weekday::Wednesday,
+        ];
+        assert_eq!(
+            normalize_work_days(days),
+            vec![Weekday::Monday, Weekday::Wednesday, Weekday::Friday]
+        );
+    }
+
+    #[test]
+    fn test_validate_household_members() {
+        assert!(validate_household_members(None).is_ok());
+        assert!(validate_household_members(Some(4)).is_ok());
+        assert_eq!(
+            validate_household_members(Some(0)),
+            Err(JobAttributeError::InvalidHouseholdMembers)
+        );
+        assert_eq!(
+            validate_household_members(Some(31)),
+            Err(JobAttributeError::InvalidHouseholdMembers)
+        );
+    }
+}
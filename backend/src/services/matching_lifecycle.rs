@@ -0,0 +1,72 @@
+use thiserror::Error;
+
+use crate::models::matching::MatchingStatus;
+use crate::services::format::matching_status_label;
+
+#[derive(Debug, Error, PartialEq)]
+#[error(
+    "매칭 상태를 '{}'에서 '{}'(으)로 변경할 수 없습니다",
+    matching_status_label(from),
+    matching_status_label(to)
+)]
+pub struct InvalidTransition {
+    pub from: MatchingStatus,
+    pub to: MatchingStatus,
+}
+
+/// Whether a matching may move from `from` to `to`.
+///
+/// Only an in-progress matching can be completed or cancelled; completed
+/// and cancelled matchings are final. Keeping the current status is always
+/// allowed.
+pub fn can_transition(from: &MatchingStatus, to: &MatchingStatus) -> bool {
+    from == to
+        || matches!(
+            (from, to),
+            (
+                MatchingStatus::InProgress,
+                MatchingStatus::Completed | MatchingStatus::Cancelled
+            )
+        )
+}
+
+pub fn ensure_transition(
+    from: &MatchingStatus,
+    to: &MatchingStatus,
+) -> Result<(), InvalidTransition> {
+    if can_transition(from, to) {
+        Ok(())
+    } else {
+        Err(InvalidTransition {
+            from: from.clone(),
+            to: to.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MatchingStatus::*;
+
+    #[test]
+    fn test_matching_transitions() {
+        assert!(can_transition(&InProgress, &Completed));
+        assert!(can_transition(&InProgress, &Cancelled));
+        assert!(can_transition(&Completed, &Completed));
+
+        assert!(!can_transition(&Completed, &InProgress));
+        assert!(!can_transition(&Completed, &Cancelled));
+        assert!(!can_transition(&Cancelled, &InProgress));
+        assert!(!can_transition(&Cancelled, &Completed));
+    }
+
+    #[test]
+    fn test_transition_error_message() {
+        let error = ensure_transition(&Cancelled, &Completed).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "매칭 상태를 '취소'에서 '완료'(으)로 변경할 수 없습니다"
+        );
+    }
+}
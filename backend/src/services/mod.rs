@@ -1 +1,26 @@
+pub mod analytics;
 pub mod auth;
+pub mod bank_import;
+pub mod business_number;
+pub mod customer_merge;
+pub mod export;
+pub mod fee;
+pub mod fee_document;
+pub mod format;
+pub mod guarantee;
+pub mod hangul;
+pub mod job_attributes;
+pub mod matching_lifecycle;
+pub mod monthly_report;
+pub mod payment;
+pub mod pdf;
+pub mod phone;
+pub mod posting_lifecycle;
+pub mod receivables;
+pub mod salary;
+pub mod search;
+pub mod settlement;
+pub mod statement;
+pub mod storage;
+pub mod suggestion;
+pub mod trash;
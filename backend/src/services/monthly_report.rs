@@ -0,0 +1,496 @@
+use std::time::Duration as IntervalDuration;
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_xlsxwriter::XlsxError;
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::models::analytics::{AnalyticsMetric, MetricRow};
+use crate::models::monthly_report::{
+    CancellationReasonCount, MonthlyFeeRow, MonthlyReport, NewMonthlyReport, StoredMonthlyReport,
+    TopCustomer,
+};
+use crate::models::report::ReceivableRow;
+use crate::repositories::{analytics, monthly_report, report};
+use crate::services::export::{ExportCell, ExportRecord, XlsxExport};
+use crate::services::format::format_won;
+use crate::services::pdf::{self, PdfError, PdfLine};
+use crate::services::{receivables, storage};
+
+/// Customers listed in the report
+pub const TOP_CUSTOMERS: usize = 5;
+
+const NO_REASON: &str = "사유 미입력";
+
+#[derive(Debug, Error)]
+pub enum MonthlyReportError {
+    #[error("올바른 연월이 아닙니다")]
+    InvalidMonth,
+    #[error(transparent)]
+    Pdf(#[from] PdfError),
+    #[error("XLSX 생성 실패: {0}")]
+    Xlsx(#[from] XlsxError),
+    #[error("보고서 저장 실패: {0}")]
+    Storage(String),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+/// First and last day of a month
+pub fn month_range(year: i32, month: u32) -> Result<(NaiveDate, NaiveDate), MonthlyReportError> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1).ok_or(MonthlyReportError::InvalidMonth)?;
+    Ok((start, start + Months::new(1) - Duration::days(1)))
+}
+
+/// Year and month before the one `today` falls in
+pub fn previous_month(today: NaiveDate) -> (i32, u32) {
+    let last_month = today.with_day(1).expect("every month has a first day") - Months::new(1);
+    (last_month.year(), last_month.month())
+}
+
+/// Assemble the report of the month starting at `period_start` from its daily
+/// metrics, the fees of the matchings completed in it, all receivables and
+/// the counts of cancellation reasons. Outstanding amounts are as of the last
+/// day of the month.
+pub fn build_report(
+    period_start: NaiveDate,
+    metrics: &[MetricRow],
+    fee_rows: &[MonthlyFeeRow],
+    receivable_rows: Vec<ReceivableRow>,
+    cancellation_reasons: Vec<(Option<String>, i64)>,
+) -> MonthlyReport {
+    let period_end = period_start + Months::new(1) - Duration::days(1);
+    let total = |metric| -> Decimal {
+        metrics
+            .iter()
+            .filter(|row| row.metric == metric && (period_start..=period_end).contains(&row.bucket))
+            .map(|row| row.value)
+            .sum()
+    };
+    let count = |metric| total(metric).to_i64().unwrap_or_default();
+
+    let receivable_rows = receivable_rows
+        .into_iter()
+        .filter(|row| row.completed_at.date() <= period_end)
+        .collect();
+    let outstanding = receivables::build_report(receivable_rows, period_end).totals;
+
+    MonthlyReport {
+        year: period_start.year(),
+        month: period_start.month(),
+        period_start,
+        period_end,
+        matchings_created: count(AnalyticsMetric::MatchingsCreated),
+        matchings_completed: count(AnalyticsMetric::MatchingsCompleted),
+        matchings_cancelled: count(AnalyticsMetric::MatchingsCancelled),
+        fees_earned: total(AnalyticsMetric::RevenueEarned),
+        fees_collected: total(AnalyticsMetric::RevenueCollected),
+        outstanding,
+        top_customers: top_customers(fee_rows),
+        cancellation_reasons: cancellation_reasons
+            .into_iter()
+            .map(|(reason, count)| CancellationReasonCount {
+                reason: reason.unwrap_or_else(|| NO_REASON.to_string()),
+                count,
+            })
+            .collect(),
+    }
+}
+
+/// Customers the most fees were earned from, net of guarantee refunds
+fn top_customers(fee_rows: &[MonthlyFeeRow]) -> Vec<TopCustomer> {
+    let mut customers: Vec<TopCustomer> = Vec::new();
+    for row in fee_rows {
+        let index = match customers
+            .iter()
+            .position(|customer| customer.customer_id == row.customer_id)
+        {
+            Some(index) => index,
+            None => {
+                customers.push(TopCustomer {
+                    customer_id: row.customer_id,
+                    customer_name: row.customer_name.clone(),
+                    matchings_completed: 0,
+                    fees_earned: Decimal::ZERO,
+                });
+                customers.len() - 1
+            }
+        };
+        let customer = &mut customers[index];
+        customer.matchings_completed += 1;
+        customer.fees_earned += row.fee_amount - row.refunded_amount;
+    }
+
+    customers.retain(|customer| customer.fees_earned > Decimal::ZERO);
+    customers.sort_by(|a, b| {
+        b.fees_earned
+            .cmp(&a.fees_earned)
+            .then(a.customer_id.cmp(&b.customer_id))
+    });
+    customers.truncate(TOP_CUSTOMERS);
+    customers
+}
+
+fn won(amount: Decimal) -> String {
+    format!("{}원", format_won(amount))
+}
+
+fn title(report: &MonthlyReport) -> String {
+    format!("{}년 {}월 업무 보고서", report.year, report.month)
+}
+
+pub fn report_lines(report: &MonthlyReport) -> Vec<PdfLine> {
+    let outstanding = &report.outstanding;
+    let mut lines = vec![
+        PdfLine::Title(title(report)),
+        PdfLine::pair(
+            "기간",
+            format!(
+                "{} ~ {}",
+                report.period_start.format("%Y-%m-%d"),
+                report.period_end.format("%Y-%m-%d")
+            ),
+        ),
+        PdfLine::Rule,
+        PdfLine::Heading("매칭".to_string()),
+        PdfLine::pair("신규 매칭", format!("{}건", report.matchings_created)),
+        PdfLine::pair("완료", format!("{}건", report.matchings_completed)),
+        PdfLine::pair("취소", format!("{}건", report.matchings_cancelled)),
+        PdfLine::Heading("수수료".to_string()),
+        PdfLine::pair("발생 수수료", won(report.fees_earned)),
+        PdfLine::pair("수금액", won(report.fees_collected)),
+        PdfLine::Heading("미수금 (말일 기준)".to_string()),
+        PdfLine::pair("0-30일", won(outstanding.current)),
+        PdfLine::pair("31-60일", won(outstanding.days_31_60)),
+        PdfLine::pair("61-90일", won(outstanding.days_61_90)),
+        PdfLine::pair("90일 초과", won(outstanding.over_90)),
+        PdfLine::pair("합계", won(outstanding.total)),
+        PdfLine::Heading("상위 고객".to_string()),
+    ];
+
+    if report.top_customers.is_empty() {
+        lines.push(PdfLine::Text("완료된 매칭이 없습니다".to_string()));
+    } else {
+        lines.push(PdfLine::Row(
+            ["순위", "고객명", "완료 매칭", "수수료"]
+                .map(String::from)
+                .to_vec(),
+        ));
+        for (rank, customer) in report.top_customers.iter().enumerate() {
+            lines.push(PdfLine::Row(vec![
+                (rank + 1).to_string(),
+                customer.customer_name.clone(),
+                format!("{}건", customer.matchings_completed),
+                won(customer.fees_earned),
+            ]));
+        }
+    }
+
+    lines.push(PdfLine::Heading("취소 사유".to_string()));
+    if report.cancellation_reasons.is_empty() {
+        lines.push(PdfLine::Text("취소된 매칭이 없습니다".to_string()));
+    } else {
+        for reason in &report.cancellation_reasons {
+            lines.push(PdfLine::Row(vec![
+                reason.reason.clone(),
+                format!("{}건", reason.count),
+            ]));
+        }
+    }
+
+    lines
+}
+
+/// One line of the XLSX report
+#[derive(Debug, Clone)]
+pub struct MonthlyReportRow {
+    pub section: &'static str,
+    pub item: String,
+    pub value: ExportCell,
+}
+
+impl ExportRecord for MonthlyReportRow {
+    const HEADERS: &'static [&'static str] = &["구분", "항목", "값"];
+
+    fn cells(&self) -> Vec<ExportCell> {
+        vec![
+            self.section.into(),
+            self.item.clone().into(),
+            self.value.clone(),
+        ]
+    }
+}
+
+pub fn report_rows(report: &MonthlyReport) -> Vec<MonthlyReportRow> {
+    let row = |section, item: &str, value: ExportCell| MonthlyReportRow {
+        section,
+        item: item.to_string(),
+        value,
+    };
+    let outstanding = &report.outstanding;
+    let mut rows = vec![
+        row("기간", "시작일", report.period_start.into()),
+        row("기간", "종료일", report.period_end.into()),
+        row("매칭", "신규 매칭", report.matchings_created.into()),
+        row("매칭", "완료", report.matchings_completed.into()),
+        row("매칭", "취소", report.matchings_cancelled.into()),
+        row("수수료", "발생 수수료", report.fees_earned.into()),
+        row("수수료", "수금액", report.fees_collected.into()),
+        row("미수금", "0-30일", outstanding.current.into()),
+        row("미수금", "31-60일", outstanding.days_31_60.into()),
+        row("미수금", "61-90일", outstanding.days_61_90.into()),
+        row("미수금", "90일 초과", outstanding.over_90.into()),
+        row("미수금", "합계", outstanding.total.into()),
+    ];
+    for (rank, customer) in report.top_customers.iter().enumerate() {
+        rows.push(row(
+            "상위 고객",
+            &format!(
+                "{}. {} (완료 {}건)",
+                rank + 1,
+                customer.customer_name,
+                customer.matchings_completed
+            ),
+            customer.fees_earned.into(),
+        ));
+    }
+    for reason in &report.cancellation_reasons {
+        rows.push(row("취소 사유", &reason.reason, reason.count.into()));
+    }
+
+    rows
+}
+
+pub fn render_xlsx(report: &MonthlyReport) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = XlsxExport::new::<MonthlyReportRow>("월간 보고서")?;
+    for row in report_rows(report) {
+        workbook.push(&row)?;
+    }
+    workbook.finish()
+}
+
+pub async fn render_pdf(config: &Config, report: &MonthlyReport) -> Result<Vec<u8>, PdfError> {
+    let font = pdf::load_font(config).await?;
+    pdf::render(&title(report), &report_lines(report), &font)
+}
+
+/// Gather the figures of the user's report for a month
+pub async fn generate_report(
+    pool: &PgPool,
+    user_id: i64,
+    year: i32,
+    month: u32,
+) -> Result<MonthlyReport, MonthlyReportError> {
+    let (from, to) = month_range(year, month)?;
+
+    analytics::refresh_dirty_days(pool, user_id).await?;
+    let metrics = analytics::list_rollup_metrics(pool, user_id, from, to).await?;
+    let fee_rows = monthly_report::list_monthly_fee_rows(pool, user_id, from, to).await?;
+    let receivable_rows = report::list_receivable_rows(pool, user_id).await?;
+    let reasons = monthly_report::list_cancellation_reasons(pool, user_id, from, to).await?;
+
+    Ok(build_report(
+        from,
+        &metrics,
+        &fee_rows,
+        receivable_rows,
+        reasons,
+    ))
+}
+
+/// Generate the user's report for a month, store the PDF and XLSX in MinIO
+/// and record them, replacing an earlier report of the same month
+pub async fn store_report(
+    pool: &PgPool,
+    config: &Config,
+    user_id: i64,
+    year: i32,
+    month: u32,
+) -> Result<StoredMonthlyReport, MonthlyReportError> {
+    let report = generate_report(pool, user_id, year, month).await?;
+    let pdf = render_pdf(config, &report).await?;
+    let xlsx = render_xlsx(&report)?;
+
+    let stem = format!("reports/{}/{}-{:02}", user_id, year, month);
+    let pdf_path = format!("{}.pdf", stem);
+    let xlsx_path = format!("{}.xlsx", stem);
+    storage::put_object(config, &pdf_path, &pdf, "application/pdf")
+        .await
+        .map_err(MonthlyReportError::Storage)?;
+    storage::put_object(
+        config,
+        &xlsx_path,
+        &xlsx,
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    )
+    .await
+    .map_err(MonthlyReportError::Storage)?;
+
+    Ok(monthly_report::upsert_monthly_report(
+        pool,
+        &NewMonthlyReport {
+            user_id,
+            year,
+            month: month as i32,
+            pdf_path,
+            pdf_size: pdf.len() as i64,
+            xlsx_path,
+            xlsx_size: xlsx.len() as i64,
+        },
+    )
+    .await?)
+}
+
+/// Store last month's report of every user once the month is over, i.e. on
+/// the first of the month or on the first run after it
+pub fn spawn_monthly_report_task(pool: PgPool, config: Config) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(IntervalDuration::from_secs(
+            config.monthly_report_interval_secs,
+        ));
+
+        loop {
+            interval.tick().await;
+            let (year, month) = previous_month(Local::now().date_naive());
+            let Ok((_, period_end)) = month_range(year, month) else {
+                continue;
+            };
+            let user_ids = match monthly_report::list_users_missing_report(
+                &pool,
+                year,
+                month as i32,
+                period_end,
+            )
+            .await
+            {
+                Ok(user_ids) => user_ids,
+                Err(e) => {
+                    tracing::error!("Monthly report lookup failed: {:?}", e);
+                    continue;
+                }
+            };
+
+            for user_id in user_ids {
+                match store_report(&pool, &config, user_id, year, month).await {
+                    Ok(_) => tracing::info!(
+                        "Stored {}-{:02} monthly report of user {}",
+                        year,
+                        month,
+                        user_id
+                    ),
+                    Err(MonthlyReportError::Pdf(PdfError::FontNotConfigured)) => {
+                        tracing::warn!("Skipping monthly reports: PDF_FONT_PATH is not set");
+                        break;
+                    }
+                    Err(e) => tracing::error!("Monthly report of user {} failed: {:?}", user_id, e),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::payment::PayerSide;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn won(amount: i64) -> Decimal {
+        Decimal::new(amount, 0)
+    }
+
+    fn fee(matching_id: i64, customer_id: i64, fee: i64, refunded: i64) -> MonthlyFeeRow {
+        MonthlyFeeRow {
+            matching_id,
+            payer_side: PayerSide::Employer,
+            customer_id,
+            customer_name: format!("고객{}", customer_id),
+            fee_amount: won(fee),
+            refunded_amount: won(refunded),
+        }
+    }
+
+    fn receivable(matching_id: i64, completed_on: NaiveDate, fee: i64) -> ReceivableRow {
+        ReceivableRow {
+            matching_id,
+            payer_side: PayerSide::Employer,
+            customer_id: 1,
+            customer_name: "고객1".to_string(),
+            phone: "010-0000-0000".to_string(),
+            completed_at: completed_on.and_hms_opt(10, 0, 0).unwrap(),
+            fee_amount: won(fee),
+            refunded_amount: Decimal::ZERO,
+            paid_amount: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_month_range_and_previous_month() {
+        assert_eq!(
+            month_range(2024, 2).unwrap(),
+            (date(2024, 2, 1), date(2024, 2, 29))
+        );
+        assert!(matches!(
+            month_range(2024, 13),
+            Err(MonthlyReportError::InvalidMonth)
+        ));
+        assert_eq!(previous_month(date(2025, 1, 1)), (2024, 12));
+        assert_eq!(previous_month(date(2025, 3, 31)), (2025, 2));
+    }
+
+    #[test]
+    fn test_build_report() {
+        let start = date(2025, 10, 1);
+        let metrics = [
+            MetricRow {
+                bucket: date(2025, 10, 3),
+                metric: AnalyticsMetric::MatchingsCompleted,
+                value: Decimal::ONE,
+            },
+            MetricRow {
+                bucket: date(2025, 10, 20),
+                metric: AnalyticsMetric::RevenueCollected,
+                value: won(100_000),
+            },
+            MetricRow {
+                bucket: date(2025, 11, 1),
+                metric: AnalyticsMetric::RevenueCollected,
+                value: won(50_000),
+            },
+        ];
+        let fees = [
+            fee(1, 1, 300_000, 0),
+            fee(2, 2, 500_000, 0),
+            fee(3, 1, 300_000, 0),
+            fee(4, 3, 200_000, 200_000),
+        ];
+        let receivables = vec![
+            receivable(1, date(2025, 10, 3), 300_000),
+            receivable(9, date(2025, 11, 2), 400_000),
+        ];
+        let reasons = vec![(Some("근무 조건 불일치".to_string()), 2), (None, 1)];
+
+        let report = build_report(start, &metrics, &fees, receivables, reasons);
+        assert_eq!(report.period_end, date(2025, 10, 31));
+        assert_eq!(report.matchings_completed, 1);
+        assert_eq!(report.fees_collected, won(100_000));
+        assert_eq!(report.outstanding.total, won(300_000));
+        assert_eq!(
+            report
+                .top_customers
+                .iter()
+                .map(|customer| (customer.customer_id, customer.matchings_completed))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1)]
+        );
+        assert_eq!(report.top_customers[0].fees_earned, won(600_000));
+        assert_eq!(report.cancellation_reasons[1].reason, NO_REASON);
+        assert_eq!(report_rows(&report).len(), 12 + 2 + 2);
+    }
+}
@@ -0,0 +1,172 @@
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::models::job_posting::SettlementStatus;
+use crate::models::payment::{PayerSide, SideBalance};
+use crate::services::format::format_won;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PaymentError {
+    #[error("입금액은 0원보다 커야 합니다")]
+    InvalidAmount,
+    #[error("입금액이 미수금({}원)보다 많습니다", format_won(*.0))]
+    Overpayment(Decimal),
+}
+
+/// Settlement status of a fee of `due` of which `paid` has been received
+pub fn settlement_status(due: Decimal, paid: Decimal) -> SettlementStatus {
+    if paid >= due {
+        SettlementStatus::Settled
+    } else if paid > Decimal::ZERO {
+        SettlementStatus::PartiallySettled
+    } else {
+        SettlementStatus::Unsettled
+    }
+}
+
+pub fn side_balance(
+    payer_side: PayerSide,
+    fee_amount: Decimal,
+    refunded_amount: Decimal,
+    paid_amount: Decimal,
+) -> SideBalance {
+    let due = (fee_amount - refunded_amount).max(Decimal::ZERO);
+
+    SideBalance {
+        payer_side,
+        fee_amount,
+        refunded_amount,
+        paid_amount,
+        outstanding_amount: (due - paid_amount).max(Decimal::ZERO),
+        settlement_status: settlement_status(due, paid_amount),
+    }
+}
+
+/// Check that `amount` can be paid against `balance` without overpaying
+pub fn ensure_payable(balance: &SideBalance, amount: Decimal) -> Result<(), PaymentError> {
+    if amount <= Decimal::ZERO {
+        return Err(PaymentError::InvalidAmount);
+    }
+    if amount > balance.outstanding_amount {
+        return Err(PaymentError::Overpayment(balance.outstanding_amount));
+    }
+    Ok(())
+}
+
+/// Settlement status of a posting from the balances of its matchings:
+/// settled when every one is, unsettled when none has been paid, otherwise
+/// partially settled. `None` when the posting has no matchings.
+pub fn posting_settlement_status(balances: &[SideBalance]) -> Option<SettlementStatus> {
+    let first = balances.first()?;
+    if balances
+        .iter()
+        .all(|balance| balance.settlement_status == first.settlement_status)
+    {
+        Some(first.settlement_status.clone())
+    } else {
+        Some(SettlementStatus::PartiallySettled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn won(amount: i64) -> Decimal {
+        Decimal::new(amount, 0)
+    }
+
+    #[test]
+    fn test_side_balance_with_instalments() {
+        let unpaid = side_balance(
+            PayerSide::Employer,
+            won(500_000),
+            Decimal::ZERO,
+            Decimal::ZERO,
+        );
+        assert_eq!(unpaid.outstanding_amount, won(500_000));
+        assert_eq!(unpaid.settlement_status, SettlementStatus::Unsettled);
+
+        let half = side_balance(
+            PayerSide::Employer,
+            won(500_000),
+            Decimal::ZERO,
+            won(250_000),
+        );
+        assert_eq!(half.outstanding_amount, won(250_000));
+        assert_eq!(half.settlement_status, SettlementStatus::PartiallySettled);
+
+        let paid = side_balance(
+            PayerSide::Employer,
+            won(500_000),
+            Decimal::ZERO,
+            won(500_000),
+        );
+        assert_eq!(paid.outstanding_amount, Decimal::ZERO);
+        assert_eq!(paid.settlement_status, SettlementStatus::Settled);
+    }
+
+    #[test]
+    fn test_refund_reduces_amount_due() {
+        let balance = side_balance(
+            PayerSide::Employer,
+            won(300_000),
+            won(200_000),
+            won(100_000),
+        );
+        assert_eq!(balance.outstanding_amount, Decimal::ZERO);
+        assert_eq!(balance.settlement_status, SettlementStatus::Settled);
+
+        // 수수료가 없는 쪽은 정산할 것이 없음
+        let free = side_balance(
+            PayerSide::Employer,
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::ZERO,
+        );
+        assert_eq!(free.settlement_status, SettlementStatus::Settled);
+    }
+
+    #[test]
+    fn test_ensure_payable() {
+        let balance = side_balance(
+            PayerSide::Employee,
+            won(500_000),
+            Decimal::ZERO,
+            won(200_000),
+        );
+        assert!(ensure_payable(&balance, won(300_000)).is_ok());
+        assert_eq!(
+            ensure_payable(&balance, won(300_001)),
+            Err(PaymentError::Overpayment(won(300_000)))
+        );
+        assert_eq!(
+            ensure_payable(&balance, Decimal::ZERO),
+            Err(PaymentError::InvalidAmount)
+        );
+        assert_eq!(
+            PaymentError::Overpayment(won(300_000)).to_string(),
+            "입금액이 미수금(300,000원)보다 많습니다"
+        );
+    }
+
+    #[test]
+    fn test_posting_settlement_status() {
+        let settled = side_balance(PayerSide::Employer, won(100), Decimal::ZERO, won(100));
+        let unsettled = side_balance(PayerSide::Employer, won(100), Decimal::ZERO, Decimal::ZERO);
+
+        assert_eq!(posting_settlement_status(&[]), None);
+        assert_eq!(
+            posting_settlement_status(&[settled.clone(), settled.clone()]),
+            Some(SettlementStatus::Settled)
+        );
+        assert_eq!(
+            posting_settlement_status(std::slice::from_ref(&unsettled)),
+            Some(SettlementStatus::Unsettled)
+        );
+        assert_eq!(
+            posting_settlement_status(&[settled, unsettled]),
+            Some(SettlementStatus::PartiallySettled)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::io::Cursor;
+
+use printpdf::{IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference, Point};
+use thiserror::Error;
+
+use crate::config::Config;
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+/// x position of the value of a `Pair`
+const VALUE_X: f32 = 65.0;
+
+#[derive(Debug, Error)]
+pub enum PdfError {
+    #[error("PDF 글꼴이 설정되지 않았습니다 (PDF_FONT_PATH)")]
+    FontNotConfigured,
+    #[error("PDF 글꼴을 읽을 수 없습니다: {0}")]
+    Font(String),
+    #[error("PDF 생성 실패: {0}")]
+    Render(String),
+}
+
+/// One line of a generated document, laid out top to bottom
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfLine {
+    Title(String),
+    Heading(String),
+    Text(String),
+    /// Label on the left, value aligned in a second column
+    Pair(String, String),
+    /// Cells spread evenly across the page
+    Row(Vec<String>),
+    Rule,
+    Blank,
+}
+
+impl PdfLine {
+    pub fn pair(label: impl Into<String>, value: impl Into<String>) -> Self {
+        PdfLine::Pair(label.into(), value.into())
+    }
+
+    fn font_size(&self) -> f32 {
+        match self {
+            PdfLine::Title(_) => 18.0,
+            PdfLine::Heading(_) => 12.0,
+            _ => 10.0,
+        }
+    }
+
+    /// Vertical space the line takes, in mm
+    fn height(&self) -> f32 {
+        match self {
+            PdfLine::Title(_) => 14.0,
+            PdfLine::Heading(_) => 9.0,
+            PdfLine::Rule | PdfLine::Blank => 4.0,
+            _ => 6.0,
+        }
+    }
+}
+
+/// Read the TrueType font with Hangul glyphs documents are rendered with
+pub async fn load_font(config: &Config) -> Result<Vec<u8>, PdfError> {
+    let path = config
+        .pdf_font_path
+        .as_deref()
+        .ok_or(PdfError::FontNotConfigured)?;
+
+    tokio::fs::read(path)
+        .await
+        .map_err(|e| PdfError::Font(format!("{}: {}", path, e)))
+}
+
+/// Render `lines` onto A4 pages, starting a new page when one is full
+pub fn render(title: &str, lines: &[PdfLine], font: &[u8]) -> Result<Vec<u8>, PdfError> {
+    let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+    let font = doc
+        .add_external_font(Cursor::new(font))
+        .map_err(|e| PdfError::Font(e.to_string()))?;
+
+    let mut layer = doc.get_page(page).get_layer(layer);
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    for line in lines {
+        if y - line.height() < MARGIN {
+            let (page, next) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+            layer = doc.get_page(page).get_layer(next);
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= line.height();
+        draw_line(&layer, &font, line, y);
+    }
+
+    doc.save_to_bytes()
+        .map_err(|e| PdfError::Render(e.to_string()))
+}
+
+fn draw_line(layer: &PdfLayerReference, font: &IndirectFontRef, line: &PdfLine, y: f32) {
+    let size = line.font_size();
+    match line {
+        PdfLine::Title(text) | PdfLine::Heading(text) | PdfLine::Text(text) => {
+            layer.use_text(text.as_str(), size, Mm(MARGIN), Mm(y), font);
+        }
+        PdfLine::Pair(label, value) => {
+            layer.use_text(label.as_str(), size, Mm(MARGIN), Mm(y), font);
+            layer.use_text(value.as_str(), size, Mm(VALUE_X), Mm(y), font);
+        }
+        PdfLine::Row(cells) => {
+            let width = (PAGE_WIDTH - 2.0 * MARGIN) / cells.len().max(1) as f32;
+            for (i, cell) in cells.iter().enumerate() {
+                let x = MARGIN + width * i as f32;
+                layer.use_text(cell.as_str(), size, Mm(x), Mm(y), font);
+            }
+        }
+        PdfLine::Rule => {
+            let y = y + 2.0;
+            layer.add_line(Line {
+                points: vec![
+                    (Point::new(Mm(MARGIN), Mm(y)), false),
+                    (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(y)), false),
+                ],
+                is_closed: false,
+            });
+        }
+        PdfLine::Blank => {}
+    }
+}
@@ -0,0 +1,161 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PhoneError {
+    #[error("전화번호를 입력해주세요")]
+    Empty,
+    #[error("전화번호에 사용할 수 없는 문자가 포함되어 있습니다")]
+    InvalidCharacters,
+    #[error("올바른 전화번호 형식이 아닙니다")]
+    InvalidFormat,
+}
+
+/// Normalize a Korean phone number to its canonical digits-only form.
+///
+/// Accepts the usual ways numbers are written ("010-1234-5678",
+/// "010 1234 5678", "(02) 123-4567", "+82 10-1234-5678", "0082-10-...")
+/// and returns the domestic digits, e.g. "01012345678".
+pub fn normalize_phone(input: &str) -> Result<String, PhoneError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(PhoneError::Empty);
+    }
+
+    let allowed = |(i, c): (usize, char)| {
+        c.is_ascii_digit() || matches!(c, '-' | ' ' | '.' | '(' | ')') || (c == '+' && i == 0)
+    };
+    if !trimmed.char_indices().all(allowed) {
+        return Err(PhoneError::InvalidCharacters);
+    }
+
+    let digits = to_domestic(trimmed.starts_with('+'), extract_digits(trimmed))
+        .ok_or(PhoneError::InvalidFormat)?;
+
+    if is_valid_domestic(&digits) {
+        Ok(digits)
+    } else {
+        Err(PhoneError::InvalidFormat)
+    }
+}
+
+/// Digits to match against `customers.phone_normalized` for a search term.
+///
+/// Returns `None` when the term does not look like (part of) a phone number.
+pub fn phone_search_digits(term: &str) -> Option<String> {
+    let trimmed = term.trim();
+    if !trimmed
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '-' | ' ' | '.' | '(' | ')' | '+'))
+    {
+        return None;
+    }
+
+    let digits = extract_digits(trimmed);
+    let digits = if trimmed.starts_with('+') || digits.starts_with("0082") {
+        to_domestic(true, digits)?
+    } else {
+        digits
+    };
+
+    (digits.len() >= 3).then_some(digits)
+}
+
+fn extract_digits(input: &str) -> String {
+    input.chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+/// Strip an international prefix (+82 / 0082) and restore the leading trunk 0
+fn to_domestic(has_plus: bool, digits: String) -> Option<String> {
+    let national = if let Some(rest) = digits.strip_prefix("0082") {
+        rest
+    } else if has_plus {
+        digits.strip_prefix("82")?
+    } else {
+        return Some(digits);
+    };
+
+    if national.starts_with('0') {
+        Some(national.to_string())
+    } else {
+        Some(format!("0{}", national))
+    }
+}
+
+fn is_valid_domestic(digits: &str) -> bool {
+    let len = digits.len();
+    let bytes = digits.as_bytes();
+
+    match bytes {
+        // 휴대전화: 010 (11자리), 011/016/017/018/019 (10~11자리)
+        [b'0', b'1', b'0', ..] => len == 11,
+        [b'0', b'1', b'1' | b'6'..=b'9', ..] => len == 10 || len == 11,
+        // 서울 지역번호
+        [b'0', b'2', ..] => len == 9 || len == 10,
+        // 지역번호 031~033, 041~044, 051~055, 061~064
+        [b'0', b'3', b'1'..=b'3', ..]
+        | [b'0', b'4', b'1'..=b'4', ..]
+        | [b'0', b'5', b'1'..=b'5', ..]
+        | [b'0', b'6', b'1'..=b'4', ..] => len == 10 || len == 11,
+        // 인터넷 전화 / 안심번호
+        [b'0', b'7', b'0', ..] => len == 11,
+        [b'0', b'5', b'0', ..] => len == 11 || len == 12,
+        // 대표번호 (1588-xxxx 등)
+        [b'1', b'5' | b'6' | b'8', ..] => len == 8,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_common_formats() {
+        for input in [
+            "010-1234-5678",
+            "01012345678",
+            "010 1234 5678",
+            "010.1234.5678",
+            "+82 10-1234-5678",
+            "+82-010-1234-5678",
+            "0082-10-1234-5678",
+            " 010-1234-5678 ",
+        ] {
+            assert_eq!(normalize_phone(input).unwrap(), "01012345678", "{}", input);
+        }
+
+        assert_eq!(normalize_phone("(02) 123-4567").unwrap(), "021234567");
+        assert_eq!(normalize_phone("031-123-4567").unwrap(), "0311234567");
+        assert_eq!(normalize_phone("1588-1234").unwrap(), "15881234");
+    }
+
+    #[test]
+    fn test_normalize_rejects_invalid_numbers() {
+        assert_eq!(normalize_phone("  "), Err(PhoneError::Empty));
+        assert_eq!(
+            normalize_phone("010-1234-567a"),
+            Err(PhoneError::InvalidCharacters)
+        );
+        assert_eq!(
+            normalize_phone("010-123-4567"),
+            Err(PhoneError::InvalidFormat)
+        );
+        assert_eq!(normalize_phone("12345"), Err(PhoneError::InvalidFormat));
+        assert_eq!(
+            normalize_phone("+1 415 555 0100"),
+            Err(PhoneError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn test_phone_search_digits() {
+        assert_eq!(phone_search_digits("5678").as_deref(), Some("5678"));
+        assert_eq!(phone_search_digits("010-1234").as_deref(), Some("0101234"));
+        assert_eq!(
+            phone_search_digits("+82 10 1234").as_deref(),
+            Some("0101234")
+        );
+        assert_eq!(phone_search_digits("12"), None);
+        assert_eq!(phone_search_digits("김민수"), None);
+    }
+}
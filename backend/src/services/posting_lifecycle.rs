@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::models::job_posting::PostingStatus;
+use crate::repositories::posting_status;
+use crate::services::format::posting_status_label;
+
+#[derive(Debug, Error, PartialEq)]
+#[error(
+    "공고 상태를 '{}'에서 '{}'(으)로 변경할 수 없습니다",
+    posting_status_label(from),
+    posting_status_label(to)
+)]
+pub struct InvalidTransition {
+    pub from: PostingStatus,
+    pub to: PostingStatus,
+}
+
+/// Whether a posting may move from `from` to `to`.
+///
+/// - 게시중 → 진행중 / 마감 / 취소
+/// - 진행중 → 게시중 (매칭 취소) / 마감 / 취소
+/// - 마감 → 게시중 (재게시)
+/// - 취소 → 변경 불가
+///
+/// Keeping the current status is always allowed.
+pub fn can_transition(from: &PostingStatus, to: &PostingStatus) -> bool {
+    use PostingStatus::*;
+
+    from == to
+        || matches!(
+            (from, to),
+            (Published, InProgress | Closed | Cancelled)
+                | (InProgress, Published | Closed | Cancelled)
+                | (Closed, Published)
+        )
+}
+
+pub fn ensure_transition(
+    from: &PostingStatus,
+    to: &PostingStatus,
+) -> Result<(), InvalidTransition> {
+    if can_transition(from, to) {
+        Ok(())
+    } else {
+        Err(InvalidTransition {
+            from: from.clone(),
+            to: to.clone(),
+        })
+    }
+}
+
+/// Run `close_expired_postings` in the background every `posting_expiry_interval_secs`
+pub fn spawn_expiry_task(pool: PgPool, config: Config) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(config.posting_expiry_interval_secs));
+
+        loop {
+            interval.tick().await;
+            let now = Utc::now().naive_utc();
+            match posting_status::close_expired_postings(&pool, now).await {
+                Ok(closed) if closed > 0 => {
+                    tracing::info!("Closed {} expired postings", closed);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Posting expiry failed: {:?}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PostingStatus::*;
+
+    #[test]
+    fn test_allowed_transitions() {
+        for (from, to) in [
+            (Published, InProgress),
+            (Published, Closed),
+            (Published, Cancelled),
+            (InProgress, Published),
+            (InProgress, Closed),
+            (InProgress, Cancelled),
+            (Closed, Published),
+            (Cancelled, Cancelled),
+        ] {
+            assert!(can_transition(&from, &to), "{:?} -> {:?}", from, to);
+        }
+    }
+
+    #[test]
+    fn test_rejected_transitions() {
+        for (from, to) in [
+            (Cancelled, Published),
+            (Cancelled, InProgress),
+            (Cancelled, Closed),
+            (Closed, InProgress),
+            (Closed, Cancelled),
+        ] {
+            assert!(!can_transition(&from, &to), "{:?} -> {:?}", from, to);
+        }
+    }
+
+    #[test]
+    fn test_transition_error_message() {
+        let error = ensure_transition(&Cancelled, &Published).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "공고 상태를 '취소'에서 '게시중'(으)로 변경할 수 없습니다"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::models::report::{
+    AgingBucket, AgingTotals, CustomerReceivables, Receivable, ReceivableRow, ReceivablesReport,
+};
+use crate::services::payment;
+
+pub fn aging_bucket(days_outstanding: i64) -> AgingBucket {
+    match days_outstanding {
+        ..=30 => AgingBucket::Current,
+        31..=60 => AgingBucket::Days31To60,
+        61..=90 => AgingBucket::Days61To90,
+        _ => AgingBucket::Over90,
+    }
+}
+
+fn add_to_totals(totals: &mut AgingTotals, bucket: AgingBucket, amount: Decimal) {
+    let slot = match bucket {
+        AgingBucket::Current => &mut totals.current,
+        AgingBucket::Days31To60 => &mut totals.days_31_60,
+        AgingBucket::Days61To90 => &mut totals.days_61_90,
+        AgingBucket::Over90 => &mut totals.over_90,
+    };
+    *slot += amount;
+    totals.total += amount;
+}
+
+/// Build the receivables report as of `as_of` from the fees of completed
+/// matchings. A fee is due on the day its matching completed; fully paid
+/// fees are left out. Customers are listed by outstanding total, largest
+/// first, each with their fees oldest first.
+pub fn build_report(rows: Vec<ReceivableRow>, as_of: NaiveDate) -> ReceivablesReport {
+    let mut totals = AgingTotals::default();
+    let mut customers: Vec<CustomerReceivables> = Vec::new();
+
+    for row in rows {
+        let balance = payment::side_balance(
+            row.payer_side,
+            row.fee_amount,
+            row.refunded_amount,
+            row.paid_amount,
+        );
+        if balance.outstanding_amount <= Decimal::ZERO {
+            continue;
+        }
+
+        let due_date = row.completed_at.date();
+        let days_outstanding = (as_of - due_date).num_days().max(0);
+        let bucket = aging_bucket(days_outstanding);
+        add_to_totals(&mut totals, bucket, balance.outstanding_amount);
+
+        let index = match customers
+            .iter()
+            .position(|customer| customer.customer_id == row.customer_id)
+        {
+            Some(index) => index,
+            None => {
+                customers.push(CustomerReceivables {
+                    customer_id: row.customer_id,
+                    customer_name: row.customer_name,
+                    phone: row.phone,
+                    oldest_due_date: due_date,
+                    totals: AgingTotals::default(),
+                    receivables: Vec::new(),
+                });
+                customers.len() - 1
+            }
+        };
+        let customer = &mut customers[index];
+        customer.oldest_due_date = customer.oldest_due_date.min(due_date);
+        add_to_totals(&mut customer.totals, bucket, balance.outstanding_amount);
+        customer.receivables.push(Receivable {
+            matching_id: row.matching_id,
+            payer_side: row.payer_side,
+            completed_at: row.completed_at,
+            due_date,
+            days_outstanding,
+            bucket,
+            fee_amount: balance.fee_amount,
+            refunded_amount: balance.refunded_amount,
+            paid_amount: balance.paid_amount,
+            outstanding_amount: balance.outstanding_amount,
+        });
+    }
+
+    for customer in &mut customers {
+        customer
+            .receivables
+            .sort_by_key(|receivable| (receivable.due_date, receivable.matching_id));
+    }
+    customers.sort_by(|a, b| {
+        b.totals
+            .total
+            .cmp(&a.totals.total)
+            .then(a.customer_id.cmp(&b.customer_id))
+    });
+
+    ReceivablesReport {
+        as_of,
+        totals,
+        customers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::payment::PayerSide;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn row(
+        matching_id: i64,
+        customer_id: i64,
+        completed_on: &str,
+        fee: i64,
+        paid: i64,
+    ) -> ReceivableRow {
+        ReceivableRow {
+            matching_id,
+            payer_side: PayerSide::Employer,
+            customer_id,
+            customer_name: format!("고객{}", customer_id),
+            phone: "010-0000-0000".to_string(),
+            completed_at: date(completed_on).and_hms_opt(10, 0, 0).unwrap(),
+            fee_amount: Decimal::new(fee, 0),
+            refunded_amount: Decimal::ZERO,
+            paid_amount: Decimal::new(paid, 0),
+        }
+    }
+
+    #[test]
+    fn test_aging_bucket_boundaries() {
+        assert_eq!(aging_bucket(0), AgingBucket::Current);
+        assert_eq!(aging_bucket(30), AgingBucket::Current);
+        assert_eq!(aging_bucket(31), AgingBucket::Days31To60);
+        assert_eq!(aging_bucket(60), AgingBucket::Days31To60);
+        assert_eq!(aging_bucket(61), AgingBucket::Days61To90);
+        assert_eq!(aging_bucket(90), AgingBucket::Days61To90);
+        assert_eq!(aging_bucket(91), AgingBucket::Over90);
+    }
+
+    #[test]
+    fn test_build_report_groups_by_customer() {
+        let as_of = date("2025-06-30");
+        let report = build_report(
+            vec![
+                row(1, 10, "2025-06-20", 100_000, 0),
+                row(2, 20, "2025-03-01", 500_000, 200_000),
+                row(3, 10, "2025-05-15", 300_000, 0),
+                // 완납된 수수료는 제외
+                row(4, 30, "2025-01-01", 100_000, 100_000),
+            ],
+            as_of,
+        );
+
+        assert_eq!(report.customers.len(), 2);
+        assert_eq!(report.totals.total, Decimal::new(700_000, 0));
+        assert_eq!(report.totals.current, Decimal::new(100_000, 0));
+        assert_eq!(report.totals.days_31_60, Decimal::new(300_000, 0));
+        assert_eq!(report.totals.over_90, Decimal::new(300_000, 0));
+
+        let first = &report.customers[0];
+        assert_eq!(first.customer_id, 10);
+        assert_eq!(first.totals.total, Decimal::new(400_000, 0));
+        assert_eq!(first.oldest_due_date, date("2025-05-15"));
+        assert_eq!(first.receivables[0].matching_id, 3);
+        assert_eq!(first.receivables[0].days_outstanding, 46);
+
+        let second = &report.customers[1];
+        assert_eq!(
+            second.receivables[0].outstanding_amount,
+            Decimal::new(300_000, 0)
+        );
+        assert_eq!(second.receivables[0].bucket, AgingBucket::Over90);
+    }
+}
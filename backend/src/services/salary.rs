@@ -0,0 +1,142 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use thiserror::Error;
+
+use crate::models::job_posting::SalaryBasis;
+
+/// Average number of weeks in a month (365 / 12 / 7)
+pub const WEEKS_PER_MONTH: Decimal = Decimal::from_parts(4345, 0, 0, false, 3);
+/// Weekly hours assumed when an hourly or daily salary has no expected hours
+pub const DEFAULT_WEEKLY_HOURS: Decimal = Decimal::from_parts(40, 0, 0, false, 0);
+const HOURS_PER_DAY: Decimal = Decimal::from_parts(8, 0, 0, false, 0);
+const DEFAULT_DAYS_PER_WEEK: Decimal = Decimal::from_parts(5, 0, 0, false, 0);
+const MAX_WEEKLY_HOURS: Decimal = Decimal::from_parts(168, 0, 0, false, 0);
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SalaryError {
+    #[error("주당 근무시간은 0시간 초과 168시간 이하로 입력해주세요")]
+    InvalidWeeklyHours,
+}
+
+/// Convert a salary to its monthly equivalent in won.
+///
+/// Hourly pay is multiplied by the weekly hours, daily pay by the working
+/// days per week (the posting's work days, otherwise weekly hours / 8,
+/// otherwise 5 days), and both by the average weeks per month. Paid weekly
+/// holidays (주휴수당) are not added.
+///
+/// Must stay in sync with the `monthly_salary_equivalent` SQL function that
+/// fills the `monthly_salary` columns.
+pub fn monthly_equivalent(
+    amount: Decimal,
+    basis: SalaryBasis,
+    weekly_hours: Option<Decimal>,
+    days_per_week: Option<usize>,
+) -> Decimal {
+    let monthly = match basis {
+        SalaryBasis::Monthly => amount,
+        SalaryBasis::Hourly => {
+            amount * weekly_hours.unwrap_or(DEFAULT_WEEKLY_HOURS) * WEEKS_PER_MONTH
+        }
+        SalaryBasis::Daily => {
+            let days = match (days_per_week, weekly_hours) {
+                (Some(days), _) if days > 0 => Decimal::from(days),
+                (_, Some(hours)) => hours / HOURS_PER_DAY,
+                _ => DEFAULT_DAYS_PER_WEEK,
+            };
+            amount * days * WEEKS_PER_MONTH
+        }
+    };
+
+    monthly.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+}
+
+pub fn validate_weekly_hours(hours: Option<Decimal>) -> Result<(), SalaryError> {
+    match hours {
+        Some(h) if h <= Decimal::ZERO || h > MAX_WEEKLY_HOURS => {
+            Err(SalaryError::InvalidWeeklyHours)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monthly_salary_is_unchanged() {
+        let amount = Decimal::new(2_500_000, 0);
+        assert_eq!(
+            monthly_equivalent(
+                amount,
+                SalaryBasis::Monthly,
+                Some(Decimal::new(20, 0)),
+                None
+            ),
+            amount
+        );
+    }
+
+    #[test]
+    fn test_hourly_salary_uses_weekly_hours() {
+        // 15,000원 x 40시간 x 4.345주
+        assert_eq!(
+            monthly_equivalent(Decimal::new(15_000, 0), SalaryBasis::Hourly, None, None),
+            Decimal::new(2_607_000, 0)
+        );
+        // 12,500원 x 20.5시간 x 4.345주 = 1,113,406.25
+        assert_eq!(
+            monthly_equivalent(
+                Decimal::new(12_500, 0),
+                SalaryBasis::Hourly,
+                Some(Decimal::new(205, 1)),
+                None
+            ),
+            Decimal::new(1_113_406, 0)
+        );
+    }
+
+    #[test]
+    fn test_daily_salary_uses_work_days_then_hours() {
+        let daily = Decimal::new(130_000, 0);
+        // 주 3일
+        assert_eq!(
+            monthly_equivalent(
+                daily,
+                SalaryBasis::Daily,
+                Some(Decimal::new(40, 0)),
+                Some(3)
+            ),
+            Decimal::new(1_694_550, 0)
+        );
+        // 주 24시간 → 3일
+        assert_eq!(
+            monthly_equivalent(
+                daily,
+                SalaryBasis::Daily,
+                Some(Decimal::new(24, 0)),
+                Some(0)
+            ),
+            Decimal::new(1_694_550, 0)
+        );
+        // 기본 주 5일
+        assert_eq!(
+            monthly_equivalent(daily, SalaryBasis::Daily, None, None),
+            Decimal::new(2_824_250, 0)
+        );
+    }
+
+    #[test]
+    fn test_validate_weekly_hours() {
+        assert!(validate_weekly_hours(None).is_ok());
+        assert!(validate_weekly_hours(Some(Decimal::new(40, 0))).is_ok());
+        assert_eq!(
+            validate_weekly_hours(Some(Decimal::ZERO)),
+            Err(SalaryError::InvalidWeeklyHours)
+        );
+        assert_eq!(
+            validate_weekly_hours(Some(Decimal::new(169, 0))),
+            Err(SalaryError::InvalidWeeklyHours)
+        );
+    }
+}
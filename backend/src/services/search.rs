@@ -0,0 +1,57 @@
+/// `ts_headline` options shared by every snippet in the global search. The
+/// source text is passed through `html_escape` first, so `<mark>` is the only
+/// markup in a snippet.
+pub const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxWords=25, MinWords=8, ShortWord=1, MaxFragments=2, FragmentDelimiter= … ";
+
+/// Build a prefix-matching `tsquery` for the 'simple' configuration.
+///
+/// Every word must match (`&`) and each word matches as a prefix (`:*`) so that
+/// Korean words followed by particles ("분당에서", "강아지가") are still found.
+/// Returns `None` when the input has no searchable words.
+pub fn build_prefix_tsquery(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("{}:*", word))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" & "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_prefix_tsquery() {
+        assert_eq!(
+            build_prefix_tsquery("분당 강아지").as_deref(),
+            Some("분당:* & 강아지:*")
+        );
+        assert_eq!(
+            build_prefix_tsquery("  Care-giver's  ").as_deref(),
+            Some("caregivers:*")
+        );
+    }
+
+    #[test]
+    fn test_build_prefix_tsquery_strips_operators() {
+        assert_eq!(
+            build_prefix_tsquery("a & !b | (c)").as_deref(),
+            Some("a:* & b:* & c:*")
+        );
+        assert_eq!(build_prefix_tsquery("& | !"), None);
+        assert_eq!(build_prefix_tsquery(""), None);
+    }
+}
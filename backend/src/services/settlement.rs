@@ -0,0 +1,233 @@
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::models::job_posting::SettlementStatus;
+use crate::models::payment::SideBalance;
+use crate::models::settlement::{PostingSettlement, UpdateSettlementRequest};
+use crate::services::format::{format_won, settlement_status_label};
+use crate::services::payment;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SettlementError {
+    #[error("업데이트할 정산 정보를 제공해주세요")]
+    NothingToUpdate,
+    #[error("정산 금액은 0원보다 커야 합니다")]
+    InvalidAmount,
+    #[error("정산 금액을 입력해주세요")]
+    AmountRequired,
+    #[error("정산 금액은 연결된 매칭의 수수료 합계({}원)와 같아야 합니다", format_won(*.0))]
+    AmountMismatch(Decimal),
+    #[error(
+        "연결된 매칭의 입금 내역 기준 정산 상태는 '{}'입니다. 상태를 바꾸려면 입금을 등록하거나 삭제해주세요",
+        settlement_status_label(.0)
+    )]
+    StatusMismatch(SettlementStatus),
+}
+
+/// Settlement a posting's linked matchings call for
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedSettlement {
+    pub settlement_status: SettlementStatus,
+    /// 수수료 합계 (보증 환불 차감)
+    pub settlement_amount: Decimal,
+}
+
+/// Expected settlement from the balances of a posting's matchings that are
+/// not cancelled. `None` when it has none, and is settled by hand.
+pub fn expected_settlement(balances: &[SideBalance]) -> Option<ExpectedSettlement> {
+    let settlement_status = payment::posting_settlement_status(balances)?;
+    let settlement_amount = balances
+        .iter()
+        .map(|balance| (balance.fee_amount - balance.refunded_amount).max(Decimal::ZERO))
+        .sum();
+
+    Some(ExpectedSettlement {
+        settlement_status,
+        settlement_amount,
+    })
+}
+
+/// Apply a settlement update to `current` after checking it against the
+/// `expected` settlement of the posting's matchings.
+///
+/// A posting with matchings always takes the expected status and amount; the
+/// request may only repeat them. Without matchings any status can be set, but
+/// a settled posting needs an amount.
+pub fn resolve_update(
+    current: &PostingSettlement,
+    req: &UpdateSettlementRequest,
+    expected: Option<&ExpectedSettlement>,
+) -> Result<PostingSettlement, SettlementError> {
+    if req.settlement_status.is_none()
+        && req.settlement_amount.is_none()
+        && req.settlement_memo.is_none()
+    {
+        return Err(SettlementError::NothingToUpdate);
+    }
+    if req
+        .settlement_amount
+        .is_some_and(|amount| amount <= Decimal::ZERO)
+    {
+        return Err(SettlementError::InvalidAmount);
+    }
+    let settlement_memo = req
+        .settlement_memo
+        .clone()
+        .or_else(|| current.settlement_memo.clone());
+
+    if let Some(expected) = expected {
+        if let Some(amount) = req.settlement_amount {
+            if amount != expected.settlement_amount {
+                return Err(SettlementError::AmountMismatch(expected.settlement_amount));
+            }
+        }
+        if let Some(status) = &req.settlement_status {
+            if *status != expected.settlement_status {
+                return Err(SettlementError::StatusMismatch(
+                    expected.settlement_status.clone(),
+                ));
+            }
+        }
+
+        return Ok(PostingSettlement {
+            settlement_status: expected.settlement_status.clone(),
+            settlement_amount: Some(expected.settlement_amount),
+            settlement_memo,
+        });
+    }
+
+    let settlement_status = req
+        .settlement_status
+        .clone()
+        .unwrap_or_else(|| current.settlement_status.clone());
+    let settlement_amount = req.settlement_amount.or(current.settlement_amount);
+    if settlement_status != SettlementStatus::Unsettled && settlement_amount.is_none() {
+        return Err(SettlementError::AmountRequired);
+    }
+
+    Ok(PostingSettlement {
+        settlement_status,
+        settlement_amount,
+        settlement_memo,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::payment::PayerSide;
+
+    fn won(amount: i64) -> Decimal {
+        Decimal::new(amount, 0)
+    }
+
+    fn unsettled() -> PostingSettlement {
+        PostingSettlement {
+            settlement_status: SettlementStatus::Unsettled,
+            settlement_amount: None,
+            settlement_memo: None,
+        }
+    }
+
+    fn update(
+        status: Option<SettlementStatus>,
+        amount: Option<Decimal>,
+    ) -> UpdateSettlementRequest {
+        UpdateSettlementRequest {
+            settlement_status: status,
+            settlement_amount: amount,
+            settlement_memo: None,
+        }
+    }
+
+    #[test]
+    fn test_expected_settlement_sums_fees_less_refunds() {
+        let balances = [
+            payment::side_balance(
+                PayerSide::Employer,
+                won(300_000),
+                won(100_000),
+                won(200_000),
+            ),
+            payment::side_balance(
+                PayerSide::Employer,
+                won(250_000),
+                Decimal::ZERO,
+                Decimal::ZERO,
+            ),
+        ];
+        assert_eq!(
+            expected_settlement(&balances),
+            Some(ExpectedSettlement {
+                settlement_status: SettlementStatus::PartiallySettled,
+                settlement_amount: won(450_000),
+            })
+        );
+        assert_eq!(expected_settlement(&[]), None);
+    }
+
+    #[test]
+    fn test_linked_posting_follows_its_matchings() {
+        let expected = ExpectedSettlement {
+            settlement_status: SettlementStatus::Unsettled,
+            settlement_amount: won(250_000),
+        };
+
+        let memo_only = UpdateSettlementRequest {
+            settlement_memo: Some("다음 주 입금 예정".to_string()),
+            ..update(None, None)
+        };
+        let resolved = resolve_update(&unsettled(), &memo_only, Some(&expected)).unwrap();
+        assert_eq!(resolved.settlement_amount, Some(won(250_000)));
+        assert_eq!(
+            resolved.settlement_memo.as_deref(),
+            Some("다음 주 입금 예정")
+        );
+
+        assert_eq!(
+            resolve_update(
+                &unsettled(),
+                &update(Some(SettlementStatus::Settled), None),
+                Some(&expected)
+            ),
+            Err(SettlementError::StatusMismatch(SettlementStatus::Unsettled))
+        );
+        assert_eq!(
+            resolve_update(
+                &unsettled(),
+                &update(None, Some(won(200_000))),
+                Some(&expected)
+            ),
+            Err(SettlementError::AmountMismatch(won(250_000)))
+        );
+    }
+
+    #[test]
+    fn test_unlinked_posting_needs_amount_to_settle() {
+        assert_eq!(
+            resolve_update(
+                &unsettled(),
+                &update(Some(SettlementStatus::Settled), None),
+                None
+            ),
+            Err(SettlementError::AmountRequired)
+        );
+        assert_eq!(
+            resolve_update(&unsettled(), &update(None, Some(Decimal::ZERO)), None),
+            Err(SettlementError::InvalidAmount)
+        );
+        assert_eq!(
+            resolve_update(&unsettled(), &update(None, None), None),
+            Err(SettlementError::NothingToUpdate)
+        );
+
+        let settled = resolve_update(
+            &unsettled(),
+            &update(Some(SettlementStatus::Settled), Some(won(300_000))),
+            None,
+        )
+        .unwrap();
+        assert_eq!(settled.settlement_status, SettlementStatus::Settled);
+        assert_eq!(settled.settlement_amount, Some(won(300_000)));
+    }
+}
@@ -0,0 +1,459 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::models::customer::Customer;
+use crate::models::matching::MatchingStatus;
+use crate::models::statement::{
+    CustomerStatement, StatementEntry, StatementEntryKind, StatementMatching, StatementMatchingRow,
+    StatementPaymentRow,
+};
+use crate::repositories::{customer, statement};
+use crate::services::format::{
+    format_won, matching_status_label, payer_side_label, payment_method_label,
+};
+use crate::services::payment;
+use crate::services::pdf::{self, PdfError, PdfLine};
+
+#[derive(Debug, Error)]
+pub enum StatementError {
+    #[error("시작일은 종료일보다 늦을 수 없습니다")]
+    InvalidRange,
+    #[error("고객을 찾을 수 없습니다")]
+    CustomerNotFound,
+    #[error(transparent)]
+    Pdf(#[from] PdfError),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+fn kind_label(kind: StatementEntryKind) -> &'static str {
+    match kind {
+        StatementEntryKind::Charge => "수수료",
+        StatementEntryKind::Refund => "보증 환불",
+        StatementEntryKind::Payment => "입금",
+    }
+}
+
+/// Ledger entries of every matching side and payment, oldest first, with
+/// the balance left at zero. Cancelled matchings charge nothing.
+fn ledger_entries(
+    matchings: &[StatementMatchingRow],
+    payments: &[StatementPaymentRow],
+) -> Vec<StatementEntry> {
+    let entry = |date, kind, matching_id, payer_side, description, charge, credit| StatementEntry {
+        date,
+        kind,
+        matching_id,
+        payer_side,
+        description,
+        charge,
+        credit,
+        balance: Decimal::ZERO,
+    };
+    let mut entries = Vec::new();
+
+    for row in matchings {
+        if row.matching_status == MatchingStatus::Cancelled {
+            continue;
+        }
+        if row.fee_amount > Decimal::ZERO {
+            entries.push(entry(
+                row.matched_at.date(),
+                StatementEntryKind::Charge,
+                row.matching_id,
+                row.payer_side,
+                format!(
+                    "매칭 #{} {} 수수료 (상대: {})",
+                    row.matching_id,
+                    payer_side_label(&row.payer_side),
+                    row.counterparty_name
+                ),
+                row.fee_amount,
+                Decimal::ZERO,
+            ));
+        }
+        if row.refunded_amount > Decimal::ZERO {
+            let refunded_on = row
+                .refunded_on
+                .or(row.completed_at.map(|completed_at| completed_at.date()))
+                .unwrap_or(row.matched_at.date());
+            entries.push(entry(
+                refunded_on,
+                StatementEntryKind::Refund,
+                row.matching_id,
+                row.payer_side,
+                format!("매칭 #{} 보증 환불", row.matching_id),
+                Decimal::ZERO,
+                row.refunded_amount,
+            ));
+        }
+    }
+
+    for payment in payments {
+        let mut description = format!(
+            "매칭 #{} 입금 ({})",
+            payment.matching_id,
+            payment_method_label(&payment.payment_method)
+        );
+        if let Some(memo) = payment.memo.as_deref().filter(|memo| !memo.is_empty()) {
+            description.push_str(" - ");
+            description.push_str(memo);
+        }
+        entries.push(entry(
+            payment.paid_on,
+            StatementEntryKind::Payment,
+            payment.matching_id,
+            payment.payer_side,
+            description,
+            Decimal::ZERO,
+            payment.amount,
+        ));
+    }
+
+    entries.sort_by_key(|entry| (entry.date, entry.kind, entry.matching_id));
+    entries
+}
+
+/// Build the customer's statement from `from` (or their first transaction)
+/// to `to`: the matchings with activity in the period and the ledger of
+/// fees charged, refunds and payments with a running balance carried over
+/// from before the period
+pub fn build_statement(
+    customer: &Customer,
+    from: Option<NaiveDate>,
+    to: NaiveDate,
+    matchings: &[StatementMatchingRow],
+    payments: &[StatementPaymentRow],
+) -> CustomerStatement {
+    let in_period = |date: NaiveDate| from.is_none_or(|from| date >= from) && date <= to;
+
+    let mut opening_balance = Decimal::ZERO;
+    let mut balance = Decimal::ZERO;
+    let mut entries = Vec::new();
+    for mut entry in ledger_entries(matchings, payments) {
+        if entry.date > to {
+            break;
+        }
+        balance += entry.charge - entry.credit;
+        if !in_period(entry.date) {
+            opening_balance = balance;
+            continue;
+        }
+        entry.balance = balance;
+        entries.push(entry);
+    }
+
+    let statement_matchings = matchings
+        .iter()
+        .filter(|row| {
+            in_period(row.matched_at.date())
+                || entries.iter().any(|entry| {
+                    entry.matching_id == row.matching_id && entry.payer_side == row.payer_side
+                })
+        })
+        .map(|row| {
+            let paid_amount = payments
+                .iter()
+                .filter(|payment| {
+                    payment.matching_id == row.matching_id
+                        && payment.payer_side == row.payer_side
+                        && payment.paid_on <= to
+                })
+                .map(|payment| payment.amount)
+                .sum();
+            let cancelled = row.matching_status == MatchingStatus::Cancelled;
+            let charged_amount = if cancelled {
+                Decimal::ZERO
+            } else {
+                row.fee_amount
+            };
+            let balance = payment::side_balance(
+                row.payer_side,
+                charged_amount,
+                row.refunded_amount,
+                paid_amount,
+            );
+
+            StatementMatching {
+                matching_id: row.matching_id,
+                payer_side: row.payer_side,
+                counterparty_name: row.counterparty_name.clone(),
+                matched_at: row.matched_at,
+                completed_at: row.completed_at,
+                matching_status: row.matching_status.clone(),
+                charged_amount,
+                refunded_amount: row.refunded_amount,
+                paid_amount,
+                outstanding_amount: balance.outstanding_amount,
+            }
+        })
+        .collect();
+
+    let total_charged = entries.iter().map(|entry| entry.charge).sum();
+    let total_credited = entries.iter().map(|entry| entry.credit).sum();
+
+    CustomerStatement {
+        customer_id: customer.id,
+        customer_name: customer.name.clone(),
+        phone: customer.phone.clone(),
+        from,
+        to,
+        opening_balance,
+        total_charged,
+        total_credited,
+        closing_balance: balance,
+        matchings: statement_matchings,
+        entries,
+    }
+}
+
+/// Amount in won, keeping the sign of credit balances
+fn won(amount: Decimal) -> String {
+    let sign = if amount < Decimal::ZERO { "-" } else { "" };
+    format!("{}{}원", sign, format_won(amount))
+}
+
+pub fn statement_lines(statement: &CustomerStatement) -> Vec<PdfLine> {
+    let from = statement
+        .from
+        .map(|from| from.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "첫 거래".to_string());
+    let mut lines = vec![
+        PdfLine::Title("거래 명세서".to_string()),
+        PdfLine::pair("고객명", statement.customer_name.as_str()),
+        PdfLine::pair("연락처", statement.phone.as_str()),
+        PdfLine::pair(
+            "기간",
+            format!("{} ~ {}", from, statement.to.format("%Y-%m-%d")),
+        ),
+        PdfLine::Rule,
+        PdfLine::Heading("매칭 내역".to_string()),
+    ];
+
+    if statement.matchings.is_empty() {
+        lines.push(PdfLine::Text("기간 중 매칭이 없습니다".to_string()));
+    } else {
+        lines.push(PdfLine::Row(
+            ["매칭", "매칭일", "구분", "상태", "청구액", "미수금"]
+                .map(String::from)
+                .to_vec(),
+        ));
+        for matching in &statement.matchings {
+            lines.push(PdfLine::Row(vec![
+                format!("#{}", matching.matching_id),
+                matching.matched_at.format("%Y-%m-%d").to_string(),
+                payer_side_label(&matching.payer_side).to_string(),
+                matching_status_label(&matching.matching_status).to_string(),
+                won(matching.charged_amount - matching.refunded_amount),
+                won(matching.outstanding_amount),
+            ]));
+        }
+    }
+
+    lines.push(PdfLine::Heading("거래 내역".to_string()));
+    lines.push(PdfLine::pair("이월 잔액", won(statement.opening_balance)));
+    if !statement.entries.is_empty() {
+        lines.push(PdfLine::Row(
+            ["일자", "내용", "청구", "입금/환불", "잔액"]
+                .map(String::from)
+                .to_vec(),
+        ));
+        for entry in &statement.entries {
+            let amount = |value: Decimal| {
+                if value.is_zero() {
+                    String::new()
+                } else {
+                    won(value)
+                }
+            };
+            lines.push(PdfLine::Row(vec![
+                entry.date.format("%Y-%m-%d").to_string(),
+                format!("{} #{}", kind_label(entry.kind), entry.matching_id),
+                amount(entry.charge),
+                amount(entry.credit),
+                won(entry.balance),
+            ]));
+        }
+    }
+    lines.push(PdfLine::Rule);
+    lines.push(PdfLine::pair("청구 합계", won(statement.total_charged)));
+    lines.push(PdfLine::pair(
+        "입금/환불 합계",
+        won(statement.total_credited),
+    ));
+    lines.push(PdfLine::pair("잔액", won(statement.closing_balance)));
+
+    lines
+}
+
+pub async fn render_pdf(
+    config: &Config,
+    statement: &CustomerStatement,
+) -> Result<Vec<u8>, PdfError> {
+    let font = pdf::load_font(config).await?;
+    pdf::render("거래 명세서", &statement_lines(statement), &font)
+}
+
+/// Statement of one of the user's customers
+pub async fn generate_statement(
+    pool: &PgPool,
+    customer_id: i64,
+    user_id: i64,
+    from: Option<NaiveDate>,
+    to: NaiveDate,
+) -> Result<CustomerStatement, StatementError> {
+    if from.is_some_and(|from| from > to) {
+        return Err(StatementError::InvalidRange);
+    }
+
+    let customer = customer::get_customer_by_id(pool, customer_id, user_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => StatementError::CustomerNotFound,
+            e => StatementError::DatabaseError(e),
+        })?;
+    let matchings = statement::list_statement_matchings(pool, customer_id, user_id).await?;
+    let payments = statement::list_statement_payments(pool, customer_id, user_id).await?;
+
+    Ok(build_statement(&customer, from, to, &matchings, &payments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::customer::CustomerType;
+    use crate::models::payment::{PayerSide, PaymentMethod};
+    use chrono::NaiveDateTime;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn at(day: NaiveDate) -> NaiveDateTime {
+        day.and_hms_opt(10, 0, 0).unwrap()
+    }
+
+    fn won(amount: i64) -> Decimal {
+        Decimal::new(amount, 0)
+    }
+
+    fn customer() -> Customer {
+        Customer {
+            id: 1,
+            user_id: 1,
+            name: "김구인".to_string(),
+            birth_date: None,
+            phone: "010-1111-2222".to_string(),
+            address: None,
+            business_number: None,
+            profile_photo_id: None,
+            customer_type: CustomerType::Employer,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+            deleted_at: None,
+        }
+    }
+
+    fn matching(
+        matching_id: i64,
+        matched_on: NaiveDate,
+        status: MatchingStatus,
+        fee: i64,
+    ) -> StatementMatchingRow {
+        StatementMatchingRow {
+            matching_id,
+            payer_side: PayerSide::Employer,
+            counterparty_name: "이구직".to_string(),
+            matched_at: at(matched_on),
+            completed_at: None,
+            matching_status: status,
+            fee_amount: won(fee),
+            refunded_amount: Decimal::ZERO,
+            refunded_on: None,
+        }
+    }
+
+    fn payment(id: i64, matching_id: i64, paid_on: NaiveDate, amount: i64) -> StatementPaymentRow {
+        StatementPaymentRow {
+            id,
+            matching_id,
+            payer_side: PayerSide::Employer,
+            amount: won(amount),
+            paid_on,
+            payment_method: PaymentMethod::BankTransfer,
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn test_statement_carries_over_opening_balance() {
+        let matchings = [
+            matching(1, date(2025, 1, 10), MatchingStatus::Completed, 300_000),
+            matching(2, date(2025, 3, 5), MatchingStatus::InProgress, 250_000),
+            matching(3, date(2025, 3, 20), MatchingStatus::Cancelled, 200_000),
+        ];
+        let payments = [
+            payment(1, 1, date(2025, 2, 1), 100_000),
+            payment(2, 1, date(2025, 3, 10), 200_000),
+            payment(3, 2, date(2025, 4, 2), 250_000),
+        ];
+
+        let statement = build_statement(
+            &customer(),
+            Some(date(2025, 3, 1)),
+            date(2025, 3, 31),
+            &matchings,
+            &payments,
+        );
+        assert_eq!(statement.opening_balance, won(200_000));
+        assert_eq!(
+            statement
+                .entries
+                .iter()
+                .map(|entry| (entry.kind, entry.matching_id, entry.balance))
+                .collect::<Vec<_>>(),
+            vec![
+                (StatementEntryKind::Charge, 2, won(450_000)),
+                (StatementEntryKind::Payment, 1, won(250_000)),
+            ]
+        );
+        assert_eq!(statement.closing_balance, won(250_000));
+        assert_eq!(
+            statement
+                .matchings
+                .iter()
+                .map(|matching| (
+                    matching.matching_id,
+                    matching.charged_amount,
+                    matching.outstanding_amount
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, won(300_000), Decimal::ZERO),
+                (2, won(250_000), won(250_000)),
+                (3, Decimal::ZERO, Decimal::ZERO),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_refund_credits_the_balance() {
+        let mut refunded = matching(1, date(2025, 1, 10), MatchingStatus::Completed, 300_000);
+        refunded.refunded_amount = won(150_000);
+        refunded.refunded_on = Some(date(2025, 2, 20));
+
+        let statement = build_statement(
+            &customer(),
+            None,
+            date(2025, 12, 31),
+            &[refunded],
+            &[payment(1, 1, date(2025, 1, 15), 300_000)],
+        );
+        let balances: Vec<_> = statement.entries.iter().map(|e| e.balance).collect();
+        assert_eq!(balances, vec![won(300_000), Decimal::ZERO, won(-150_000)]);
+        assert_eq!(statement.total_credited, won(450_000));
+    }
+}
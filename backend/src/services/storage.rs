@@ -0,0 +1,60 @@
+use s3::creds::Credentials;
+use s3::{Bucket, Region};
+
+use crate::config::Config;
+
+/// Open the configured MinIO bucket (path-style addressing)
+pub fn bucket(config: &Config) -> Result<Box<Bucket>, String> {
+    let credentials = Credentials::new(
+        Some(&config.minio_access_key),
+        Some(&config.minio_secret_key),
+        None,
+        None,
+        None,
+    )
+    .map_err(|e| format!("Failed to create credentials: {}", e))?;
+
+    let region = Region::Custom {
+        region: "us-east-1".to_string(),
+        endpoint: config.minio_endpoint.clone(),
+    };
+
+    Ok(Bucket::new(&config.minio_bucket, region, credentials)
+        .map_err(|e| format!("Failed to create bucket: {}", e))?
+        .with_path_style())
+}
+
+/// Delete an object from MinIO
+pub async fn delete_object(config: &Config, path: &str) -> Result<(), String> {
+    bucket(config)?
+        .delete_object(path)
+        .await
+        .map_err(|e| format!("Failed to delete object: {}", e))?;
+
+    Ok(())
+}
+
+/// Store an object in MinIO
+pub async fn put_object(
+    config: &Config,
+    path: &str,
+    data: &[u8],
+    content_type: &str,
+) -> Result<(), String> {
+    bucket(config)?
+        .put_object_with_content_type(path, data, content_type)
+        .await
+        .map_err(|e| format!("Failed to put object: {}", e))?;
+
+    Ok(())
+}
+
+/// Read an object from MinIO
+pub async fn get_object(config: &Config, path: &str) -> Result<Vec<u8>, String> {
+    let response = bucket(config)?
+        .get_object(path)
+        .await
+        .map_err(|e| format!("Failed to get object: {}", e))?;
+
+    Ok(response.bytes().to_vec())
+}
@@ -0,0 +1,338 @@
+use chrono::NaiveDateTime;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::models::job_posting::PostingKind;
+use crate::models::suggestion::{
+    Suggestion, SuggestionFactor, SuggestionFactorKind, SuggestionProfile,
+};
+use crate::services::format::format_won;
+
+/// Number of suggestions returned when the request does not say
+pub const DEFAULT_LIMIT: usize = 10;
+pub const MAX_LIMIT: usize = 50;
+
+const SALARY_WEIGHT: f64 = 40.0;
+const LOCATION_WEIGHT: f64 = 30.0;
+const TAGS_WEIGHT: f64 = 20.0;
+const RECENCY_WEIGHT: f64 = 10.0;
+
+/// Offer shortfall (relative to the desired salary) at which the salary
+/// factor drops to zero
+const MAX_SALARY_SHORTFALL: f64 = 0.2;
+/// Days after which the recency factor halves
+const RECENCY_HALF_LIFE_DAYS: f64 = 14.0;
+
+/// Score `candidates` against `target` and return the best `limit`, highest
+/// score first.
+///
+/// `target_kind` says which side `target` is on: for a job posting the
+/// candidates are job seekings, and vice versa.
+pub fn rank(
+    target: &SuggestionProfile,
+    target_kind: PostingKind,
+    candidates: Vec<SuggestionProfile>,
+    now: NaiveDateTime,
+    limit: usize,
+) -> Vec<Suggestion> {
+    let mut suggestions: Vec<(Suggestion, NaiveDateTime)> = candidates
+        .into_iter()
+        .map(|candidate| {
+            let (offered, desired) = match target_kind {
+                PostingKind::JobPosting => (target.monthly_salary, candidate.monthly_salary),
+                PostingKind::JobSeeking => (candidate.monthly_salary, target.monthly_salary),
+            };
+            let factors = vec![
+                salary_factor(offered, desired),
+                location_factor(target, &candidate),
+                tags_factor(&target.tags, &candidate.tags),
+                recency_factor(candidate.created_at, now),
+            ];
+            let score = round1(factors.iter().map(|f| f.score).sum());
+
+            (
+                Suggestion {
+                    posting_id: candidate.posting_id,
+                    customer_id: candidate.customer_id,
+                    customer_name: candidate.customer_name,
+                    monthly_salary: candidate.monthly_salary,
+                    score,
+                    factors,
+                },
+                candidate.created_at,
+            )
+        })
+        .collect();
+
+    suggestions.sort_by(|(a, a_created), (b, b_created)| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b_created.cmp(a_created))
+            .then(a.posting_id.cmp(&b.posting_id))
+    });
+
+    suggestions
+        .into_iter()
+        .take(limit)
+        .map(|(suggestion, _)| suggestion)
+        .collect()
+}
+
+/// Full marks when the offer covers the desired salary, falling linearly to
+/// zero at a 20% shortfall
+fn salary_factor(offered: Decimal, desired: Decimal) -> SuggestionFactor {
+    let (ratio, explanation) = if offered >= desired || desired <= Decimal::ZERO {
+        (
+            1.0,
+            format!(
+                "제시 급여 월 {}원이 희망 급여 월 {}원 이상",
+                format_won(offered),
+                format_won(desired)
+            ),
+        )
+    } else {
+        let shortfall = ((desired - offered) / desired).to_f64().unwrap_or(1.0);
+        (
+            (1.0 - shortfall / MAX_SALARY_SHORTFALL).max(0.0),
+            format!(
+                "제시 급여 월 {}원이 희망 급여 월 {}원보다 {:.0}% 낮음",
+                format_won(offered),
+                format_won(desired),
+                shortfall * 100.0
+            ),
+        )
+    };
+
+    factor(
+        SuggestionFactorKind::Salary,
+        SALARY_WEIGHT,
+        ratio,
+        explanation,
+    )
+}
+
+/// Region codes are compared first: a code covering the other (e.g. `11`
+/// and `11680`) is a full match, the same 시·도 half. Postings without codes
+/// fall back to a shared word between the location texts.
+fn location_factor(target: &SuggestionProfile, candidate: &SuggestionProfile) -> SuggestionFactor {
+    let (ratio, explanation) =
+        if !target.region_codes.is_empty() && !candidate.region_codes.is_empty() {
+            let pairs = || {
+                target
+                    .region_codes
+                    .iter()
+                    .flat_map(|a| candidate.region_codes.iter().map(move |b| (a, b)))
+            };
+
+            if let Some((a, b)) =
+                pairs().find(|(a, b)| a.starts_with(b.as_str()) || b.starts_with(a.as_str()))
+            {
+                let code = if a.len() >= b.len() { a } else { b };
+                (1.0, format!("지역 코드 일치 ({})", code))
+            } else if let Some((a, _)) = pairs().find(|(a, b)| a.get(..2) == b.get(..2)) {
+                (0.5, format!("같은 시·도 ({})", &a[..2]))
+            } else {
+                (0.0, "지역 불일치".to_string())
+            }
+        } else {
+            match shared_location_word(target.location.as_deref(), candidate.location.as_deref()) {
+                Some(word) => (0.5, format!("주소에 '{}' 포함", word)),
+                None if target.location.is_none() && candidate.location.is_none() => {
+                    (0.0, "지역 정보 없음".to_string())
+                }
+                None => (0.0, "지역 불일치".to_string()),
+            }
+        };
+
+    factor(
+        SuggestionFactorKind::Location,
+        LOCATION_WEIGHT,
+        ratio,
+        explanation,
+    )
+}
+
+fn shared_location_word(a: Option<&str>, b: Option<&str>) -> Option<String> {
+    let words = |text: &str| -> Vec<String> {
+        text.split(|c: char| c.is_whitespace() || c == ',' || c == '/')
+            .filter(|word| word.chars().count() >= 2)
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (words(a?), words(b?));
+
+    // The most specific shared word, e.g. 강남구 rather than 서울
+    a.into_iter().rev().find(|word| b.contains(word))
+}
+
+/// Shared tags over all tags of both sides
+fn tags_factor(target: &[String], candidate: &[String]) -> SuggestionFactor {
+    let shared: Vec<&String> = target
+        .iter()
+        .filter(|tag| candidate.contains(tag))
+        .collect();
+    let union = target.len() + candidate.len() - shared.len();
+
+    let (ratio, explanation) = if shared.is_empty() {
+        (0.0, "공통 태그 없음".to_string())
+    } else {
+        (
+            shared.len() as f64 / union as f64,
+            format!(
+                "공통 태그: {} ({}/{})",
+                shared
+                    .iter()
+                    .map(|tag| tag.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                shared.len(),
+                union
+            ),
+        )
+    };
+
+    factor(SuggestionFactorKind::Tags, TAGS_WEIGHT, ratio, explanation)
+}
+
+fn recency_factor(created_at: NaiveDateTime, now: NaiveDateTime) -> SuggestionFactor {
+    let days = (now - created_at).num_days().max(0);
+    let ratio = 0.5_f64.powf(days as f64 / RECENCY_HALF_LIFE_DAYS);
+    let explanation = if days == 0 {
+        "오늘 등록".to_string()
+    } else {
+        format!("{}일 전 등록", days)
+    };
+
+    factor(
+        SuggestionFactorKind::Recency,
+        RECENCY_WEIGHT,
+        ratio,
+        explanation,
+    )
+}
+
+fn factor(
+    kind: SuggestionFactorKind,
+    weight: f64,
+    ratio: f64,
+    explanation: String,
+) -> SuggestionFactor {
+    SuggestionFactor {
+        factor: kind,
+        score: round1(weight * ratio),
+        max_score: weight,
+        explanation,
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 10, 27)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    fn profile(
+        id: i64,
+        salary: i64,
+        regions: &[&str],
+        tags: &[&str],
+        age_days: i64,
+    ) -> SuggestionProfile {
+        SuggestionProfile {
+            posting_id: id,
+            customer_id: id * 10,
+            customer_name: format!("고객{}", id),
+            monthly_salary: Decimal::from(salary),
+            region_codes: regions.iter().map(|r| r.to_string()).collect(),
+            location: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            created_at: now() - Duration::days(age_days),
+        }
+    }
+
+    #[test]
+    fn test_salary_factor() {
+        let full = salary_factor(Decimal::from(2_500_000), Decimal::from(2_300_000));
+        assert_eq!(full.score, 40.0);
+        assert_eq!(
+            full.explanation,
+            "제시 급여 월 2,500,000원이 희망 급여 월 2,300,000원 이상"
+        );
+
+        // 10% short → half marks
+        let half = salary_factor(Decimal::from(2_700_000), Decimal::from(3_000_000));
+        assert_eq!(half.score, 20.0);
+        assert!(half.explanation.ends_with("10% 낮음"));
+
+        let none = salary_factor(Decimal::from(2_000_000), Decimal::from(3_000_000));
+        assert_eq!(none.score, 0.0);
+    }
+
+    #[test]
+    fn test_location_factor() {
+        let seoul = profile(1, 0, &["11"], &[], 0);
+        let gangnam = profile(2, 0, &["11680"], &[], 0);
+        let mapo = profile(3, 0, &["11440"], &[], 0);
+        let busan = profile(4, 0, &["26"], &[], 0);
+
+        assert_eq!(location_factor(&seoul, &gangnam).score, 30.0);
+        assert_eq!(
+            location_factor(&seoul, &gangnam).explanation,
+            "지역 코드 일치 (11680)"
+        );
+        assert_eq!(location_factor(&mapo, &gangnam).score, 15.0);
+        assert_eq!(location_factor(&busan, &gangnam).score, 0.0);
+
+        let mut by_text = profile(5, 0, &[], &[], 0);
+        by_text.location = Some("서울 강남구 역삼동".to_string());
+        let mut other = profile(6, 0, &["11680"], &[], 0);
+        other.location = Some("서울, 강남구".to_string());
+        let text = location_factor(&by_text, &other);
+        assert_eq!(text.score, 15.0);
+        assert_eq!(text.explanation, "주소에 '강남구' 포함");
+    }
+
+    #[test]
+    fn test_tags_and_recency_factors() {
+        let tags = tags_factor(
+            &["비흡연".to_string(), "요리".to_string()],
+            &["요리".to_string(), "운전".to_string()],
+        );
+        assert_eq!(tags.score, 6.7);
+        assert_eq!(tags.explanation, "공통 태그: 요리 (1/3)");
+
+        assert_eq!(recency_factor(now(), now()).score, 10.0);
+        assert_eq!(recency_factor(now() - Duration::days(14), now()).score, 5.0);
+    }
+
+    #[test]
+    fn test_rank_orders_by_score() {
+        let posting = profile(1, 2_500_000, &["11"], &["요리"], 0);
+        let candidates = vec![
+            profile(2, 3_500_000, &["26"], &[], 30),
+            profile(3, 2_400_000, &["11680"], &["요리"], 1),
+            profile(4, 2_400_000, &["11680"], &[], 1),
+        ];
+
+        let ranked = rank(&posting, PostingKind::JobPosting, candidates, now(), 2);
+        assert_eq!(
+            ranked.iter().map(|s| s.posting_id).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(ranked[0].factors.len(), 4);
+        assert_eq!(
+            ranked[0].score,
+            ranked[0].factors.iter().map(|f| f.score).sum::<f64>()
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::models::trash::PurgeSummary;
+use crate::repositories::trash;
+use crate::services::storage;
+
+/// Maximum number of files removed from storage per purge run
+const FILE_BATCH_SIZE: i64 = 500;
+
+/// Permanently delete records (and their stored objects) that have been in
+/// the trash longer than the configured retention period
+pub async fn purge_expired(pool: &PgPool, config: &Config) -> Result<PurgeSummary, sqlx::Error> {
+    let cutoff = Utc::now().naive_utc() - chrono::Duration::days(config.trash_retention_days);
+    let mut summary = PurgeSummary::default();
+
+    // Remove storage objects first; a row is only deleted once its object is
+    // gone so that a failed removal is retried later, with a growing delay
+    for file in trash::list_expired_files(pool, cutoff, FILE_BATCH_SIZE).await? {
+        let paths = std::iter::once(&file.file_path).chain(file.thumbnail_path.as_ref());
+        let mut removed = true;
+        for path in paths {
+            if let Err(e) = storage::delete_object(config, path).await {
+                tracing::warn!("Failed to delete {} from MinIO: {}", path, e);
+                removed = false;
+            }
+        }
+
+        if removed {
+            trash::hard_delete_file(pool, &file).await?;
+            summary.files += 1;
+        } else {
+            trash::record_file_purge_failure(pool, &file).await?;
+        }
+    }
+
+    summary.rows = trash::purge_expired_rows(pool, cutoff).await?;
+
+    Ok(summary)
+}
+
+/// Run `purge_expired` in the background every `trash_purge_interval_secs`
+pub fn spawn_purge_task(pool: PgPool, config: Config) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(config.trash_purge_interval_secs));
+
+        loop {
+            interval.tick().await;
+            match purge_expired(&pool, &config).await {
+                Ok(summary) if summary.files > 0 || summary.rows > 0 => {
+                    tracing::info!(
+                        "Trash purge removed {} files and {} rows",
+                        summary.files,
+                        summary.rows
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Trash purge failed: {:?}", e),
+            }
+        }
+    });
+}
@@ -1,8 +1,42 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
+use chrono::Datelike;
+use futures_util::TryStreamExt;
 use helpernote_backend::{
-    models::matching::{CreateMatchingRequest, MatchingStatus},
-    repositories::{matching, user::UserRepository},
-    services::auth::{hash_password, AuthService, TokenType},
+    models::{
+        analytics::AnalyticsMetric,
+        bank_import::{ConfirmMatch, ParsedDeposit},
+        customer::{CustomerType, MergeSelection, MergeSide, UpdateCustomerRequest},
+        export::TaxInvoiceExportRow,
+        fee_policy::FeePolicyRequest,
+        job_posting::{PostingKind, SettlementStatus},
+        matching::{
+            CreateMatchingRequest, FeeRateSource, Matching, MatchingStatus, ReplaceMatchingRequest,
+            UpdateMatchingRequest,
+        },
+        payment::{CreatePaymentRequest, MatchingBalance, PayerSide, Payment, PaymentMethod},
+        settlement::{SettlementChangeSource, UpdateSettlementRequest},
+        statement::StatementEntryKind,
+        timeline::TimelineEventType,
+        trash::ExpiredFile,
+        user::User,
+    },
+    repositories::{
+        analytics, bank_import,
+        customer::{self, CustomerMergeError},
+        export, fee_policy, matching, payment, search, settlement, timeline, trash,
+        user::UserRepository,
+    },
+    services::{
+        analytics as series,
+        auth::{hash_password, AuthService, TokenType},
+        bank_import as statement,
+        export::{ExportCell, ExportRecord},
+        monthly_report,
+        search::build_prefix_tsquery,
+        statement as customer_statement,
+    },
 };
 use rust_decimal::Decimal;
 use sqlx::{migrate::Migrator, PgPool, Row};
@@ -93,6 +127,181 @@ async fn user_repository_round_trip_and_token_validation() -> Result<()> {
     Ok(())
 }
 
+/// A user with one employer and one employee customer, each with a published
+/// posting: the employer's at 10% and the employee's at 5%
+struct Fixture {
+    pool: PgPool,
+    user: User,
+    employer_customer_id: i64,
+    job_posting_id: i64,
+    job_seeking_posting_id: i64,
+}
+
+impl Fixture {
+    async fn seed(pool: PgPool, username: &str) -> Result<Self> {
+        let question_id: i64 = sqlx::query(
+            r#"
+            INSERT INTO security_questions (question_text)
+            VALUES ($1)
+            RETURNING id
+            "#,
+        )
+        .bind("테스트 보안 질문")
+        .fetch_one(&pool)
+        .await?
+        .get("id");
+
+        let user_repo = UserRepository::new(pool.clone());
+        let password_hash = hash_password("Password123!")?;
+        let security_answer_hash = hash_password("answer")?;
+        let user = user_repo
+            .create_user(
+                username,
+                &password_hash,
+                question_id,
+                &security_answer_hash,
+                None,
+            )
+            .await?;
+
+        let employer_customer_id: i64 = sqlx::query(
+            r#"
+            INSERT INTO customers (user_id, name, phone, customer_type)
+            VALUES ($1, $2, $3, 'employer')
+            RETURNING id
+            "#,
+        )
+        .bind(user.id)
+        .bind("테스트 구인자")
+        .bind("010-1111-2222")
+        .fetch_one(&pool)
+        .await?
+        .get("id");
+
+        let employee_customer_id: i64 = sqlx::query(
+            r#"
+            INSERT INTO customers (user_id, name, phone, customer_type)
+            VALUES ($1, $2, $3, 'employee')
+            RETURNING id
+            "#,
+        )
+        .bind(user.id)
+        .bind("테스트 구직자")
+        .bind("010-3333-4444")
+        .fetch_one(&pool)
+        .await?
+        .get("id");
+
+        let job_posting_id: i64 = sqlx::query(
+            r#"
+            INSERT INTO job_postings (customer_id, salary, description, employer_fee_rate)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(employer_customer_id)
+        .bind(Decimal::new(5_000_000, 0))
+        .bind("테스트 구인 포지션")
+        .bind(Decimal::new(1000, 2)) // 10%
+        .fetch_one(&pool)
+        .await?
+        .get("id");
+
+        let job_seeking_posting_id: i64 = sqlx::query(
+            r#"
+            INSERT INTO job_seeking_postings (customer_id, desired_salary, description, preferred_location, employee_fee_rate)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(employee_customer_id)
+        .bind(Decimal::new(4_500_000, 0))
+        .bind("테스트 구직 설명")
+        .bind("서울")
+        .bind(Decimal::new(500, 2)) // 5%
+        .fetch_one(&pool)
+        .await?
+        .get("id");
+
+        Ok(Self {
+            pool,
+            user,
+            employer_customer_id,
+            job_posting_id,
+            job_seeking_posting_id,
+        })
+    }
+
+    fn matching_request(&self, employer_fee_rate: Option<Decimal>) -> CreateMatchingRequest {
+        CreateMatchingRequest {
+            job_posting_id: self.job_posting_id,
+            job_seeking_posting_id: self.job_seeking_posting_id,
+            agreed_salary: Decimal::new(4_800_000, 0),
+            salary_basis: None,
+            expected_weekly_hours: None,
+            employer_fee_rate,
+            employee_fee_rate: None,
+            contract_days: None,
+            guarantee_days: None,
+        }
+    }
+
+    /// Matching at an agreed salary of 4,800,000 with a 12% employer fee
+    /// (576,000) and the posting's 5% employee fee (240,000)
+    async fn create_matching(&self) -> Result<Matching> {
+        let request = self.matching_request(Some(Decimal::new(1200, 2)));
+        Ok(matching::create_matching(&self.pool, request, self.user.id).await?)
+    }
+
+    async fn pay(
+        &self,
+        matching_id: i64,
+        payer_side: PayerSide,
+        amount: Decimal,
+    ) -> Result<(Payment, MatchingBalance)> {
+        let request = CreatePaymentRequest {
+            payer_side,
+            amount,
+            paid_on: None,
+            payment_method: PaymentMethod::BankTransfer,
+            memo: None,
+        };
+        Ok(payment::create_payment(&self.pool, matching_id, request, self.user.id).await?)
+    }
+
+    /// Put the job posting under a fee policy with no tiers and 10% VAT
+    async fn attach_employer_fee_policy(&self, minimum_fee: Option<Decimal>) -> Result<()> {
+        let policy = fee_policy::create_fee_policy(
+            &self.pool,
+            self.user.id,
+            FeePolicyRequest {
+                name: "부가세 포함".to_string(),
+                minimum_fee,
+                short_term_max_days: None,
+                short_term_fee: None,
+                vat_rate: None,
+                tiers: Vec::new(),
+            },
+        )
+        .await?;
+        sqlx::query("UPDATE job_postings SET fee_policy_id = $1 WHERE id = $2")
+            .bind(policy.id)
+            .bind(self.job_posting_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn posting_status(&self, table: &str, id: i64) -> Result<String> {
+        Ok(sqlx::query_scalar::<_, String>(&format!(
+            "SELECT posting_status FROM {table} WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?)
+    }
+}
+
 #[tokio::test]
 async fn matching_repository_calculates_fee_amounts() -> Result<()> {
     let Some(pool) = setup_pool().await else {
@@ -101,108 +310,1022 @@ async fn matching_repository_calculates_fee_amounts() -> Result<()> {
         );
         return Ok(());
     };
+    let fixture = Fixture::seed(pool.clone(), "matching_owner").await?;
+    let user = &fixture.user;
 
-    // Seed base data: security question, user, customers, postings
-    let question_id: i64 = sqlx::query(
+    let matching = fixture.create_matching().await?;
+    let hundred = Decimal::new(100, 0);
+    let expected_employer_fee = (matching.agreed_salary * matching.employer_fee_rate) / hundred;
+    let expected_employee_fee = (matching.agreed_salary * matching.employee_fee_rate) / hundred;
+
+    assert_eq!(matching.matching_status, MatchingStatus::InProgress);
+    assert_eq!(matching.employer_fee_rate, Decimal::new(1200, 2));
+    assert_eq!(matching.employer_fee_rate_source, FeeRateSource::Request);
+    assert_eq!(matching.employee_fee_rate, Decimal::new(500, 2));
+    assert_eq!(matching.employee_fee_rate_source, FeeRateSource::Posting);
+    assert_eq!(matching.employer_fee_amount.unwrap(), expected_employer_fee);
+    assert_eq!(matching.employee_fee_amount.unwrap(), expected_employee_fee);
+
+    assert_eq!(
+        fixture
+            .posting_status("job_postings", fixture.job_posting_id)
+            .await?,
+        "in_progress"
+    );
+    assert_eq!(
+        fixture
+            .posting_status("job_seeking_postings", fixture.job_seeking_posting_id)
+            .await?,
+        "in_progress"
+    );
+
+    assert!(matches!(
+        matching::create_matching(&pool, fixture.matching_request(None), user.id).await,
+        Err(matching::MatchingError::AlreadyInProgress(id)) if id == matching.id
+    ));
+
+    let cancelled = matching::cancel_matching(&pool, matching.id, user.id, None).await?;
+    assert_eq!(cancelled.matching_status, MatchingStatus::Cancelled);
+    assert_eq!(
+        fixture
+            .posting_status("job_postings", fixture.job_posting_id)
+            .await?,
+        "published"
+    );
+    assert_eq!(
+        fixture
+            .posting_status("job_seeking_postings", fixture.job_seeking_posting_id)
+            .await?,
+        "published"
+    );
+    assert!(matches!(
+        matching::complete_matching(&pool, matching.id, user.id).await,
+        Err(matching::MatchingError::InvalidTransition(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn replacement_matching_stays_free_when_edited() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping replacement_matching_stays_free_when_edited: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "replacement_owner").await?;
+    let user = &fixture.user;
+    fixture
+        .attach_employer_fee_policy(Some(Decimal::new(300_000, 0)))
+        .await?;
+
+    let original = matching::create_matching(
+        &pool,
+        CreateMatchingRequest {
+            guarantee_days: Some(90),
+            ..fixture.matching_request(Some(Decimal::new(1200, 2)))
+        },
+        user.id,
+    )
+    .await?;
+    matching::complete_matching(&pool, original.id, user.id).await?;
+    let replacement_seeker_id: i64 = sqlx::query_scalar(
         r#"
-        INSERT INTO security_questions (question_text)
-        VALUES ($1)
+        INSERT INTO job_seeking_postings (customer_id, desired_salary, description, preferred_location)
+        SELECT customer_id, desired_salary, '교체 근무자', preferred_location
+        FROM job_seeking_postings WHERE id = $1
         RETURNING id
         "#,
     )
-    .bind("테스트 보안 질문")
+    .bind(fixture.job_seeking_posting_id)
     .fetch_one(&pool)
-    .await?
-    .get("id");
+    .await?;
+    let replacement = matching::replace_matching(
+        &pool,
+        original.id,
+        ReplaceMatchingRequest {
+            left_on: chrono::Utc::now().date_naive(),
+            job_seeking_posting_id: replacement_seeker_id,
+            agreed_salary: None,
+            salary_basis: None,
+            expected_weekly_hours: None,
+            employee_fee_rate: None,
+            contract_days: None,
+            guarantee_days: None,
+        },
+        user.id,
+    )
+    .await?;
+    assert_eq!(replacement.employer_fee_amount, Some(Decimal::ZERO));
 
-    let user_repo = UserRepository::new(pool.clone());
-    let password_hash = hash_password("Password123!")?;
-    let security_answer_hash = hash_password("answer")?;
-    let user = user_repo
-        .create_user(
-            "matching_owner",
-            &password_hash,
-            question_id,
-            &security_answer_hash,
-            None,
+    // 급여를 바꿔 다시 계산해도 정책의 최저 수수료가 붙지 않음
+    let edit = |agreed_salary, employer_fee_rate| UpdateMatchingRequest {
+        agreed_salary,
+        salary_basis: None,
+        expected_weekly_hours: None,
+        employer_fee_rate,
+        employee_fee_rate: None,
+        contract_days: None,
+        guarantee_days: None,
+        matching_status: None,
+        cancellation_reason: None,
+    };
+    let edited = matching::update_matching_status(
+        &pool,
+        replacement.id,
+        edit(Some(Decimal::new(5_000_000, 0)), None),
+        user.id,
+    )
+    .await?;
+    assert_eq!(edited.employer_fee_amount, Some(Decimal::ZERO));
+    assert_eq!(edited.employer_fee_rate_source, FeeRateSource::Replacement);
+
+    assert!(matches!(
+        matching::update_matching_status(
+            &pool,
+            replacement.id,
+            edit(None, Some(Decimal::new(1000, 2))),
+            user.id
+        )
+        .await,
+        Err(matching::MatchingError::ReplacementFeeRate)
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_ledger_tracks_partial_payments_and_settlement() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!("Skipping payment_ledger_tracks_partial_payments_and_settlement: DATABASE_URL not available");
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "ledger_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+    let employer_fee = matching.employer_fee_amount.unwrap();
+
+    // 구인자 수수료 분할 입금
+    let (_, balance) = fixture
+        .pay(matching.id, PayerSide::Employer, Decimal::new(100_000, 0))
+        .await?;
+    assert_eq!(
+        balance.employer.outstanding_amount,
+        employer_fee - Decimal::new(100_000, 0)
+    );
+    assert_eq!(
+        balance.employer.settlement_status,
+        SettlementStatus::PartiallySettled
+    );
+    assert!(matches!(
+        payment::create_payment(
+            &pool,
+            matching.id,
+            CreatePaymentRequest {
+                payer_side: PayerSide::Employer,
+                amount: employer_fee,
+                paid_on: None,
+                payment_method: PaymentMethod::BankTransfer,
+                memo: None,
+            },
+            user.id
+        )
+        .await,
+        Err(payment::LedgerError::Payment(_))
+    ));
+
+    // 공고의 정산 금액/상태는 매칭 수수료와 입금 내역을 따름
+    let history =
+        settlement::list_settlement_history(&pool, PostingKind::JobPosting, fixture.job_posting_id)
+            .await?;
+    let last = history.last().expect("settlement history recorded");
+    assert_eq!(last.change_source, SettlementChangeSource::Payment);
+    assert_eq!(last.to_status, SettlementStatus::PartiallySettled);
+    assert_eq!(last.to_amount, Some(employer_fee));
+    let settle_by_hand = UpdateSettlementRequest {
+        settlement_status: Some(SettlementStatus::Settled),
+        settlement_amount: None,
+        settlement_memo: None,
+    };
+    assert!(matches!(
+        settlement::update_settlement(
+            &pool,
+            PostingKind::JobPosting,
+            fixture.job_posting_id,
+            &settle_by_hand,
+            user.id
         )
+        .await,
+        Err(settlement::SettlementUpdateError::Invalid(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn matching_fee_edits_respect_the_ledger() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!("Skipping matching_fee_edits_respect_the_ledger: DATABASE_URL not available");
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "fee_edit_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+    fixture
+        .pay(matching.id, PayerSide::Employer, Decimal::new(300_000, 0))
         .await?;
 
-    let employer_customer_id: i64 = sqlx::query(
+    let edit = |employer_fee_rate| UpdateMatchingRequest {
+        agreed_salary: None,
+        salary_basis: None,
+        expected_weekly_hours: None,
+        employer_fee_rate: Some(employer_fee_rate),
+        employee_fee_rate: None,
+        contract_days: None,
+        guarantee_days: None,
+        matching_status: None,
+        cancellation_reason: None,
+    };
+
+    // 5% 는 240,000 원으로 이미 입금된 300,000 원보다 적음
+    assert!(matches!(
+        matching::update_matching_status(&pool, matching.id, edit(Decimal::new(500, 2)), user.id)
+            .await,
+        Err(matching::MatchingError::FeeBelowPaid(PayerSide::Employer))
+    ));
+    let edited =
+        matching::update_matching_status(&pool, matching.id, edit(Decimal::new(1000, 2)), user.id)
+            .await?;
+    assert_eq!(edited.employer_fee_amount, Some(Decimal::new(480_000, 0)));
+
+    sqlx::query(
         r#"
-        INSERT INTO customers (user_id, name, phone, customer_type)
-        VALUES ($1, $2, $3, 'employer')
-        RETURNING id
+        INSERT INTO fee_documents (
+            user_id, matching_id, document_type, payer_side, document_number,
+            issued_on, total_amount, file_path, file_size
+        )
+        VALUES ($1, $2, 'receipt', 'employer', 'RCP-2025-0001', CURRENT_DATE, 300000,
+            'documents/1/RCP-2025-0001.pdf', 1024)
         "#,
     )
     .bind(user.id)
-    .bind("테스트 구인자")
-    .bind("010-1111-2222")
-    .fetch_one(&pool)
-    .await?
-    .get("id");
+    .bind(matching.id)
+    .execute(&pool)
+    .await?;
+    assert!(matches!(
+        matching::update_matching_status(&pool, matching.id, edit(Decimal::new(1200, 2)), user.id)
+            .await,
+        Err(matching::MatchingError::FeeDocumentsIssued)
+    ));
 
-    let employee_customer_id: i64 = sqlx::query(
-        r#"
-        INSERT INTO customers (user_id, name, phone, customer_type)
-        VALUES ($1, $2, $3, 'employee')
-        RETURNING id
-        "#,
+    Ok(())
+}
+
+#[tokio::test]
+async fn bank_import_confirms_deposits_against_open_fees() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping bank_import_confirms_deposits_against_open_fees: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "bank_import_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+
+    let deposits = vec![ParsedDeposit {
+        transacted_at: chrono::Local::now().naive_local(),
+        depositor: "테스트구인자".to_string(),
+        amount: matching.employer_fee_amount.unwrap(),
+        memo: None,
+        balance_after: None,
+    }];
+    let import = bank_import::create_import(&pool, user.id, None, &deposits).await?;
+    assert_eq!(import.imported_rows, 1);
+    let reimport = bank_import::create_import(&pool, user.id, None, &deposits).await?;
+    assert_eq!(reimport.imported_rows, 0);
+    // 같은 내용의 입금이 한 번 더 들어온 파일은 새 입금 한 건만 저장
+    let twice = [deposits[0].clone(), deposits[0].clone()];
+    let overlapping = bank_import::create_import(&pool, user.id, None, &twice).await?;
+    assert_eq!(overlapping.imported_rows, 1);
+    let reimport = bank_import::create_import(&pool, user.id, None, &twice).await?;
+    assert_eq!(reimport.imported_rows, 0);
+
+    let open_fees = bank_import::list_open_fees(&pool, user.id).await?;
+    let suggestions = statement::suggest(&deposits[0], &open_fees);
+    assert_eq!(suggestions[0].matching_id, matching.id);
+    assert_eq!(suggestions[0].payer_side, PayerSide::Employer);
+
+    let transactions = bank_import::list_import_transactions(&pool, import.id, user.id).await?;
+    let confirm = ConfirmMatch {
+        transaction_id: transactions[0].id,
+        matching_id: matching.id,
+        payer_side: PayerSide::Employer,
+    };
+    bank_import::confirm_matches(&pool, import.id, user.id, std::slice::from_ref(&confirm)).await?;
+    let balance = payment::get_matching_balance(&pool, matching.id, user.id).await?;
+    assert_eq!(
+        balance.employer.settlement_status,
+        SettlementStatus::Settled
+    );
+    assert!(matches!(
+        bank_import::confirm_matches(&pool, import.id, user.id, &[confirm]).await,
+        Err(bank_import::BankImportError::AlreadyConfirmed(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn analytics_rollups_match_live_metrics() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!("Skipping analytics_rollups_match_live_metrics: DATABASE_URL not available");
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "analytics_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+    fixture
+        .pay(matching.id, PayerSide::Employer, Decimal::new(100_000, 0))
+        .await?;
+    matching::cancel_matching(&pool, matching.id, user.id, None).await?;
+
+    let today = chrono::Utc::now().date_naive();
+    let (from, to) = (
+        today - chrono::Duration::days(30),
+        today + chrono::Duration::days(1),
+    );
+    assert!(analytics::refresh_dirty_days(&pool, user.id).await? > 0);
+    let rollup = analytics::list_rollup_metrics(&pool, user.id, from, to).await?;
+    let live = analytics::list_live_metrics(&pool, user.id, from, to).await?;
+    assert!(series::compare_metrics(&rollup, &live).is_empty());
+    let totals = analytics::list_rollup_totals(&pool, user.id).await?;
+    let total = |metric| {
+        totals
+            .iter()
+            .find(|total| total.metric == metric)
+            .map(|total| total.value)
+            .unwrap_or_default()
+    };
+    assert_eq!(total(AnalyticsMetric::JobPostingsOpened), Decimal::ONE);
+    assert_eq!(total(AnalyticsMetric::JobSeekingsOpened), Decimal::ONE);
+    assert_eq!(total(AnalyticsMetric::MatchingsCancelled), Decimal::ONE);
+    assert_eq!(total(AnalyticsMetric::FeesCharged), Decimal::ZERO);
+    // 취소된 매칭의 입금은 수금액에 넣지 않음
+    assert_eq!(total(AnalyticsMetric::FeesPaid), Decimal::ZERO);
+    assert_eq!(analytics::refresh_dirty_days(&pool, user.id).await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn monthly_report_summarizes_the_month() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!("Skipping monthly_report_summarizes_the_month: DATABASE_URL not available");
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "report_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+    matching::cancel_matching(&pool, matching.id, user.id, None).await?;
+    analytics::refresh_dirty_days(&pool, user.id).await?;
+
+    let today = chrono::Utc::now().date_naive();
+    let report =
+        monthly_report::generate_report(&pool, user.id, today.year(), today.month()).await?;
+    assert_eq!(report.matchings_cancelled, 1);
+    assert_eq!(report.cancellation_reasons.len(), 1);
+    assert!(report.top_customers.is_empty());
+    assert!(!monthly_report::render_xlsx(&report)?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn monthly_report_totals_fees_of_completed_matchings() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping monthly_report_totals_fees_of_completed_matchings: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "report_fee_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+    matching::complete_matching(&pool, matching.id, user.id).await?;
+    fixture
+        .pay(matching.id, PayerSide::Employer, Decimal::new(300_000, 0))
+        .await?;
+    fixture
+        .pay(matching.id, PayerSide::Employee, Decimal::new(240_000, 0))
+        .await?;
+    analytics::refresh_dirty_days(&pool, user.id).await?;
+
+    let today = chrono::Utc::now().date_naive();
+    let report =
+        monthly_report::generate_report(&pool, user.id, today.year(), today.month()).await?;
+    assert_eq!(report.matchings_created, 1);
+    assert_eq!(report.matchings_completed, 1);
+    assert_eq!(report.matchings_cancelled, 0);
+    // 구인자 576,000 + 구직자 240,000
+    assert_eq!(report.fees_earned, Decimal::new(816_000, 0));
+    assert_eq!(report.fees_collected, Decimal::new(540_000, 0));
+    assert_eq!(report.outstanding.total, Decimal::new(276_000, 0));
+
+    let top: Vec<_> = report
+        .top_customers
+        .iter()
+        .map(|customer| {
+            (
+                customer.customer_name.as_str(),
+                customer.matchings_completed,
+                customer.fees_earned,
+            )
+        })
+        .collect();
+    assert_eq!(
+        top,
+        vec![
+            ("테스트 구인자", 1, Decimal::new(576_000, 0)),
+            ("테스트 구직자", 1, Decimal::new(240_000, 0)),
+        ]
+    );
+    assert_eq!(
+        report.top_customers[0].customer_id,
+        fixture.employer_customer_id
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn customer_statement_lists_the_customer_ledger() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping customer_statement_lists_the_customer_ledger: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "statement_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+    fixture
+        .pay(matching.id, PayerSide::Employer, Decimal::new(100_000, 0))
+        .await?;
+    matching::cancel_matching(&pool, matching.id, user.id, None).await?;
+
+    let today = chrono::Utc::now().date_naive();
+    let statement = customer_statement::generate_statement(
+        &pool,
+        fixture.employer_customer_id,
+        user.id,
+        None,
+        today,
     )
-    .bind(user.id)
-    .bind("테스트 구직자")
-    .bind("010-3333-4444")
-    .fetch_one(&pool)
-    .await?
-    .get("id");
+    .await?;
+    assert_eq!(statement.matchings.len(), 1);
+    assert_eq!(statement.matchings[0].charged_amount, Decimal::ZERO);
+    assert_eq!(statement.total_charged, Decimal::ZERO);
+    assert_eq!(statement.closing_balance, -statement.total_credited);
+    assert!(matches!(
+        customer_statement::generate_statement(
+            &pool,
+            fixture.employer_customer_id,
+            user.id,
+            Some(today + chrono::Duration::days(1)),
+            today
+        )
+        .await,
+        Err(customer_statement::StatementError::InvalidRange)
+    ));
 
-    let job_posting_id: i64 = sqlx::query(
-        r#"
-        INSERT INTO job_postings (customer_id, salary, description, employer_fee_rate)
-        VALUES ($1, $2, $3, $4)
-        RETURNING id
-        "#,
+    Ok(())
+}
+
+#[tokio::test]
+async fn customer_statement_carries_the_balance_over_charged_fees() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping customer_statement_carries_the_balance_over_charged_fees: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "statement_fee_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+    matching::complete_matching(&pool, matching.id, user.id).await?;
+    for amount in [200_000, 100_000] {
+        fixture
+            .pay(matching.id, PayerSide::Employer, Decimal::new(amount, 0))
+            .await?;
+    }
+
+    let today = chrono::Utc::now().date_naive();
+    let statement = customer_statement::generate_statement(
+        &pool,
+        fixture.employer_customer_id,
+        user.id,
+        None,
+        today,
     )
-    .bind(employer_customer_id)
-    .bind(Decimal::new(5_000_000, 0))
-    .bind("테스트 구인 포지션")
-    .bind(Decimal::new(1000, 2)) // 10%
-    .fetch_one(&pool)
-    .await?
-    .get("id");
+    .await?;
+
+    let ledger: Vec<_> = statement
+        .entries
+        .iter()
+        .map(|entry| (entry.kind, entry.charge, entry.credit, entry.balance))
+        .collect();
+    assert_eq!(
+        ledger,
+        vec![
+            (
+                StatementEntryKind::Charge,
+                Decimal::new(576_000, 0),
+                Decimal::ZERO,
+                Decimal::new(576_000, 0)
+            ),
+            (
+                StatementEntryKind::Payment,
+                Decimal::ZERO,
+                Decimal::new(200_000, 0),
+                Decimal::new(376_000, 0)
+            ),
+            (
+                StatementEntryKind::Payment,
+                Decimal::ZERO,
+                Decimal::new(100_000, 0),
+                Decimal::new(276_000, 0)
+            ),
+        ]
+    );
+    assert_eq!(statement.opening_balance, Decimal::ZERO);
+    assert_eq!(statement.total_charged, Decimal::new(576_000, 0));
+    assert_eq!(statement.total_credited, Decimal::new(300_000, 0));
+    assert_eq!(statement.closing_balance, Decimal::new(276_000, 0));
+
+    let side = &statement.matchings[0];
+    assert_eq!(side.matching_status, MatchingStatus::Completed);
+    assert_eq!(side.counterparty_name, "테스트 구직자");
+    assert_eq!(side.charged_amount, Decimal::new(576_000, 0));
+    assert_eq!(side.paid_amount, Decimal::new(300_000, 0));
+    assert_eq!(side.outstanding_amount, Decimal::new(276_000, 0));
+
+    Ok(())
+}
 
-    let job_seeking_posting_id: i64 = sqlx::query(
+#[tokio::test]
+async fn tax_invoice_export_skips_cancelled_matchings() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping tax_invoice_export_skips_cancelled_matchings: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "tax_invoice_owner").await?;
+    let user = &fixture.user;
+    let matching = fixture.create_matching().await?;
+    matching::cancel_matching(&pool, matching.id, user.id, None).await?;
+
+    sqlx::query("UPDATE users SET business_number = $1, business_name = $2 WHERE id = $3")
+        .bind("2208162517")
+        .bind("헬퍼노트 직업소개소")
+        .bind(user.id)
+        .execute(&pool)
+        .await?;
+    let agency = UserRepository::new(pool.clone())
+        .find_by_id(user.id)
+        .await?;
+    assert_eq!(agency.business_number.as_deref(), Some("2208162517"));
+    let invoices: Vec<_> = export::stream_tax_invoices(pool.clone(), user.id, None, None)
+        .try_collect()
+        .await?;
+    assert!(invoices.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tax_invoice_export_splits_partly_paid_fees() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping tax_invoice_export_splits_partly_paid_fees: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "tax_invoice_fee_owner").await?;
+    let user = &fixture.user;
+
+    // 구인자 공고에만 VAT 10% 정책을 지정
+    fixture.attach_employer_fee_policy(None).await?;
+    sqlx::query("UPDATE users SET business_number = $1, business_name = $2 WHERE id = $3")
+        .bind("2208162517")
+        .bind("헬퍼노트 직업소개소")
+        .bind(user.id)
+        .execute(&pool)
+        .await?;
+    sqlx::query("UPDATE customers SET business_number = $1 WHERE id = $2")
+        .bind("1248100998")
+        .bind(fixture.employer_customer_id)
+        .execute(&pool)
+        .await?;
+
+    // 구인자 수수료 576,000 + VAT 57,600 = 633,600
+    let matching = fixture.create_matching().await?;
+    assert_eq!(matching.employer_fee_amount, Some(Decimal::new(633_600, 0)));
+    matching::complete_matching(&pool, matching.id, user.id).await?;
+    sqlx::query("UPDATE matchings SET guarantee_refund_amount = $1 WHERE id = $2")
+        .bind(Decimal::new(33_600, 0))
+        .bind(matching.id)
+        .execute(&pool)
+        .await?;
+    fixture
+        .pay(matching.id, PayerSide::Employer, Decimal::new(300_000, 0))
+        .await?;
+    fixture
+        .pay(matching.id, PayerSide::Employee, Decimal::new(240_000, 0))
+        .await?;
+
+    let invoices: Vec<_> = export::stream_tax_invoices(pool.clone(), user.id, None, None)
+        .try_collect()
+        .await?;
+    assert_eq!(invoices.len(), 2);
+    let cells = |side: PayerSide| -> HashMap<&str, ExportCell> {
+        let invoice = invoices
+            .iter()
+            .find(|invoice| invoice.payer_side == side)
+            .unwrap();
+        TaxInvoiceExportRow::HEADERS
+            .iter()
+            .copied()
+            .zip(invoice.cells())
+            .collect()
+    };
+    let text = |value: &str| ExportCell::Text(value.to_string());
+    let number = |value: i64| ExportCell::Number(Decimal::new(value, 0));
+
+    // 보증 환불을 뺀 600,000 원을 VAT 10% 로 나누고, 절반만 입금되어 청구
+    let employer = cells(PayerSide::Employer);
+    assert_eq!(employer["공급자 등록번호"], text("2208162517"));
+    assert_eq!(employer["공급자 상호"], text("헬퍼노트 직업소개소"));
+    assert_eq!(employer["공급받는자 등록번호"], text("1248100998"));
+    assert_eq!(employer["공급받는자 상호"], text("테스트 구인자"));
+    assert_eq!(employer["공급가액"], number(545_455));
+    assert_eq!(employer["세액"], number(54_545));
+    assert_eq!(employer["합계금액"], number(600_000));
+    assert_eq!(employer["영수/청구"], text("청구"));
+    assert_eq!(employer["입금액"], number(300_000));
+    assert_eq!(employer["비고"], text("보증 환불 33,600원 차감"));
+
+    // 정책이 없는 구직자 수수료는 VAT 없이 전액 입금되어 영수
+    let employee = cells(PayerSide::Employee);
+    assert_eq!(employee["공급받는자 등록번호"], ExportCell::Empty);
+    assert_eq!(employee["공급받는자 상호"], text("테스트 구직자"));
+    assert_eq!(employee["공급가액"], number(240_000));
+    assert_eq!(employee["세액"], number(0));
+    assert_eq!(employee["합계금액"], number(240_000));
+    assert_eq!(employee["영수/청구"], text("영수"));
+    assert_eq!(employee["비고"], ExportCell::Empty);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn customer_business_number_can_be_cleared() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!("Skipping customer_business_number_can_be_cleared: DATABASE_URL not available");
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "business_number_owner").await?;
+    let user = &fixture.user;
+    let update = |customer_type, business_number: Option<&str>| UpdateCustomerRequest {
+        name: None,
+        birth_date: None,
+        phone: None,
+        address: None,
+        profile_photo_id: None,
+        customer_type,
+        business_number: business_number.map(String::from),
+        allow_duplicate_phone: false,
+    };
+    let id = fixture.employer_customer_id;
+
+    let updated =
+        customer::update_customer(&pool, id, user.id, update(None, Some("1248100998")), None)
+            .await?;
+    assert_eq!(updated.business_number.as_deref(), Some("1248100998"));
+    let updated = customer::update_customer(&pool, id, user.id, update(None, None), None).await?;
+    assert_eq!(updated.business_number.as_deref(), Some("1248100998"));
+    let updated =
+        customer::update_customer(&pool, id, user.id, update(None, Some("")), None).await?;
+    assert_eq!(updated.business_number, None);
+
+    // 구직자로 바꾸면 저장된 번호도 지움
+    customer::update_customer(&pool, id, user.id, update(None, Some("1248100998")), None).await?;
+    let updated = customer::update_customer(
+        &pool,
+        id,
+        user.id,
+        update(Some(CustomerType::Employee), None),
+        None,
+    )
+    .await?;
+    assert_eq!(updated.business_number, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn customer_merge_keeps_postings_with_a_compatible_type() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping customer_merge_keeps_postings_with_a_compatible_type: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "merge_owner").await?;
+    let user = &fixture.user;
+    let employee_customer_id: i64 =
+        sqlx::query_scalar("SELECT customer_id FROM job_seeking_postings WHERE id = $1")
+            .bind(fixture.job_seeking_posting_id)
+            .fetch_one(&pool)
+            .await?;
+    let selection = |customer_type| MergeSelection {
+        name: MergeSide::Target,
+        birth_date: MergeSide::Target,
+        phone: MergeSide::Target,
+        address: MergeSide::Target,
+        customer_type,
+        profile_photo: MergeSide::Target,
+    };
+
+    // 구직자로 병합하면 옮겨지는 구인 공고를 가질 수 없으므로 아무것도 바뀌지 않음
+    let result = customer::merge_customers(
+        &pool,
+        user.id,
+        employee_customer_id,
+        fixture.employer_customer_id,
+        selection(MergeSide::Target),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(CustomerMergeError::IncompatibleType(CustomerType::Employee))
+    ));
+    let owner: i64 = sqlx::query_scalar("SELECT customer_id FROM job_postings WHERE id = $1")
+        .bind(fixture.job_posting_id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(owner, fixture.employer_customer_id);
+
+    // 구직자로 병합된 고객은 사업자번호를 넘겨받지 않음
+    let business_id: i64 = sqlx::query_scalar(
         r#"
-        INSERT INTO job_seeking_postings (customer_id, desired_salary, description, preferred_location, employee_fee_rate)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO customers (user_id, name, phone, customer_type, business_number)
+        VALUES ($1, $2, $3, 'employer', $4)
         RETURNING id
         "#,
     )
-    .bind(employee_customer_id)
-    .bind(Decimal::new(4_500_000, 0))
-    .bind("테스트 구직 설명")
-    .bind("서울")
-    .bind(Decimal::new(500, 2)) // 5%
+    .bind(user.id)
+    .bind("사업자 구인자")
+    .bind("010-5555-6666")
+    .bind("1248100998")
     .fetch_one(&pool)
-    .await?
-    .get("id");
+    .await?;
+    let (merged, _) = customer::merge_customers(
+        &pool,
+        user.id,
+        employee_customer_id,
+        business_id,
+        selection(MergeSide::Target),
+    )
+    .await?;
+    assert_eq!(merged.customer_type, CustomerType::Employee);
+    assert_eq!(merged.business_number, None);
+
+    Ok(())
+}
 
-    let request = CreateMatchingRequest {
-        job_posting_id,
-        job_seeking_posting_id,
-        agreed_salary: Decimal::new(4_800_000, 0),
-        employer_fee_rate: Decimal::new(1200, 2), // 12%
-        employee_fee_rate: Decimal::new(600, 2),  // 6%
+#[tokio::test]
+async fn search_snippets_escape_html() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!("Skipping search_snippets_escape_html: DATABASE_URL not available");
+        return Ok(());
     };
+    let fixture = Fixture::seed(pool.clone(), "search_owner").await?;
+    let user = &fixture.user;
 
-    let matching = matching::create_matching(&pool, request).await?;
-    let hundred = Decimal::new(100, 0);
-    let expected_employer_fee = (matching.agreed_salary * matching.employer_fee_rate) / hundred;
-    let expected_employee_fee = (matching.agreed_salary * matching.employee_fee_rate) / hundred;
+    sqlx::query("INSERT INTO user_memos (user_id, memo_content) VALUES ($1, $2)")
+        .bind(user.id)
+        .bind("<img src=x onerror=alert(1)> 강아지 & 고양이")
+        .execute(&pool)
+        .await?;
 
-    assert_eq!(matching.matching_status, MatchingStatus::InProgress);
-    assert_eq!(matching.employer_fee_amount.unwrap(), expected_employer_fee);
-    assert_eq!(matching.employee_fee_amount.unwrap(), expected_employee_fee);
+    let tsquery = build_prefix_tsquery("강아지").unwrap();
+    let hits = search::search_memos(&pool, user.id, &tsquery, 10).await?;
+    assert_eq!(hits.len(), 1);
+    let snippet = &hits[0].snippet;
+    assert!(snippet.contains("<mark>강아지</mark> &amp; 고양이"));
+    // <mark> 외에는 태그가 남지 않음
+    let unmarked = snippet.replace("<mark>", "").replace("</mark>", "");
+    assert!(!unmarked.contains('<') && !unmarked.contains('>'));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn customer_timeline_orders_simultaneous_events() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping customer_timeline_orders_simultaneous_events: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "timeline_owner").await?;
+
+    let mut memo_ids = Vec::new();
+    for content in ["첫 상담", "두 번째 상담"] {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO customer_memos (customer_id, memo_content, created_at)
+            VALUES ($1, $2, '2025-01-01 09:00:00')
+            RETURNING id
+            "#,
+        )
+        .bind(fixture.employer_customer_id)
+        .bind(content)
+        .fetch_one(&pool)
+        .await?;
+        memo_ids.push(id);
+    }
+    let matching = fixture.create_matching().await?;
+
+    let events =
+        timeline::list_customer_timeline(&pool, fixture.employer_customer_id, 50, 0).await?;
+    let order: Vec<_> = events
+        .iter()
+        .map(|event| (event.event_type, event.memo_id))
+        .collect();
+
+    // 같은 시각의 이벤트는 유형, 그다음 최근 행 순
+    assert_eq!(
+        order,
+        vec![
+            (TimelineEventType::JobPostingStatusChanged, None),
+            (TimelineEventType::MatchingCreated, None),
+            (TimelineEventType::JobPostingCreated, None),
+            (TimelineEventType::MemoAdded, Some(memo_ids[1])),
+            (TimelineEventType::MemoAdded, Some(memo_ids[0])),
+        ]
+    );
+    assert_eq!(events[1].matching_id, Some(matching.id));
+    assert_eq!(events[0].occurred_at, events[1].occurred_at);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn trash_purge_backs_off_failing_files() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!("Skipping trash_purge_backs_off_failing_files: DATABASE_URL not available");
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "trash_owner").await?;
+
+    let mut file_ids = Vec::new();
+    for (path, deleted_at) in [
+        ("users/trash/newer.pdf", "2020-01-02 00:00:00"),
+        ("users/trash/older.pdf", "2020-01-01 00:00:00"),
+    ] {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO user_files (user_id, file_path, file_type, deleted_at)
+            VALUES ($1, $2, 'document', $3::timestamp)
+            RETURNING id
+            "#,
+        )
+        .bind(fixture.user.id)
+        .bind(path)
+        .bind(deleted_at)
+        .fetch_one(&pool)
+        .await?;
+        file_ids.push(id);
+    }
+
+    let cutoff = chrono::Utc::now().naive_utc();
+    let expired_ids = |files: Vec<ExpiredFile>| -> Vec<i64> {
+        files
+            .into_iter()
+            .map(|file| file.id)
+            .filter(|id| file_ids.contains(id))
+            .collect()
+    };
+
+    // 먼저 삭제된 파일부터
+    let expired = trash::list_expired_files(&pool, cutoff, 10_000).await?;
+    assert_eq!(expired_ids(expired.clone()), vec![file_ids[1], file_ids[0]]);
+
+    let older = expired.iter().find(|file| file.id == file_ids[1]).unwrap();
+    trash::record_file_purge_failure(&pool, older).await?;
+    let expired = trash::list_expired_files(&pool, cutoff, 10_000).await?;
+    assert_eq!(expired_ids(expired), vec![file_ids[0]]);
+
+    let attempts: i32 = sqlx::query_scalar("SELECT purge_attempts FROM user_files WHERE id = $1")
+        .bind(file_ids[1])
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(attempts, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn trash_purge_keeps_matchings_with_payments() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!("Skipping trash_purge_keeps_matchings_with_payments: DATABASE_URL not available");
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "purge_ledger_owner").await?;
+    let matching = fixture.create_matching().await?;
+    let (payment, _) = fixture
+        .pay(matching.id, PayerSide::Employer, Decimal::new(100_000, 0))
+        .await?;
+
+    // 매칭과 공고, 고객을 모두 보존 기간이 지난 휴지통 항목으로 만듦
+    for (table, id) in [
+        ("matchings", matching.id),
+        ("job_postings", fixture.job_posting_id),
+        ("job_seeking_postings", fixture.job_seeking_posting_id),
+        ("customers", fixture.employer_customer_id),
+    ] {
+        sqlx::query(&format!(
+            "UPDATE {table} SET deleted_at = '2020-01-01 00:00:00' WHERE id = $1"
+        ))
+        .bind(id)
+        .execute(&pool)
+        .await?;
+    }
+
+    trash::purge_expired_rows(&pool, chrono::Utc::now().naive_utc()).await?;
+
+    let kept: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM payments WHERE id = $1")
+        .bind(payment.id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(kept, 1);
+    for (table, id) in [
+        ("matchings", matching.id),
+        ("job_postings", fixture.job_posting_id),
+        ("job_seeking_postings", fixture.job_seeking_posting_id),
+        ("customers", fixture.employer_customer_id),
+    ] {
+        let kept: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table} WHERE id = $1"))
+            .bind(id)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(kept, 1, "{table} {id} was purged");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn trash_purge_keeps_matchings_with_documents() -> Result<()> {
+    let Some(pool) = setup_pool().await else {
+        eprintln!(
+            "Skipping trash_purge_keeps_matchings_with_documents: DATABASE_URL not available"
+        );
+        return Ok(());
+    };
+    let fixture = Fixture::seed(pool.clone(), "purge_document_owner").await?;
+    let matching = fixture.create_matching().await?;
+    sqlx::query(
+        r#"
+        INSERT INTO fee_documents (
+            user_id, matching_id, document_type, payer_side, document_number,
+            issued_on, total_amount, file_path, file_size
+        )
+        VALUES ($1, $2, 'invoice', 'employer', 'INV-2020-0001', '2020-01-01', 576000,
+            'documents/1/INV-2020-0001.pdf', 1024)
+        "#,
+    )
+    .bind(fixture.user.id)
+    .bind(matching.id)
+    .execute(&pool)
+    .await?;
+    sqlx::query("UPDATE matchings SET deleted_at = '2020-01-01 00:00:00' WHERE id = $1")
+        .bind(matching.id)
+        .execute(&pool)
+        .await?;
+
+    trash::purge_expired_rows(&pool, chrono::Utc::now().naive_utc()).await?;
+
+    let documents: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM fee_documents WHERE matching_id = $1")
+            .bind(matching.id)
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(documents, 1);
 
     Ok(())
 }
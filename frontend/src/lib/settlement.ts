@@ -24,8 +24,12 @@ export const settlementApi = {
   updateJobSeekingSettlement: async (
     seekingId: number,
     data: UpdateSettlementRequest
-  ): Promise<void> => {
-    await apiClient.put(`/api/job-seekings/${seekingId}/settlement`, data);
+  ): Promise<JobSeekingPosting> => {
+    const response = await apiClient.put(
+      `/api/job-seekings/${seekingId}/settlement`,
+      data
+    );
+    return response.data.job_seeking;
   },
 
   // Toggle favorite for job posting
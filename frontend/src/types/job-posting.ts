@@ -1,5 +1,5 @@
 export type PostingStatus = "Published" | "InProgress" | "Closed" | "Cancelled";
-export type SettlementStatus = "Unsettled" | "Settled";
+export type SettlementStatus = "Unsettled" | "PartiallySettled" | "Settled";
 
 export interface JobPosting {
   id: number;